@@ -0,0 +1,208 @@
+use crate::db::queries;
+use crate::domain::{adjustments, alerts};
+use crate::models::{Alert, AlertKind, AlertTriggered, AssetType, Interval, PriceAdjustment};
+use crate::state::AppState;
+use chrono::Utc;
+use rusqlite::Connection;
+use tauri::{AppHandle, Emitter, State};
+
+/// Tauri event name carrying an `AlertTriggered` payload.
+pub const ALERT_TRIGGERED_EVENT: &str = "alert-triggered";
+
+fn validate_alert(kind: AlertKind, threshold: f64, window_days: Option<i64>) -> Result<(), String> {
+    if !threshold.is_finite() {
+        return Err("Threshold must be a number".to_string());
+    }
+    match kind {
+        AlertKind::PctMove | AlertKind::Drawdown if threshold <= 0.0 => {
+            Err("Percentage must be positive".to_string())
+        }
+        AlertKind::PriceAbove | AlertKind::PriceBelow | AlertKind::PositionAbove | AlertKind::PositionBelow
+            if threshold < 0.0 =>
+        {
+            Err("Threshold must be zero or positive".to_string())
+        }
+        _ if window_days.is_some_and(|d| d <= 0) => Err("Window must be at least one day".to_string()),
+        _ => Ok(()),
+    }
+}
+
+/// Evaluate the enabled alerts of one asset against its stored daily closes
+/// and record the outcome. Closes and the position are split-adjusted, so a
+/// split doesn't read as a crash. Returns the alerts that went from armed to
+/// triggered; ones already triggered stay quiet until their condition clears.
+pub fn check_alerts(conn: &Connection, asset_id: &str) -> anyhow::Result<Vec<AlertTriggered>> {
+    let pending: Vec<Alert> = queries::alerts::list_alerts(conn, Some(asset_id))?
+        .into_iter()
+        .filter(|a| a.enabled)
+        .collect();
+    if pending.is_empty() {
+        return Ok(Vec::new());
+    }
+    let Some(asset) = queries::assets::get_asset(conn, asset_id)? else {
+        return Ok(Vec::new());
+    };
+    if asset.asset_type == AssetType::Cash {
+        return Ok(Vec::new());
+    }
+
+    let actions = queries::corporate_actions::list_corporate_actions(conn, asset_id)?;
+    let mut candles = queries::prices::get_prices(conn, asset_id, Interval::OneDay, None, None)?;
+    adjustments::adjust_prices(&mut candles, &actions, PriceAdjustment::Split);
+    let transactions = queries::transactions::list_transactions_by_asset(conn, asset_id)?;
+    let quantity = adjustments::split_adjusted_net_quantity(&transactions, &actions);
+    let now = Utc::now().timestamp();
+    let mut fired = Vec::new();
+    for alert in pending {
+        let Some(observation) = alerts::observe(&alert, &candles, quantity) else { continue };
+        if observation.holds == alert.triggered {
+            continue;
+        }
+        if observation.holds {
+            queries::alerts::set_alert_triggered(conn, &alert.id, true, Some(now))?;
+            fired.push(AlertTriggered {
+                message: alerts::describe(&alert, &asset.symbol, observation.value),
+                alert_id: alert.id,
+                asset_id: asset.id.clone(),
+                symbol: asset.symbol.clone(),
+                kind: alert.kind,
+                threshold: alert.threshold,
+                value: observation.value,
+                ts: now,
+            });
+        } else {
+            queries::alerts::set_alert_triggered(conn, &alert.id, false, None)?;
+        }
+    }
+    Ok(fired)
+}
+
+/// Run `check_alerts` after a price fetch and emit one event per firing.
+/// Best effort: alerts never fail a fetch.
+pub fn notify_alerts(app: &AppHandle, state: &AppState, asset_id: &str) {
+    let Ok(fired) = state.with_db(|conn| check_alerts(conn, asset_id)) else { return };
+    for event in fired {
+        let _ = app.emit(ALERT_TRIGGERED_EVENT, event);
+    }
+}
+
+#[tauri::command]
+pub fn list_alerts(asset_id: Option<String>, state: State<'_, AppState>) -> Result<Vec<Alert>, String> {
+    state
+        .with_db(|conn| queries::alerts::list_alerts(conn, asset_id.as_deref()))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn create_alert(
+    asset_id: String,
+    kind: AlertKind,
+    threshold: f64,
+    window_days: Option<i64>,
+    state: State<'_, AppState>,
+) -> Result<Alert, String> {
+    validate_alert(kind, threshold, window_days)?;
+    state
+        .with_db(|conn| queries::alerts::insert_alert(conn, &asset_id, kind, threshold, window_days))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn update_alert(alert: Alert, state: State<'_, AppState>) -> Result<(), String> {
+    validate_alert(alert.kind, alert.threshold, alert.window_days)?;
+    state
+        .with_db(|conn| {
+            queries::alerts::update_alert(conn, &alert.id, alert.threshold, alert.window_days, alert.enabled)
+        })
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn delete_alert(id: String, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .with_db(|conn| queries::alerts::delete_alert(conn, &id))
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_db;
+    use crate::models::{CorporateAction, CorporateActionType, OHLCVRow, TxType};
+
+    fn close(conn: &Connection, asset_id: &str, day: i64, close: f64) {
+        let row = OHLCVRow {
+            id: None,
+            asset_id: asset_id.to_string(),
+            interval: Interval::OneDay,
+            ts: day * 86_400,
+            open: None,
+            high: None,
+            low: None,
+            close,
+            volume: None,
+            provider: None,
+        };
+        queries::prices::upsert_prices(conn, &[row]).unwrap();
+    }
+
+    #[test]
+    fn test_check_alerts_fires_once_and_rearms() {
+        let conn = test_db();
+        let asset = queries::assets::insert_asset(&conn, "AAPL", "Apple", &AssetType::Stock, "USD", None).unwrap();
+        let price = queries::alerts::insert_alert(&conn, &asset.id, AlertKind::PriceAbove, 200.0, None).unwrap();
+        queries::alerts::insert_alert(&conn, &asset.id, AlertKind::PositionAbove, 1000.0, None).unwrap();
+
+        close(&conn, &asset.id, 1, 190.0);
+        assert!(check_alerts(&conn, &asset.id).unwrap().is_empty());
+
+        close(&conn, &asset.id, 2, 205.0);
+        let fired = check_alerts(&conn, &asset.id).unwrap();
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].alert_id, price.id);
+        assert_eq!(fired[0].value, 205.0);
+        assert!(check_alerts(&conn, &asset.id).unwrap().is_empty());
+
+        // Dropping back below re-arms; a holding makes the position alert fire too
+        close(&conn, &asset.id, 3, 195.0);
+        assert!(check_alerts(&conn, &asset.id).unwrap().is_empty());
+        queries::transactions::insert_transaction(&conn, &asset.id, &TxType::Buy, 10.0, 150.0, 0, None, 0.0, None)
+            .unwrap();
+        close(&conn, &asset.id, 4, 210.0);
+        assert_eq!(check_alerts(&conn, &asset.id).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_split_is_not_a_crash() {
+        let conn = test_db();
+        let asset = queries::assets::insert_asset(&conn, "NVDA", "Nvidia", &AssetType::Stock, "USD", None).unwrap();
+        queries::alerts::insert_alert(&conn, &asset.id, AlertKind::Drawdown, 20.0, None).unwrap();
+        queries::alerts::insert_alert(&conn, &asset.id, AlertKind::PctMove, 20.0, Some(1)).unwrap();
+        let position = queries::alerts::insert_alert(&conn, &asset.id, AlertKind::PositionBelow, 5000.0, None).unwrap();
+        queries::transactions::insert_transaction(&conn, &asset.id, &TxType::Buy, 10.0, 1000.0, 86_400, None, 0.0, None)
+            .unwrap();
+        close(&conn, &asset.id, 1, 1000.0);
+        close(&conn, &asset.id, 2, 1010.0);
+        assert!(check_alerts(&conn, &asset.id).unwrap().is_empty());
+
+        // A 10:1 split on day 3: the raw close drops 90%, the position is still worth ~10k
+        queries::corporate_actions::upsert_corporate_actions(&conn, &[CorporateAction {
+            id: None,
+            asset_id: asset.id.clone(),
+            action_type: CorporateActionType::Split,
+            ts: 3 * 86_400,
+            value: 10.0,
+            provider: "test".to_string(),
+        }])
+        .unwrap();
+        close(&conn, &asset.id, 3, 102.0);
+        assert!(check_alerts(&conn, &asset.id).unwrap().is_empty());
+
+        // A real drop on the new scale still fires all three
+        close(&conn, &asset.id, 4, 40.0);
+        let fired = check_alerts(&conn, &asset.id).unwrap();
+        assert_eq!(fired.len(), 3);
+        let value = fired.iter().find(|f| f.alert_id == position.id).unwrap().value;
+        assert_eq!(value, 100.0 * 40.0);
+    }
+}
@@ -1,56 +1,95 @@
+use crate::commands::transactions::ensure_valid_reinstatement;
 use crate::db::queries;
-use crate::models::{Asset, AssetType};
+use crate::domain::calendar::TradingCalendar;
+use crate::models::{Asset, AssetMetadata, AssetProfile, AssetType, MarketStatus, ProviderSymbol, Transaction};
 use crate::state::AppState;
+use chrono::Utc;
 use tauri::State;
 
+/// Add an asset. `exchange`, `provider` and `provider_id` come from the search
+/// result the asset was picked from; the last two are stored as its symbol on
+/// that provider. An asset already followed from a watchlist moves into the
+/// portfolio instead.
 #[tauri::command]
 pub fn add_asset(
     symbol: String,
     name: String,
     asset_type: String,
+    exchange: Option<String>,
+    provider: Option<String>,
+    provider_id: Option<String>,
     state: State<'_, AppState>,
 ) -> Result<Asset, String> {
     let asset_type = AssetType::from_str(&asset_type).map_err(|e| e.to_string())?;
 
     state
         .with_db(|conn| {
-            // Check for active duplicate
-            if let Some(_existing) = queries::assets::get_asset_by_symbol(conn, &symbol)? {
-                anyhow::bail!("Asset with symbol {} already exists", symbol.to_uppercase());
-            }
-            // Check for soft-deleted asset with same symbol — restore it
-            if let Some(deleted) =
-                queries::assets::get_asset_by_symbol_including_deleted(conn, &symbol)?
-            {
-                if deleted.deleted_at.is_some() {
-                    return queries::assets::restore_asset(
-                        conn,
-                        &deleted.id,
-                        &name,
-                        &asset_type,
-                    );
+            if let Some(existing) = queries::assets::get_asset_by_symbol(conn, &symbol, exchange.as_deref(), &asset_type)? {
+                if queries::watchlists::clear_watch_only(conn, &existing.id)? {
+                    return Ok(existing);
                 }
             }
-            queries::assets::insert_asset(conn, &symbol, &name, &asset_type, "USD")
+            let asset = create_or_restore_asset(conn, &symbol, &name, &asset_type, exchange.as_deref())?;
+            if let (Some(provider), Some(provider_id)) = (&provider, &provider_id) {
+                queries::provider_symbols::set_provider_symbol(conn, &asset.id, provider, provider_id)?;
+            }
+            Ok(asset)
         })
         .map_err(|e| e.to_string())
 }
 
+/// Take an asset out of the trash under the given name and type. The
+/// transactions trashed with it come back too, so they are checked like any
+/// other reinstatement first.
+pub(crate) fn restore_trashed_asset(
+    conn: &rusqlite::Connection,
+    id: &str,
+    name: &str,
+    asset_type: &AssetType,
+) -> anyhow::Result<Asset> {
+    let returning: Vec<Transaction> = queries::transactions::list_trashed_with_asset(conn, id)?
+        .into_iter()
+        .map(|t| Transaction { deleted_at: None, ..t })
+        .collect();
+    ensure_valid_reinstatement(conn, &[], &returning)?;
+    queries::assets::restore_asset(conn, id, name, asset_type)
+}
+
+pub(crate) fn create_or_restore_asset(
+    conn: &rusqlite::Connection,
+    symbol: &str,
+    name: &str,
+    asset_type: &AssetType,
+    exchange: Option<&str>,
+) -> anyhow::Result<Asset> {
+    // Check for active duplicate
+    if let Some(existing) = queries::assets::get_asset_by_symbol(conn, symbol, exchange, asset_type)? {
+        match &existing.exchange {
+            Some(exchange) => anyhow::bail!("{} on {} is already tracked", existing.symbol, exchange),
+            None => anyhow::bail!("Asset with symbol {} already exists", existing.symbol),
+        }
+    }
+    // Check for soft-deleted asset with same identity — restore it
+    if let Some(deleted) =
+        queries::assets::get_asset_by_symbol_including_deleted(conn, symbol, exchange, asset_type)?
+    {
+        return restore_trashed_asset(conn, &deleted.id, name, asset_type);
+    }
+    queries::assets::insert_asset(conn, symbol, name, asset_type, "USD", exchange)
+}
+
 #[tauri::command]
 pub fn remove_asset(id: String, state: State<'_, AppState>) -> Result<(), String> {
     state
-        .with_db(|conn| {
-            queries::assets::soft_delete_asset(conn, &id)?;
-            queries::transactions::soft_delete_transactions_by_asset(conn, &id)?;
-            Ok(())
-        })
+        .with_db(|conn| queries::assets::soft_delete_asset(conn, &id))
         .map_err(|e| e.to_string())
 }
 
+/// Portfolio assets; assets followed only from a watchlist are left out.
 #[tauri::command]
 pub fn list_assets(state: State<'_, AppState>) -> Result<Vec<Asset>, String> {
     state
-        .with_db(queries::assets::list_assets)
+        .with_db(queries::assets::list_portfolio_assets)
         .map_err(|e| e.to_string())
 }
 
@@ -61,9 +100,92 @@ pub fn list_all_assets(state: State<'_, AppState>) -> Result<Vec<Asset>, String>
         .map_err(|e| e.to_string())
 }
 
+/// Open/closed state of each active asset's market right now.
+#[tauri::command]
+pub fn list_market_status(state: State<'_, AppState>) -> Result<Vec<MarketStatus>, String> {
+    let assets = state
+        .with_db(queries::assets::list_assets)
+        .map_err(|e| e.to_string())?;
+    let now = Utc::now().timestamp();
+    Ok(assets
+        .into_iter()
+        .map(|asset| {
+            let calendar = TradingCalendar::for_asset(&asset);
+            MarketStatus {
+                asset_id: asset.id,
+                market: calendar.name().to_string(),
+                is_open: calendar.is_open(now),
+                next_open: calendar.next_open(now),
+                next_close: calendar.next_close(now),
+            }
+        })
+        .collect())
+}
+
 #[tauri::command]
 pub fn purge_asset(id: String, state: State<'_, AppState>) -> Result<(), String> {
     state
         .with_db(|conn| queries::assets::hard_delete_asset(conn, &id))
         .map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+pub fn list_provider_symbols(
+    asset_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<ProviderSymbol>, String> {
+    state
+        .with_db(|conn| queries::provider_symbols::list_provider_symbols(conn, Some(&asset_id)))
+        .map_err(|e| e.to_string())
+}
+
+/// Set the identifier `provider` knows the asset by; an empty value removes
+/// the mapping so the provider goes back to guessing from the ticker.
+#[tauri::command]
+pub fn set_provider_symbol(
+    asset_id: String,
+    provider: String,
+    provider_symbol: String,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    if !matches!(provider.as_str(), "binance" | "coingecko" | "twelve_data") {
+        return Err(format!("Unknown provider: {}", provider));
+    }
+    let provider_symbol = provider_symbol.trim();
+    state
+        .with_db(|conn| {
+            if provider_symbol.is_empty() {
+                queries::provider_symbols::delete_provider_symbol(conn, &asset_id, &provider)
+            } else {
+                queries::provider_symbols::set_provider_symbol(conn, &asset_id, &provider, provider_symbol)
+            }
+        })
+        .map_err(|e| e.to_string())
+}
+
+/// Sector, industry, country, asset class and tags of every active asset.
+#[tauri::command]
+pub fn list_asset_metadata(state: State<'_, AppState>) -> Result<Vec<AssetMetadata>, String> {
+    state
+        .with_db(queries::metadata::list_asset_metadata)
+        .map_err(|e| e.to_string())
+}
+
+/// Save the user's metadata for an asset. Blank fields are cleared, `tags`
+/// replaces the asset's tags and `allow_short` turns oversell checks off.
+#[tauri::command]
+pub fn update_asset_metadata(metadata: AssetMetadata, state: State<'_, AppState>) -> Result<(), String> {
+    let profile = AssetProfile {
+        sector: metadata.sector,
+        industry: metadata.industry,
+        country: metadata.country,
+        asset_class: metadata.asset_class,
+    };
+    state
+        .with_db(|conn| {
+            queries::metadata::update_asset_metadata(conn, &metadata.asset_id, &profile)?;
+            queries::metadata::set_asset_tags(conn, &metadata.asset_id, &metadata.tags)?;
+            queries::metadata::set_allow_short(conn, &metadata.asset_id, metadata.allow_short)
+        })
+        .map_err(|e| e.to_string())
+}
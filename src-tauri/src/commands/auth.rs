@@ -1,5 +1,6 @@
+use crate::commands::trash::auto_purge_trash;
 use crate::state::AppState;
-use tauri::State;
+use tauri::{AppHandle, State};
 
 #[tauri::command]
 pub fn check_first_run(state: State<'_, AppState>) -> Result<bool, String> {
@@ -7,20 +8,32 @@ pub fn check_first_run(state: State<'_, AppState>) -> Result<bool, String> {
 }
 
 #[tauri::command]
-pub fn setup_db(passphrase: String, state: State<'_, AppState>) -> Result<(), String> {
+pub fn setup_db(
+    passphrase: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
     if passphrase.len() < 8 {
         return Err("Passphrase must be at least 8 characters".to_string());
     }
 
     let conn = crate::db::create_db(&state.db_path, &passphrase).map_err(|e| e.to_string())?;
 
-    let mut db = state.db.lock().map_err(|e| e.to_string())?;
-    *db = Some(conn);
-    Ok(())
+    {
+        let mut db = state.db.lock().map_err(|e| e.to_string())?;
+        *db = Some(conn);
+    }
+    state.load_rate_limits().map_err(|e| e.to_string())?;
+    auto_purge_trash(&state);
+    state.start_scheduler(app).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub fn unlock_db(passphrase: String, state: State<'_, AppState>) -> Result<(), String> {
+pub fn unlock_db(
+    passphrase: String,
+    app: AppHandle,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
     let conn = crate::db::unlock_db(&state.db_path, &passphrase).map_err(|e| {
         if e.to_string().contains("not a database") {
             "Incorrect passphrase".to_string()
@@ -29,7 +42,11 @@ pub fn unlock_db(passphrase: String, state: State<'_, AppState>) -> Result<(), S
         }
     })?;
 
-    let mut db = state.db.lock().map_err(|e| e.to_string())?;
-    *db = Some(conn);
-    Ok(())
+    {
+        let mut db = state.db.lock().map_err(|e| e.to_string())?;
+        *db = Some(conn);
+    }
+    state.load_rate_limits().map_err(|e| e.to_string())?;
+    auto_purge_trash(&state);
+    state.start_scheduler(app).map_err(|e| e.to_string())
 }
@@ -0,0 +1,103 @@
+use crate::db::queries;
+use crate::models::{Account, CashBalance, CashEntryKind, CashLedgerEntry};
+use crate::state::AppState;
+use tauri::State;
+
+#[tauri::command]
+pub fn create_account(
+    name: String,
+    currency: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Account, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Account name is required".to_string());
+    }
+    let currency = currency
+        .map(|c| c.trim().to_uppercase())
+        .filter(|c| !c.is_empty())
+        .unwrap_or_else(|| "USD".to_string());
+    state
+        .with_db(|conn| queries::cash::insert_account(conn, name, &currency))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_accounts(state: State<'_, AppState>) -> Result<Vec<Account>, String> {
+    state
+        .with_db(queries::cash::list_accounts)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn delete_account(id: String, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .with_db(|conn| queries::cash::delete_account(conn, &id))
+        .map_err(|e| e.to_string())
+}
+
+/// Deposits, withdrawals and dividends only; buys and sells move cash through
+/// their transaction's account.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub fn add_cash_entry(
+    account_id: String,
+    kind: String,
+    amount: f64,
+    currency: Option<String>,
+    ts: i64,
+    asset_id: Option<String>,
+    notes: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<CashLedgerEntry, String> {
+    let kind = CashEntryKind::from_str(&kind).map_err(|e| e.to_string())?;
+    if !amount.is_finite() || amount <= 0.0 {
+        return Err("Amount must be positive".to_string());
+    }
+    state
+        .with_db(|conn| {
+            let currency = match currency.map(|c| c.trim().to_uppercase()).filter(|c| !c.is_empty()) {
+                Some(c) => c,
+                None => queries::cash::list_accounts(conn)?
+                    .into_iter()
+                    .find(|a| a.id == account_id)
+                    .map(|a| a.currency)
+                    .ok_or_else(|| anyhow::anyhow!("Account not found"))?,
+            };
+            queries::cash::insert_cash_entry(
+                conn,
+                &account_id,
+                kind,
+                amount,
+                &currency,
+                ts,
+                asset_id.as_deref(),
+                notes.as_deref(),
+            )
+        })
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn delete_cash_entry(id: String, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .with_db(|conn| queries::cash::delete_cash_entry(conn, &id))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_cash_ledger(
+    account_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<CashLedgerEntry>, String> {
+    state
+        .with_db(|conn| queries::cash::list_cash_ledger(conn, account_id.as_deref()))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn get_cash_balances(state: State<'_, AppState>) -> Result<Vec<CashBalance>, String> {
+    state
+        .with_db(queries::cash::cash_balances)
+        .map_err(|e| e.to_string())
+}
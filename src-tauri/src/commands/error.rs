@@ -0,0 +1,68 @@
+use crate::domain::validation::ValidationError;
+use crate::models::FieldError;
+use crate::providers::error::ProviderError;
+use serde::Serialize;
+
+/// Error returned to the frontend by price and transaction commands: a stable
+/// `code` to branch on plus a human-readable `message`. Provider failures keep
+/// their `ProviderError::code`; everything else (DB, locking) is `internal`.
+/// Input that fails validation lists the offending `fields`.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct CommandError {
+    pub code: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<FieldError>,
+}
+
+impl CommandError {
+    pub fn new(code: &str, message: impl Into<String>) -> Self {
+        Self { code: code.to_string(), message: message.into(), fields: Vec::new() }
+    }
+
+    /// `code` with one entry per rejected field; the message joins theirs.
+    pub fn with_fields(code: &str, fields: Vec<FieldError>) -> Self {
+        let message = fields.iter().map(|f| f.message.as_str()).collect::<Vec<_>>().join("; ");
+        Self { code: code.to_string(), message, fields }
+    }
+}
+
+impl From<anyhow::Error> for CommandError {
+    fn from(e: anyhow::Error) -> Self {
+        if let Some(invalid) = e.downcast_ref::<ValidationError>() {
+            return Self::with_fields(invalid.code, invalid.fields.clone());
+        }
+        match e.downcast_ref::<ProviderError>() {
+            Some(provider_error) => Self::new(provider_error.code(), e.to_string()),
+            None => Self::new("internal", e.to_string()),
+        }
+    }
+}
+
+impl From<ProviderError> for CommandError {
+    fn from(e: ProviderError) -> Self {
+        Self::new(e.code(), e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_code_survives_context() {
+        let err = anyhow::Error::from(ProviderError::Auth("bad key".into()));
+        let cmd = CommandError::from(err);
+        assert_eq!(cmd.code, "auth");
+        assert_eq!(cmd.message, "Authentication failed: bad key");
+
+        let cmd = CommandError::from(anyhow::anyhow!("Database not unlocked"));
+        assert_eq!(cmd.code, "internal");
+
+        let invalid = ValidationError { code: "invalid_input", fields: vec![FieldError::new("fee", "Fee must be zero or positive")] };
+        let cmd = CommandError::from(anyhow::Error::from(invalid));
+        assert_eq!(cmd.code, "invalid_input");
+        assert_eq!(cmd.fields[0].field, "fee");
+        assert_eq!(cmd.message, "Fee must be zero or positive");
+    }
+}
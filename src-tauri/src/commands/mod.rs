@@ -1,6 +1,15 @@
+pub mod alerts;
 pub mod assets;
 pub mod auth;
+pub mod cash;
+pub mod error;
+pub mod portfolio;
+pub mod price_issues;
 pub mod prices;
 pub mod search;
 pub mod settings;
+pub mod storage;
+pub mod streaming;
 pub mod transactions;
+pub mod trash;
+pub mod watchlists;
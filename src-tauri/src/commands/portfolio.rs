@@ -0,0 +1,270 @@
+use crate::db::queries;
+use crate::domain::{adjustments, allocation};
+use crate::domain::rebalance::{self, Position};
+use crate::models::{AllocationReport, AllocationTarget, Asset, AssetType, CashBalance, RebalancePlan, Transaction};
+use crate::state::AppState;
+use rusqlite::Connection;
+use std::collections::{BTreeMap, HashMap};
+use tauri::State;
+
+const CASH_LABEL: &str = "Cash";
+
+/// Net quantity of an asset per account (`None` for transactions without
+/// one), in post-split shares so that it can be valued at the latest price.
+/// Accounts that net to zero are left out.
+fn quantities_by_account(conn: &Connection, asset_id: &str) -> anyhow::Result<Vec<(Option<String>, f64)>> {
+    let actions = queries::corporate_actions::list_corporate_actions(conn, asset_id)?;
+    let mut by_account: BTreeMap<Option<String>, Vec<Transaction>> = BTreeMap::new();
+    for tx in queries::transactions::list_transactions_by_asset(conn, asset_id)? {
+        by_account.entry(tx.account_id.clone()).or_default().push(tx);
+    }
+    Ok(by_account
+        .into_iter()
+        .map(|(account_id, txs)| (account_id, adjustments::split_adjusted_net_quantity(&txs, &actions)))
+        .filter(|(_, quantity)| *quantity != 0.0)
+        .collect())
+}
+
+/// Active assets with a non-zero holding (in post-split shares) and their
+/// latest stored price (`None` when nothing is stored yet). Cash assets are
+/// worth 1.0.
+fn holdings(conn: &Connection) -> anyhow::Result<Vec<(Asset, f64, Option<f64>)>> {
+    let mut result = Vec::new();
+    for asset in queries::assets::list_assets(conn)? {
+        let quantity: f64 = quantities_by_account(conn, &asset.id)?.iter().map(|(_, q)| q).sum();
+        if quantity == 0.0 {
+            continue;
+        }
+        let price = if asset.asset_type == AssetType::Cash {
+            Some(1.0)
+        } else {
+            queries::prices::get_latest_price(conn, &asset.id)?.map(|row| row.close)
+        };
+        result.push((asset, quantity, price));
+    }
+    Ok(result)
+}
+
+/// Non-zero USD cash balances; other currencies aren't converted.
+fn usd_cash(conn: &Connection) -> anyhow::Result<Vec<CashBalance>> {
+    Ok(queries::cash::cash_balances(conn)?
+        .into_iter()
+        .filter(|b| b.currency == "USD" && b.balance != 0.0)
+        .collect())
+}
+
+/// Current value by asset, asset type, tag, sector and account. Assets without
+/// a stored price are left out; an asset with several tags is split evenly
+/// between them.
+#[tauri::command]
+pub fn get_allocation(state: State<'_, AppState>) -> Result<AllocationReport, String> {
+    state
+        .with_db(|conn| {
+            let holdings = holdings(conn)?;
+            let cash_balances = usd_cash(conn)?;
+            let cash_total: f64 = cash_balances.iter().map(|b| b.balance).sum();
+            let metadata: HashMap<String, _> = queries::metadata::list_asset_metadata(conn)?
+                .into_iter()
+                .map(|m| (m.asset_id.clone(), m))
+                .collect();
+            let valued: Vec<(&Asset, f64)> = holdings
+                .iter()
+                .filter_map(|(asset, quantity, price)| price.map(|p| (asset, quantity * p)))
+                .collect();
+            let cash = || (CASH_LABEL.to_lowercase(), CASH_LABEL.to_string(), cash_total);
+
+            let by_asset = allocation::slices(
+                valued
+                    .iter()
+                    .map(|(asset, value)| (asset.id.clone(), asset.symbol.clone(), *value))
+                    .chain(Some(cash()).filter(|_| cash_total != 0.0)),
+            );
+            let by_type = allocation::slices(
+                valued
+                    .iter()
+                    .map(|(asset, value)| {
+                        let key = asset.asset_type.as_str().to_string();
+                        (key.clone(), key, *value)
+                    })
+                    .chain(Some(cash()).filter(|_| cash_total != 0.0)),
+            );
+            let mut tagged = Vec::new();
+            for (asset, value) in &valued {
+                let tags = metadata.get(&asset.id).map(|m| m.tags.clone()).unwrap_or_default();
+                if tags.is_empty() {
+                    tagged.push((String::new(), "Untagged".to_string(), *value));
+                }
+                for tag in &tags {
+                    tagged.push((tag.to_lowercase(), tag.clone(), value / tags.len() as f64));
+                }
+            }
+            if cash_total != 0.0 {
+                tagged.push(cash());
+            }
+            let by_tag = allocation::slices(tagged);
+            let by_sector = allocation::slices(
+                valued
+                    .iter()
+                    .map(|(asset, value)| match metadata.get(&asset.id).and_then(|m| m.sector.clone()) {
+                        Some(sector) => (sector.to_lowercase(), sector, *value),
+                        None => (String::new(), "Unclassified".to_string(), *value),
+                    })
+                    .chain(Some(cash()).filter(|_| cash_total != 0.0)),
+            );
+
+            let account_names: HashMap<String, String> = queries::cash::list_accounts(conn)?
+                .into_iter()
+                .map(|a| (a.id, a.name))
+                .collect();
+            let mut by_account_items = Vec::new();
+            for (asset, _, price) in &holdings {
+                let Some(price) = price else { continue };
+                for (account_id, quantity) in quantities_by_account(conn, &asset.id)? {
+                    let account_id = account_id.unwrap_or_default();
+                    let label = account_names.get(&account_id).cloned().unwrap_or_else(|| "No account".to_string());
+                    by_account_items.push((account_id, label, quantity * price));
+                }
+            }
+            by_account_items.extend(cash_balances.into_iter().map(|b| (b.account_id, b.account_name, b.balance)));
+            let by_account = allocation::slices(by_account_items);
+
+            Ok(AllocationReport {
+                total_value: valued.iter().map(|(_, v)| v).sum::<f64>() + cash_total,
+                by_asset,
+                by_type,
+                by_tag,
+                by_sector,
+                by_account,
+            })
+        })
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn list_allocation_targets(state: State<'_, AppState>) -> Result<Vec<AllocationTarget>, String> {
+    state
+        .with_db(queries::allocation_targets::list_allocation_targets)
+        .map_err(|e| e.to_string())
+}
+
+/// Set an asset's target weight. Targets across assets may not add up to
+/// more than 100%; the remainder is held as cash.
+#[tauri::command]
+pub fn set_allocation_target(target: AllocationTarget, state: State<'_, AppState>) -> Result<(), String> {
+    if !(0.0..=100.0).contains(&target.target_pct) {
+        return Err("Target must be between 0 and 100%".to_string());
+    }
+    if !target.lot_size.is_finite() || target.lot_size < 0.0 {
+        return Err("Lot size must be zero or positive".to_string());
+    }
+    state
+        .with_db(|conn| {
+            let others: f64 = queries::allocation_targets::list_allocation_targets(conn)?
+                .iter()
+                .filter(|t| t.asset_id != target.asset_id)
+                .map(|t| t.target_pct)
+                .sum();
+            if others + target.target_pct > 100.0 + 1e-9 {
+                anyhow::bail!("Targets would add up to {:.2}%, more than 100%", others + target.target_pct);
+            }
+            queries::allocation_targets::set_allocation_target(conn, &target)
+        })
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn delete_allocation_target(asset_id: String, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .with_db(|conn| queries::allocation_targets::delete_allocation_target(conn, &asset_id))
+        .map_err(|e| e.to_string())
+}
+
+/// Trades that bring the targeted assets to their weights, at the latest
+/// stored prices. USD cash balances are invested too unless `include_cash`
+/// is false; assets without a target are left alone.
+#[tauri::command]
+pub fn plan_rebalance(
+    min_trade_value: Option<f64>,
+    include_cash: Option<bool>,
+    state: State<'_, AppState>,
+) -> Result<RebalancePlan, String> {
+    state
+        .with_db(|conn| {
+            let targets = queries::allocation_targets::list_allocation_targets(conn)?;
+            let held: HashMap<String, (f64, Option<f64>)> = holdings(conn)?
+                .into_iter()
+                .map(|(asset, quantity, price)| (asset.id, (quantity, price)))
+                .collect();
+
+            let mut positions = Vec::new();
+            let mut unpriced = Vec::new();
+            for target in targets {
+                let (quantity, price) = match held.get(&target.asset_id) {
+                    Some(&(quantity, price)) => (quantity, price),
+                    None => {
+                        let asset = queries::assets::get_asset(conn, &target.asset_id)?;
+                        let price = match asset {
+                            Some(a) if a.asset_type == AssetType::Cash => Some(1.0),
+                            _ => queries::prices::get_latest_price(conn, &target.asset_id)?.map(|r| r.close),
+                        };
+                        (0.0, price)
+                    }
+                };
+                match price.filter(|p| *p > 0.0) {
+                    Some(price) => positions.push(Position { target, quantity, price }),
+                    None => unpriced.push(target.asset_id),
+                }
+            }
+
+            let cash = if include_cash.unwrap_or(true) { usd_cash(conn)?.iter().map(|b| b.balance).sum() } else { 0.0 };
+            let mut plan = rebalance::plan_rebalance(&positions, cash, min_trade_value.unwrap_or(0.0).max(0.0));
+            plan.unpriced_asset_ids = unpriced;
+            Ok(plan)
+        })
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_db;
+    use crate::models::{CorporateAction, CorporateActionType, Interval, OHLCVRow, TxType};
+
+    #[test]
+    fn test_holdings_are_valued_in_post_split_shares() {
+        let conn = test_db();
+        let asset = queries::assets::insert_asset(&conn, "AAPL", "Apple", &AssetType::Stock, "USD", None).unwrap();
+        queries::transactions::insert_transaction(&conn, &asset.id, &TxType::Buy, 10.0, 400.0, 86_400, None, 0.0, None)
+            .unwrap();
+        queries::corporate_actions::upsert_corporate_actions(&conn, &[CorporateAction {
+            id: None,
+            asset_id: asset.id.clone(),
+            action_type: CorporateActionType::Split,
+            ts: 10 * 86_400,
+            value: 4.0,
+            provider: "test".to_string(),
+        }])
+        .unwrap();
+        queries::transactions::insert_transaction(&conn, &asset.id, &TxType::Sell, 5.0, 100.0, 20 * 86_400, None, 0.0, None)
+            .unwrap();
+        queries::prices::upsert_prices(&conn, &[OHLCVRow {
+            id: None,
+            asset_id: asset.id.clone(),
+            interval: Interval::OneDay,
+            ts: 30 * 86_400,
+            open: None,
+            high: None,
+            low: None,
+            close: 110.0,
+            volume: None,
+            provider: None,
+        }])
+        .unwrap();
+
+        // 10 shares became 40 in the 4:1 split, 5 of which were sold
+        let held = holdings(&conn).unwrap();
+        assert_eq!(held.len(), 1);
+        assert_eq!((held[0].1, held[0].2), (35.0, Some(110.0)));
+        assert_eq!(quantities_by_account(&conn, &asset.id).unwrap(), vec![(None, 35.0)]);
+    }
+}
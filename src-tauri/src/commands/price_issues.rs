@@ -0,0 +1,35 @@
+use crate::commands::error::CommandError;
+use crate::db::queries;
+use crate::models::{PriceIssue, PriceIssueStatus};
+use crate::state::AppState;
+use tauri::State;
+
+/// Candles quarantined by the ingest checks, newest first. Defaults to the
+/// ones still waiting for review.
+#[tauri::command]
+pub fn list_price_issues(
+    asset_id: Option<String>,
+    status: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<PriceIssue>, CommandError> {
+    let status = match status.as_deref() {
+        Some("all") => None,
+        Some(s) => Some(
+            PriceIssueStatus::from_str(s).map_err(|e| CommandError::new("invalid_input", e.to_string()))?,
+        ),
+        None => Some(PriceIssueStatus::Pending),
+    };
+    Ok(state.with_db(|conn| queries::price_issues::list_price_issues(conn, asset_id.as_deref(), status))?)
+}
+
+/// Mark a quarantined candle as genuine and store it in price history.
+#[tauri::command]
+pub fn accept_price_issue(id: i64, state: State<'_, AppState>) -> Result<PriceIssue, CommandError> {
+    Ok(state.with_db(|conn| queries::price_issues::accept_price_issue(conn, id))?)
+}
+
+/// Mark a quarantined candle as bad data so it stays out of price history.
+#[tauri::command]
+pub fn reject_price_issue(id: i64, state: State<'_, AppState>) -> Result<PriceIssue, CommandError> {
+    Ok(state.with_db(|conn| queries::price_issues::reject_price_issue(conn, id))?)
+}
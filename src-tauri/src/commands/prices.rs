@@ -1,163 +1,742 @@
+use crate::commands::alerts::notify_alerts;
+use crate::commands::error::CommandError;
 use crate::db::queries;
-use crate::models::{AssetType, DateRange, OHLCVRow, PriceCacheMeta};
+use crate::domain::adjustments;
+use crate::domain::calendar::TradingCalendar;
+use crate::domain::cash;
+use crate::domain::gaps;
+use crate::domain::quality::{self, SPIKE_SIGMAS};
+use crate::domain::reconcile;
+use crate::models::{
+    Asset, AssetProfile, AssetType, CorporateAction, DateRange, Interval, OHLCVRow, PriceAdjustment,
+    PriceCacheMeta, PriceDivergence, PriceGap, PriceIssueStatus, PriceTick, ReconciliationReport,
+};
 use crate::providers::binance::BinanceProvider;
 use crate::providers::coingecko::CoinGeckoProvider;
+use crate::providers::error::{ProviderError, ProviderResult};
 use crate::providers::twelve_data::TwelveDataProvider;
 use crate::providers::MarketDataProvider;
 use crate::state::AppState;
 use chrono::Utc;
-use tauri::State;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tauri::{AppHandle, State};
 
 const CACHE_STALENESS_SECS: i64 = 3600; // 1 hour
+const CORPORATE_ACTIONS_STALENESS_SECS: i64 = 7 * 86_400; // 1 week
+const INTERACTIVE_MAX_WAIT: Duration = Duration::from_secs(30);
 
+/// Gaps closer together than this many candles are backfilled with one
+/// request (one Binance kline page).
+const BACKFILL_SPAN_CANDLES: i64 = 1000;
+
+/// Default `reconcile_prices` window and divergence threshold.
+const RECONCILE_DEFAULT_DAYS: i64 = 90;
+const RECONCILE_DEFAULT_THRESHOLD_PCT: f64 = 1.0;
+
+/// Stored candles on each side of a fetch that spike detection compares against.
+const QUALITY_CONTEXT_CANDLES: i64 = 40;
+
+/// How `sync_prices` treats the cache and provider rate limits.
+#[derive(Debug, Clone, Copy)]
+pub struct FetchPolicy {
+    /// Cached data younger than this is considered fresh. Older data stays
+    /// fresh while the asset's market is closed, if fetched after the close.
+    pub max_age_secs: i64,
+    /// Longest wait for rate-limit budget before failing; `None` queues indefinitely.
+    pub max_wait: Option<Duration>,
+}
+
+impl FetchPolicy {
+    /// Interactive fetches from the UI: hourly cache (one candle for intraday),
+    /// and a short wait for budget so the UI doesn't hang.
+    pub fn interactive(interval: Interval) -> Self {
+        Self {
+            max_age_secs: CACHE_STALENESS_SECS.min(interval.secs()),
+            max_wait: Some(INTERACTIVE_MAX_WAIT),
+        }
+    }
+}
+
+/// Download candles for `range` from the asset's provider: Binance for crypto
+/// (CoinGecko when Binance doesn't list the coin), none for cash, Twelve Data
+/// for the rest.
+/// Returns the rows (without `asset_id`) and the provider that served them.
+async fn fetch_history(
+    state: &AppState,
+    asset: &Asset,
+    interval: Interval,
+    range: &DateRange,
+    policy: FetchPolicy,
+) -> anyhow::Result<(Vec<OHLCVRow>, String)> {
+    let provider_name;
+    let rows = match asset.asset_type {
+        AssetType::Crypto => {
+            // Binance: free, no key, real OHLCV, history from 2017
+            let binance = BinanceProvider::new()
+                .with_rate_limiter(state.rate_limiter.clone(), policy.max_wait);
+            let binance_name = binance.name().to_string();
+            let symbol = provider_symbol(state, asset, &binance)?;
+            match binance.fetch_ohlcv(&symbol, interval, range).await {
+                Ok(rows) => {
+                    provider_name = binance_name;
+                    rows
+                }
+                // Fallback: coin not listed on Binance → try CoinGecko. Other
+                // failures (network, rate limit) would hit CoinGecko the same way.
+                Err(ProviderError::NotFound(_)) => {
+                    let api_key = state.with_db(|conn| {
+                        queries::settings::get_setting(conn, "coingecko_api_key")
+                    })?;
+                    let cg = CoinGeckoProvider::new_with_key(api_key.filter(|k| !k.is_empty()))
+                        .with_rate_limiter(state.rate_limiter.clone(), policy.max_wait);
+                    provider_name = cg.name().to_string();
+                    let symbol = provider_symbol(state, asset, &cg)?;
+                    cg.fetch_ohlcv(&symbol, interval, range).await?
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+        AssetType::Cash => anyhow::bail!("Cash has no price provider"),
+        _ => {
+            let provider = twelve_data(state, policy)?;
+            provider_name = provider.name().to_string();
+            let symbol = provider_symbol(state, asset, &provider)?;
+            provider.fetch_ohlcv(&symbol, interval, range).await?
+        }
+    };
+    Ok((rows, provider_name))
+}
+
+/// The identifier `provider` knows `asset` by: the mapping stored from search
+/// (or set by hand), else the provider's guess from the ticker.
+fn provider_symbol(
+    state: &AppState,
+    asset: &Asset,
+    provider: &dyn MarketDataProvider,
+) -> anyhow::Result<String> {
+    let mapped = state.with_db(|conn| {
+        queries::provider_symbols::get_provider_symbol(conn, &asset.id, provider.name())
+    })?;
+    Ok(mapped.unwrap_or_else(|| provider.default_symbol(asset)))
+}
+
+/// Twelve Data client for stocks and commodities; needs an API key.
+fn twelve_data(state: &AppState, policy: FetchPolicy) -> anyhow::Result<TwelveDataProvider> {
+    let api_key = state
+        .with_db(|conn| queries::settings::get_setting(conn, "twelve_data_api_key"))?
+        .filter(|k| !k.is_empty())
+        .ok_or_else(|| {
+            ProviderError::Auth("Twelve Data API key not configured. Add it in Settings.".to_string())
+        })?;
+    Ok(TwelveDataProvider::new(api_key).with_rate_limiter(state.rate_limiter.clone(), policy.max_wait))
+}
+
+/// A provider by its stored name, for fetching from a specific source.
+fn provider_named(
+    state: &AppState,
+    name: &str,
+    policy: FetchPolicy,
+) -> anyhow::Result<Box<dyn MarketDataProvider>> {
+    let limiter = state.rate_limiter.clone();
+    Ok(match name {
+        "binance" => Box::new(BinanceProvider::new().with_rate_limiter(limiter, policy.max_wait)),
+        "coingecko" => {
+            let api_key = state.with_db(|conn| queries::settings::get_setting(conn, "coingecko_api_key"))?;
+            Box::new(
+                CoinGeckoProvider::new_with_key(api_key.filter(|k| !k.is_empty()))
+                    .with_rate_limiter(limiter, policy.max_wait),
+            )
+        }
+        "twelve_data" => Box::new(twelve_data(state, policy)?),
+        other => anyhow::bail!("Unknown provider: {}", other),
+    })
+}
+
+/// Refresh an asset's splits and dividends at most once a week, for the types
+/// that have them. Each refresh costs 40 Twelve Data credits; refreshing an
+/// asset's daily history forces one.
+async fn sync_corporate_actions(
+    state: &AppState,
+    asset: &Asset,
+    policy: FetchPolicy,
+) -> anyhow::Result<()> {
+    let now = Utc::now().timestamp();
+    let last_fetched =
+        state.with_db(|conn| queries::corporate_actions::get_last_fetched(conn, &asset.id))?;
+    if !asset.asset_type.has_corporate_actions()
+        || last_fetched.is_some_and(|t| now - t <= CORPORATE_ACTIONS_STALENESS_SECS)
+    {
+        return Ok(());
+    }
+
+    let provider = twelve_data(state, policy)?;
+    let symbol = provider_symbol(state, asset, &provider)?;
+    let mut actions = provider.fetch_corporate_actions(&symbol).await?;
+    for action in &mut actions {
+        action.asset_id = asset.id.clone();
+    }
+    state.with_db(|conn| {
+        queries::corporate_actions::upsert_corporate_actions(conn, &actions)?;
+        queries::corporate_actions::set_last_fetched(conn, &asset.id, now)
+    })
+}
+
+/// Store fetched candles that pass the quality checks and quarantine the rest
+/// in `price_issues`. A candle already reviewed keeps its verdict as long as
+/// the provider still sends the same close.
+fn ingest_prices(
+    conn: &rusqlite::Connection,
+    asset_id: &str,
+    interval: Interval,
+    rows: &[OHLCVRow],
+) -> anyhow::Result<()> {
+    let reviewed: HashMap<i64, (PriceIssueStatus, f64)> =
+        queries::price_issues::list_price_issues(conn, Some(asset_id), None)?
+            .into_iter()
+            .filter(|i| i.candle.interval == interval && i.status != PriceIssueStatus::Pending)
+            .map(|i| (i.candle.ts, (i.status, i.candle.close)))
+            .collect();
+
+    let mut trusted = Vec::new();
+    let mut unchecked = Vec::new();
+    for row in rows {
+        match reviewed.get(&row.ts) {
+            Some(&(PriceIssueStatus::Accepted, close)) if close == row.close => trusted.push(row.clone()),
+            Some(&(PriceIssueStatus::Rejected, close)) if close == row.close => {}
+            _ => unchecked.push(row.clone()),
+        }
+    }
+
+    let first = unchecked.iter().map(|r| r.ts).min();
+    let last = unchecked.iter().map(|r| r.ts).max();
+    let (Some(first), Some(last)) = (first, last) else {
+        return queries::prices::upsert_prices(conn, &trusted);
+    };
+    let margin = QUALITY_CONTEXT_CANDLES * interval.secs();
+    let context =
+        queries::prices::get_prices(conn, asset_id, interval, Some(first - margin), Some(last + margin))?;
+
+    let issues = quality::check_candles(&context, &unchecked, SPIKE_SIGMAS);
+    let flagged: HashSet<i64> = issues.iter().map(|i| i.ts).collect();
+    let (quarantined, clean): (Vec<OHLCVRow>, Vec<OHLCVRow>) =
+        unchecked.into_iter().partition(|r| flagged.contains(&r.ts));
+    trusted.extend(clean);
+    queries::prices::upsert_prices(conn, &trusted)?;
+
+    for issue in &issues {
+        if let Some(candle) = quarantined.iter().find(|r| r.ts == issue.ts) {
+            queries::price_issues::quarantine(conn, candle, issue.kind, &issue.detail)?;
+        }
+    }
+    Ok(())
+}
+
+/// Fetch and store new candles for one asset if its cache is stale.
+/// Shared by the `fetch_prices` command and the background scheduler.
+pub async fn sync_prices(
+    state: &AppState,
+    asset_id: &str,
+    interval: Interval,
+    policy: FetchPolicy,
+) -> anyhow::Result<()> {
+    // Get asset info
+    let asset = state.with_db(|conn| {
+        queries::assets::get_asset(conn, asset_id)?
+            .ok_or_else(|| anyhow::anyhow!("Asset not found"))
+    })?;
+
+    // Cash is valued at a flat 1.0 on read; there is nothing to fetch
+    if asset.asset_type == AssetType::Cash {
+        return Ok(());
+    }
+
+    // Check cache staleness: a closed market has nothing new after its final candle
+    let now = Utc::now().timestamp();
+    let calendar = TradingCalendar::for_asset(&asset);
+    let is_stale = state.with_db(|conn| {
+        let meta = queries::prices::get_cache_meta(conn, asset_id, interval)?;
+        Ok(meta.is_none_or(|m| calendar.is_stale(m.last_fetched, now, policy.max_age_secs)))
+    })?;
+    if !is_stale {
+        return Ok(());
+    }
+
+    // Incremental fetch: start from the last stored candle, or fall back to max
+    // history (daily) / the retention window (intraday).
+    let max_ts = state.with_db(|conn| queries::prices::get_max_ts(conn, asset_id, interval))?;
+
+    let range = DateRange {
+        from: match max_ts {
+            // Re-fetch the last candle: it may still have been forming when
+            // stored, and spike checks need its successor to judge it
+            Some(ts) => ts,
+            // Each provider handles 0 as "max history" internally
+            None => interval.retention_secs().map_or(0, |r| now - r),
+        },
+        to: now,
+    };
+
+    let (mut rows, provider_name) = fetch_history(state, &asset, interval, &range, policy).await?;
+
+    // Set asset_id and provenance on all rows
+    for row in &mut rows {
+        row.asset_id = asset_id.to_string();
+        row.provider = Some(provider_name.clone());
+    }
+    // Save to DB, folding intraday candles that fell out of the retention window into daily ones
+    state.with_db(|conn| {
+        ingest_prices(conn, asset_id, interval, &rows)?;
+        if let Some(retention) = interval.retention_secs() {
+            queries::prices::compact_prices(conn, asset_id, interval, now - retention)?;
+        }
+        queries::prices::update_cache_meta(conn, asset_id, interval, &provider_name, now)?;
+        Ok(())
+    })?;
+
+    // Best effort: until corporate actions load, adjusted series equal raw ones
+    if interval == Interval::OneDay {
+        let _ = sync_corporate_actions(state, &asset, policy).await;
+        let _ = sync_profile(state, &asset, policy, false).await;
+    }
+    Ok(())
+}
+
+/// Fill in sector, industry and country from the provider's company profile,
+/// once per asset unless `force`d. Fields the user has set are kept.
+async fn sync_profile(
+    state: &AppState,
+    asset: &Asset,
+    policy: FetchPolicy,
+    force: bool,
+) -> anyhow::Result<()> {
+    if !matches!(asset.asset_type, AssetType::Stock | AssetType::Etf | AssetType::MutualFund) {
+        return Ok(());
+    }
+    let fetched_at = state.with_db(|conn| queries::metadata::get_profile_fetched_at(conn, &asset.id))?;
+    if fetched_at.is_some() && !force {
+        return Ok(());
+    }
+
+    let provider = twelve_data(state, policy)?;
+    let symbol = provider_symbol(state, asset, &provider)?;
+    let profile = match provider.fetch_profile(&symbol).await {
+        Ok(profile) => profile.unwrap_or_default(),
+        // Funds often have no profile; don't ask again
+        Err(ProviderError::NotFound(_)) => AssetProfile::default(),
+        Err(e) => return Err(e.into()),
+    };
+    let now = Utc::now().timestamp();
+    state.with_db(|conn| queries::metadata::fill_asset_metadata(conn, &asset.id, &profile, now))
+}
+
+/// Interval argument from the frontend; daily when omitted.
+fn parse_interval(interval: Option<String>) -> Result<Interval, CommandError> {
+    match interval {
+        Some(s) => Interval::from_str(&s).map_err(|e| CommandError::new("invalid_input", e.to_string())),
+        None => Ok(Interval::OneDay),
+    }
+}
+
+/// Candles for an asset, raw by default or adjusted for splits (`"split"`) or
+/// splits and dividends (`"total"`). Stored candles stay raw.
 #[tauri::command]
 pub async fn fetch_prices(
     asset_id: String,
+    interval: Option<String>,
+    adjustment: Option<String>,
+    app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<Vec<OHLCVRow>, String> {
-    // Get asset info
-    let asset = state
-        .with_db(|conn| {
-            queries::assets::get_asset(conn, &asset_id)?
-                .ok_or_else(|| anyhow::anyhow!("Asset not found"))
-        })
-        .map_err(|e| e.to_string())?;
+) -> Result<Vec<OHLCVRow>, CommandError> {
+    let interval = parse_interval(interval)?;
+    let adjustment = match adjustment {
+        Some(s) => PriceAdjustment::from_str(&s).map_err(|e| CommandError::new("invalid_input", e.to_string()))?,
+        None => PriceAdjustment::Raw,
+    };
+
+    let synced = sync_prices(&state, &asset_id, interval, FetchPolicy::interactive(interval)).await;
+    if synced.is_ok() && interval == Interval::OneDay {
+        notify_alerts(&app, &state, &asset_id);
+    }
+
+    // Return from DB — on fetch failure, cached data is still better than an error
+    let cached = state.with_db(|conn| {
+        if let Some(asset) = queries::assets::get_asset(conn, &asset_id)? {
+            if asset.asset_type == AssetType::Cash {
+                return cash_candles(conn, &asset, interval);
+            }
+        }
+        let mut rows = queries::prices::get_prices(conn, &asset_id, interval, None, None)?;
+        if adjustment != PriceAdjustment::Raw {
+            let actions = queries::corporate_actions::list_corporate_actions(conn, &asset_id)?;
+            adjustments::adjust_prices(&mut rows, &actions, adjustment);
+        }
+        Ok(rows)
+    })?;
+    match synced {
+        Err(e) if cached.is_empty() => {
+            let err = CommandError::from(e);
+            Err(CommandError::new(&err.code, format!("Failed to fetch prices: {}", err.message)))
+        }
+        _ => Ok(cached),
+    }
+}
 
-    // Check cache staleness
+/// Flat candles for a cash asset from its first transaction (or when it was
+/// added), limited to the interval's retention window.
+fn cash_candles(
+    conn: &rusqlite::Connection,
+    asset: &Asset,
+    interval: Interval,
+) -> anyhow::Result<Vec<OHLCVRow>> {
     let now = Utc::now().timestamp();
-    let is_stale = state
-        .with_db(|conn| {
-            let meta = queries::prices::get_cache_meta(conn, &asset_id)?;
-            Ok(meta.is_none_or(|m| now - m.last_fetched > CACHE_STALENESS_SECS))
+    let first_tx = queries::transactions::list_transactions_by_asset(conn, &asset.id)?
+        .iter()
+        .map(|tx| tx.ts)
+        .min();
+    let mut from = first_tx.map_or(asset.added_at, |ts| ts.min(asset.added_at));
+    if let Some(retention) = interval.retention_secs() {
+        from = from.max(now - retention);
+    }
+    Ok(cash::flat_candles(&asset.id, interval, from, now))
+}
+
+/// One batch request to `provider` for all `assets`; returns quotes for the
+/// symbols it knew and the assets it didn't. `mappings` holds stored provider
+/// symbols keyed by (asset id, provider).
+async fn batch_quotes(
+    provider: &dyn MarketDataProvider,
+    assets: Vec<Asset>,
+    mappings: &HashMap<(String, String), String>,
+    now: i64,
+) -> ProviderResult<(Vec<PriceTick>, Vec<Asset>)> {
+    if assets.is_empty() {
+        return Ok((vec![], vec![]));
+    }
+    let symbols: Vec<String> = assets
+        .iter()
+        .map(|a| {
+            mappings
+                .get(&(a.id.clone(), provider.name().to_string()))
+                .cloned()
+                .unwrap_or_else(|| provider.default_symbol(a))
         })
-        .map_err(|e| e.to_string())?;
-
-    if is_stale {
-        // Incremental fetch: start from the day after the last stored price,
-        // or fall back to 1 year ago if no data exists yet.
-        let max_ts = state
-            .with_db(|conn| queries::prices::get_max_ts(conn, &asset_id))
-            .map_err(|e| e.to_string())?;
-
-        let range = DateRange {
-            from: match max_ts {
-                Some(ts) => ts + 86400, // day after last stored price
-                None => 0, // Each provider handles 0 as "max history" internally
-            },
-            to: now,
-        };
+        .collect();
+    let prices = provider.fetch_current_prices(&symbols).await?;
 
-        let provider_name;
-        let result = match asset.asset_type {
-            AssetType::Crypto => {
-                // Binance: free, no key, real OHLCV, history from 2017
-                let binance = BinanceProvider::new();
-                let binance_name = binance.name().to_string();
-                state
-                    .check_rate_limit(&binance_name)
-                    .map_err(|e| e.to_string())?;
-                match binance.fetch_ohlcv(&asset.symbol, &range).await {
-                    Ok(rows) => {
-                        provider_name = binance_name;
-                        Ok(rows)
-                    }
-                    Err(_) => {
-                        // Fallback: coin not listed on Binance → try CoinGecko
-                        let api_key = state
-                            .with_db(|conn| {
-                                queries::settings::get_setting(conn, "coingecko_api_key")
-                            })
-                            .map_err(|e| e.to_string())?;
-                        let cg =
-                            CoinGeckoProvider::new_with_key(api_key.filter(|k| !k.is_empty()));
-                        provider_name = cg.name().to_string();
-                        state
-                            .check_rate_limit(&provider_name)
-                            .map_err(|e| e.to_string())?;
-                        cg.fetch_ohlcv(&asset.symbol, &range).await
-                    }
-                }
-            }
-            _ => {
-                // Need API key for Twelve Data
-                let api_key = state
-                    .with_db(|conn| queries::settings::get_setting(conn, "twelve_data_api_key"))
-                    .map_err(|e| e.to_string())?
-                    .filter(|k| !k.is_empty())
-                    .ok_or_else(|| {
-                        "Twelve Data API key not configured. Add it in Settings.".to_string()
-                    })?;
-                let provider = TwelveDataProvider::new(api_key);
-                provider_name = provider.name().to_string();
-                state
-                    .check_rate_limit(&provider_name)
-                    .map_err(|e| e.to_string())?;
-                provider.fetch_ohlcv(&asset.symbol, &range).await
-            }
-        };
+    let mut quotes = Vec::new();
+    let mut missing = Vec::new();
+    for (asset, symbol) in assets.into_iter().zip(&symbols) {
+        match prices.get(symbol) {
+            // A zero quote is a provider glitch, not a price
+            Some(&price) if price > 0.0 => quotes.push(PriceTick {
+                asset_id: asset.id,
+                symbol: asset.symbol,
+                price,
+                ts: now,
+                provider: provider.name().to_string(),
+            }),
+            _ => missing.push(asset),
+        }
+    }
+    Ok((quotes, missing))
+}
 
-        match result {
-            Ok(mut rows) => {
-                // Set asset_id on all rows
-                for row in &mut rows {
-                    row.asset_id = asset_id.clone();
-                }
-                // Save to DB
-                state
-                    .with_db(|conn| {
-                        queries::prices::upsert_prices(conn, &rows)?;
-                        queries::prices::update_cache_meta(conn, &asset_id, &provider_name, now)?;
-                        Ok(())
-                    })
-                    .map_err(|e| e.to_string())?;
-            }
-            Err(e) => {
-                // If fetch fails, try to return cached data
-                let cached = state
-                    .with_db(|conn| queries::prices::get_prices(conn, &asset_id, None, None))
-                    .map_err(|e| e.to_string())?;
-                if cached.is_empty() {
-                    return Err(format!("Failed to fetch prices: {}", e));
-                }
-                return Ok(cached);
+/// Current prices for every active asset using provider batch endpoints —
+/// one request per provider instead of one per asset. Each quote is folded
+/// into today's daily candle so holdings value off the latest price.
+#[tauri::command]
+pub async fn fetch_quotes(state: State<'_, AppState>) -> Result<Vec<PriceTick>, CommandError> {
+    let (assets, providers_by_asset, mappings, td_key, cg_key) = state
+        .with_db(|conn| {
+            let assets = queries::assets::list_assets(conn)?;
+            let providers_by_asset: HashMap<String, String> =
+                queries::prices::list_all_cache_meta(conn)?
+                    .into_iter()
+                    .map(|m| (m.asset_id, m.provider))
+                    .collect();
+            let mappings: HashMap<(String, String), String> =
+                queries::provider_symbols::list_provider_symbols(conn, None)?
+                    .into_iter()
+                    .map(|m| ((m.asset_id, m.provider), m.provider_symbol))
+                    .collect();
+            let td_key = queries::settings::get_setting(conn, "twelve_data_api_key")?
+                .filter(|k| !k.is_empty());
+            let cg_key = queries::settings::get_setting(conn, "coingecko_api_key")?
+                .filter(|k| !k.is_empty());
+            Ok((assets, providers_by_asset, mappings, td_key, cg_key))
+        })?;
+
+    let mut binance_assets = Vec::new();
+    let mut coingecko_assets = Vec::new();
+    let mut twelve_data_assets = Vec::new();
+    for asset in assets {
+        match asset.asset_type {
+            // History that came from CoinGecko means the coin isn't listed on Binance
+            AssetType::Crypto if providers_by_asset.get(&asset.id).map(String::as_str) == Some("coingecko") => {
+                coingecko_assets.push(asset)
             }
+            AssetType::Crypto => binance_assets.push(asset),
+            AssetType::Cash => {}
+            _ => twelve_data_assets.push(asset),
+        }
+    }
+
+    let now = Utc::now().timestamp();
+    let mut quotes = Vec::new();
+    let mut errors = Vec::new();
+
+    let limiter = state.rate_limiter.clone();
+    let max_wait = Some(INTERACTIVE_MAX_WAIT);
+    let binance = BinanceProvider::new().with_rate_limiter(limiter.clone(), max_wait);
+    match batch_quotes(&binance, binance_assets, &mappings, now).await {
+        // Coins Binance doesn't list get another chance on CoinGecko
+        Ok((found, missing)) => {
+            quotes.extend(found);
+            coingecko_assets.extend(missing);
+        }
+        Err(e) => errors.push(e),
+    }
+    let coingecko =
+        CoinGeckoProvider::new_with_key(cg_key).with_rate_limiter(limiter.clone(), max_wait);
+    match batch_quotes(&coingecko, coingecko_assets, &mappings, now).await {
+        Ok((found, _)) => quotes.extend(found),
+        Err(e) => errors.push(e),
+    }
+    // Stocks and commodities can only be quoted when a Twelve Data key is configured
+    if let Some(key) = td_key {
+        let twelve_data = TwelveDataProvider::new(key).with_rate_limiter(limiter, max_wait);
+        match batch_quotes(&twelve_data, twelve_data_assets, &mappings, now).await {
+            Ok((found, _)) => quotes.extend(found),
+            Err(e) => errors.push(e),
         }
     }
 
-    // Return from DB
     state
-        .with_db(|conn| queries::prices::get_prices(conn, &asset_id, None, None))
-        .map_err(|e| e.to_string())
+        .with_db(|conn| {
+            for quote in &quotes {
+                queries::prices::apply_quote(conn, &quote.asset_id, quote.price, quote.ts, &quote.provider)?;
+            }
+            Ok(())
+        })?;
+
+    // Partial results are still useful; only fail when nothing came back at all
+    if let (true, Some(first)) = (quotes.is_empty(), errors.first()) {
+        let messages: Vec<String> = errors.iter().map(ToString::to_string).collect();
+        return Err(CommandError::new(
+            first.code(),
+            format!("Failed to fetch quotes: {}", messages.join("; ")),
+        ));
+    }
+    Ok(quotes)
 }
 
 #[tauri::command]
 pub async fn list_cache_meta(
     state: State<'_, AppState>,
-) -> Result<Vec<PriceCacheMeta>, String> {
-    state
-        .with_db(|conn| queries::prices::list_all_cache_meta(conn))
-        .map_err(|e| e.to_string())
+) -> Result<Vec<PriceCacheMeta>, CommandError> {
+    Ok(state.with_db(queries::prices::list_all_cache_meta)?)
 }
 
+/// Re-download one interval's history from scratch. Other intervals are kept:
+/// intraday candles past the provider's reach couldn't be fetched again.
 #[tauri::command]
 pub async fn refresh_asset(
     asset_id: String,
+    interval: Option<String>,
+    app: AppHandle,
     state: State<'_, AppState>,
-) -> Result<Vec<OHLCVRow>, String> {
-    // Clear cache meta and price history to force full re-download
+) -> Result<Vec<OHLCVRow>, CommandError> {
+    let parsed = parse_interval(interval.clone())?;
     state
         .with_db(|conn| {
             conn.execute(
-                "DELETE FROM price_cache_meta WHERE asset_id = ?1",
-                rusqlite::params![asset_id],
+                "DELETE FROM price_cache_meta WHERE asset_id = ?1 AND interval = ?2",
+                rusqlite::params![asset_id, parsed.as_str()],
             )?;
             conn.execute(
-                "DELETE FROM historical_prices WHERE asset_id = ?1",
-                rusqlite::params![asset_id],
+                "DELETE FROM historical_prices WHERE asset_id = ?1 AND interval = ?2",
+                rusqlite::params![asset_id, parsed.as_str()],
             )?;
+            if parsed == Interval::OneDay {
+                conn.execute(
+                    "DELETE FROM corporate_actions_meta WHERE asset_id = ?1",
+                    rusqlite::params![asset_id],
+                )?;
+            }
             Ok(())
+        })?;
+
+    fetch_prices(asset_id, interval, None, app, state).await
+}
+
+/// Fetch the asset's company profile again, filling metadata the user hasn't set.
+#[tauri::command]
+pub async fn refresh_asset_profile(asset_id: String, state: State<'_, AppState>) -> Result<(), CommandError> {
+    let asset = state.with_db(|conn| {
+        queries::assets::get_asset(conn, &asset_id)?.ok_or_else(|| anyhow::anyhow!("Asset not found"))
+    })?;
+    sync_profile(&state, &asset, FetchPolicy::interactive(Interval::OneDay), true).await?;
+    Ok(())
+}
+
+/// Stored splits and dividends for an asset, oldest first.
+#[tauri::command]
+pub async fn list_corporate_actions(
+    asset_id: String,
+    state: State<'_, AppState>,
+) -> Result<Vec<CorporateAction>, CommandError> {
+    Ok(state.with_db(|conn| queries::corporate_actions::list_corporate_actions(conn, &asset_id))?)
+}
+
+fn scan_asset_gaps(
+    conn: &rusqlite::Connection,
+    asset: &Asset,
+    interval: Interval,
+) -> anyhow::Result<Vec<PriceGap>> {
+    let timestamps = queries::prices::get_timestamps(conn, &asset.id, interval)?;
+    let calendar = TradingCalendar::for_asset(asset);
+    Ok(gaps::find_gaps(&timestamps, interval, calendar)
+        .into_iter()
+        .map(|gap| PriceGap {
+            asset_id: asset.id.clone(),
+            symbol: asset.symbol.clone(),
+            interval,
+            from: gap.from,
+            to: gap.to,
+            missing: gap.missing,
         })
-        .map_err(|e| e.to_string())?;
+        .collect())
+}
+
+/// Missing candles inside stored history, for one asset or all active ones.
+/// Weekends and exchange holidays are not gaps for stocks; crypto trades 24/7.
+#[tauri::command]
+pub async fn scan_price_gaps(
+    asset_id: Option<String>,
+    interval: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<PriceGap>, CommandError> {
+    let interval = parse_interval(interval)?;
+    Ok(state.with_db(|conn| {
+        let assets = match &asset_id {
+            Some(id) => vec![queries::assets::get_asset(conn, id)?
+                .ok_or_else(|| anyhow::anyhow!("Asset not found"))?],
+            None => queries::assets::list_assets(conn)?,
+        };
+        let mut found = Vec::new();
+        for asset in &assets {
+            found.extend(scan_asset_gaps(conn, asset, interval)?);
+        }
+        Ok(found)
+    })?)
+}
+
+/// Refetch only the missing ranges of one asset's history, leaving the rest
+/// of the cache alone. Returns the gaps that remain afterwards — usually
+/// closures the calendar doesn't know about.
+#[tauri::command]
+pub async fn backfill_price_gaps(
+    asset_id: String,
+    interval: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<PriceGap>, CommandError> {
+    let interval = parse_interval(interval)?;
+    let (asset, found) = state.with_db(|conn| {
+        let asset = queries::assets::get_asset(conn, &asset_id)?
+            .ok_or_else(|| anyhow::anyhow!("Asset not found"))?;
+        let calendar = TradingCalendar::for_asset(&asset);
+        let timestamps = queries::prices::get_timestamps(conn, &asset.id, interval)?;
+        Ok((asset, gaps::find_gaps(&timestamps, interval, calendar)))
+    })?;
+
+    let policy = FetchPolicy::interactive(interval);
+    for gap in gaps::coalesce(&found, interval, BACKFILL_SPAN_CANDLES) {
+        let range = DateRange { from: gap.from, to: gap.end(interval) };
+        let (mut rows, provider_name) = fetch_history(&state, &asset, interval, &range, policy).await?;
+        for row in &mut rows {
+            row.asset_id = asset.id.clone();
+            row.provider = Some(provider_name.clone());
+        }
+        state.with_db(|conn| ingest_prices(conn, &asset.id, interval, &rows))?;
+    }
+
+    Ok(state.with_db(|conn| scan_asset_gaps(conn, &asset, interval))?)
+}
+
+/// Fetch a range of an asset's history from a second provider, keep it
+/// alongside the stored series, and report candles whose closes differ by more
+/// than `threshold_pct` (default 1%). Defaults to the last 90 days of daily candles.
+#[tauri::command]
+pub async fn reconcile_prices(
+    asset_id: String,
+    reference_provider: String,
+    interval: Option<String>,
+    from: Option<i64>,
+    to: Option<i64>,
+    threshold_pct: Option<f64>,
+    state: State<'_, AppState>,
+) -> Result<ReconciliationReport, CommandError> {
+    let interval = parse_interval(interval)?;
+    let to = to.unwrap_or_else(|| Utc::now().timestamp());
+    let from = from.unwrap_or(to - RECONCILE_DEFAULT_DAYS * 86_400);
+    let threshold_pct = threshold_pct.unwrap_or(RECONCILE_DEFAULT_THRESHOLD_PCT);
+    if from > to || threshold_pct < 0.0 {
+        return Err(CommandError::new("invalid_input", "Invalid range or threshold"));
+    }
+
+    let asset = state.with_db(|conn| {
+        queries::assets::get_asset(conn, &asset_id)?.ok_or_else(|| anyhow::anyhow!("Asset not found"))
+    })?;
+    let policy = FetchPolicy::interactive(interval);
+    let provider = provider_named(&state, &reference_provider, policy)
+        .map_err(|e| CommandError::new("invalid_input", e.to_string()))?;
+    if !provider.supports_interval(interval) {
+        return Err(CommandError::new(
+            "invalid_input",
+            format!("{} has no {} candles", reference_provider, interval.as_str()),
+        ));
+    }
+
+    let symbol = provider_symbol(&state, &asset, provider.as_ref())?;
+    let mut rows = provider.fetch_ohlcv(&symbol, interval, &DateRange { from, to }).await?;
+    for row in &mut rows {
+        row.asset_id = asset.id.clone();
+        row.provider = Some(reference_provider.clone());
+    }
+
+    let now = Utc::now().timestamp();
+    let (compared, divergences) = state.with_db(|conn| {
+        queries::prices::upsert_reference_prices(conn, &rows, now)?;
+        let primary = queries::prices::get_prices(conn, &asset.id, interval, Some(from), Some(to))?;
+        let reference = queries::prices::get_reference_prices(
+            conn,
+            &asset.id,
+            interval,
+            &reference_provider,
+            from,
+            to,
+        )?;
+        Ok(reconcile::find_divergences(&primary, &reference, &reference_provider, threshold_pct))
+    })?;
 
-    fetch_prices(asset_id, state).await
+    Ok(ReconciliationReport {
+        asset_id: asset.id,
+        interval,
+        reference_provider,
+        from,
+        to,
+        compared,
+        threshold_pct,
+        divergences: divergences
+            .into_iter()
+            .map(|d| PriceDivergence {
+                ts: d.ts,
+                provider: d.provider,
+                close: d.close,
+                reference_close: d.reference_close,
+                diff_pct: d.diff_pct,
+            })
+            .collect(),
+    })
 }
@@ -44,8 +44,9 @@ pub async fn search_symbols(
     );
 
     // Merge: TwelveData (stocks/ETFs) → Binance (crypto primary) → CoinGecko (rare alts fallback)
-    // Deduplicate by symbol — keeps first occurrence, so Binance crypto beats CoinGecko duplicates
-    let mut seen: HashSet<String> = HashSet::new();
+    // Deduplicate by symbol and type, plus exchange outside crypto so each listing of a
+    // ticker stays pickable — keeps first occurrence, so Binance crypto beats CoinGecko duplicates
+    let mut seen: HashSet<(String, String, Option<String>)> = HashSet::new();
     let mut results: Vec<SymbolSearchResult> = Vec::new();
 
     for r in [td_results, bn_results, cg_results]
@@ -53,7 +54,8 @@ pub async fn search_symbols(
         .filter_map(|r| r.ok())
         .flatten()
     {
-        if seen.insert(r.symbol.clone()) {
+        let venue = if r.asset_type == "crypto" { None } else { r.exchange.clone() };
+        if seen.insert((r.symbol.clone(), r.asset_type.clone(), venue)) {
             results.push(r);
         }
     }
@@ -1,4 +1,7 @@
+use crate::commands::trash::{purge_expired_trash, TRASH_RETENTION_KEY};
 use crate::db::queries;
+use crate::rate_limit::{RateLimit, RATE_LIMIT_SUFFIX};
+use crate::scheduler::REFRESH_INTERVAL_KEY;
 use crate::state::AppState;
 use tauri::State;
 
@@ -38,7 +41,22 @@ pub fn get_setting(key: String, state: State<'_, AppState>) -> Result<Option<Str
 
 #[tauri::command]
 pub fn save_setting(key: String, value: String, state: State<'_, AppState>) -> Result<(), String> {
+    let is_rate_limit = key.ends_with(RATE_LIMIT_SUFFIX);
+    // Empty resets to the provider default; anything else must parse
+    if is_rate_limit && !value.trim().is_empty() {
+        RateLimit::parse(&value).map_err(|e| e.to_string())?;
+    }
     state
         .with_db(|conn| queries::settings::set_setting(conn, &key, &value))
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+    if key == REFRESH_INTERVAL_KEY {
+        state.wake_scheduler();
+    }
+    if is_rate_limit {
+        state.load_rate_limits().map_err(|e| e.to_string())?;
+    }
+    if key == TRASH_RETENTION_KEY {
+        state.with_db(purge_expired_trash).map_err(|e| e.to_string())?;
+    }
+    Ok(())
 }
@@ -0,0 +1,35 @@
+use crate::db::queries;
+use crate::models::{OptimizeResult, StorageReport};
+use crate::state::AppState;
+use chrono::Utc;
+use tauri::State;
+
+#[tauri::command]
+pub fn get_storage_report(state: State<'_, AppState>) -> Result<StorageReport, String> {
+    state
+        .with_db(queries::storage::storage_report)
+        .map_err(|e| e.to_string())
+}
+
+/// Apply retention to all price history, drop orphaned price rows, then
+/// `VACUUM` to shrink the file.
+#[tauri::command]
+pub fn optimize_database(state: State<'_, AppState>) -> Result<OptimizeResult, String> {
+    state
+        .with_db(|conn| {
+            let (bytes_before, _) = queries::storage::database_size(conn)?;
+            let (daily_rows_added, intraday_rows_removed) =
+                queries::storage::apply_retention(conn, Utc::now().timestamp())?;
+            let orphan_rows_removed = queries::storage::delete_orphans(conn)?;
+            queries::storage::vacuum(conn)?;
+            let (bytes_after, _) = queries::storage::database_size(conn)?;
+            Ok(OptimizeResult {
+                daily_rows_added,
+                intraday_rows_removed,
+                orphan_rows_removed,
+                bytes_before,
+                bytes_after,
+            })
+        })
+        .map_err(|e| e.to_string())
+}
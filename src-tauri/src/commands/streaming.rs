@@ -0,0 +1,122 @@
+use crate::db::queries;
+use crate::models::AssetType;
+use crate::providers::binance::BinanceProvider;
+use crate::providers::coingecko::CoinGeckoProvider;
+use crate::providers::twelve_data::TwelveDataProvider;
+use crate::providers::MarketDataProvider;
+use crate::state::AppState;
+use crate::streaming::{binance_ws, poller, StreamHandle, Subscription, TickSink, PRICE_TICK_EVENT};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::watch;
+
+const POLL_INTERVAL_SECS: u64 = 60;
+
+/// Start (or restart) live prices for all active assets.
+/// Crypto follows Binance WebSocket tickers; everything else but cash is polled.
+#[tauri::command]
+pub async fn start_price_stream(app: AppHandle, state: State<'_, AppState>) -> Result<(), String> {
+    let (assets, providers_by_asset, mappings, td_key, cg_key) = state
+        .with_db(|conn| {
+            let assets = queries::assets::list_assets(conn)?;
+            let providers_by_asset: HashMap<String, String> =
+                queries::prices::list_all_cache_meta(conn)?
+                    .into_iter()
+                    .map(|m| (m.asset_id, m.provider))
+                    .collect();
+            let mappings: HashMap<(String, String), String> =
+                queries::provider_symbols::list_provider_symbols(conn, None)?
+                    .into_iter()
+                    .map(|m| ((m.asset_id, m.provider), m.provider_symbol))
+                    .collect();
+            let td_key = queries::settings::get_setting(conn, "twelve_data_api_key")?
+                .filter(|k| !k.is_empty());
+            let cg_key = queries::settings::get_setting(conn, "coingecko_api_key")?
+                .filter(|k| !k.is_empty());
+            Ok((assets, providers_by_asset, mappings, td_key, cg_key))
+        })
+        .map_err(|e| e.to_string())?;
+
+    // Pollers never wait for budget: an exhausted limit just skips that round
+    let limiter = state.rate_limiter.clone();
+    let no_wait = Some(Duration::ZERO);
+    let binance = BinanceProvider::new();
+    let coingecko = CoinGeckoProvider::new_with_key(cg_key).with_rate_limiter(limiter.clone(), no_wait);
+    // Stocks and commodities can only be polled when a Twelve Data key is configured
+    let twelve_data = td_key.map(|key| TwelveDataProvider::new(key).with_rate_limiter(limiter, no_wait));
+    let mut binance_subs = Vec::new();
+    let mut coingecko_subs = Vec::new();
+    let mut twelve_data_subs = Vec::new();
+    for asset in assets {
+        // A stored mapping wins over the provider's guess from the ticker
+        let subscribe = |provider: &str, default_symbol: String| Subscription {
+            asset_id: asset.id.clone(),
+            symbol: asset.symbol.clone(),
+            provider_symbol: mappings
+                .get(&(asset.id.clone(), provider.to_string()))
+                .cloned()
+                .unwrap_or(default_symbol),
+        };
+        match asset.asset_type {
+            // History that came from CoinGecko means the coin isn't listed on Binance
+            AssetType::Crypto if providers_by_asset.get(&asset.id).map(String::as_str) == Some("coingecko") => {
+                coingecko_subs.push(subscribe(coingecko.name(), coingecko.default_symbol(&asset)))
+            }
+            AssetType::Crypto => {
+                binance_subs.push(subscribe(binance.name(), binance.default_symbol(&asset)))
+            }
+            AssetType::Cash => {}
+            _ => {
+                if let Some(td) = &twelve_data {
+                    twelve_data_subs.push(subscribe(td.name(), td.default_symbol(&asset)))
+                }
+            }
+        }
+    }
+
+    let sink: TickSink = {
+        let app = app.clone();
+        Arc::new(move |tick| {
+            let _ = app.emit(PRICE_TICK_EVENT, tick);
+        })
+    };
+    let every = Duration::from_secs(POLL_INTERVAL_SECS);
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
+    let mut tasks = vec![tokio::spawn(binance_ws::run(
+        binance_ws::BINANCE_WS_URL.to_string(),
+        binance_subs,
+        sink.clone(),
+        shutdown_rx.clone(),
+    ))];
+    tasks.push(tokio::spawn(poller::run(
+        Arc::new(coingecko) as Arc<dyn MarketDataProvider>,
+        coingecko_subs,
+        every,
+        sink.clone(),
+        shutdown_rx.clone(),
+    )));
+    if let Some(td) = twelve_data {
+        tasks.push(tokio::spawn(poller::run(
+            Arc::new(td) as Arc<dyn MarketDataProvider>,
+            twelve_data_subs,
+            every,
+            sink,
+            shutdown_rx,
+        )));
+    }
+
+    // Replacing the previous handle drops it, which stops the old tasks
+    let mut stream = state.price_stream.lock().map_err(|e| e.to_string())?;
+    *stream = Some(StreamHandle::new(shutdown_tx, tasks));
+    Ok(())
+}
+
+#[tauri::command]
+pub fn stop_price_stream(state: State<'_, AppState>) -> Result<(), String> {
+    let mut stream = state.price_stream.lock().map_err(|e| e.to_string())?;
+    stream.take();
+    Ok(())
+}
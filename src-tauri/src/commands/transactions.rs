@@ -1,9 +1,86 @@
+use crate::commands::error::CommandError;
 use crate::db::queries;
-use crate::models::{AssetHoldingSummary, Transaction, TxType};
+use crate::domain::adjustments;
+use crate::domain::validation::{self, ValidationError};
+use crate::models::{
+    AssetHoldingSummary, AuditEntry, FieldError, NewTransaction, PeriodClose, Transaction, TransactionEdit,
+    TransactionSelection, TxType,
+};
 use crate::state::AppState;
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+use std::collections::BTreeSet;
 use tauri::State;
 
+fn parse_tx_type(tx_type: &str) -> Result<TxType, CommandError> {
+    TxType::from_str(tx_type).map_err(|e| CommandError::new("invalid_input", e.to_string()))
+}
+
+fn validate_fields(quantity: f64, price_usd: f64, ts: i64, fee: f64) -> Result<(), CommandError> {
+    let errors = validation::validate_transaction(quantity, price_usd, ts, fee, Utc::now().timestamp());
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(CommandError::with_fields("invalid_input", errors))
+    }
+}
+
+/// Fail with an `oversell` error if `change` makes a sell take the asset's
+/// position below zero at some point in time. Quantities are compared in
+/// split-adjusted terms, so selling the shares a split created is fine.
+/// Assets with short selling enabled are not checked. A history that already
+/// went negative can still be corrected, as long as the change doesn't make
+/// the position worse (see `worsened_oversell`).
+fn ensure_no_oversell(
+    conn: &Connection,
+    asset_id: &str,
+    change: impl FnOnce(&mut Vec<Transaction>),
+) -> anyhow::Result<()> {
+    if queries::metadata::allows_short(conn, asset_id)? {
+        return Ok(());
+    }
+    let actions = queries::corporate_actions::list_corporate_actions(conn, asset_id)?;
+    let mut transactions = queries::transactions::list_transactions_by_asset(conn, asset_id)?;
+    let mut before = transactions.clone();
+    adjustments::split_adjust_transactions(&mut before, &actions);
+    change(&mut transactions);
+    adjustments::split_adjust_transactions(&mut transactions, &actions);
+    let Some(oversell) = validation::worsened_oversell(&before, &transactions) else {
+        return Ok(());
+    };
+    let date = DateTime::from_timestamp(oversell.ts, 0)
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    Err(ValidationError {
+        code: "oversell",
+        fields: vec![FieldError::new(
+            "quantity",
+            format!(
+                "The position would fall to {} with the sell on {}; enable short selling for this asset to allow it",
+                oversell.balance, date
+            ),
+        )],
+    }
+    .into())
+}
+
+/// `ensure_no_oversell` for a bulk change that takes `removed` out of the
+/// histories and puts `added` in, checked for every asset either touches.
+fn ensure_no_oversell_in_bulk(conn: &Connection, removed: &[Transaction], added: &[Transaction]) -> anyhow::Result<()> {
+    let asset_ids: BTreeSet<&str> = removed.iter().chain(added).map(|t| t.asset_id.as_str()).collect();
+    for asset_id in asset_ids {
+        ensure_no_oversell(conn, asset_id, |txs| {
+            txs.retain(|t| !removed.iter().any(|r| r.id == t.id));
+            txs.extend(added.iter().filter(|t| t.asset_id == asset_id).cloned());
+        })?;
+    }
+    Ok(())
+}
+
+/// Buys and sells with an `account_id` move cash in that account's ledger;
+/// the fee is charged on top of a buy and taken out of a sell's proceeds.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub fn add_transaction(
     asset_id: String,
     tx_type: String,
@@ -11,11 +88,30 @@ pub fn add_transaction(
     price_usd: f64,
     ts: i64,
     notes: Option<String>,
+    fee: Option<f64>,
+    account_id: Option<String>,
     state: State<'_, AppState>,
-) -> Result<Transaction, String> {
-    let tx_type = TxType::from_str(&tx_type).map_err(|e| e.to_string())?;
+) -> Result<Transaction, CommandError> {
+    let tx_type = parse_tx_type(&tx_type)?;
+    let fee = fee.unwrap_or(0.0);
+    validate_fields(quantity, price_usd, ts, fee)?;
     state
         .with_db(|conn| {
+            let proposed = Transaction {
+                id: String::new(),
+                asset_id: asset_id.clone(),
+                tx_type: tx_type.clone(),
+                quantity,
+                price_usd,
+                ts,
+                notes: None,
+                created_at: 0,
+                deleted_at: None,
+                locked_at: None,
+                fee,
+                account_id: None,
+            };
+            ensure_no_oversell(conn, &asset_id, |txs| txs.push(proposed))?;
             queries::transactions::insert_transaction(
                 conn,
                 &asset_id,
@@ -24,22 +120,35 @@ pub fn add_transaction(
                 price_usd,
                 ts,
                 notes.as_deref(),
+                fee,
+                account_id.as_deref(),
             )
         })
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
+/// With `split_adjusted`, quantities and prices are restated in post-split
+/// shares so they line up with split-adjusted charts and current prices.
 #[tauri::command]
 pub fn list_transactions(
     asset_id: String,
+    split_adjusted: Option<bool>,
     state: State<'_, AppState>,
-) -> Result<Vec<Transaction>, String> {
+) -> Result<Vec<Transaction>, CommandError> {
     state
-        .with_db(|conn| queries::transactions::list_transactions_by_asset(conn, &asset_id))
-        .map_err(|e| e.to_string())
+        .with_db(|conn| {
+            let mut transactions = queries::transactions::list_transactions_by_asset(conn, &asset_id)?;
+            if split_adjusted.unwrap_or(false) {
+                let actions = queries::corporate_actions::list_corporate_actions(conn, &asset_id)?;
+                adjustments::split_adjust_transactions(&mut transactions, &actions);
+            }
+            Ok(transactions)
+        })
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub fn update_transaction(
     id: String,
     tx_type: String,
@@ -47,11 +156,24 @@ pub fn update_transaction(
     price_usd: f64,
     ts: i64,
     notes: Option<String>,
+    fee: Option<f64>,
+    account_id: Option<String>,
     state: State<'_, AppState>,
-) -> Result<(), String> {
-    let tx_type = TxType::from_str(&tx_type).map_err(|e| e.to_string())?;
+) -> Result<(), CommandError> {
+    let tx_type = parse_tx_type(&tx_type)?;
+    let fee = fee.unwrap_or(0.0);
+    validate_fields(quantity, price_usd, ts, fee)?;
     state
         .with_db(|conn| {
+            if let Some(existing) = queries::transactions::get_transaction(conn, &id)? {
+                ensure_no_oversell(conn, &existing.asset_id, |txs| {
+                    for tx in txs.iter_mut().filter(|t| t.id == id) {
+                        tx.tx_type = tx_type.clone();
+                        tx.quantity = quantity;
+                        tx.ts = ts;
+                    }
+                })?;
+            }
             queries::transactions::update_transaction(
                 conn,
                 &id,
@@ -60,47 +182,327 @@ pub fn update_transaction(
                 price_usd,
                 ts,
                 notes.as_deref(),
+                fee,
+                account_id.as_deref(),
             )
         })
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
+/// Deleting a buy is refused like a sell when it would leave a later sell
+/// short.
 #[tauri::command]
 pub fn delete_transaction(
     id: String,
     state: State<'_, AppState>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
+    state
+        .with_db(|conn| {
+            if let Some(existing) = queries::transactions::get_transaction(conn, &id)? {
+                ensure_no_oversell(conn, &existing.asset_id, |txs| txs.retain(|t| t.id != id))?;
+            }
+            queries::transactions::soft_delete_transaction(conn, &id)
+        })
+        .map_err(CommandError::from)
+}
+
+/// Checks for transactions coming back from the trash or the history: the
+/// returning rows' fields are validated and, with `removed` taken out of the
+/// histories, they must not oversell.
+pub(crate) fn ensure_valid_reinstatement(
+    conn: &Connection,
+    removed: &[Transaction],
+    reinstated: &[Transaction],
+) -> anyhow::Result<()> {
+    let now = Utc::now().timestamp();
+    for t in reinstated {
+        let errors = validation::validate_transaction(t.quantity, t.price_usd, t.ts, t.fee, now);
+        if !errors.is_empty() {
+            return Err(ValidationError { code: "invalid_input", fields: errors }.into());
+        }
+    }
+    ensure_no_oversell_in_bulk(conn, removed, reinstated)
+}
+
+/// Field errors for each row of a bulk change, renamed `<row index>.<field>`.
+fn row_errors(rows: impl Iterator<Item = Vec<FieldError>>) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    for (i, row) in rows.enumerate() {
+        for e in row {
+            let field = format!("{}.{}", i, e.field);
+            errors.push(FieldError::new(&field, format!("Row {}: {}", i + 1, e.message)));
+        }
+    }
+    errors
+}
+
+/// Add several transactions at once; if any row is invalid or would oversell,
+/// nothing is added. Field errors are named `<row index>.<field>`.
+#[tauri::command]
+pub fn bulk_add_transactions(
+    transactions: Vec<NewTransaction>,
+    state: State<'_, AppState>,
+) -> Result<Vec<Transaction>, CommandError> {
+    let now = Utc::now().timestamp();
+    let errors = row_errors(
+        transactions.iter().map(|r| validation::validate_transaction(r.quantity, r.price_usd, r.ts, r.fee, now)),
+    );
+    if !errors.is_empty() {
+        return Err(CommandError::with_fields("invalid_input", errors));
+    }
+    state
+        .with_db(|conn| {
+            let proposed: Vec<Transaction> = transactions
+                .iter()
+                .enumerate()
+                .map(|(i, row)| Transaction {
+                    id: format!("new-{}", i),
+                    asset_id: row.asset_id.clone(),
+                    tx_type: row.tx_type.clone(),
+                    quantity: row.quantity,
+                    price_usd: row.price_usd,
+                    ts: row.ts,
+                    notes: None,
+                    created_at: now,
+                    deleted_at: None,
+                    locked_at: None,
+                    fee: row.fee,
+                    account_id: None,
+                })
+                .collect();
+            ensure_no_oversell_in_bulk(conn, &[], &proposed)?;
+            queries::transactions::insert_transactions(conn, &transactions)
+        })
+        .map_err(CommandError::from)
+}
+
+/// Soft-delete the selected transactions in one go. Returns how many were
+/// deleted.
+#[tauri::command]
+pub fn bulk_delete_transactions(
+    selection: TransactionSelection,
+    state: State<'_, AppState>,
+) -> Result<u64, CommandError> {
+    state
+        .with_db(|conn| {
+            let selected = queries::transactions::select_for_bulk(conn, &selection)?;
+            ensure_no_oversell_in_bulk(conn, &selected, &[])?;
+            queries::transactions::soft_delete_transactions(conn, &selection)
+        })
+        .map_err(CommandError::from)
+}
+
+/// Returns how many of the selected transactions were newly locked.
+#[tauri::command]
+pub fn bulk_lock_transactions(
+    selection: TransactionSelection,
+    state: State<'_, AppState>,
+) -> Result<u64, CommandError> {
+    state
+        .with_db(|conn| queries::transactions::lock_transactions(conn, &selection))
+        .map_err(CommandError::from)
+}
+
+/// Reassign, re-date or annotate the selected transactions in one go.
+/// Returns how many changed. Field errors are named `<row index>.<field>`,
+/// indexed in selection order: ids as given, a filter's matches newest first.
+#[tauri::command]
+pub fn bulk_edit_transactions(
+    selection: TransactionSelection,
+    edit: TransactionEdit,
+    state: State<'_, AppState>,
+) -> Result<u64, CommandError> {
+    let now = Utc::now().timestamp();
     state
-        .with_db(|conn| queries::transactions::soft_delete_transaction(conn, &id))
-        .map_err(|e| e.to_string())
+        .with_db(|conn| {
+            let selected = queries::transactions::select_for_bulk(conn, &selection)?;
+            let edited: Vec<Result<Transaction, FieldError>> = selected.iter().map(|t| edit.apply(t)).collect();
+            let errors = row_errors(edited.iter().map(|t| match t {
+                Ok(t) => validation::validate_transaction(t.quantity, t.price_usd, t.ts, t.fee, now),
+                Err(e) => vec![e.clone()],
+            }));
+            if !errors.is_empty() {
+                return Err(ValidationError { code: "invalid_input", fields: errors }.into());
+            }
+            let edited: Vec<Transaction> = edited.into_iter().flatten().collect();
+            ensure_no_oversell_in_bulk(conn, &selected, &edited)?;
+            queries::transactions::edit_transactions(conn, &selection, &edit)
+        })
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
 pub fn lock_transaction(
     id: String,
     state: State<'_, AppState>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     state
         .with_db(|conn| queries::transactions::lock_transaction(conn, &id))
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
 }
 
+/// Lock every transaction dated before `before_ts` (of one asset, or all),
+/// closing that period: until it is reopened, nothing can be added or moved
+/// into it. Returns how many were locked.
+#[tauri::command]
+pub fn lock_transactions_before(
+    before_ts: i64,
+    asset_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<usize, CommandError> {
+    state
+        .with_db(|conn| queries::transactions::lock_transactions_before(conn, before_ts, asset_id.as_deref()))
+        .map_err(CommandError::from)
+}
+
+/// Reopen the closed periods of one asset, or the global ones, unlocking the
+/// transactions they covered. The reason is recorded on the close and in each
+/// transaction's history. Returns how many were unlocked.
+#[tauri::command]
+pub fn reopen_period(
+    asset_id: Option<String>,
+    reason: String,
+    state: State<'_, AppState>,
+) -> Result<usize, CommandError> {
+    state
+        .with_db(|conn| queries::transactions::reopen_period(conn, asset_id.as_deref(), &reason))
+        .map_err(CommandError::from)
+}
+
+/// Every period close, reopened ones included, most recent first.
+#[tauri::command]
+pub fn list_period_closes(state: State<'_, AppState>) -> Result<Vec<PeriodClose>, CommandError> {
+    state
+        .with_db(queries::transactions::list_period_closes)
+        .map_err(CommandError::from)
+}
+
+/// Locked transactions can't be edited or deleted until unlocked; the reason
+/// is recorded in the transaction's history.
 #[tauri::command]
 pub fn unlock_transaction(
     id: String,
+    reason: String,
     state: State<'_, AppState>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     state
-        .with_db(|conn| queries::transactions::unlock_transaction(conn, &id))
-        .map_err(|e| e.to_string())
+        .with_db(|conn| queries::transactions::unlock_transaction(conn, &id, &reason))
+        .map_err(CommandError::from)
+}
+
+/// Logged changes to one transaction or to every transaction of an asset,
+/// newest first.
+#[tauri::command]
+pub fn list_transaction_history(
+    transaction_id: Option<String>,
+    asset_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<AuditEntry>, CommandError> {
+    if transaction_id.is_none() && asset_id.is_none() {
+        return Err(CommandError::new("invalid_input", "A transaction or an asset is required"));
+    }
+    state
+        .with_db(|conn| {
+            queries::audit::list_audit_entries(conn, transaction_id.as_deref(), asset_id.as_deref())
+        })
+        .map_err(CommandError::from)
+}
+
+/// Reverting is checked like any other change: it may not bring back invalid
+/// values or oversell.
+#[tauri::command]
+pub fn revert_transaction_change(
+    audit_id: i64,
+    state: State<'_, AppState>,
+) -> Result<AuditEntry, CommandError> {
+    state
+        .with_db(|conn| {
+            if let Some(entry) = queries::audit::get_audit_entry(conn, audit_id)? {
+                let current = queries::transactions::get_transaction(conn, &entry.transaction_id)?;
+                let removed: Vec<Transaction> = current.into_iter().filter(|t| t.deleted_at.is_none()).collect();
+                let reverted: Vec<Transaction> = entry.before.into_iter().filter(|t| t.deleted_at.is_none()).collect();
+                ensure_valid_reinstatement(conn, &removed, &reverted)?;
+            }
+            queries::transactions::revert_change(conn, audit_id)
+        })
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
 pub fn get_holding_summary(
     asset_id: String,
     state: State<'_, AppState>,
-) -> Result<AssetHoldingSummary, String> {
+) -> Result<AssetHoldingSummary, CommandError> {
     state
         .with_db(|conn| queries::transactions::get_holding_summary(conn, &asset_id))
-        .map_err(|e| e.to_string())
+        .map_err(CommandError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_db;
+    use crate::models::{AssetType, CorporateAction, CorporateActionType};
+
+    const DAY: i64 = 86400;
+
+    fn sell(asset_id: &str, quantity: f64, ts: i64) -> Transaction {
+        Transaction {
+            id: "proposed".to_string(),
+            asset_id: asset_id.to_string(),
+            tx_type: TxType::Sell,
+            quantity,
+            price_usd: 50.0,
+            ts,
+            notes: None,
+            created_at: 0,
+            deleted_at: None,
+            locked_at: None,
+            fee: 0.0,
+            account_id: None,
+        }
+    }
+
+    #[test]
+    fn test_oversell_check_follows_splits() {
+        let conn = test_db();
+        let asset = queries::assets::insert_asset(&conn, "AAPL", "Apple", &AssetType::Stock, "USD", None).unwrap();
+        queries::transactions::insert_transaction(&conn, &asset.id, &TxType::Buy, 10.0, 100.0, DAY, None, 0.0, None)
+            .unwrap();
+        queries::corporate_actions::upsert_corporate_actions(&conn, &[CorporateAction {
+            id: None,
+            asset_id: asset.id.clone(),
+            action_type: CorporateActionType::Split,
+            ts: 10 * DAY,
+            value: 2.0,
+            provider: "test".to_string(),
+        }])
+        .unwrap();
+
+        // The 20 shares held after the 2:1 split can all be sold, but not 21
+        assert!(ensure_no_oversell(&conn, &asset.id, |txs| txs.push(sell(&asset.id, 20.0, 20 * DAY))).is_ok());
+        let err = ensure_no_oversell(&conn, &asset.id, |txs| txs.push(sell(&asset.id, 21.0, 20 * DAY))).unwrap_err();
+        assert_eq!(err.downcast_ref::<ValidationError>().unwrap().code, "oversell");
+    }
+
+    #[test]
+    fn test_reinstating_a_sell_is_checked_for_oversell() {
+        let conn = test_db();
+        let asset = queries::assets::insert_asset(&conn, "BTC", "Bitcoin", &AssetType::Crypto, "USD", None).unwrap();
+        let insert = |tx_type: TxType, ts: i64| {
+            queries::transactions::insert_transaction(&conn, &asset.id, &tx_type, 5.0, 100.0, ts, None, 0.0, None)
+                .unwrap()
+        };
+        insert(TxType::Buy, DAY);
+        let first_sell = insert(TxType::Sell, 2 * DAY);
+        queries::transactions::soft_delete_transaction(&conn, &first_sell.id).unwrap();
+        insert(TxType::Sell, 3 * DAY);
+
+        let restored = Transaction { deleted_at: None, ..first_sell };
+        let err = ensure_valid_reinstatement(&conn, &[], std::slice::from_ref(&restored)).unwrap_err();
+        assert_eq!(err.downcast_ref::<ValidationError>().unwrap().code, "oversell");
+
+        queries::metadata::set_allow_short(&conn, &asset.id, true).unwrap();
+        assert!(ensure_valid_reinstatement(&conn, &[], &[restored]).is_ok());
+    }
 }
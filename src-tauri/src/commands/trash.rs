@@ -0,0 +1,152 @@
+use crate::commands::assets::restore_trashed_asset;
+use crate::commands::error::CommandError;
+use crate::commands::transactions::ensure_valid_reinstatement;
+use crate::db::queries;
+use crate::models::{Asset, Transaction, Trash, TrashPurge};
+use crate::state::AppState;
+use chrono::Utc;
+use rusqlite::Connection;
+use tauri::State;
+
+/// Settings key for how many days deleted items stay in the trash ("0" or
+/// unset = until emptied by hand).
+pub const TRASH_RETENTION_KEY: &str = "trash_retention_days";
+
+/// Parse the stored retention. `None` means never purge automatically.
+pub fn parse_trash_retention(value: Option<&str>) -> Option<u32> {
+    value.and_then(|v| v.trim().parse::<u32>().ok()).filter(|days| *days > 0)
+}
+
+/// Purge what has been in the trash longer than `deleted_before` allows, or
+/// everything when it is `None`. Transactions go first so those deleted with
+/// an asset are counted on their own.
+fn purge(conn: &Connection, deleted_before: Option<i64>) -> anyhow::Result<TrashPurge> {
+    let transactions = queries::transactions::purge_deleted_transactions(conn, deleted_before)?;
+    let assets = queries::assets::purge_deleted_assets(conn, deleted_before)?;
+    Ok(TrashPurge { assets, transactions })
+}
+
+/// Apply the retention setting: purge items deleted more than N days ago.
+pub fn purge_expired_trash(conn: &Connection) -> anyhow::Result<TrashPurge> {
+    let setting = queries::settings::get_setting(conn, TRASH_RETENTION_KEY)?;
+    match parse_trash_retention(setting.as_deref()) {
+        Some(days) => purge(conn, Some(Utc::now().timestamp() - i64::from(days) * 86400)),
+        None => Ok(TrashPurge { assets: 0, transactions: 0 }),
+    }
+}
+
+/// `purge_expired_trash` for unlocking and scheduled refreshes, which carry
+/// on regardless. The app has no log file, so a failure goes to stderr.
+pub fn auto_purge_trash(state: &AppState) {
+    if let Err(e) = state.with_db(purge_expired_trash) {
+        eprintln!("Purging expired trash failed: {:#}", e);
+    }
+}
+
+#[tauri::command]
+pub fn list_trash(state: State<'_, AppState>) -> Result<Trash, String> {
+    state
+        .with_db(|conn| {
+            Ok(Trash {
+                assets: queries::assets::list_deleted_assets(conn)?,
+                transactions: queries::transactions::list_deleted_transactions(conn)?,
+            })
+        })
+        .map_err(|e| e.to_string())
+}
+
+/// Restore a deleted asset along with the transactions removed with it.
+/// Refused if those transactions are invalid or oversell.
+#[tauri::command]
+pub fn restore_deleted_asset(id: String, state: State<'_, AppState>) -> Result<Asset, CommandError> {
+    state
+        .with_db(|conn| {
+            let Some(asset) = queries::assets::get_asset_including_deleted(conn, &id)?.filter(|a| a.deleted_at.is_some())
+            else {
+                anyhow::bail!("Asset is not in the trash");
+            };
+            restore_trashed_asset(conn, &id, &asset.name, &asset.asset_type)
+        })
+        .map_err(CommandError::from)
+}
+
+/// Refused if the transaction is invalid or would oversell.
+#[tauri::command]
+pub fn restore_transaction(id: String, state: State<'_, AppState>) -> Result<(), CommandError> {
+    state
+        .with_db(|conn| {
+            if let Some(trashed) = queries::transactions::get_transaction(conn, &id)?.filter(|t| t.deleted_at.is_some()) {
+                ensure_valid_reinstatement(conn, &[], &[Transaction { deleted_at: None, ..trashed }])?;
+            }
+            queries::transactions::restore_transaction(conn, &id)
+        })
+        .map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub fn purge_transaction(id: String, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .with_db(|conn| queries::transactions::purge_transaction(conn, &id))
+        .map_err(|e| e.to_string())
+}
+
+/// Purge everything in the trash now.
+#[tauri::command]
+pub fn empty_trash(state: State<'_, AppState>) -> Result<TrashPurge, String> {
+    state
+        .with_db(|conn| purge(conn, None))
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::assets::create_or_restore_asset;
+    use crate::db::test_db;
+    use crate::domain::validation::ValidationError;
+    use crate::models::{AssetType, TxType};
+
+    #[test]
+    fn test_purge_expired_trash() {
+        assert_eq!(parse_trash_retention(None), None);
+        assert_eq!(parse_trash_retention(Some("0")), None);
+        assert_eq!(parse_trash_retention(Some(" 30 ")), Some(30));
+
+        let conn = test_db();
+        let old = queries::assets::insert_asset(&conn, "OLD", "Old", &AssetType::Stock, "USD", None).unwrap();
+        let recent = queries::assets::insert_asset(&conn, "NEW", "New", &AssetType::Stock, "USD", None).unwrap();
+        queries::assets::soft_delete_asset(&conn, &old.id).unwrap();
+        queries::assets::soft_delete_asset(&conn, &recent.id).unwrap();
+        let long_ago = Utc::now().timestamp() - 40 * 86400;
+        conn.execute("UPDATE assets SET deleted_at = ?1 WHERE id = ?2", rusqlite::params![long_ago, old.id])
+            .unwrap();
+
+        // Nothing is purged until a retention is set
+        assert_eq!(purge_expired_trash(&conn).unwrap(), TrashPurge { assets: 0, transactions: 0 });
+        queries::settings::set_setting(&conn, TRASH_RETENTION_KEY, "30").unwrap();
+        assert_eq!(purge_expired_trash(&conn).unwrap().assets, 1);
+        let left: Vec<String> =
+            queries::assets::list_deleted_assets(&conn).unwrap().into_iter().map(|a| a.symbol).collect();
+        assert_eq!(left, vec!["NEW".to_string()]);
+    }
+
+    #[test]
+    fn test_re_adding_a_trashed_asset_checks_its_transactions() {
+        let conn = test_db();
+        let asset = queries::assets::insert_asset(&conn, "BTC", "Bitcoin", &AssetType::Crypto, "USD", None).unwrap();
+        let insert = |tx_type: TxType, ts: i64| {
+            queries::transactions::insert_transaction(&conn, &asset.id, &tx_type, 5.0, 100.0, ts, None, 0.0, None)
+                .unwrap()
+        };
+        let buy = insert(TxType::Buy, 86400);
+        insert(TxType::Sell, 2 * 86400);
+        queries::transactions::soft_delete_transaction(&conn, &buy.id).unwrap();
+        queries::assets::soft_delete_asset(&conn, &asset.id).unwrap();
+
+        // Only the sell was trashed with the asset; bringing it back alone would oversell
+        let err = create_or_restore_asset(&conn, "BTC", "Bitcoin", &AssetType::Crypto, None).unwrap_err();
+        assert_eq!(err.downcast_ref::<ValidationError>().unwrap().code, "oversell");
+        assert!(queries::assets::get_asset(&conn, &asset.id).unwrap().is_none());
+    }
+}
+
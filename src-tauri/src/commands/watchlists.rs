@@ -0,0 +1,167 @@
+use crate::commands::assets::create_or_restore_asset;
+use crate::db::queries;
+use crate::models::{Asset, AssetType, Watchlist};
+use crate::state::AppState;
+use tauri::State;
+
+fn validate_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Watchlist name is required".to_string());
+    }
+    Ok(name.to_string())
+}
+
+fn clean_notes(notes: Option<String>) -> Option<String> {
+    notes.map(|n| n.trim().to_string()).filter(|n| !n.is_empty())
+}
+
+#[tauri::command]
+pub fn list_watchlists(state: State<'_, AppState>) -> Result<Vec<Watchlist>, String> {
+    state
+        .with_db(queries::watchlists::list_watchlists)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn create_watchlist(name: String, state: State<'_, AppState>) -> Result<Watchlist, String> {
+    let name = validate_name(&name)?;
+    state
+        .with_db(|conn| queries::watchlists::insert_watchlist(conn, &name))
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn rename_watchlist(id: String, name: String, state: State<'_, AppState>) -> Result<(), String> {
+    let name = validate_name(&name)?;
+    state
+        .with_db(|conn| queries::watchlists::rename_watchlist(conn, &id, &name))
+        .map_err(|e| e.to_string())
+}
+
+/// Delete a watchlist; assets followed only from it are removed too.
+#[tauri::command]
+pub fn delete_watchlist(id: String, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .with_db(|conn| {
+            queries::watchlists::delete_watchlist(conn, &id)?;
+            queries::watchlists::prune_watch_only_assets(conn)?;
+            Ok(())
+        })
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn reorder_watchlists(ids: Vec<String>, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .with_db(|conn| queries::watchlists::reorder_watchlists(conn, &ids))
+        .map_err(|e| e.to_string())
+}
+
+/// Put an existing asset (held or already watched) on a watchlist.
+#[tauri::command]
+pub fn add_watchlist_item(
+    watchlist_id: String,
+    asset_id: String,
+    notes: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let notes = clean_notes(notes);
+    state
+        .with_db(|conn| queries::watchlists::add_watchlist_item(conn, &watchlist_id, &asset_id, notes.as_deref()))
+        .map_err(|e| e.to_string())
+}
+
+/// Follow a search result on a watchlist. A new asset is created watch-only,
+/// so it is fetched and charted but stays out of the portfolio until it is
+/// traded or added there; an asset already tracked is reused as is. A trashed
+/// asset is brought back, unless it still has trashed transactions: those
+/// belong in the portfolio, so it has to be restored from the trash instead.
+#[allow(clippy::too_many_arguments)]
+#[tauri::command]
+pub fn watch_symbol(
+    watchlist_id: String,
+    symbol: String,
+    name: String,
+    asset_type: String,
+    exchange: Option<String>,
+    provider: Option<String>,
+    provider_id: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Asset, String> {
+    let asset_type = AssetType::from_str(&asset_type).map_err(|e| e.to_string())?;
+    if asset_type == AssetType::Cash {
+        return Err("Cash can't be watched".to_string());
+    }
+
+    state
+        .with_db(|conn| {
+            let asset = match queries::assets::get_asset_by_symbol(conn, &symbol, exchange.as_deref(), &asset_type)? {
+                Some(existing) => existing,
+                None => {
+                    if let Some(trashed) = queries::assets::get_asset_by_symbol_including_deleted(
+                        conn,
+                        &symbol,
+                        exchange.as_deref(),
+                        &asset_type,
+                    )? {
+                        let held = queries::transactions::list_trashed_with_asset(conn, &trashed.id)?.len();
+                        if held > 0 {
+                            anyhow::bail!(
+                                "{} is in the trash with {} transaction(s); restore it from the trash to follow it",
+                                trashed.symbol,
+                                held
+                            );
+                        }
+                    }
+                    let asset = create_or_restore_asset(conn, &symbol, &name, &asset_type, exchange.as_deref())?;
+                    queries::watchlists::mark_watch_only(conn, &asset.id)?;
+                    if let (Some(provider), Some(provider_id)) = (&provider, &provider_id) {
+                        queries::provider_symbols::set_provider_symbol(conn, &asset.id, provider, provider_id)?;
+                    }
+                    asset
+                }
+            };
+            queries::watchlists::add_watchlist_item(conn, &watchlist_id, &asset.id, None)?;
+            Ok(asset)
+        })
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn update_watchlist_item_notes(
+    watchlist_id: String,
+    asset_id: String,
+    notes: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    let notes = clean_notes(notes);
+    state
+        .with_db(|conn| {
+            queries::watchlists::update_watchlist_item_notes(conn, &watchlist_id, &asset_id, notes.as_deref())
+        })
+        .map_err(|e| e.to_string())
+}
+
+/// Take an asset off a watchlist; a watch-only asset on no other list is removed.
+#[tauri::command]
+pub fn remove_watchlist_item(watchlist_id: String, asset_id: String, state: State<'_, AppState>) -> Result<(), String> {
+    state
+        .with_db(|conn| {
+            queries::watchlists::remove_watchlist_item(conn, &watchlist_id, &asset_id)?;
+            queries::watchlists::prune_watch_only_assets(conn)?;
+            Ok(())
+        })
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn reorder_watchlist_items(
+    watchlist_id: String,
+    asset_ids: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<(), String> {
+    state
+        .with_db(|conn| queries::watchlists::reorder_watchlist_items(conn, &watchlist_id, &asset_ids))
+        .map_err(|e| e.to_string())
+}
@@ -127,4 +127,155 @@ mod tests {
             .unwrap();
         assert_eq!(count, 5);
     }
+
+    #[test]
+    fn test_interval_migration_preserves_daily_rows() {
+        let conn = Connection::open_in_memory().unwrap();
+        // Pre-interval schema: one row per (asset_id, ts), cache keyed by asset only
+        conn.execute_batch(
+            "
+            CREATE TABLE assets (
+                id TEXT PRIMARY KEY, symbol TEXT NOT NULL, name TEXT NOT NULL,
+                asset_type TEXT NOT NULL, currency TEXT NOT NULL DEFAULT 'USD',
+                added_at INTEGER NOT NULL, deleted_at INTEGER, UNIQUE(symbol)
+            );
+            CREATE TABLE historical_prices (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                asset_id TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+                ts INTEGER NOT NULL, open REAL, high REAL, low REAL, close REAL NOT NULL,
+                volume REAL, UNIQUE(asset_id, ts)
+            );
+            CREATE TABLE price_cache_meta (
+                asset_id TEXT PRIMARY KEY REFERENCES assets(id) ON DELETE CASCADE,
+                provider TEXT NOT NULL, last_fetched INTEGER NOT NULL
+            );
+            INSERT INTO assets (id, symbol, name, asset_type, added_at) VALUES ('a1', 'BTC', 'Bitcoin', 'crypto', 0);
+            INSERT INTO historical_prices (asset_id, ts, close) VALUES ('a1', 1700006400, 100.0);
+            INSERT INTO price_cache_meta (asset_id, provider, last_fetched) VALUES ('a1', 'binance', 1700006400);
+            ",
+        )
+        .unwrap();
+
+        schema::run_migrations(&conn).unwrap();
+
+        let interval: String = conn
+            .query_row("SELECT interval FROM historical_prices WHERE asset_id = 'a1'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(interval, "1d");
+        let meta_interval: String = conn
+            .query_row("SELECT interval FROM price_cache_meta WHERE asset_id = 'a1'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(meta_interval, "1d");
+
+        // Migrations are idempotent
+        schema::run_migrations(&conn).unwrap();
+    }
+
+    #[test]
+    fn test_corporate_actions_migration_drops_adjusted_twelve_data_history() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "
+            CREATE TABLE assets (
+                id TEXT PRIMARY KEY, symbol TEXT NOT NULL, name TEXT NOT NULL,
+                asset_type TEXT NOT NULL, currency TEXT NOT NULL DEFAULT 'USD',
+                added_at INTEGER NOT NULL, deleted_at INTEGER, UNIQUE(symbol)
+            );
+            CREATE TABLE historical_prices (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                asset_id TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+                interval TEXT NOT NULL DEFAULT '1d', ts INTEGER NOT NULL,
+                open REAL, high REAL, low REAL, close REAL NOT NULL, volume REAL,
+                UNIQUE(asset_id, interval, ts)
+            );
+            CREATE TABLE price_cache_meta (
+                asset_id TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+                interval TEXT NOT NULL DEFAULT '1d', provider TEXT NOT NULL,
+                last_fetched INTEGER NOT NULL, PRIMARY KEY(asset_id, interval)
+            );
+            INSERT INTO assets (id, symbol, name, asset_type, added_at) VALUES ('a1', 'AAPL', 'Apple', 'stock', 0);
+            INSERT INTO assets (id, symbol, name, asset_type, added_at) VALUES ('a2', 'BTC', 'Bitcoin', 'crypto', 0);
+            INSERT INTO historical_prices (asset_id, ts, close) VALUES ('a1', 1700006400, 100.0);
+            INSERT INTO historical_prices (asset_id, ts, close) VALUES ('a2', 1700006400, 100.0);
+            INSERT INTO price_cache_meta (asset_id, provider, last_fetched) VALUES ('a1', 'twelve_data', 1700006400);
+            INSERT INTO price_cache_meta (asset_id, provider, last_fetched) VALUES ('a2', 'binance', 1700006400);
+            ",
+        )
+        .unwrap();
+
+        schema::run_migrations(&conn).unwrap();
+
+        let remaining: Vec<String> = conn
+            .prepare("SELECT asset_id FROM historical_prices UNION SELECT asset_id FROM price_cache_meta")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .map(|r| r.unwrap())
+            .collect();
+        assert_eq!(remaining, vec!["a2".to_string()]);
+
+        // Later runs leave fresh Twelve Data history alone
+        conn.execute("INSERT INTO historical_prices (asset_id, ts, close) VALUES ('a1', 1700006400, 100.0)", [])
+            .unwrap();
+        schema::run_migrations(&conn).unwrap();
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM historical_prices WHERE asset_id = 'a1'", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_asset_identity_migration() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "
+            CREATE TABLE assets (
+                id TEXT PRIMARY KEY, symbol TEXT NOT NULL, name TEXT NOT NULL,
+                asset_type TEXT NOT NULL CHECK(asset_type IN ('stock','crypto','commodity')),
+                currency TEXT NOT NULL DEFAULT 'USD',
+                added_at INTEGER NOT NULL, deleted_at INTEGER, UNIQUE(symbol) ON CONFLICT ABORT
+            );
+            CREATE TABLE asset_provider_symbols (
+                asset_id TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+                provider TEXT NOT NULL, provider_symbol TEXT NOT NULL, PRIMARY KEY(asset_id, provider)
+            );
+            INSERT INTO assets (id, symbol, name, asset_type, added_at) VALUES ('a1', 'SHOP', 'Shopify', 'stock', 0);
+            INSERT INTO assets (id, symbol, name, asset_type, added_at) VALUES ('a2', 'BTC', 'Bitcoin', 'crypto', 0);
+            INSERT INTO asset_provider_symbols VALUES ('a1', 'twelve_data', 'SHOP:tsx');
+            ",
+        )
+        .unwrap();
+        conn.execute_batch("PRAGMA foreign_keys = ON;").unwrap();
+
+        schema::run_migrations(&conn).unwrap();
+        schema::run_migrations(&conn).unwrap();
+
+        let exchanges: Vec<(String, String)> = conn
+            .prepare("SELECT id, exchange FROM assets ORDER BY id")
+            .unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+            .unwrap()
+            .map(|r| r.unwrap())
+            .collect();
+        assert_eq!(exchanges, vec![("a1".to_string(), "TSX".to_string()), ("a2".to_string(), String::new())]);
+
+        // The NYSE listing of the same ticker can now be added alongside
+        conn.execute(
+            "INSERT INTO assets (id, symbol, name, asset_type, added_at, exchange) VALUES ('a3', 'SHOP', 'Shopify', 'stock', 0, 'NYSE')",
+            [],
+        )
+        .unwrap();
+        // The widened type CHECK accepts the new types
+        conn.execute(
+            "INSERT INTO assets (id, symbol, name, asset_type, added_at) VALUES ('a4', 'USD', 'Cash', 'cash', 0)",
+            [],
+        )
+        .unwrap();
+        // References into the rebuilt table still cascade
+        conn.execute("DELETE FROM assets WHERE id = 'a1'", []).unwrap();
+        let mappings: i64 = conn
+            .query_row("SELECT COUNT(*) FROM asset_provider_symbols", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(mappings, 0);
+    }
 }
@@ -0,0 +1,143 @@
+use crate::models::{Alert, AlertKind};
+use chrono::Utc;
+use rusqlite::{params, Connection, Row};
+use uuid::Uuid;
+
+const ALERT_COLUMNS: &str =
+    "al.id, al.asset_id, al.kind, al.threshold, al.window_days, al.enabled, al.triggered, al.last_triggered_at, al.created_at";
+
+fn row_to_alert(row: &Row) -> rusqlite::Result<Alert> {
+    Ok(Alert {
+        id: row.get(0)?,
+        asset_id: row.get(1)?,
+        kind: AlertKind::from_str(&row.get::<_, String>(2)?).unwrap_or(AlertKind::PriceAbove),
+        threshold: row.get(3)?,
+        window_days: row.get(4)?,
+        enabled: row.get(5)?,
+        triggered: row.get(6)?,
+        last_triggered_at: row.get(7)?,
+        created_at: row.get(8)?,
+    })
+}
+
+pub fn insert_alert(
+    conn: &Connection,
+    asset_id: &str,
+    kind: AlertKind,
+    threshold: f64,
+    window_days: Option<i64>,
+) -> anyhow::Result<Alert> {
+    let id = Uuid::new_v4().to_string();
+    let now = Utc::now().timestamp();
+    conn.execute(
+        "INSERT INTO alerts (id, asset_id, kind, threshold, window_days, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![id, asset_id, kind.as_str(), threshold, window_days, now],
+    )?;
+    Ok(Alert {
+        id,
+        asset_id: asset_id.to_string(),
+        kind,
+        threshold,
+        window_days,
+        enabled: true,
+        triggered: false,
+        last_triggered_at: None,
+        created_at: now,
+    })
+}
+
+/// Change an alert's condition or enable it again. Either way it re-arms, so
+/// a condition that already holds fires on the next evaluation.
+pub fn update_alert(
+    conn: &Connection,
+    id: &str,
+    threshold: f64,
+    window_days: Option<i64>,
+    enabled: bool,
+) -> anyhow::Result<()> {
+    let changed = conn.execute(
+        "UPDATE alerts SET threshold = ?2, window_days = ?3, enabled = ?4, triggered = 0 WHERE id = ?1",
+        params![id, threshold, window_days, enabled],
+    )?;
+    if changed == 0 {
+        anyhow::bail!("Alert not found: {}", id);
+    }
+    Ok(())
+}
+
+pub fn delete_alert(conn: &Connection, id: &str) -> anyhow::Result<()> {
+    conn.execute("DELETE FROM alerts WHERE id = ?1", params![id])?;
+    Ok(())
+}
+
+/// Alerts on active assets, optionally limited to one asset.
+pub fn list_alerts(conn: &Connection, asset_id: Option<&str>) -> anyhow::Result<Vec<Alert>> {
+    let sql = format!(
+        "SELECT {ALERT_COLUMNS} FROM alerts al JOIN assets a ON a.id = al.asset_id
+         WHERE a.deleted_at IS NULL AND (?1 IS NULL OR al.asset_id = ?1)
+         ORDER BY a.symbol, al.created_at"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(params![asset_id], row_to_alert)?;
+
+    let mut result = Vec::new();
+    for row in rows {
+        result.push(row?);
+    }
+    Ok(result)
+}
+
+/// Record the outcome of an evaluation. `fired_at` is set only when the alert
+/// went from armed to triggered.
+pub fn set_alert_triggered(
+    conn: &Connection,
+    id: &str,
+    triggered: bool,
+    fired_at: Option<i64>,
+) -> anyhow::Result<()> {
+    conn.execute(
+        "UPDATE alerts SET triggered = ?2, last_triggered_at = COALESCE(?3, last_triggered_at) WHERE id = ?1",
+        params![id, triggered, fired_at],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::queries::assets;
+    use crate::db::test_db;
+    use crate::models::AssetType;
+
+    #[test]
+    fn test_alert_lifecycle() {
+        let conn = test_db();
+        let aapl = assets::insert_asset(&conn, "AAPL", "Apple", &AssetType::Stock, "USD", None).unwrap();
+        let btc = assets::insert_asset(&conn, "BTC", "Bitcoin", &AssetType::Crypto, "USD", None).unwrap();
+        let alert = insert_alert(&conn, &aapl.id, AlertKind::PriceAbove, 200.0, None).unwrap();
+        insert_alert(&conn, &btc.id, AlertKind::Drawdown, 20.0, Some(90)).unwrap();
+        assert!(insert_alert(&conn, &btc.id, AlertKind::PctMove, 5.0, Some(0)).is_err());
+
+        assert_eq!(list_alerts(&conn, None).unwrap().len(), 2);
+        assert_eq!(list_alerts(&conn, Some(&aapl.id)).unwrap(), vec![alert.clone()]);
+
+        set_alert_triggered(&conn, &alert.id, true, Some(1_700_000_000)).unwrap();
+        set_alert_triggered(&conn, &alert.id, true, None).unwrap();
+        let stored = &list_alerts(&conn, Some(&aapl.id)).unwrap()[0];
+        assert!(stored.triggered);
+        assert_eq!(stored.last_triggered_at, Some(1_700_000_000));
+
+        // Editing re-arms but keeps the history
+        update_alert(&conn, &alert.id, 250.0, None, true).unwrap();
+        let stored = &list_alerts(&conn, Some(&aapl.id)).unwrap()[0];
+        assert!(!stored.triggered);
+        assert_eq!(stored.threshold, 250.0);
+        assert_eq!(stored.last_triggered_at, Some(1_700_000_000));
+        assert!(update_alert(&conn, "missing", 1.0, None, true).is_err());
+
+        assets::soft_delete_asset(&conn, &btc.id).unwrap();
+        assert_eq!(list_alerts(&conn, None).unwrap().len(), 1);
+        delete_alert(&conn, &alert.id).unwrap();
+        assert!(list_alerts(&conn, None).unwrap().is_empty());
+    }
+}
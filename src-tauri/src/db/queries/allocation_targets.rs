@@ -0,0 +1,68 @@
+use crate::models::AllocationTarget;
+use rusqlite::{params, Connection};
+
+pub fn set_allocation_target(conn: &Connection, target: &AllocationTarget) -> anyhow::Result<()> {
+    conn.execute(
+        "INSERT INTO allocation_targets (asset_id, target_pct, lot_size, no_sell) VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(asset_id) DO UPDATE SET
+            target_pct = excluded.target_pct, lot_size = excluded.lot_size, no_sell = excluded.no_sell",
+        params![target.asset_id, target.target_pct, target.lot_size, target.no_sell],
+    )?;
+    Ok(())
+}
+
+pub fn delete_allocation_target(conn: &Connection, asset_id: &str) -> anyhow::Result<()> {
+    conn.execute("DELETE FROM allocation_targets WHERE asset_id = ?1", params![asset_id])?;
+    Ok(())
+}
+
+/// Targets of active assets.
+pub fn list_allocation_targets(conn: &Connection) -> anyhow::Result<Vec<AllocationTarget>> {
+    let mut stmt = conn.prepare(
+        "SELECT t.asset_id, t.target_pct, t.lot_size, t.no_sell FROM allocation_targets t
+         JOIN assets a ON a.id = t.asset_id WHERE a.deleted_at IS NULL ORDER BY t.target_pct DESC",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(AllocationTarget {
+            asset_id: row.get(0)?,
+            target_pct: row.get(1)?,
+            lot_size: row.get(2)?,
+            no_sell: row.get(3)?,
+        })
+    })?;
+
+    let mut result = Vec::new();
+    for row in rows {
+        result.push(row?);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::queries::assets;
+    use crate::db::test_db;
+    use crate::models::AssetType;
+
+    #[test]
+    fn test_allocation_targets() {
+        let conn = test_db();
+        let vti = assets::insert_asset(&conn, "VTI", "Vanguard Total", &AssetType::Etf, "USD", None).unwrap();
+        let bnd = assets::insert_asset(&conn, "BND", "Vanguard Bond", &AssetType::Etf, "USD", None).unwrap();
+        let mut target = AllocationTarget { asset_id: vti.id.clone(), target_pct: 60.0, lot_size: 1.0, no_sell: false };
+        set_allocation_target(&conn, &target).unwrap();
+        target.no_sell = true;
+        set_allocation_target(&conn, &target).unwrap();
+        set_allocation_target(&conn, &AllocationTarget { asset_id: bnd.id.clone(), target_pct: 40.0, lot_size: 0.0, no_sell: false }).unwrap();
+
+        let targets = list_allocation_targets(&conn).unwrap();
+        assert_eq!(targets.len(), 2);
+        assert_eq!(targets[0], target);
+
+        assert!(set_allocation_target(&conn, &AllocationTarget { target_pct: 120.0, ..target.clone() }).is_err());
+        delete_allocation_target(&conn, &bnd.id).unwrap();
+        assets::soft_delete_asset(&conn, &vti.id).unwrap();
+        assert!(list_allocation_targets(&conn).unwrap().is_empty());
+    }
+}
@@ -1,20 +1,27 @@
+use crate::db::queries::transactions;
 use crate::models::{Asset, AssetType};
 use chrono::Utc;
 use rusqlite::{params, Connection};
 use uuid::Uuid;
 
+// No exchange is stored as '' so that UNIQUE(symbol, exchange, asset_type) holds
+const SELECT_ASSET: &str =
+    "SELECT id, symbol, name, asset_type, currency, added_at, deleted_at, NULLIF(exchange, '') FROM assets";
+
 pub fn insert_asset(
     conn: &Connection,
     symbol: &str,
     name: &str,
     asset_type: &AssetType,
     currency: &str,
+    exchange: Option<&str>,
 ) -> anyhow::Result<Asset> {
     let id = Uuid::new_v4().to_string();
     let now = Utc::now().timestamp();
+    let exchange = normalize_exchange(exchange);
     conn.execute(
-        "INSERT INTO assets (id, symbol, name, asset_type, currency, added_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
-        params![id, symbol.to_uppercase(), name, asset_type.as_str(), currency, now],
+        "INSERT INTO assets (id, symbol, name, asset_type, currency, added_at, exchange) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![id, symbol.to_uppercase(), name, asset_type.as_str(), currency, now, exchange.as_deref().unwrap_or("")],
     )?;
     Ok(Asset {
         id,
@@ -22,26 +29,33 @@ pub fn insert_asset(
         name: name.to_string(),
         asset_type: asset_type.clone(),
         currency: currency.to_string(),
+        exchange,
         added_at: now,
         deleted_at: None,
     })
 }
 
 pub fn list_assets(conn: &Connection) -> anyhow::Result<Vec<Asset>> {
-    let mut stmt = conn.prepare(
-        "SELECT id, symbol, name, asset_type, currency, added_at, deleted_at FROM assets WHERE deleted_at IS NULL ORDER BY added_at DESC",
-    )?;
-    let rows = stmt.query_map([], |row| {
-        Ok(Asset {
-            id: row.get(0)?,
-            symbol: row.get(1)?,
-            name: row.get(2)?,
-            asset_type: AssetType::from_str(&row.get::<_, String>(3)?).unwrap_or(AssetType::Stock),
-            currency: row.get(4)?,
-            added_at: row.get(5)?,
-            deleted_at: row.get(6)?,
-        })
-    })?;
+    let mut stmt = conn.prepare(&format!("{} WHERE deleted_at IS NULL ORDER BY added_at DESC", SELECT_ASSET))?;
+    let rows = stmt.query_map([], row_to_asset)?;
+    let mut assets = Vec::new();
+    for row in rows {
+        assets.push(row?);
+    }
+    Ok(assets)
+}
+
+/// Active assets that belong in the portfolio: everything except assets
+/// added only from a watchlist that have never been traded.
+pub fn list_portfolio_assets(conn: &Connection) -> anyhow::Result<Vec<Asset>> {
+    let mut stmt = conn.prepare(&format!(
+        "{} WHERE deleted_at IS NULL
+           AND (id NOT IN (SELECT asset_id FROM watch_only_assets)
+                OR id IN (SELECT asset_id FROM transactions WHERE deleted_at IS NULL))
+         ORDER BY added_at DESC",
+        SELECT_ASSET
+    ))?;
+    let rows = stmt.query_map([], row_to_asset)?;
     let mut assets = Vec::new();
     for row in rows {
         assets.push(row?);
@@ -49,21 +63,33 @@ pub fn list_assets(conn: &Connection) -> anyhow::Result<Vec<Asset>> {
     Ok(assets)
 }
 
+/// Soft-deleted assets, most recently deleted first.
+pub fn list_deleted_assets(conn: &Connection) -> anyhow::Result<Vec<Asset>> {
+    let mut stmt = conn.prepare(&format!("{} WHERE deleted_at IS NOT NULL ORDER BY deleted_at DESC", SELECT_ASSET))?;
+    let rows = stmt.query_map([], row_to_asset)?;
+    let mut assets = Vec::new();
+    for row in rows {
+        assets.push(row?);
+    }
+    Ok(assets)
+}
+
+/// Purge soft-deleted assets, only those trashed before `deleted_before` if
+/// given. Returns how many were removed.
+pub fn purge_deleted_assets(conn: &Connection, deleted_before: Option<i64>) -> anyhow::Result<u64> {
+    let mut purged = 0;
+    for asset in list_deleted_assets(conn)? {
+        if deleted_before.is_none_or(|cutoff| asset.deleted_at.is_some_and(|ts| ts < cutoff)) {
+            hard_delete_asset(conn, &asset.id)?;
+            purged += 1;
+        }
+    }
+    Ok(purged)
+}
+
 pub fn list_all_assets(conn: &Connection) -> anyhow::Result<Vec<Asset>> {
-    let mut stmt = conn.prepare(
-        "SELECT id, symbol, name, asset_type, currency, added_at, deleted_at FROM assets ORDER BY added_at DESC",
-    )?;
-    let rows = stmt.query_map([], |row| {
-        Ok(Asset {
-            id: row.get(0)?,
-            symbol: row.get(1)?,
-            name: row.get(2)?,
-            asset_type: AssetType::from_str(&row.get::<_, String>(3)?).unwrap_or(AssetType::Stock),
-            currency: row.get(4)?,
-            added_at: row.get(5)?,
-            deleted_at: row.get(6)?,
-        })
-    })?;
+    let mut stmt = conn.prepare(&format!("{} ORDER BY added_at DESC", SELECT_ASSET))?;
+    let rows = stmt.query_map([], row_to_asset)?;
     let mut assets = Vec::new();
     for row in rows {
         assets.push(row?);
@@ -71,116 +97,138 @@ pub fn list_all_assets(conn: &Connection) -> anyhow::Result<Vec<Asset>> {
     Ok(assets)
 }
 
+/// Delete an asset for good. Its transactions go with it, logged as purged.
 pub fn hard_delete_asset(conn: &Connection, id: &str) -> anyhow::Result<()> {
-    let deleted = conn.execute("DELETE FROM assets WHERE id = ?1", params![id])?;
+    let tx = conn.unchecked_transaction()?;
+    transactions::purge_transactions_by_asset(&tx, id)?;
+    let deleted = tx.execute("DELETE FROM assets WHERE id = ?1", params![id])?;
     if deleted == 0 {
         anyhow::bail!("Asset not found");
     }
+    tx.commit()?;
     Ok(())
 }
 
-pub fn get_asset(conn: &Connection, id: &str) -> anyhow::Result<Option<Asset>> {
-    let mut stmt = conn.prepare(
-        "SELECT id, symbol, name, asset_type, currency, added_at, deleted_at FROM assets WHERE id = ?1 AND deleted_at IS NULL",
-    )?;
-    let mut rows = stmt.query_map(params![id], |row| {
-        Ok(Asset {
-            id: row.get(0)?,
-            symbol: row.get(1)?,
-            name: row.get(2)?,
-            asset_type: AssetType::from_str(&row.get::<_, String>(3)?).unwrap_or(AssetType::Stock),
-            currency: row.get(4)?,
-            added_at: row.get(5)?,
-            deleted_at: row.get(6)?,
-        })
-    })?;
+pub fn get_asset_including_deleted(conn: &Connection, id: &str) -> anyhow::Result<Option<Asset>> {
+    let mut stmt = conn.prepare(&format!("{} WHERE id = ?1", SELECT_ASSET))?;
+    let mut rows = stmt.query_map(params![id], row_to_asset)?;
     match rows.next() {
         Some(row) => Ok(Some(row?)),
         None => Ok(None),
     }
 }
 
-pub fn get_asset_by_symbol(conn: &Connection, symbol: &str) -> anyhow::Result<Option<Asset>> {
-    let mut stmt = conn.prepare(
-        "SELECT id, symbol, name, asset_type, currency, added_at, deleted_at FROM assets WHERE symbol = ?1 AND deleted_at IS NULL",
-    )?;
-    let mut rows = stmt.query_map(params![symbol.to_uppercase()], |row| {
-        Ok(Asset {
-            id: row.get(0)?,
-            symbol: row.get(1)?,
-            name: row.get(2)?,
-            asset_type: AssetType::from_str(&row.get::<_, String>(3)?).unwrap_or(AssetType::Stock),
-            currency: row.get(4)?,
-            added_at: row.get(5)?,
-            deleted_at: row.get(6)?,
-        })
-    })?;
+pub fn get_asset(conn: &Connection, id: &str) -> anyhow::Result<Option<Asset>> {
+    let mut stmt = conn.prepare(&format!("{} WHERE id = ?1 AND deleted_at IS NULL", SELECT_ASSET))?;
+    let mut rows = stmt.query_map(params![id], row_to_asset)?;
     match rows.next() {
         Some(row) => Ok(Some(row?)),
         None => Ok(None),
     }
 }
 
+/// The active asset with this identity: symbol, exchange (`None` for
+/// unspecified) and asset type together.
+pub fn get_asset_by_symbol(
+    conn: &Connection,
+    symbol: &str,
+    exchange: Option<&str>,
+    asset_type: &AssetType,
+) -> anyhow::Result<Option<Asset>> {
+    Ok(get_asset_by_symbol_including_deleted(conn, symbol, exchange, asset_type)?
+        .filter(|asset| asset.deleted_at.is_none()))
+}
+
 pub fn get_asset_by_symbol_including_deleted(
     conn: &Connection,
     symbol: &str,
+    exchange: Option<&str>,
+    asset_type: &AssetType,
 ) -> anyhow::Result<Option<Asset>> {
-    let mut stmt = conn.prepare(
-        "SELECT id, symbol, name, asset_type, currency, added_at, deleted_at FROM assets WHERE symbol = ?1",
+    let mut stmt = conn.prepare(&format!(
+        "{} WHERE symbol = ?1 AND exchange = ?2 AND asset_type = ?3",
+        SELECT_ASSET
+    ))?;
+    let exchange = normalize_exchange(exchange);
+    let mut rows = stmt.query_map(
+        params![symbol.to_uppercase(), exchange.as_deref().unwrap_or(""), asset_type.as_str()],
+        row_to_asset,
     )?;
-    let mut rows = stmt.query_map(params![symbol.to_uppercase()], |row| {
-        Ok(Asset {
-            id: row.get(0)?,
-            symbol: row.get(1)?,
-            name: row.get(2)?,
-            asset_type: AssetType::from_str(&row.get::<_, String>(3)?).unwrap_or(AssetType::Stock),
-            currency: row.get(4)?,
-            added_at: row.get(5)?,
-            deleted_at: row.get(6)?,
-        })
-    })?;
     match rows.next() {
         Some(row) => Ok(Some(row?)),
         None => Ok(None),
     }
 }
 
+/// Bring back a soft-deleted asset under the given name and type, together
+/// with the transactions that were deleted with it.
 pub fn restore_asset(
     conn: &Connection,
     id: &str,
     name: &str,
     asset_type: &AssetType,
 ) -> anyhow::Result<Asset> {
+    let was_deleted = get_asset_including_deleted(conn, id)?.is_some_and(|a| a.deleted_at.is_some());
     let now = Utc::now().timestamp();
-    conn.execute(
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
         "UPDATE assets SET deleted_at = NULL, name = ?1, asset_type = ?2, added_at = ?3 WHERE id = ?4",
         params![name, asset_type.as_str(), now, id],
     )?;
+    if was_deleted {
+        transactions::restore_transactions_by_asset(&tx, id)?;
+    }
+    tx.commit()?;
     get_asset(conn, id)?
         .ok_or_else(|| anyhow::anyhow!("Failed to restore asset"))
 }
 
+/// Move an asset and its live transactions to the trash, together. The
+/// transactions are marked as trashed with it, so restoring the asset brings
+/// back exactly those.
 pub fn soft_delete_asset(conn: &Connection, id: &str) -> anyhow::Result<()> {
     let now = Utc::now().timestamp();
-    let updated = conn.execute(
+    let tx = conn.unchecked_transaction()?;
+    transactions::soft_delete_transactions_by_asset(&tx, id, now)?;
+    let updated = tx.execute(
         "UPDATE assets SET deleted_at = ?1 WHERE id = ?2 AND deleted_at IS NULL",
         params![now, id],
     )?;
     if updated == 0 {
         anyhow::bail!("Asset not found");
     }
+    tx.commit()?;
     Ok(())
 }
 
+/// Exchange codes are compared case-insensitively; blank means unspecified.
+fn normalize_exchange(exchange: Option<&str>) -> Option<String> {
+    exchange.map(|e| e.trim().to_uppercase()).filter(|e| !e.is_empty())
+}
+
+fn row_to_asset(row: &rusqlite::Row) -> rusqlite::Result<Asset> {
+    Ok(Asset {
+        id: row.get(0)?,
+        symbol: row.get(1)?,
+        name: row.get(2)?,
+        asset_type: AssetType::from_str(&row.get::<_, String>(3)?).unwrap_or(AssetType::Stock),
+        currency: row.get(4)?,
+        exchange: row.get(7)?,
+        added_at: row.get(5)?,
+        deleted_at: row.get(6)?,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::db::test_db;
+    use crate::models::TxType;
 
     #[test]
     fn test_insert_and_list_assets() {
         let conn = test_db();
-        let asset = insert_asset(&conn, "AAPL", "Apple Inc.", &AssetType::Stock, "USD").unwrap();
+        let asset = insert_asset(&conn, "AAPL", "Apple Inc.", &AssetType::Stock, "USD", None).unwrap();
         assert_eq!(asset.symbol, "AAPL");
         assert_eq!(asset.name, "Apple Inc.");
 
@@ -192,22 +240,39 @@ mod tests {
     #[test]
     fn test_symbol_uppercased() {
         let conn = test_db();
-        let asset = insert_asset(&conn, "aapl", "Apple", &AssetType::Stock, "USD").unwrap();
+        let asset = insert_asset(&conn, "aapl", "Apple", &AssetType::Stock, "USD", None).unwrap();
         assert_eq!(asset.symbol, "AAPL");
     }
 
     #[test]
     fn test_duplicate_symbol_fails() {
         let conn = test_db();
-        insert_asset(&conn, "AAPL", "Apple", &AssetType::Stock, "USD").unwrap();
-        let result = insert_asset(&conn, "AAPL", "Apple 2", &AssetType::Stock, "USD");
+        insert_asset(&conn, "AAPL", "Apple", &AssetType::Stock, "USD", None).unwrap();
+        let result = insert_asset(&conn, "AAPL", "Apple 2", &AssetType::Stock, "USD", None);
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_same_ticker_on_other_exchange_or_type() {
+        let conn = test_db();
+        let nyse = insert_asset(&conn, "SHOP", "Shopify", &AssetType::Stock, "USD", Some("NYSE")).unwrap();
+        let tsx = insert_asset(&conn, "SHOP", "Shopify", &AssetType::Stock, "CAD", Some("tsx")).unwrap();
+        insert_asset(&conn, "SHOP", "Shop token", &AssetType::Crypto, "USD", None).unwrap();
+        assert_eq!(tsx.exchange.as_deref(), Some("TSX"));
+        assert!(insert_asset(&conn, "SHOP", "Shopify", &AssetType::Stock, "CAD", Some("TSX")).is_err());
+
+        let found = get_asset_by_symbol(&conn, "shop", Some("TSX"), &AssetType::Stock).unwrap().unwrap();
+        assert_eq!(found.id, tsx.id);
+        assert_eq!(get_asset(&conn, &nyse.id).unwrap().unwrap().exchange.as_deref(), Some("NYSE"));
+        // No exchange is its own identity, not a wildcard
+        assert!(get_asset_by_symbol(&conn, "SHOP", None, &AssetType::Stock).unwrap().is_none());
+        assert_eq!(list_assets(&conn).unwrap().len(), 3);
+    }
+
     #[test]
     fn test_get_asset_by_id() {
         let conn = test_db();
-        let asset = insert_asset(&conn, "BTC", "Bitcoin", &AssetType::Crypto, "USD").unwrap();
+        let asset = insert_asset(&conn, "BTC", "Bitcoin", &AssetType::Crypto, "USD", None).unwrap();
         let found = get_asset(&conn, &asset.id).unwrap();
         assert!(found.is_some());
         assert_eq!(found.unwrap().symbol, "BTC");
@@ -216,8 +281,8 @@ mod tests {
     #[test]
     fn test_get_asset_by_symbol() {
         let conn = test_db();
-        insert_asset(&conn, "ETH", "Ethereum", &AssetType::Crypto, "USD").unwrap();
-        let found = get_asset_by_symbol(&conn, "eth").unwrap();
+        insert_asset(&conn, "ETH", "Ethereum", &AssetType::Crypto, "USD", None).unwrap();
+        let found = get_asset_by_symbol(&conn, "eth", None, &AssetType::Crypto).unwrap();
         assert!(found.is_some());
         assert_eq!(found.unwrap().symbol, "ETH");
     }
@@ -225,7 +290,7 @@ mod tests {
     #[test]
     fn test_soft_delete() {
         let conn = test_db();
-        let asset = insert_asset(&conn, "TSLA", "Tesla", &AssetType::Stock, "USD").unwrap();
+        let asset = insert_asset(&conn, "TSLA", "Tesla", &AssetType::Stock, "USD", None).unwrap();
 
         soft_delete_asset(&conn, &asset.id).unwrap();
 
@@ -241,14 +306,14 @@ mod tests {
     #[test]
     fn test_restore_soft_deleted_asset() {
         let conn = test_db();
-        let asset = insert_asset(&conn, "TSLA", "Tesla", &AssetType::Stock, "USD").unwrap();
+        let asset = insert_asset(&conn, "TSLA", "Tesla", &AssetType::Stock, "USD", None).unwrap();
         soft_delete_asset(&conn, &asset.id).unwrap();
 
         // Should not appear in active queries
-        assert!(get_asset_by_symbol(&conn, "TSLA").unwrap().is_none());
+        assert!(get_asset_by_symbol(&conn, "TSLA", None, &AssetType::Stock).unwrap().is_none());
 
         // Should appear when including deleted
-        let deleted = get_asset_by_symbol_including_deleted(&conn, "TSLA").unwrap();
+        let deleted = get_asset_by_symbol_including_deleted(&conn, "TSLA", None, &AssetType::Stock).unwrap();
         assert!(deleted.is_some());
         assert!(deleted.unwrap().deleted_at.is_some());
 
@@ -258,13 +323,42 @@ mod tests {
         assert_eq!(restored.name, "Tesla Inc.");
 
         // Should now appear in active queries
-        let found = get_asset_by_symbol(&conn, "TSLA").unwrap();
+        let found = get_asset_by_symbol(&conn, "TSLA", None, &AssetType::Stock).unwrap();
         assert!(found.is_some());
 
         let assets = list_assets(&conn).unwrap();
         assert_eq!(assets.len(), 1);
     }
 
+    #[test]
+    fn test_restore_asset_brings_back_its_transactions() {
+        let conn = test_db();
+        let asset = insert_asset(&conn, "TSLA", "Tesla", &AssetType::Stock, "USD", None).unwrap();
+        let kept = transactions::insert_transaction(&conn, &asset.id, &TxType::Buy, 1.0, 200.0, 0, None, 0.0, None).unwrap();
+        let dropped = transactions::insert_transaction(&conn, &asset.id, &TxType::Buy, 2.0, 200.0, 0, None, 0.0, None).unwrap();
+        transactions::soft_delete_transaction(&conn, &dropped.id).unwrap();
+
+        soft_delete_asset(&conn, &asset.id).unwrap();
+        // Deleted on its own, even if in the same second as the asset
+        conn.execute(
+            "UPDATE transactions SET deleted_at = (SELECT deleted_at FROM assets WHERE id = ?1) WHERE id = ?2",
+            params![asset.id, dropped.id],
+        )
+        .unwrap();
+        assert_eq!(list_deleted_assets(&conn).unwrap().len(), 1);
+        assert_eq!(transactions::list_deleted_transactions(&conn).unwrap().len(), 2);
+        assert!(transactions::restore_transaction(&conn, &kept.id).is_err());
+
+        restore_asset(&conn, &asset.id, "Tesla", &AssetType::Stock).unwrap();
+        assert_eq!(transactions::list_transactions_by_asset(&conn, &asset.id).unwrap(), vec![kept]);
+        assert!(list_deleted_assets(&conn).unwrap().is_empty());
+
+        soft_delete_asset(&conn, &asset.id).unwrap();
+        assert_eq!(purge_deleted_assets(&conn, Some(0)).unwrap(), 0);
+        assert_eq!(purge_deleted_assets(&conn, None).unwrap(), 1);
+        assert!(get_asset_including_deleted(&conn, &asset.id).unwrap().is_none());
+    }
+
     #[test]
     fn test_soft_delete_nonexistent() {
         let conn = test_db();
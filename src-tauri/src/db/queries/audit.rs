@@ -0,0 +1,111 @@
+use crate::models::{AuditAction, AuditEntry, Transaction};
+use chrono::Utc;
+use rusqlite::{params, Connection, Row};
+
+const SELECT_AUDIT: &str =
+    "SELECT id, transaction_id, asset_id, action, before, after, ts, reverts, reason FROM transaction_audit";
+
+fn row_to_entry(row: &Row) -> rusqlite::Result<AuditEntry> {
+    let snapshot = |json: Option<String>| json.and_then(|j| serde_json::from_str::<Transaction>(&j).ok());
+    Ok(AuditEntry {
+        id: row.get(0)?,
+        transaction_id: row.get(1)?,
+        asset_id: row.get(2)?,
+        action: AuditAction::from_str(&row.get::<_, String>(3)?).unwrap_or(AuditAction::Update),
+        before: snapshot(row.get(4)?),
+        after: snapshot(row.get(5)?),
+        ts: row.get(6)?,
+        reverts: row.get(7)?,
+        reason: row.get(8)?,
+    })
+}
+
+/// Append one change to the log. At least one of `before` and `after` must be
+/// given; they are stored as JSON snapshots of the whole row.
+pub fn record(
+    conn: &Connection,
+    action: AuditAction,
+    before: Option<&Transaction>,
+    after: Option<&Transaction>,
+    reverts: Option<i64>,
+    reason: Option<&str>,
+) -> anyhow::Result<i64> {
+    let Some(subject) = after.or(before) else {
+        anyhow::bail!("Nothing to record");
+    };
+    let before = before.map(serde_json::to_string).transpose()?;
+    let after = after.map(serde_json::to_string).transpose()?;
+    conn.execute(
+        "INSERT INTO transaction_audit (transaction_id, asset_id, action, before, after, ts, reverts, reason)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+        params![
+            subject.id,
+            subject.asset_id,
+            action.as_str(),
+            before,
+            after,
+            Utc::now().timestamp(),
+            reverts,
+            reason
+        ],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+pub fn get_audit_entry(conn: &Connection, id: i64) -> anyhow::Result<Option<AuditEntry>> {
+    let mut stmt = conn.prepare(&format!("{} WHERE id = ?1", SELECT_AUDIT))?;
+    let mut rows = stmt.query_map(params![id], row_to_entry)?;
+    match rows.next() {
+        Some(row) => Ok(Some(row?)),
+        None => Ok(None),
+    }
+}
+
+/// Changes to one transaction or to all transactions of one asset, newest
+/// first. Entries survive deletes and purges. A move between assets shows up
+/// under both.
+pub fn list_audit_entries(
+    conn: &Connection,
+    transaction_id: Option<&str>,
+    asset_id: Option<&str>,
+) -> anyhow::Result<Vec<AuditEntry>> {
+    let mut stmt = conn.prepare(&format!(
+        "{} WHERE (?1 IS NULL OR transaction_id = ?1)
+         AND (?2 IS NULL OR asset_id = ?2 OR json_extract(before, '$.asset_id') = ?2) ORDER BY id DESC",
+        SELECT_AUDIT
+    ))?;
+    let rows = stmt.query_map(params![transaction_id, asset_id], row_to_entry)?;
+
+    let mut result = Vec::new();
+    for row in rows {
+        result.push(row?);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::queries::{assets, transactions};
+    use crate::db::test_db;
+    use crate::models::{AssetType, TxType};
+
+    #[test]
+    fn test_audit_log_is_append_only() {
+        let conn = test_db();
+        let asset = assets::insert_asset(&conn, "BTC", "Bitcoin", &AssetType::Crypto, "USD", None).unwrap();
+        let tx = transactions::insert_transaction(&conn, &asset.id, &TxType::Buy, 1.0, 100.0, 0, None, 0.0, None)
+            .unwrap();
+
+        let entries = list_audit_entries(&conn, Some(&tx.id), None).unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, AuditAction::Insert);
+        assert_eq!(entries[0].after.as_ref(), Some(&tx));
+        assert!(entries[0].before.is_none());
+        assert_eq!(get_audit_entry(&conn, entries[0].id).unwrap(), Some(entries[0].clone()));
+
+        assert!(conn.execute("UPDATE transaction_audit SET action = 'update'", []).is_err());
+        assert!(conn.execute("DELETE FROM transaction_audit", []).is_err());
+        assert!(record(&conn, AuditAction::Update, None, None, None, None).is_err());
+    }
+}
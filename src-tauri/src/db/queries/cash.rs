@@ -0,0 +1,240 @@
+use crate::db::queries::transactions;
+use crate::models::{Account, CashBalance, CashEntryKind, CashLedgerEntry};
+use chrono::Utc;
+use rusqlite::{params, Connection};
+use uuid::Uuid;
+
+/// Explicit entries, signed by kind, plus one derived entry per live buy or
+/// sell that names an account. Trades are priced in USD, so their cash
+/// movements are too.
+const LEDGER_CTE: &str = "
+    WITH ledger AS (
+        SELECT id, account_id, kind,
+               CASE kind WHEN 'withdrawal' THEN -amount ELSE amount END AS amount,
+               currency, ts, asset_id, NULL AS transaction_id, notes
+        FROM cash_ledger
+        UNION ALL
+        SELECT 'tx:' || id, account_id, tx_type,
+               CASE tx_type WHEN 'buy' THEN -(quantity * price_usd + fee) ELSE quantity * price_usd - fee END,
+               'USD', ts, asset_id, id, notes
+        FROM transactions
+        WHERE account_id IS NOT NULL AND deleted_at IS NULL
+    )";
+
+pub fn insert_account(conn: &Connection, name: &str, currency: &str) -> anyhow::Result<Account> {
+    let id = Uuid::new_v4().to_string();
+    let now = Utc::now().timestamp();
+    conn.execute(
+        "INSERT INTO accounts (id, name, currency, created_at) VALUES (?1, ?2, ?3, ?4)",
+        params![id, name, currency, now],
+    )?;
+    Ok(Account {
+        id,
+        name: name.to_string(),
+        currency: currency.to_string(),
+        created_at: now,
+    })
+}
+
+pub fn list_accounts(conn: &Connection) -> anyhow::Result<Vec<Account>> {
+    let mut stmt = conn.prepare("SELECT id, name, currency, created_at FROM accounts ORDER BY name")?;
+    let rows = stmt.query_map([], |row| {
+        Ok(Account {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            currency: row.get(2)?,
+            created_at: row.get(3)?,
+        })
+    })?;
+
+    let mut result = Vec::new();
+    for row in rows {
+        result.push(row?);
+    }
+    Ok(result)
+}
+
+/// Removes the account and its explicit entries. Transactions keep their
+/// holdings but stop moving cash; unlinking them is logged, and refused while
+/// any of them is locked.
+pub fn delete_account(conn: &Connection, id: &str) -> anyhow::Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    transactions::detach_account(&tx, id)?;
+    let deleted = tx.execute("DELETE FROM accounts WHERE id = ?1", params![id])?;
+    if deleted == 0 {
+        anyhow::bail!("Account not found");
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// Records a deposit, withdrawal or dividend. `amount` is the positive size
+/// of the movement; its direction comes from `kind`.
+#[allow(clippy::too_many_arguments)]
+pub fn insert_cash_entry(
+    conn: &Connection,
+    account_id: &str,
+    kind: CashEntryKind,
+    amount: f64,
+    currency: &str,
+    ts: i64,
+    asset_id: Option<&str>,
+    notes: Option<&str>,
+) -> anyhow::Result<CashLedgerEntry> {
+    if matches!(kind, CashEntryKind::Buy | CashEntryKind::Sell) {
+        anyhow::bail!("Buy and sell cash movements come from transactions");
+    }
+    let id = Uuid::new_v4().to_string();
+    let now = Utc::now().timestamp();
+    conn.execute(
+        "INSERT INTO cash_ledger (id, account_id, kind, amount, currency, ts, asset_id, notes, created_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![id, account_id, kind.as_str(), amount, currency, ts, asset_id, notes, now],
+    )?;
+    Ok(CashLedgerEntry {
+        id,
+        account_id: account_id.to_string(),
+        kind,
+        amount: if kind == CashEntryKind::Withdrawal { -amount } else { amount },
+        currency: currency.to_string(),
+        ts,
+        asset_id: asset_id.map(|s| s.to_string()),
+        transaction_id: None,
+        notes: notes.map(|s| s.to_string()),
+    })
+}
+
+pub fn delete_cash_entry(conn: &Connection, id: &str) -> anyhow::Result<()> {
+    let deleted = conn.execute("DELETE FROM cash_ledger WHERE id = ?1", params![id])?;
+    if deleted == 0 {
+        anyhow::bail!("Cash entry not found");
+    }
+    Ok(())
+}
+
+/// Ledger for one account, or for all accounts when `account_id` is `None`, newest first.
+pub fn list_cash_ledger(conn: &Connection, account_id: Option<&str>) -> anyhow::Result<Vec<CashLedgerEntry>> {
+    let sql = format!(
+        "{LEDGER_CTE}
+         SELECT id, account_id, kind, amount, currency, ts, asset_id, transaction_id, notes
+         FROM ledger WHERE ?1 IS NULL OR account_id = ?1 ORDER BY ts DESC, id"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(params![account_id], |row| {
+        Ok(CashLedgerEntry {
+            id: row.get(0)?,
+            account_id: row.get(1)?,
+            kind: CashEntryKind::from_str(&row.get::<_, String>(2)?).unwrap_or(CashEntryKind::Deposit),
+            amount: row.get(3)?,
+            currency: row.get(4)?,
+            ts: row.get(5)?,
+            asset_id: row.get(6)?,
+            transaction_id: row.get(7)?,
+            notes: row.get(8)?,
+        })
+    })?;
+
+    let mut result = Vec::new();
+    for row in rows {
+        result.push(row?);
+    }
+    Ok(result)
+}
+
+/// Balance per account and currency. Accounts with no movements report a
+/// zero balance in their own currency.
+pub fn cash_balances(conn: &Connection) -> anyhow::Result<Vec<CashBalance>> {
+    let sql = format!(
+        "{LEDGER_CTE}
+         SELECT a.id, a.name, COALESCE(l.currency, a.currency), COALESCE(SUM(l.amount), 0)
+         FROM accounts a LEFT JOIN ledger l ON l.account_id = a.id
+         GROUP BY a.id, l.currency ORDER BY a.name, 3"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map([], |row| {
+        Ok(CashBalance {
+            account_id: row.get(0)?,
+            account_name: row.get(1)?,
+            currency: row.get(2)?,
+            balance: row.get(3)?,
+        })
+    })?;
+
+    let mut result = Vec::new();
+    for row in rows {
+        result.push(row?);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::queries::{assets, audit};
+    use crate::db::test_db;
+    use crate::models::{AssetType, AuditAction, TxType};
+
+    #[test]
+    fn test_cash_balance_follows_trades_and_entries() {
+        let conn = test_db();
+        let broker = insert_account(&conn, "Broker", "USD").unwrap();
+        let aapl = assets::insert_asset(&conn, "AAPL", "Apple", &AssetType::Stock, "USD", None).unwrap();
+
+        insert_cash_entry(&conn, &broker.id, CashEntryKind::Deposit, 10000.0, "USD", 100, None, None).unwrap();
+        transactions::insert_transaction(&conn, &aapl.id, &TxType::Buy, 10.0, 150.0, 200, None, 5.0, Some(&broker.id)).unwrap();
+        transactions::insert_transaction(&conn, &aapl.id, &TxType::Sell, 4.0, 200.0, 300, None, 2.0, Some(&broker.id)).unwrap();
+        insert_cash_entry(&conn, &broker.id, CashEntryKind::Dividend, 12.5, "USD", 400, Some(&aapl.id), None).unwrap();
+        insert_cash_entry(&conn, &broker.id, CashEntryKind::Withdrawal, 1000.0, "USD", 500, None, None).unwrap();
+        // No account: holdings change, cash does not
+        transactions::insert_transaction(&conn, &aapl.id, &TxType::Buy, 1.0, 150.0, 600, None, 0.0, None).unwrap();
+
+        let balances = cash_balances(&conn).unwrap();
+        assert_eq!(balances.len(), 1);
+        let expected = 10000.0 - 1505.0 + 798.0 + 12.5 - 1000.0;
+        assert!((balances[0].balance - expected).abs() < 1e-9);
+
+        let ledger = list_cash_ledger(&conn, Some(&broker.id)).unwrap();
+        assert_eq!(ledger.len(), 5);
+        assert_eq!(ledger[0].kind, CashEntryKind::Withdrawal);
+        assert_eq!(ledger[0].amount, -1000.0);
+        assert_eq!(ledger[3].kind, CashEntryKind::Buy);
+        assert!(ledger[3].transaction_id.is_some());
+
+        // Deleting the account unlinks its trades through the audit log, and
+        // not at all while one of them is locked
+        let buy_id = ledger[3].transaction_id.clone().unwrap();
+        transactions::lock_transaction(&conn, &buy_id).unwrap();
+        assert!(delete_account(&conn, &broker.id).is_err());
+        assert_eq!(list_accounts(&conn).unwrap().len(), 1);
+        transactions::unlock_transaction(&conn, &buy_id, "Closing the broker account").unwrap();
+        delete_account(&conn, &broker.id).unwrap();
+        assert!(transactions::list_transactions_by_asset(&conn, &aapl.id).unwrap().iter().all(|t| t.account_id.is_none()));
+        let unlinked = &audit::list_audit_entries(&conn, Some(&buy_id), None).unwrap()[0];
+        assert_eq!(unlinked.action, AuditAction::Update);
+        assert!(unlinked.after.as_ref().unwrap().account_id.is_none());
+    }
+
+    #[test]
+    fn test_cash_balances_split_by_currency() {
+        let conn = test_db();
+        let account = insert_account(&conn, "Savings", "EUR").unwrap();
+        let empty = insert_account(&conn, "Empty", "CAD").unwrap();
+        insert_cash_entry(&conn, &account.id, CashEntryKind::Deposit, 500.0, "EUR", 1, None, None).unwrap();
+        insert_cash_entry(&conn, &account.id, CashEntryKind::Deposit, 200.0, "USD", 2, None, None).unwrap();
+
+        let balances = cash_balances(&conn).unwrap();
+        assert_eq!(balances.len(), 3);
+        assert_eq!(balances[0], CashBalance {
+            account_id: empty.id.clone(),
+            account_name: "Empty".to_string(),
+            currency: "CAD".to_string(),
+            balance: 0.0,
+        });
+        assert_eq!(balances[1].currency, "EUR");
+        assert_eq!(balances[2].currency, "USD");
+
+        assert!(insert_cash_entry(&conn, &account.id, CashEntryKind::Buy, 1.0, "USD", 3, None, None).is_err());
+        delete_account(&conn, &account.id).unwrap();
+        assert!(list_cash_ledger(&conn, None).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,125 @@
+use crate::models::{CorporateAction, CorporateActionType};
+use rusqlite::{params, Connection};
+
+/// Store provider events for one asset. Re-fetched events replace their value
+/// in place, keyed by (asset, type, ex-date).
+pub fn upsert_corporate_actions(conn: &Connection, actions: &[CorporateAction]) -> anyhow::Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO corporate_actions (asset_id, action_type, ts, value, provider)
+             VALUES (?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(asset_id, action_type, ts) DO UPDATE SET
+                value = excluded.value,
+                provider = excluded.provider",
+        )?;
+        for action in actions {
+            stmt.execute(params![
+                action.asset_id,
+                action.action_type.as_str(),
+                action.ts,
+                action.value,
+                action.provider,
+            ])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// All splits and dividends for an asset, oldest first.
+pub fn list_corporate_actions(conn: &Connection, asset_id: &str) -> anyhow::Result<Vec<CorporateAction>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, asset_id, action_type, ts, value, provider FROM corporate_actions WHERE asset_id = ?1 ORDER BY ts ASC, action_type ASC",
+    )?;
+    let rows = stmt.query_map(params![asset_id], row_to_corporate_action)?;
+
+    let mut result = Vec::new();
+    for row in rows {
+        result.push(row?);
+    }
+    Ok(result)
+}
+
+pub fn get_last_fetched(conn: &Connection, asset_id: &str) -> anyhow::Result<Option<i64>> {
+    let mut stmt = conn.prepare("SELECT last_fetched FROM corporate_actions_meta WHERE asset_id = ?1")?;
+    let mut rows = stmt.query_map(params![asset_id], |row| row.get(0))?;
+    match rows.next() {
+        Some(row) => Ok(Some(row?)),
+        None => Ok(None),
+    }
+}
+
+pub fn set_last_fetched(conn: &Connection, asset_id: &str, last_fetched: i64) -> anyhow::Result<()> {
+    conn.execute(
+        "INSERT INTO corporate_actions_meta (asset_id, last_fetched) VALUES (?1, ?2)
+         ON CONFLICT(asset_id) DO UPDATE SET last_fetched = excluded.last_fetched",
+        params![asset_id, last_fetched],
+    )?;
+    Ok(())
+}
+
+fn row_to_corporate_action(row: &rusqlite::Row) -> rusqlite::Result<CorporateAction> {
+    Ok(CorporateAction {
+        id: row.get(0)?,
+        asset_id: row.get(1)?,
+        action_type: CorporateActionType::from_str(&row.get::<_, String>(2)?)
+            .unwrap_or(CorporateActionType::Dividend),
+        ts: row.get(3)?,
+        value: row.get(4)?,
+        provider: row.get(5)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_db;
+    use crate::db::queries::assets;
+    use crate::models::AssetType;
+
+    fn action(asset_id: &str, action_type: CorporateActionType, ts: i64, value: f64) -> CorporateAction {
+        CorporateAction {
+            id: None,
+            asset_id: asset_id.to_string(),
+            action_type,
+            ts,
+            value,
+            provider: "twelve_data".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_upsert_and_list_corporate_actions() {
+        let conn = test_db();
+        let asset = assets::insert_asset(&conn, "AAPL", "Apple", &AssetType::Stock, "USD", None).unwrap();
+
+        upsert_corporate_actions(
+            &conn,
+            &[
+                action(&asset.id, CorporateActionType::Split, 1598832000, 4.0),
+                action(&asset.id, CorporateActionType::Dividend, 1596758400, 0.82),
+            ],
+        )
+        .unwrap();
+        // Re-fetch corrects the amount instead of duplicating the event
+        upsert_corporate_actions(&conn, &[action(&asset.id, CorporateActionType::Dividend, 1596758400, 0.205)])
+            .unwrap();
+
+        let actions = list_corporate_actions(&conn, &asset.id).unwrap();
+        assert_eq!(actions.len(), 2);
+        assert_eq!(actions[0].action_type, CorporateActionType::Dividend);
+        assert_eq!(actions[0].value, 0.205);
+        assert_eq!(actions[1].action_type, CorporateActionType::Split);
+    }
+
+    #[test]
+    fn test_last_fetched() {
+        let conn = test_db();
+        let asset = assets::insert_asset(&conn, "AAPL", "Apple", &AssetType::Stock, "USD", None).unwrap();
+        assert_eq!(get_last_fetched(&conn, &asset.id).unwrap(), None);
+        set_last_fetched(&conn, &asset.id, 100).unwrap();
+        set_last_fetched(&conn, &asset.id, 200).unwrap();
+        assert_eq!(get_last_fetched(&conn, &asset.id).unwrap(), Some(200));
+    }
+}
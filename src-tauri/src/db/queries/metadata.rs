@@ -0,0 +1,174 @@
+use crate::models::{AssetMetadata, AssetProfile, AssetType};
+use rusqlite::{params, Connection};
+use std::collections::HashMap;
+
+/// Metadata for every active asset, with the asset type's default class
+/// filled in where none is set.
+pub fn list_asset_metadata(conn: &Connection) -> anyhow::Result<Vec<AssetMetadata>> {
+    let mut tags: HashMap<String, Vec<String>> = HashMap::new();
+    let mut stmt = conn.prepare("SELECT asset_id, tag FROM asset_tags ORDER BY tag")?;
+    let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+    for row in rows {
+        let (asset_id, tag) = row?;
+        tags.entry(asset_id).or_default().push(tag);
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT a.id, a.asset_type, m.sector, m.industry, m.country, m.asset_class,
+                a.id IN (SELECT asset_id FROM short_selling_assets)
+         FROM assets a LEFT JOIN asset_metadata m ON m.asset_id = a.id
+         WHERE a.deleted_at IS NULL ORDER BY a.added_at DESC",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        let asset_type = AssetType::from_str(&row.get::<_, String>(1)?).unwrap_or(AssetType::Stock);
+        Ok(AssetMetadata {
+            asset_id: row.get(0)?,
+            sector: row.get(2)?,
+            industry: row.get(3)?,
+            country: row.get(4)?,
+            asset_class: Some(
+                row.get::<_, Option<String>>(5)?
+                    .unwrap_or_else(|| asset_type.default_asset_class().to_string()),
+            ),
+            tags: Vec::new(),
+            allow_short: row.get(6)?,
+        })
+    })?;
+
+    let mut result = Vec::new();
+    for row in rows {
+        let mut metadata = row?;
+        metadata.tags = tags.remove(&metadata.asset_id).unwrap_or_default();
+        result.push(metadata);
+    }
+    Ok(result)
+}
+
+/// Overwrite the descriptive fields with the user's values; blanks clear a field.
+pub fn update_asset_metadata(conn: &Connection, asset_id: &str, profile: &AssetProfile) -> anyhow::Result<()> {
+    let clean = |v: &Option<String>| v.as_deref().map(str::trim).filter(|v| !v.is_empty()).map(str::to_string);
+    conn.execute(
+        "INSERT INTO asset_metadata (asset_id, sector, industry, country, asset_class) VALUES (?1, ?2, ?3, ?4, ?5)
+         ON CONFLICT(asset_id) DO UPDATE SET
+            sector = excluded.sector, industry = excluded.industry,
+            country = excluded.country, asset_class = excluded.asset_class",
+        params![
+            asset_id,
+            clean(&profile.sector),
+            clean(&profile.industry),
+            clean(&profile.country),
+            clean(&profile.asset_class),
+        ],
+    )?;
+    Ok(())
+}
+
+/// Store a provider profile without overwriting anything already set, and
+/// remember when it was fetched.
+pub fn fill_asset_metadata(
+    conn: &Connection,
+    asset_id: &str,
+    profile: &AssetProfile,
+    fetched_at: i64,
+) -> anyhow::Result<()> {
+    conn.execute(
+        "INSERT INTO asset_metadata (asset_id, sector, industry, country, asset_class, profile_fetched_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+         ON CONFLICT(asset_id) DO UPDATE SET
+            sector = COALESCE(sector, excluded.sector),
+            industry = COALESCE(industry, excluded.industry),
+            country = COALESCE(country, excluded.country),
+            asset_class = COALESCE(asset_class, excluded.asset_class),
+            profile_fetched_at = excluded.profile_fetched_at",
+        params![asset_id, profile.sector, profile.industry, profile.country, profile.asset_class, fetched_at],
+    )?;
+    Ok(())
+}
+
+pub fn set_allow_short(conn: &Connection, asset_id: &str, allow: bool) -> anyhow::Result<()> {
+    if allow {
+        conn.execute("INSERT OR IGNORE INTO short_selling_assets (asset_id) VALUES (?1)", params![asset_id])?;
+    } else {
+        conn.execute("DELETE FROM short_selling_assets WHERE asset_id = ?1", params![asset_id])?;
+    }
+    Ok(())
+}
+
+pub fn allows_short(conn: &Connection, asset_id: &str) -> anyhow::Result<bool> {
+    let allowed = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM short_selling_assets WHERE asset_id = ?1)",
+        params![asset_id],
+        |row| row.get(0),
+    )?;
+    Ok(allowed)
+}
+
+pub fn get_profile_fetched_at(conn: &Connection, asset_id: &str) -> anyhow::Result<Option<i64>> {
+    let mut stmt = conn.prepare("SELECT profile_fetched_at FROM asset_metadata WHERE asset_id = ?1")?;
+    let mut rows = stmt.query_map(params![asset_id], |row| row.get::<_, Option<i64>>(0))?;
+    match rows.next() {
+        Some(row) => Ok(row?),
+        None => Ok(None),
+    }
+}
+
+/// Replace the asset's tags. Tags are trimmed, and compared case-insensitively.
+pub fn set_asset_tags(conn: &Connection, asset_id: &str, tags: &[String]) -> anyhow::Result<()> {
+    conn.execute("DELETE FROM asset_tags WHERE asset_id = ?1", params![asset_id])?;
+    for tag in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+        conn.execute(
+            "INSERT OR IGNORE INTO asset_tags (asset_id, tag) VALUES (?1, ?2)",
+            params![asset_id, tag],
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::queries::assets;
+    use crate::db::test_db;
+
+    #[test]
+    fn test_profile_fills_gaps_without_overwriting_edits() {
+        let conn = test_db();
+        let aapl = assets::insert_asset(&conn, "AAPL", "Apple", &AssetType::Stock, "USD", None).unwrap();
+        let btc = assets::insert_asset(&conn, "BTC", "Bitcoin", &AssetType::Crypto, "USD", None).unwrap();
+        assert_eq!(get_profile_fetched_at(&conn, &aapl.id).unwrap(), None);
+
+        update_asset_metadata(&conn, &aapl.id, &AssetProfile {
+            sector: Some(" Tech ".to_string()),
+            country: Some("".to_string()),
+            ..Default::default()
+        }).unwrap();
+        fill_asset_metadata(&conn, &aapl.id, &AssetProfile {
+            sector: Some("Technology".to_string()),
+            industry: Some("Consumer Electronics".to_string()),
+            country: Some("United States".to_string()),
+            asset_class: None,
+        }, 1000).unwrap();
+        assert_eq!(get_profile_fetched_at(&conn, &aapl.id).unwrap(), Some(1000));
+
+        let all = list_asset_metadata(&conn).unwrap();
+        let apple = all.iter().find(|m| m.asset_id == aapl.id).unwrap();
+        assert_eq!(apple.sector.as_deref(), Some("Tech"));
+        assert_eq!(apple.industry.as_deref(), Some("Consumer Electronics"));
+        assert_eq!(apple.country.as_deref(), Some("United States"));
+        assert_eq!(apple.asset_class.as_deref(), Some("Equity"));
+        let bitcoin = all.iter().find(|m| m.asset_id == btc.id).unwrap();
+        assert_eq!(bitcoin.asset_class.as_deref(), Some("Crypto"));
+    }
+
+    #[test]
+    fn test_asset_tags() {
+        let conn = test_db();
+        let asset = assets::insert_asset(&conn, "TSLA", "Tesla", &AssetType::Stock, "USD", None).unwrap();
+        let tags = ["speculative", "Long-term", "long-term ", ""].map(String::from);
+        set_asset_tags(&conn, &asset.id, &tags).unwrap();
+        assert_eq!(list_asset_metadata(&conn).unwrap()[0].tags, vec!["Long-term", "speculative"]);
+
+        set_asset_tags(&conn, &asset.id, &[]).unwrap();
+        assert!(list_asset_metadata(&conn).unwrap()[0].tags.is_empty());
+    }
+}
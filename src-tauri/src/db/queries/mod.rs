@@ -1,4 +1,14 @@
+pub mod alerts;
+pub mod allocation_targets;
 pub mod assets;
+pub mod audit;
+pub mod cash;
+pub mod corporate_actions;
+pub mod metadata;
+pub mod price_issues;
 pub mod prices;
+pub mod provider_symbols;
 pub mod settings;
+pub mod storage;
 pub mod transactions;
+pub mod watchlists;
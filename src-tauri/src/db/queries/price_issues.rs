@@ -0,0 +1,205 @@
+use crate::db::queries::prices;
+use crate::models::{Interval, OHLCVRow, PriceIssue, PriceIssueKind, PriceIssueStatus};
+use chrono::Utc;
+use rusqlite::{params, Connection};
+
+const SELECT_ISSUE: &str = "SELECT id, asset_id, interval, ts, open, high, low, close, volume, kind, detail, status, detected_at, resolved_at, provider FROM price_issues";
+
+/// Hold a candle out of price history for review. A candle quarantined again
+/// (e.g. re-fetched after a reset) replaces the earlier record as pending.
+/// A stored candle at the same time is removed: the last candle passes
+/// unjudged until the next one arrives and it is re-fetched with it.
+pub fn quarantine(
+    conn: &Connection,
+    candle: &OHLCVRow,
+    kind: PriceIssueKind,
+    detail: &str,
+) -> anyhow::Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "DELETE FROM historical_prices WHERE asset_id = ?1 AND interval = ?2 AND ts = ?3",
+        params![candle.asset_id, candle.interval.as_str(), candle.ts],
+    )?;
+    tx.execute(
+        "INSERT INTO price_issues (asset_id, interval, ts, open, high, low, close, volume, provider, kind, detail, status, detected_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, 'pending', ?12)
+         ON CONFLICT(asset_id, interval, ts) DO UPDATE SET
+            open = excluded.open,
+            high = excluded.high,
+            low = excluded.low,
+            close = excluded.close,
+            volume = excluded.volume,
+            provider = excluded.provider,
+            kind = excluded.kind,
+            detail = excluded.detail,
+            status = 'pending',
+            detected_at = excluded.detected_at,
+            resolved_at = NULL",
+        params![
+            candle.asset_id,
+            candle.interval.as_str(),
+            candle.ts,
+            candle.open,
+            candle.high,
+            candle.low,
+            candle.close,
+            candle.volume,
+            candle.provider,
+            kind.as_str(),
+            detail,
+            Utc::now().timestamp(),
+        ],
+    )?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Issues newest first, optionally narrowed to one asset and/or status.
+pub fn list_price_issues(
+    conn: &Connection,
+    asset_id: Option<&str>,
+    status: Option<PriceIssueStatus>,
+) -> anyhow::Result<Vec<PriceIssue>> {
+    let sql = format!(
+        "{} WHERE (?1 IS NULL OR asset_id = ?1) AND (?2 IS NULL OR status = ?2) ORDER BY detected_at DESC, ts DESC",
+        SELECT_ISSUE
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let rows = stmt.query_map(params![asset_id, status.map(|s| s.as_str())], row_to_issue)?;
+
+    let mut result = Vec::new();
+    for row in rows {
+        result.push(row?);
+    }
+    Ok(result)
+}
+
+pub fn get_price_issue(conn: &Connection, id: i64) -> anyhow::Result<Option<PriceIssue>> {
+    let mut stmt = conn.prepare(&format!("{} WHERE id = ?1", SELECT_ISSUE))?;
+    let mut rows = stmt.query_map(params![id], row_to_issue)?;
+    match rows.next() {
+        Some(row) => Ok(Some(row?)),
+        None => Ok(None),
+    }
+}
+
+/// The candle is genuine after all: store it in price history.
+pub fn accept_price_issue(conn: &Connection, id: i64) -> anyhow::Result<PriceIssue> {
+    let issue = get_price_issue(conn, id)?.ok_or_else(|| anyhow::anyhow!("Price issue not found"))?;
+    // Stored first: if the status update fails, accepting again is harmless
+    prices::upsert_prices(conn, std::slice::from_ref(&issue.candle))?;
+    set_status(conn, id, PriceIssueStatus::Accepted)?;
+    get_price_issue(conn, id)?.ok_or_else(|| anyhow::anyhow!("Price issue not found"))
+}
+
+/// The candle is bad data: keep it out of history. If it had been accepted
+/// earlier, the stored candle is removed again.
+pub fn reject_price_issue(conn: &Connection, id: i64) -> anyhow::Result<PriceIssue> {
+    let issue = get_price_issue(conn, id)?.ok_or_else(|| anyhow::anyhow!("Price issue not found"))?;
+    let tx = conn.unchecked_transaction()?;
+    if issue.status == PriceIssueStatus::Accepted {
+        tx.execute(
+            "DELETE FROM historical_prices WHERE asset_id = ?1 AND interval = ?2 AND ts = ?3",
+            params![issue.candle.asset_id, issue.candle.interval.as_str(), issue.candle.ts],
+        )?;
+    }
+    set_status(&tx, id, PriceIssueStatus::Rejected)?;
+    tx.commit()?;
+    get_price_issue(conn, id)?.ok_or_else(|| anyhow::anyhow!("Price issue not found"))
+}
+
+fn set_status(conn: &Connection, id: i64, status: PriceIssueStatus) -> anyhow::Result<()> {
+    conn.execute(
+        "UPDATE price_issues SET status = ?1, resolved_at = ?2 WHERE id = ?3",
+        params![status.as_str(), Utc::now().timestamp(), id],
+    )?;
+    Ok(())
+}
+
+fn row_to_issue(row: &rusqlite::Row) -> rusqlite::Result<PriceIssue> {
+    Ok(PriceIssue {
+        id: row.get(0)?,
+        candle: OHLCVRow {
+            id: None,
+            asset_id: row.get(1)?,
+            interval: Interval::from_str(&row.get::<_, String>(2)?).unwrap_or_default(),
+            ts: row.get(3)?,
+            open: row.get(4)?,
+            high: row.get(5)?,
+            low: row.get(6)?,
+            close: row.get(7)?,
+            volume: row.get(8)?,
+            provider: row.get(14)?,
+        },
+        kind: PriceIssueKind::from_str(&row.get::<_, String>(9)?).unwrap_or(PriceIssueKind::Spike),
+        detail: row.get(10)?,
+        status: PriceIssueStatus::from_str(&row.get::<_, String>(11)?).unwrap_or(PriceIssueStatus::Pending),
+        detected_at: row.get(12)?,
+        resolved_at: row.get(13)?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::test_db;
+    use crate::db::queries::assets;
+    use crate::models::AssetType;
+
+    fn setup(conn: &Connection) -> OHLCVRow {
+        let asset = assets::insert_asset(conn, "BTC", "Bitcoin", &AssetType::Crypto, "USD", None).unwrap();
+        OHLCVRow {
+            id: None,
+            asset_id: asset.id,
+            interval: Interval::OneDay,
+            ts: 1700006400,
+            open: Some(0.0),
+            high: Some(0.0),
+            low: Some(0.0),
+            close: 0.0,
+            volume: None,
+            provider: None,
+        }
+    }
+
+    #[test]
+    fn test_quarantine_and_list() {
+        let conn = test_db();
+        let candle = setup(&conn);
+        quarantine(&conn, &candle, PriceIssueKind::NonPositive, "close is 0").unwrap();
+        // Same candle again updates the record rather than duplicating it
+        quarantine(&conn, &candle, PriceIssueKind::NonPositive, "open is 0").unwrap();
+
+        let pending = list_price_issues(&conn, Some(&candle.asset_id), Some(PriceIssueStatus::Pending)).unwrap();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].detail, "open is 0");
+        assert!(list_price_issues(&conn, None, Some(PriceIssueStatus::Rejected)).unwrap().is_empty());
+        // Quarantined candles stay out of price history
+        assert!(prices::get_prices(&conn, &candle.asset_id, Interval::OneDay, None, None).unwrap().is_empty());
+
+        // A candle stored before it could be judged is taken out again
+        let stored = OHLCVRow { ts: candle.ts + 86400, ..candle.clone() };
+        prices::upsert_prices(&conn, std::slice::from_ref(&stored)).unwrap();
+        quarantine(&conn, &stored, PriceIssueKind::Spike, "spike").unwrap();
+        assert!(prices::get_prices(&conn, &candle.asset_id, Interval::OneDay, None, None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_accept_then_reject() {
+        let conn = test_db();
+        let candle = setup(&conn);
+        quarantine(&conn, &candle, PriceIssueKind::NonPositive, "close is 0").unwrap();
+        let id = list_price_issues(&conn, None, None).unwrap()[0].id;
+
+        let accepted = accept_price_issue(&conn, id).unwrap();
+        assert_eq!(accepted.status, PriceIssueStatus::Accepted);
+        assert!(accepted.resolved_at.is_some());
+        assert_eq!(prices::get_prices(&conn, &candle.asset_id, Interval::OneDay, None, None).unwrap().len(), 1);
+
+        let rejected = reject_price_issue(&conn, id).unwrap();
+        assert_eq!(rejected.status, PriceIssueStatus::Rejected);
+        assert!(prices::get_prices(&conn, &candle.asset_id, Interval::OneDay, None, None).unwrap().is_empty());
+
+        assert!(accept_price_issue(&conn, id + 1).is_err());
+    }
+}
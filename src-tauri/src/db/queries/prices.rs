@@ -1,28 +1,32 @@
-use crate::models::{OHLCVRow, PriceCacheMeta};
+use crate::domain::retention;
+use crate::models::{Interval, OHLCVRow, PriceCacheMeta};
 use rusqlite::{params, Connection};
 
 pub fn upsert_prices(conn: &Connection, rows: &[OHLCVRow]) -> anyhow::Result<()> {
     let tx = conn.unchecked_transaction()?;
     {
         let mut stmt = tx.prepare(
-            "INSERT INTO historical_prices (asset_id, ts, open, high, low, close, volume)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
-             ON CONFLICT(asset_id, ts) DO UPDATE SET
+            "INSERT INTO historical_prices (asset_id, interval, ts, open, high, low, close, volume, provider)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+             ON CONFLICT(asset_id, interval, ts) DO UPDATE SET
                 open = excluded.open,
                 high = excluded.high,
                 low = excluded.low,
                 close = excluded.close,
-                volume = excluded.volume",
+                volume = excluded.volume,
+                provider = excluded.provider",
         )?;
         for row in rows {
             stmt.execute(params![
                 row.asset_id,
+                row.interval.as_str(),
                 row.ts,
                 row.open,
                 row.high,
                 row.low,
                 row.close,
                 row.volume,
+                row.provider,
             ])?;
         }
     }
@@ -33,30 +37,32 @@ pub fn upsert_prices(conn: &Connection, rows: &[OHLCVRow]) -> anyhow::Result<()>
 pub fn get_prices(
     conn: &Connection,
     asset_id: &str,
+    interval: Interval,
     from_ts: Option<i64>,
     to_ts: Option<i64>,
 ) -> anyhow::Result<Vec<OHLCVRow>> {
     let sql = match (from_ts, to_ts) {
         (Some(_), Some(_)) => {
-            "SELECT id, asset_id, ts, open, high, low, close, volume FROM historical_prices WHERE asset_id = ?1 AND ts >= ?2 AND ts <= ?3 ORDER BY ts ASC"
+            "SELECT id, asset_id, interval, ts, open, high, low, close, volume, provider FROM historical_prices WHERE asset_id = ?1 AND interval = ?2 AND ts >= ?3 AND ts <= ?4 ORDER BY ts ASC"
         }
         (Some(_), None) => {
-            "SELECT id, asset_id, ts, open, high, low, close, volume FROM historical_prices WHERE asset_id = ?1 AND ts >= ?2 ORDER BY ts ASC"
+            "SELECT id, asset_id, interval, ts, open, high, low, close, volume, provider FROM historical_prices WHERE asset_id = ?1 AND interval = ?2 AND ts >= ?3 ORDER BY ts ASC"
         }
         (None, Some(_)) => {
-            "SELECT id, asset_id, ts, open, high, low, close, volume FROM historical_prices WHERE asset_id = ?1 AND ts <= ?2 ORDER BY ts ASC"
+            "SELECT id, asset_id, interval, ts, open, high, low, close, volume, provider FROM historical_prices WHERE asset_id = ?1 AND interval = ?2 AND ts <= ?3 ORDER BY ts ASC"
         }
         (None, None) => {
-            "SELECT id, asset_id, ts, open, high, low, close, volume FROM historical_prices WHERE asset_id = ?1 ORDER BY ts ASC"
+            "SELECT id, asset_id, interval, ts, open, high, low, close, volume, provider FROM historical_prices WHERE asset_id = ?1 AND interval = ?2 ORDER BY ts ASC"
         }
     };
 
+    let interval = interval.as_str();
     let mut stmt = conn.prepare(sql)?;
     let rows = match (from_ts, to_ts) {
-        (Some(f), Some(t)) => stmt.query_map(params![asset_id, f, t], row_to_ohlcv)?,
-        (Some(f), None) => stmt.query_map(params![asset_id, f], row_to_ohlcv)?,
-        (None, Some(t)) => stmt.query_map(params![asset_id, t], row_to_ohlcv)?,
-        (None, None) => stmt.query_map(params![asset_id], row_to_ohlcv)?,
+        (Some(f), Some(t)) => stmt.query_map(params![asset_id, interval, f, t], row_to_ohlcv)?,
+        (Some(f), None) => stmt.query_map(params![asset_id, interval, f], row_to_ohlcv)?,
+        (None, Some(t)) => stmt.query_map(params![asset_id, interval, t], row_to_ohlcv)?,
+        (None, None) => stmt.query_map(params![asset_id, interval], row_to_ohlcv)?,
     };
 
     let mut result = Vec::new();
@@ -66,9 +72,73 @@ pub fn get_prices(
     Ok(result)
 }
 
+/// Store candles fetched from a second provider for comparison. They never
+/// replace `historical_prices`; each provider keeps its own copy.
+pub fn upsert_reference_prices(conn: &Connection, rows: &[OHLCVRow], fetched_at: i64) -> anyhow::Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO reference_prices (asset_id, interval, ts, provider, open, high, low, close, volume, fetched_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+             ON CONFLICT(asset_id, interval, ts, provider) DO UPDATE SET
+                open = excluded.open,
+                high = excluded.high,
+                low = excluded.low,
+                close = excluded.close,
+                volume = excluded.volume,
+                fetched_at = excluded.fetched_at",
+        )?;
+        for row in rows {
+            let provider = row
+                .provider
+                .as_deref()
+                .ok_or_else(|| anyhow::anyhow!("Reference price without a provider"))?;
+            stmt.execute(params![
+                row.asset_id,
+                row.interval.as_str(),
+                row.ts,
+                provider,
+                row.open,
+                row.high,
+                row.low,
+                row.close,
+                row.volume,
+                fetched_at,
+            ])?;
+        }
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+pub fn get_reference_prices(
+    conn: &Connection,
+    asset_id: &str,
+    interval: Interval,
+    provider: &str,
+    from_ts: i64,
+    to_ts: i64,
+) -> anyhow::Result<Vec<OHLCVRow>> {
+    let mut stmt = conn.prepare(
+        "SELECT NULL, asset_id, interval, ts, open, high, low, close, volume, provider FROM reference_prices
+         WHERE asset_id = ?1 AND interval = ?2 AND provider = ?3 AND ts >= ?4 AND ts <= ?5 ORDER BY ts ASC",
+    )?;
+    let rows = stmt.query_map(
+        params![asset_id, interval.as_str(), provider, from_ts, to_ts],
+        row_to_ohlcv,
+    )?;
+
+    let mut result = Vec::new();
+    for row in rows {
+        result.push(row?);
+    }
+    Ok(result)
+}
+
+/// Most recent candle of any resolution — intraday rows are fresher than the daily close.
 pub fn get_latest_price(conn: &Connection, asset_id: &str) -> anyhow::Result<Option<OHLCVRow>> {
     let mut stmt = conn.prepare(
-        "SELECT id, asset_id, ts, open, high, low, close, volume FROM historical_prices WHERE asset_id = ?1 ORDER BY ts DESC LIMIT 1",
+        "SELECT id, asset_id, interval, ts, open, high, low, close, volume, provider FROM historical_prices WHERE asset_id = ?1 ORDER BY ts DESC LIMIT 1",
     )?;
     let mut rows = stmt.query_map(params![asset_id], row_to_ohlcv)?;
     match rows.next() {
@@ -77,24 +147,116 @@ pub fn get_latest_price(conn: &Connection, asset_id: &str) -> anyhow::Result<Opt
     }
 }
 
-pub fn get_max_ts(conn: &Connection, asset_id: &str) -> anyhow::Result<Option<i64>> {
-    let mut stmt =
-        conn.prepare("SELECT MAX(ts) FROM historical_prices WHERE asset_id = ?1")?;
-    let ts: Option<i64> = stmt.query_row(params![asset_id], |row| row.get(0))?;
+pub fn get_max_ts(conn: &Connection, asset_id: &str, interval: Interval) -> anyhow::Result<Option<i64>> {
+    let mut stmt = conn
+        .prepare("SELECT MAX(ts) FROM historical_prices WHERE asset_id = ?1 AND interval = ?2")?;
+    let ts: Option<i64> = stmt.query_row(params![asset_id, interval.as_str()], |row| row.get(0))?;
     Ok(ts)
 }
 
-pub fn get_cache_meta(conn: &Connection, asset_id: &str) -> anyhow::Result<Option<PriceCacheMeta>> {
+/// All stored candle timestamps for one resolution, ascending.
+pub fn get_timestamps(conn: &Connection, asset_id: &str, interval: Interval) -> anyhow::Result<Vec<i64>> {
+    let mut stmt = conn.prepare(
+        "SELECT ts FROM historical_prices WHERE asset_id = ?1 AND interval = ?2 ORDER BY ts ASC",
+    )?;
+    let rows = stmt.query_map(params![asset_id, interval.as_str()], |row| row.get(0))?;
+
+    let mut result = Vec::new();
+    for row in rows {
+        result.push(row?);
+    }
+    Ok(result)
+}
+
+/// Delete candles of the given resolution older than `before_ts`. Returns rows removed.
+pub fn prune_prices(
+    conn: &Connection,
+    asset_id: &str,
+    interval: Interval,
+    before_ts: i64,
+) -> anyhow::Result<usize> {
+    let deleted = conn.execute(
+        "DELETE FROM historical_prices WHERE asset_id = ?1 AND interval = ?2 AND ts < ?3",
+        params![asset_id, interval.as_str(), before_ts],
+    )?;
+    Ok(deleted)
+}
+
+/// Retire `interval` candles older than `before_ts` (aligned down to midnight
+/// UTC so no day is split): each day becomes a daily candle unless one is
+/// already stored, then the originals are deleted. Returns (daily candles
+/// added, candles removed).
+pub fn compact_prices(
+    conn: &Connection,
+    asset_id: &str,
+    interval: Interval,
+    before_ts: i64,
+) -> anyhow::Result<(usize, usize)> {
+    let cutoff = Interval::OneDay.align(before_ts);
+    let old = get_prices(conn, asset_id, interval, None, Some(cutoff - 1))?;
+    if old.is_empty() {
+        return Ok((0, 0));
+    }
+
+    let tx = conn.unchecked_transaction()?;
+    let mut added = 0;
+    {
+        let mut stmt = tx.prepare(
+            "INSERT INTO historical_prices (asset_id, interval, ts, open, high, low, close, volume, provider)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+             ON CONFLICT(asset_id, interval, ts) DO NOTHING",
+        )?;
+        for day in retention::downsample_daily(&old) {
+            added += stmt.execute(params![
+                day.asset_id,
+                day.interval.as_str(),
+                day.ts,
+                day.open,
+                day.high,
+                day.low,
+                day.close,
+                day.volume,
+                day.provider,
+            ])?;
+        }
+    }
+    let removed = prune_prices(&tx, asset_id, interval, cutoff)?;
+    tx.commit()?;
+    Ok((added, removed))
+}
+
+/// Fold a live quote into the daily candle containing `ts`: the close becomes the
+/// quote and high/low widen to include it. Creates the candle if it doesn't exist yet.
+/// The candle's provider becomes the quote's, since it now holds that provider's close.
+pub fn apply_quote(
+    conn: &Connection,
+    asset_id: &str,
+    price: f64,
+    ts: i64,
+    provider: &str,
+) -> anyhow::Result<()> {
+    conn.execute(
+        "INSERT INTO historical_prices (asset_id, interval, ts, open, high, low, close, provider)
+         VALUES (?1, ?2, ?3, ?4, ?4, ?4, ?4, ?5)
+         ON CONFLICT(asset_id, interval, ts) DO UPDATE SET
+            high = MAX(COALESCE(high, excluded.close), excluded.close),
+            low = MIN(COALESCE(low, excluded.close), excluded.close),
+            close = excluded.close,
+            provider = excluded.provider",
+        params![asset_id, Interval::OneDay.as_str(), Interval::OneDay.align(ts), price, provider],
+    )?;
+    Ok(())
+}
+
+pub fn get_cache_meta(
+    conn: &Connection,
+    asset_id: &str,
+    interval: Interval,
+) -> anyhow::Result<Option<PriceCacheMeta>> {
     let mut stmt = conn.prepare(
-        "SELECT asset_id, provider, last_fetched FROM price_cache_meta WHERE asset_id = ?1",
+        "SELECT asset_id, interval, provider, last_fetched FROM price_cache_meta WHERE asset_id = ?1 AND interval = ?2",
     )?;
-    let mut rows = stmt.query_map(params![asset_id], |row| {
-        Ok(PriceCacheMeta {
-            asset_id: row.get(0)?,
-            provider: row.get(1)?,
-            last_fetched: row.get(2)?,
-        })
-    })?;
+    let mut rows = stmt.query_map(params![asset_id, interval.as_str()], row_to_cache_meta)?;
     match rows.next() {
         Some(row) => Ok(Some(row?)),
         None => Ok(None),
@@ -103,14 +265,8 @@ pub fn get_cache_meta(conn: &Connection, asset_id: &str) -> anyhow::Result<Optio
 
 pub fn list_all_cache_meta(conn: &Connection) -> anyhow::Result<Vec<PriceCacheMeta>> {
     let mut stmt =
-        conn.prepare("SELECT asset_id, provider, last_fetched FROM price_cache_meta")?;
-    let rows = stmt.query_map([], |row| {
-        Ok(PriceCacheMeta {
-            asset_id: row.get(0)?,
-            provider: row.get(1)?,
-            last_fetched: row.get(2)?,
-        })
-    })?;
+        conn.prepare("SELECT asset_id, interval, provider, last_fetched FROM price_cache_meta")?;
+    let rows = stmt.query_map([], row_to_cache_meta)?;
     let mut result = Vec::new();
     for row in rows {
         result.push(row?);
@@ -121,27 +277,39 @@ pub fn list_all_cache_meta(conn: &Connection) -> anyhow::Result<Vec<PriceCacheMe
 pub fn update_cache_meta(
     conn: &Connection,
     asset_id: &str,
+    interval: Interval,
     provider: &str,
     last_fetched: i64,
 ) -> anyhow::Result<()> {
     conn.execute(
-        "INSERT INTO price_cache_meta (asset_id, provider, last_fetched) VALUES (?1, ?2, ?3)
-         ON CONFLICT(asset_id) DO UPDATE SET provider = excluded.provider, last_fetched = excluded.last_fetched",
-        params![asset_id, provider, last_fetched],
+        "INSERT INTO price_cache_meta (asset_id, interval, provider, last_fetched) VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(asset_id, interval) DO UPDATE SET provider = excluded.provider, last_fetched = excluded.last_fetched",
+        params![asset_id, interval.as_str(), provider, last_fetched],
     )?;
     Ok(())
 }
 
+fn row_to_cache_meta(row: &rusqlite::Row) -> rusqlite::Result<PriceCacheMeta> {
+    Ok(PriceCacheMeta {
+        asset_id: row.get(0)?,
+        interval: Interval::from_str(&row.get::<_, String>(1)?).unwrap_or_default(),
+        provider: row.get(2)?,
+        last_fetched: row.get(3)?,
+    })
+}
+
 fn row_to_ohlcv(row: &rusqlite::Row) -> rusqlite::Result<OHLCVRow> {
     Ok(OHLCVRow {
         id: row.get(0)?,
         asset_id: row.get(1)?,
-        ts: row.get(2)?,
-        open: row.get(3)?,
-        high: row.get(4)?,
-        low: row.get(5)?,
-        close: row.get(6)?,
-        volume: row.get(7)?,
+        interval: Interval::from_str(&row.get::<_, String>(2)?).unwrap_or_default(),
+        ts: row.get(3)?,
+        open: row.get(4)?,
+        high: row.get(5)?,
+        low: row.get(6)?,
+        close: row.get(7)?,
+        volume: row.get(8)?,
+        provider: row.get(9)?,
     })
 }
 
@@ -153,7 +321,7 @@ mod tests {
     use crate::models::AssetType;
 
     fn setup_asset(conn: &rusqlite::Connection) -> String {
-        let asset = assets::insert_asset(conn, "AAPL", "Apple", &AssetType::Stock, "USD").unwrap();
+        let asset = assets::insert_asset(conn, "AAPL", "Apple", &AssetType::Stock, "USD", None).unwrap();
         asset.id
     }
 
@@ -166,27 +334,31 @@ mod tests {
             OHLCVRow {
                 id: None,
                 asset_id: asset_id.clone(),
+                interval: Interval::OneDay,
                 ts: 1700000000,
                 open: Some(150.0),
                 high: Some(155.0),
                 low: Some(148.0),
                 close: 153.0,
                 volume: Some(1000000.0),
+                provider: None,
             },
             OHLCVRow {
                 id: None,
                 asset_id: asset_id.clone(),
+                interval: Interval::OneDay,
                 ts: 1700086400,
                 open: Some(153.0),
                 high: Some(157.0),
                 low: Some(152.0),
                 close: 156.0,
                 volume: None,
+                provider: None,
             },
         ];
 
         upsert_prices(&conn, &rows).unwrap();
-        let prices = get_prices(&conn, &asset_id, None, None).unwrap();
+        let prices = get_prices(&conn, &asset_id, Interval::OneDay, None, None).unwrap();
         assert_eq!(prices.len(), 2);
         assert_eq!(prices[0].ts, 1700000000);
         assert_eq!(prices[1].ts, 1700086400);
@@ -200,28 +372,32 @@ mod tests {
         let row = OHLCVRow {
             id: None,
             asset_id: asset_id.clone(),
+            interval: Interval::OneDay,
             ts: 1700000000,
             open: None,
             high: None,
             low: None,
             close: 150.0,
             volume: None,
+            provider: None,
         };
         upsert_prices(&conn, &[row]).unwrap();
 
         let row2 = OHLCVRow {
             id: None,
             asset_id: asset_id.clone(),
+            interval: Interval::OneDay,
             ts: 1700000000,
             open: None,
             high: None,
             low: None,
             close: 160.0,
             volume: None,
+            provider: None,
         };
         upsert_prices(&conn, &[row2]).unwrap();
 
-        let prices = get_prices(&conn, &asset_id, None, None).unwrap();
+        let prices = get_prices(&conn, &asset_id, Interval::OneDay, None, None).unwrap();
         assert_eq!(prices.len(), 1);
     }
 
@@ -231,8 +407,8 @@ mod tests {
         let asset_id = setup_asset(&conn);
 
         let rows = vec![
-            OHLCVRow { id: None, asset_id: asset_id.clone(), ts: 1700000000, open: None, high: None, low: None, close: 100.0, volume: None },
-            OHLCVRow { id: None, asset_id: asset_id.clone(), ts: 1700086400, open: None, high: None, low: None, close: 110.0, volume: None },
+            OHLCVRow { id: None, asset_id: asset_id.clone(), interval: Interval::OneDay, ts: 1700000000, open: None, high: None, low: None, close: 100.0, volume: None, provider: None },
+            OHLCVRow { id: None, asset_id: asset_id.clone(), interval: Interval::OneDay, ts: 1700086400, open: None, high: None, low: None, close: 110.0, volume: None, provider: None },
         ];
         upsert_prices(&conn, &rows).unwrap();
 
@@ -240,20 +416,70 @@ mod tests {
         assert_eq!(latest.ts, 1700086400);
     }
 
+    #[test]
+    fn test_apply_quote_updates_daily_candle() {
+        let conn = test_db();
+        let asset_id = setup_asset(&conn);
+        let day = 1700006400; // midnight UTC
+        let row = OHLCVRow { id: None, asset_id: asset_id.clone(), interval: Interval::OneDay, ts: day, open: Some(100.0), high: Some(105.0), low: Some(95.0), close: 101.0, volume: Some(10.0), provider: None };
+        upsert_prices(&conn, &[row]).unwrap();
+
+        apply_quote(&conn, &asset_id, 110.0, day + 3600, "binance").unwrap();
+        apply_quote(&conn, &asset_id, 90.0, day + 7200, "binance").unwrap();
+        let prices = get_prices(&conn, &asset_id, Interval::OneDay, None, None).unwrap();
+        assert_eq!(prices.len(), 1);
+        assert_eq!(prices[0].open, Some(100.0));
+        assert_eq!(prices[0].high, Some(110.0));
+        assert_eq!(prices[0].low, Some(90.0));
+        assert_eq!(prices[0].close, 90.0);
+        assert_eq!(prices[0].volume, Some(10.0));
+        assert_eq!(prices[0].provider.as_deref(), Some("binance"));
+
+        // No candle yet for the next day: the quote opens it
+        apply_quote(&conn, &asset_id, 92.0, day + 86400 + 60, "binance").unwrap();
+        let latest = get_latest_price(&conn, &asset_id).unwrap().unwrap();
+        assert_eq!(latest.ts, day + 86400);
+        assert_eq!(latest.open, Some(92.0));
+        assert_eq!(latest.close, 92.0);
+    }
+
+    #[test]
+    fn test_reference_prices_kept_per_provider() {
+        let conn = test_db();
+        let asset_id = setup_asset(&conn);
+        let row = |close, provider: &str| OHLCVRow { id: None, asset_id: asset_id.clone(), interval: Interval::OneDay, ts: 1700006400, open: None, high: None, low: None, close, volume: None, provider: Some(provider.to_string()) };
+
+        upsert_prices(&conn, &[row(100.0, "binance")]).unwrap();
+        upsert_reference_prices(&conn, &[row(101.0, "coingecko")], 1700100000).unwrap();
+        upsert_reference_prices(&conn, &[row(102.0, "coingecko")], 1700200000).unwrap();
+
+        // The primary series is untouched
+        let primary = get_prices(&conn, &asset_id, Interval::OneDay, None, None).unwrap();
+        assert_eq!((primary[0].close, primary[0].provider.as_deref()), (100.0, Some("binance")));
+        let reference = get_reference_prices(&conn, &asset_id, Interval::OneDay, "coingecko", 0, i64::MAX).unwrap();
+        assert_eq!(reference.len(), 1);
+        assert_eq!(reference[0].close, 102.0);
+        assert!(get_reference_prices(&conn, &asset_id, Interval::OneDay, "binance", 0, i64::MAX).unwrap().is_empty());
+
+        let mut unattributed = row(1.0, "x");
+        unattributed.provider = None;
+        assert!(upsert_reference_prices(&conn, &[unattributed], 0).is_err());
+    }
+
     #[test]
     fn test_cache_meta() {
         let conn = test_db();
         let asset_id = setup_asset(&conn);
 
-        assert!(get_cache_meta(&conn, &asset_id).unwrap().is_none());
+        assert!(get_cache_meta(&conn, &asset_id, Interval::OneDay).unwrap().is_none());
 
-        update_cache_meta(&conn, &asset_id, "twelve_data", 1700000000).unwrap();
-        let meta = get_cache_meta(&conn, &asset_id).unwrap().unwrap();
+        update_cache_meta(&conn, &asset_id, Interval::OneDay, "twelve_data", 1700000000).unwrap();
+        let meta = get_cache_meta(&conn, &asset_id, Interval::OneDay).unwrap().unwrap();
         assert_eq!(meta.provider, "twelve_data");
         assert_eq!(meta.last_fetched, 1700000000);
 
-        update_cache_meta(&conn, &asset_id, "coingecko", 1700001000).unwrap();
-        let meta = get_cache_meta(&conn, &asset_id).unwrap().unwrap();
+        update_cache_meta(&conn, &asset_id, Interval::OneDay, "coingecko", 1700001000).unwrap();
+        let meta = get_cache_meta(&conn, &asset_id, Interval::OneDay).unwrap().unwrap();
         assert_eq!(meta.provider, "coingecko");
     }
 
@@ -263,16 +489,16 @@ mod tests {
         let asset_id = setup_asset(&conn);
 
         // No prices yet
-        assert!(get_max_ts(&conn, &asset_id).unwrap().is_none());
+        assert!(get_max_ts(&conn, &asset_id, Interval::OneDay).unwrap().is_none());
 
         let rows = vec![
-            OHLCVRow { id: None, asset_id: asset_id.clone(), ts: 1700000000, open: None, high: None, low: None, close: 100.0, volume: None },
-            OHLCVRow { id: None, asset_id: asset_id.clone(), ts: 1700172800, open: None, high: None, low: None, close: 120.0, volume: None },
-            OHLCVRow { id: None, asset_id: asset_id.clone(), ts: 1700086400, open: None, high: None, low: None, close: 110.0, volume: None },
+            OHLCVRow { id: None, asset_id: asset_id.clone(), interval: Interval::OneDay, ts: 1700000000, open: None, high: None, low: None, close: 100.0, volume: None, provider: None },
+            OHLCVRow { id: None, asset_id: asset_id.clone(), interval: Interval::OneDay, ts: 1700172800, open: None, high: None, low: None, close: 120.0, volume: None, provider: None },
+            OHLCVRow { id: None, asset_id: asset_id.clone(), interval: Interval::OneDay, ts: 1700086400, open: None, high: None, low: None, close: 110.0, volume: None, provider: None },
         ];
         upsert_prices(&conn, &rows).unwrap();
 
-        let max = get_max_ts(&conn, &asset_id).unwrap().unwrap();
+        let max = get_max_ts(&conn, &asset_id, Interval::OneDay).unwrap().unwrap();
         assert_eq!(max, 1700172800);
     }
 
@@ -284,14 +510,95 @@ mod tests {
         let rows: Vec<OHLCVRow> = (0..5).map(|i| OHLCVRow {
             id: None,
             asset_id: asset_id.clone(),
+            interval: Interval::OneDay,
             ts: 1700000000 + i * 86400,
             open: None, high: None, low: None,
             close: 100.0,
             volume: None,
+            provider: None,
         }).collect();
         upsert_prices(&conn, &rows).unwrap();
 
-        let filtered = get_prices(&conn, &asset_id, Some(1700086400), Some(1700259200)).unwrap();
+        let filtered = get_prices(&conn, &asset_id, Interval::OneDay, Some(1700086400), Some(1700259200)).unwrap();
         assert_eq!(filtered.len(), 3);
     }
+
+    #[test]
+    fn test_intervals_stored_separately() {
+        let conn = test_db();
+        let asset_id = setup_asset(&conn);
+
+        let rows = vec![
+            OHLCVRow { id: None, asset_id: asset_id.clone(), interval: Interval::OneDay, ts: 1700006400, open: None, high: None, low: None, close: 100.0, volume: None, provider: None },
+            OHLCVRow { id: None, asset_id: asset_id.clone(), interval: Interval::OneHour, ts: 1700006400, open: None, high: None, low: None, close: 101.0, volume: None, provider: None },
+            OHLCVRow { id: None, asset_id: asset_id.clone(), interval: Interval::OneHour, ts: 1700010000, open: None, high: None, low: None, close: 102.0, volume: None, provider: None },
+        ];
+        upsert_prices(&conn, &rows).unwrap();
+
+        assert_eq!(get_prices(&conn, &asset_id, Interval::OneDay, None, None).unwrap().len(), 1);
+        let hourly = get_prices(&conn, &asset_id, Interval::OneHour, None, None).unwrap();
+        assert_eq!(hourly.len(), 2);
+        assert_eq!(hourly[0].interval, Interval::OneHour);
+        assert_eq!(get_max_ts(&conn, &asset_id, Interval::OneHour).unwrap(), Some(1700010000));
+
+        update_cache_meta(&conn, &asset_id, Interval::OneHour, "binance", 1700010000).unwrap();
+        assert!(get_cache_meta(&conn, &asset_id, Interval::OneDay).unwrap().is_none());
+        assert!(get_cache_meta(&conn, &asset_id, Interval::OneHour).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_compact_prices_keeps_existing_daily() {
+        let conn = test_db();
+        let asset_id = setup_asset(&conn);
+        let day = 1699920000; // 2023-11-14 00:00 UTC
+        let row = |interval, ts, close| OHLCVRow { id: None, asset_id: asset_id.clone(), interval, ts, open: None, high: None, low: None, close, volume: None, provider: None };
+        upsert_prices(
+            &conn,
+            &[
+                row(Interval::OneDay, day, 50.0),
+                row(Interval::OneHour, day + 3600, 100.0),
+                row(Interval::OneHour, day + 86400 + 3600, 101.0),
+                row(Interval::OneHour, day + 86400 + 7200, 102.0),
+                row(Interval::OneHour, day + 2 * 86400, 103.0),
+            ],
+        )
+        .unwrap();
+
+        // The cutoff falls mid-way through the third day, which stays whole
+        let (added, removed) = compact_prices(&conn, &asset_id, Interval::OneHour, day + 2 * 86400 + 600).unwrap();
+        assert_eq!((added, removed), (1, 3));
+
+        let daily = get_prices(&conn, &asset_id, Interval::OneDay, None, None).unwrap();
+        assert_eq!(daily.iter().map(|r| (r.ts, r.close)).collect::<Vec<_>>(), vec![(day, 50.0), (day + 86400, 102.0)]);
+        assert_eq!(daily[1].high, Some(102.0));
+        assert_eq!(get_timestamps(&conn, &asset_id, Interval::OneHour).unwrap(), vec![day + 2 * 86400]);
+    }
+
+    #[test]
+    fn test_get_timestamps_sorted_per_interval() {
+        let conn = test_db();
+        let asset_id = setup_asset(&conn);
+        let row = |interval, ts| OHLCVRow { id: None, asset_id: asset_id.clone(), interval, ts, open: None, high: None, low: None, close: 1.0, volume: None, provider: None };
+        upsert_prices(&conn, &[row(Interval::OneDay, 300), row(Interval::OneDay, 100), row(Interval::OneHour, 200)]).unwrap();
+        assert_eq!(get_timestamps(&conn, &asset_id, Interval::OneDay).unwrap(), vec![100, 300]);
+        assert_eq!(get_timestamps(&conn, &asset_id, Interval::OneHour).unwrap(), vec![200]);
+    }
+
+    #[test]
+    fn test_prune_prices_only_touches_interval() {
+        let conn = test_db();
+        let asset_id = setup_asset(&conn);
+
+        let rows = vec![
+            OHLCVRow { id: None, asset_id: asset_id.clone(), interval: Interval::OneDay, ts: 1700000000, open: None, high: None, low: None, close: 100.0, volume: None, provider: None },
+            OHLCVRow { id: None, asset_id: asset_id.clone(), interval: Interval::FiveMinutes, ts: 1700000000, open: None, high: None, low: None, close: 100.0, volume: None, provider: None },
+            OHLCVRow { id: None, asset_id: asset_id.clone(), interval: Interval::FiveMinutes, ts: 1700000300, open: None, high: None, low: None, close: 100.5, volume: None, provider: None },
+        ];
+        upsert_prices(&conn, &rows).unwrap();
+
+        let pruned = prune_prices(&conn, &asset_id, Interval::FiveMinutes, 1700000300).unwrap();
+        assert_eq!(pruned, 1);
+        assert_eq!(get_prices(&conn, &asset_id, Interval::FiveMinutes, None, None).unwrap().len(), 1);
+        assert_eq!(get_prices(&conn, &asset_id, Interval::OneDay, None, None).unwrap().len(), 1);
+    }
 }
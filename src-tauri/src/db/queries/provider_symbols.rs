@@ -0,0 +1,87 @@
+use crate::models::ProviderSymbol;
+use rusqlite::{params, Connection};
+
+pub fn set_provider_symbol(
+    conn: &Connection,
+    asset_id: &str,
+    provider: &str,
+    provider_symbol: &str,
+) -> anyhow::Result<()> {
+    conn.execute(
+        "INSERT INTO asset_provider_symbols (asset_id, provider, provider_symbol) VALUES (?1, ?2, ?3)
+         ON CONFLICT(asset_id, provider) DO UPDATE SET provider_symbol = excluded.provider_symbol",
+        params![asset_id, provider, provider_symbol],
+    )?;
+    Ok(())
+}
+
+/// Forget a mapping; the provider goes back to deriving the identifier from the ticker.
+pub fn delete_provider_symbol(conn: &Connection, asset_id: &str, provider: &str) -> anyhow::Result<()> {
+    conn.execute(
+        "DELETE FROM asset_provider_symbols WHERE asset_id = ?1 AND provider = ?2",
+        params![asset_id, provider],
+    )?;
+    Ok(())
+}
+
+pub fn get_provider_symbol(conn: &Connection, asset_id: &str, provider: &str) -> anyhow::Result<Option<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT provider_symbol FROM asset_provider_symbols WHERE asset_id = ?1 AND provider = ?2",
+    )?;
+    let mut rows = stmt.query_map(params![asset_id, provider], |row| row.get(0))?;
+    match rows.next() {
+        Some(row) => Ok(Some(row?)),
+        None => Ok(None),
+    }
+}
+
+/// Mappings for one asset, or for all assets when `asset_id` is `None`.
+pub fn list_provider_symbols(conn: &Connection, asset_id: Option<&str>) -> anyhow::Result<Vec<ProviderSymbol>> {
+    let mut stmt = conn.prepare(
+        "SELECT asset_id, provider, provider_symbol FROM asset_provider_symbols
+         WHERE ?1 IS NULL OR asset_id = ?1 ORDER BY asset_id, provider",
+    )?;
+    let rows = stmt.query_map(params![asset_id], |row| {
+        Ok(ProviderSymbol {
+            asset_id: row.get(0)?,
+            provider: row.get(1)?,
+            provider_symbol: row.get(2)?,
+        })
+    })?;
+
+    let mut result = Vec::new();
+    for row in rows {
+        result.push(row?);
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::queries::assets;
+    use crate::db::test_db;
+    use crate::models::AssetType;
+
+    #[test]
+    fn test_provider_symbol_mapping() {
+        let conn = test_db();
+        let uni = assets::insert_asset(&conn, "UNI", "Uniswap", &AssetType::Crypto, "USD", None).unwrap();
+        assert_eq!(get_provider_symbol(&conn, &uni.id, "coingecko").unwrap(), None);
+
+        set_provider_symbol(&conn, &uni.id, "coingecko", "unicorn-token").unwrap();
+        set_provider_symbol(&conn, &uni.id, "coingecko", "uniswap").unwrap();
+        set_provider_symbol(&conn, &uni.id, "binance", "UNIUSDT").unwrap();
+        assert_eq!(get_provider_symbol(&conn, &uni.id, "coingecko").unwrap().as_deref(), Some("uniswap"));
+        assert_eq!(list_provider_symbols(&conn, Some(&uni.id)).unwrap().len(), 2);
+
+        delete_provider_symbol(&conn, &uni.id, "binance").unwrap();
+        let all = list_provider_symbols(&conn, None).unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].provider, "coingecko");
+
+        // Mappings go with the asset
+        assets::hard_delete_asset(&conn, &uni.id).unwrap();
+        assert!(list_provider_symbols(&conn, None).unwrap().is_empty());
+    }
+}
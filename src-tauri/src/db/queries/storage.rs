@@ -0,0 +1,199 @@
+use crate::db::queries::prices;
+use crate::models::{AssetStorage, Interval, StorageReport};
+use rusqlite::{params, Connection};
+
+/// Per-asset price data that is useless once its asset is gone. Transactions
+/// are not in the list: they are the user's records, not derived data.
+const PRICE_TABLES: [&str; 7] = [
+    "historical_prices",
+    "reference_prices",
+    "price_cache_meta",
+    "price_issues",
+    "corporate_actions",
+    "corporate_actions_meta",
+    "asset_provider_symbols",
+];
+
+/// File size and unused space in bytes.
+pub fn database_size(conn: &Connection) -> anyhow::Result<(i64, i64)> {
+    // `PRAGMA page_size` reports SQLCipher's cipher page size as text; the
+    // table-valued form gives SQLite's own integer
+    Ok(conn.query_row(
+        "SELECT c.page_count * s.page_size, f.freelist_count * s.page_size
+         FROM pragma_page_count() c, pragma_freelist_count() f, pragma_page_size() s",
+        [],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?)
+}
+
+/// Rows and estimated bytes per asset. Bytes come from `dbstat` for the price
+/// tables and their indexes, split between assets by row count.
+pub fn storage_report(conn: &Connection) -> anyhow::Result<StorageReport> {
+    let (total_bytes, free_bytes) = database_size(conn)?;
+    let price_bytes = table_bytes(conn, "historical_prices")?;
+    let reference_bytes = table_bytes(conn, "reference_prices")?;
+    let price_total: i64 = conn.query_row("SELECT COUNT(*) FROM historical_prices", [], |row| row.get(0))?;
+    let reference_total: i64 = conn.query_row("SELECT COUNT(*) FROM reference_prices", [], |row| row.get(0))?;
+    let share = |bytes: i64, rows: i64, total: i64| if total == 0 { 0 } else { bytes * rows / total };
+
+    let mut stmt = conn.prepare(
+        "SELECT a.id, a.symbol, a.deleted_at IS NOT NULL,
+            (SELECT COUNT(*) FROM historical_prices h WHERE h.asset_id = a.id),
+            (SELECT COUNT(*) FROM historical_prices h WHERE h.asset_id = a.id AND h.interval NOT IN ('1d', '1w')),
+            (SELECT COUNT(*) FROM reference_prices r WHERE r.asset_id = a.id)
+         FROM assets a",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        let price_rows: i64 = row.get(3)?;
+        let reference_rows: i64 = row.get(5)?;
+        Ok(AssetStorage {
+            asset_id: row.get(0)?,
+            symbol: row.get(1)?,
+            deleted: row.get(2)?,
+            price_rows,
+            intraday_rows: row.get(4)?,
+            reference_rows,
+            bytes: share(price_bytes, price_rows, price_total)
+                + share(reference_bytes, reference_rows, reference_total),
+        })
+    })?;
+
+    let mut assets = Vec::new();
+    for row in rows {
+        assets.push(row?);
+    }
+    assets.sort_by_key(|a| std::cmp::Reverse(a.bytes));
+
+    let mut orphan_rows = 0;
+    for table in PRICE_TABLES {
+        orphan_rows += conn.query_row(
+            &format!("SELECT COUNT(*) FROM {} WHERE asset_id NOT IN (SELECT id FROM assets)", table),
+            [],
+            |row| row.get::<_, i64>(0),
+        )?;
+    }
+
+    Ok(StorageReport { total_bytes, free_bytes, assets, orphan_rows })
+}
+
+fn table_bytes(conn: &Connection, table: &str) -> anyhow::Result<i64> {
+    Ok(conn.query_row(
+        "SELECT COALESCE(SUM(pgsize), 0) FROM dbstat WHERE name IN (SELECT name FROM sqlite_master WHERE tbl_name = ?1)",
+        params![table],
+        |row| row.get(0),
+    )?)
+}
+
+/// Delete price data left behind by assets that no longer exist (rows from
+/// before foreign keys were enforced). Returns rows removed.
+pub fn delete_orphans(conn: &Connection) -> anyhow::Result<usize> {
+    let tx = conn.unchecked_transaction()?;
+    let mut deleted = 0;
+    for table in PRICE_TABLES {
+        deleted += tx.execute(
+            &format!("DELETE FROM {} WHERE asset_id NOT IN (SELECT id FROM assets)", table),
+            [],
+        )?;
+    }
+    tx.commit()?;
+    Ok(deleted)
+}
+
+/// Fold intraday candles past their retention window into daily candles,
+/// for every asset including soft-deleted ones. Returns (daily candles
+/// added, intraday candles removed).
+pub fn apply_retention(conn: &Connection, now: i64) -> anyhow::Result<(usize, usize)> {
+    let mut stmt = conn.prepare("SELECT DISTINCT asset_id, interval FROM historical_prices")?;
+    let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)))?;
+    let mut series = Vec::new();
+    for row in rows {
+        series.push(row?);
+    }
+
+    let (mut added, mut removed) = (0, 0);
+    for (asset_id, interval) in series {
+        let interval = Interval::from_str(&interval)?;
+        if let Some(retention) = interval.retention_secs() {
+            let (a, r) = prices::compact_prices(conn, &asset_id, interval, now - retention)?;
+            added += a;
+            removed += r;
+        }
+    }
+    Ok((added, removed))
+}
+
+/// Rebuild the database file to give free pages back to the filesystem.
+/// SQLCipher re-encrypts the rebuilt file with the connection's key. Must not
+/// run inside a transaction.
+pub fn vacuum(conn: &Connection) -> anyhow::Result<()> {
+    conn.execute_batch("PRAGMA optimize; VACUUM;")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::queries::assets;
+    use crate::db::test_db;
+    use crate::models::{AssetType, OHLCVRow};
+
+    fn candle(asset_id: &str, interval: Interval, ts: i64) -> OHLCVRow {
+        OHLCVRow {
+            id: None,
+            asset_id: asset_id.to_string(),
+            interval,
+            ts,
+            open: None,
+            high: None,
+            low: None,
+            close: 1.0,
+            volume: None,
+            provider: None,
+        }
+    }
+
+    #[test]
+    fn test_storage_report_and_orphans() {
+        let conn = test_db();
+        let btc = assets::insert_asset(&conn, "BTC", "Bitcoin", &AssetType::Crypto, "USD", None).unwrap();
+        let eth = assets::insert_asset(&conn, "ETH", "Ethereum", &AssetType::Crypto, "USD", None).unwrap();
+        let rows: Vec<OHLCVRow> = (0..50)
+            .map(|i| candle(&btc.id, Interval::OneDay, i * 86400))
+            .chain((0..5).map(|i| candle(&btc.id, Interval::OneHour, i * 3600)))
+            .chain((0..5).map(|i| candle(&eth.id, Interval::OneDay, i * 86400)))
+            .collect();
+        prices::upsert_prices(&conn, &rows).unwrap();
+        // Rows written while foreign keys were off outlive their asset
+        conn.execute_batch("PRAGMA foreign_keys = OFF;").unwrap();
+        prices::upsert_prices(&conn, &[candle("gone", Interval::OneDay, 0)]).unwrap();
+        conn.execute_batch("PRAGMA foreign_keys = ON;").unwrap();
+
+        let report = storage_report(&conn).unwrap();
+        assert!(report.total_bytes > 0);
+        assert_eq!(report.orphan_rows, 1);
+        assert_eq!(report.assets[0].symbol, "BTC");
+        assert_eq!((report.assets[0].price_rows, report.assets[0].intraday_rows), (55, 5));
+        assert!(report.assets[0].bytes > report.assets[1].bytes);
+
+        assert_eq!(delete_orphans(&conn).unwrap(), 1);
+        assert_eq!(storage_report(&conn).unwrap().orphan_rows, 0);
+    }
+
+    #[test]
+    fn test_apply_retention_and_vacuum() {
+        let conn = test_db();
+        let btc = assets::insert_asset(&conn, "BTC", "Bitcoin", &AssetType::Crypto, "USD", None).unwrap();
+        let day = 1699920000;
+        let rows: Vec<OHLCVRow> = (0..48).map(|i| candle(&btc.id, Interval::OneHour, day + i * 3600)).collect();
+        prices::upsert_prices(&conn, &rows).unwrap();
+
+        // Hourly candles are kept for 180 days
+        let now = day + 182 * 86400;
+        assert_eq!(apply_retention(&conn, now).unwrap(), (2, 48));
+        assert_eq!(apply_retention(&conn, now).unwrap(), (0, 0));
+        assert_eq!(prices::get_timestamps(&conn, &btc.id, Interval::OneDay).unwrap(), vec![day, day + 86400]);
+
+        vacuum(&conn).unwrap();
+        assert_eq!(database_size(&conn).unwrap().1, 0);
+    }
+}
@@ -1,8 +1,130 @@
-use crate::models::{AssetHoldingSummary, Transaction, TxType};
-use chrono::Utc;
-use rusqlite::{params, Connection};
+use crate::db::queries::audit;
+use crate::domain::validation::ValidationError;
+use crate::models::{
+    AssetHoldingSummary, AuditAction, AuditEntry, FieldError, NewTransaction, PeriodClose, Transaction,
+    TransactionEdit, TransactionFilter, TransactionSelection, TxType,
+};
+use chrono::{DateTime, Utc};
+use rusqlite::{params, Connection, Row};
 use uuid::Uuid;
 
+const SELECT_TRANSACTION: &str =
+    "SELECT id, asset_id, tx_type, quantity, price_usd, ts, notes, created_at, deleted_at, locked_at, fee, account_id FROM transactions";
+
+fn row_to_transaction(row: &Row) -> rusqlite::Result<Transaction> {
+    Ok(Transaction {
+        id: row.get(0)?,
+        asset_id: row.get(1)?,
+        tx_type: TxType::from_str(&row.get::<_, String>(2)?).unwrap_or(TxType::Buy),
+        quantity: row.get(3)?,
+        price_usd: row.get(4)?,
+        ts: row.get(5)?,
+        notes: row.get(6)?,
+        created_at: row.get(7)?,
+        deleted_at: row.get(8)?,
+        locked_at: row.get(9)?,
+        fee: row.get(10)?,
+        account_id: row.get(11)?,
+    })
+}
+
+/// A transaction by id, including soft-deleted ones.
+pub fn get_transaction(conn: &Connection, id: &str) -> anyhow::Result<Option<Transaction>> {
+    let mut stmt = conn.prepare(&format!("{} WHERE id = ?1", SELECT_TRANSACTION))?;
+    let mut rows = stmt.query_map(params![id], row_to_transaction)?;
+    match rows.next() {
+        Some(row) => Ok(Some(row?)),
+        None => Ok(None),
+    }
+}
+
+fn select_transactions(
+    conn: &Connection,
+    filter: &str,
+    params: impl rusqlite::Params,
+) -> anyhow::Result<Vec<Transaction>> {
+    let mut stmt = conn.prepare(&format!("{} WHERE {}", SELECT_TRANSACTION, filter))?;
+    let rows = stmt.query_map(params, row_to_transaction)?;
+    let mut transactions = Vec::new();
+    for row in rows {
+        transactions.push(row?);
+    }
+    Ok(transactions)
+}
+
+/// The date the books are closed before for an asset: the latest of its own
+/// and the global closes that haven't been reopened.
+pub fn closed_before(conn: &Connection, asset_id: &str) -> anyhow::Result<Option<i64>> {
+    Ok(conn.query_row(
+        "SELECT MAX(closed_before) FROM period_closes
+         WHERE reopened_at IS NULL AND (asset_id IS NULL OR asset_id = ?1)",
+        params![asset_id],
+        |row| row.get(0),
+    )?)
+}
+
+/// Refuse a change that puts `after` into a closed period. A live transaction
+/// that already sat there, on the same asset and date, may stay; its lock
+/// guards it instead.
+fn ensure_period_open(conn: &Connection, before: Option<&Transaction>, after: &Transaction) -> anyhow::Result<()> {
+    if after.deleted_at.is_some() {
+        return Ok(());
+    }
+    let Some(closed_before) = closed_before(conn, &after.asset_id)? else {
+        return Ok(());
+    };
+    let already_there =
+        before.is_some_and(|b| b.deleted_at.is_none() && b.asset_id == after.asset_id && b.ts == after.ts);
+    if after.ts >= closed_before || already_there {
+        return Ok(());
+    }
+    let date = DateTime::from_timestamp(closed_before, 0)
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .unwrap_or_default();
+    Err(ValidationError {
+        code: "period_closed",
+        fields: vec![FieldError::new(
+            "ts",
+            format!("The books are closed before {}; reopen the period to add or move transactions into it", date),
+        )],
+    }
+    .into())
+}
+
+/// Run one change to a transaction and log it with the row as it was before
+/// and after, both in the same database transaction. A restore works on a
+/// deleted transaction, everything else on a live one. Edits and deletes of
+/// locked transactions are refused, and so is anything that brings a live
+/// row into a closed period; a change that leaves the row as it was is not
+/// logged.
+fn audited_change(
+    conn: &Connection,
+    id: &str,
+    action: AuditAction,
+    reason: Option<&str>,
+    change: impl FnOnce(&Connection) -> rusqlite::Result<usize>,
+) -> anyhow::Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    let restoring = action == AuditAction::Restore;
+    let Some(before) = get_transaction(&tx, id)?.filter(|t| t.deleted_at.is_some() == restoring) else {
+        anyhow::bail!("Transaction not found");
+    };
+    if before.locked_at.is_some() && matches!(action, AuditAction::Update | AuditAction::Delete) {
+        anyhow::bail!("Transaction is locked; unlock it before changing it");
+    }
+    change(&tx)?;
+    let after = get_transaction(&tx, id)?;
+    if let Some(after) = &after {
+        ensure_period_open(&tx, Some(&before), after)?;
+    }
+    if after.as_ref() != Some(&before) {
+        audit::record(&tx, action, Some(&before), after.as_ref(), None, reason)?;
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn insert_transaction(
     conn: &Connection,
     asset_id: &str,
@@ -11,67 +133,85 @@ pub fn insert_transaction(
     price_usd: f64,
     ts: i64,
     notes: Option<&str>,
+    fee: f64,
+    account_id: Option<&str>,
 ) -> anyhow::Result<Transaction> {
-    let id = Uuid::new_v4().to_string();
-    let now = Utc::now().timestamp();
-    conn.execute(
-        "INSERT INTO transactions (id, asset_id, tx_type, quantity, price_usd, ts, notes, created_at) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
-        params![id, asset_id, tx_type.as_str(), quantity, price_usd, ts, notes, now],
+    let tx = conn.unchecked_transaction()?;
+    let inserted = insert_audited(
+        &tx,
+        NewTransaction {
+            asset_id: asset_id.to_string(),
+            tx_type: tx_type.clone(),
+            quantity,
+            price_usd,
+            ts,
+            notes: notes.map(|s| s.to_string()),
+            fee,
+            account_id: account_id.map(|s| s.to_string()),
+        },
+        Utc::now().timestamp(),
     )?;
-    Ok(Transaction {
-        id,
-        asset_id: asset_id.to_string(),
-        tx_type: tx_type.clone(),
-        quantity,
-        price_usd,
-        ts,
-        notes: notes.map(|s| s.to_string()),
-        created_at: now,
+    tx.commit()?;
+    Ok(inserted)
+}
+
+fn insert_audited(conn: &Connection, new: NewTransaction, created_at: i64) -> anyhow::Result<Transaction> {
+    let inserted = Transaction {
+        id: Uuid::new_v4().to_string(),
+        asset_id: new.asset_id,
+        tx_type: new.tx_type,
+        quantity: new.quantity,
+        price_usd: new.price_usd,
+        ts: new.ts,
+        notes: new.notes,
+        created_at,
         deleted_at: None,
         locked_at: None,
-    })
+        fee: new.fee,
+        account_id: new.account_id,
+    };
+    let t = &inserted;
+    ensure_period_open(conn, None, t)?;
+    conn.execute(
+        "INSERT INTO transactions (id, asset_id, tx_type, quantity, price_usd, ts, notes, created_at, fee, account_id) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+        params![t.id, t.asset_id, t.tx_type.as_str(), t.quantity, t.price_usd, t.ts, t.notes, t.created_at, t.fee, t.account_id],
+    )?;
+    audit::record(conn, AuditAction::Insert, None, Some(t), None, None)?;
+    Ok(inserted)
+}
+
+/// Insert all of `rows` or, if any fails, none of them.
+pub fn insert_transactions(conn: &Connection, rows: &[NewTransaction]) -> anyhow::Result<Vec<Transaction>> {
+    let now = Utc::now().timestamp();
+    let tx = conn.unchecked_transaction()?;
+    let mut inserted = Vec::new();
+    for row in rows {
+        inserted.push(insert_audited(&tx, row.clone(), now)?);
+    }
+    tx.commit()?;
+    Ok(inserted)
 }
 
 pub fn list_transactions_by_asset(
     conn: &Connection,
     asset_id: &str,
 ) -> anyhow::Result<Vec<Transaction>> {
-    let mut stmt = conn.prepare(
-        "SELECT id, asset_id, tx_type, quantity, price_usd, ts, notes, created_at, deleted_at, locked_at FROM transactions WHERE asset_id = ?1 AND deleted_at IS NULL ORDER BY ts DESC",
-    )?;
-    let rows = stmt.query_map(params![asset_id], |row| {
-        Ok(Transaction {
-            id: row.get(0)?,
-            asset_id: row.get(1)?,
-            tx_type: TxType::from_str(&row.get::<_, String>(2)?).unwrap_or(TxType::Buy),
-            quantity: row.get(3)?,
-            price_usd: row.get(4)?,
-            ts: row.get(5)?,
-            notes: row.get(6)?,
-            created_at: row.get(7)?,
-            deleted_at: row.get(8)?,
-            locked_at: row.get(9)?,
-        })
-    })?;
-    let mut transactions = Vec::new();
-    for row in rows {
-        transactions.push(row?);
-    }
-    Ok(transactions)
+    select_transactions(conn, "asset_id = ?1 AND deleted_at IS NULL ORDER BY ts DESC", params![asset_id])
 }
 
 pub fn soft_delete_transaction(conn: &Connection, id: &str) -> anyhow::Result<()> {
     let now = Utc::now().timestamp();
-    let updated = conn.execute(
-        "UPDATE transactions SET deleted_at = ?1 WHERE id = ?2 AND deleted_at IS NULL",
-        params![now, id],
-    )?;
-    if updated == 0 {
-        anyhow::bail!("Transaction not found");
-    }
-    Ok(())
+    audited_change(conn, id, AuditAction::Delete, None, |conn| {
+        // Deleted on its own now, so it stays behind if its asset is restored
+        conn.execute("DELETE FROM asset_trashed_transactions WHERE transaction_id = ?1", params![id])?;
+        conn.execute(
+            "UPDATE transactions SET deleted_at = ?1 WHERE id = ?2 AND deleted_at IS NULL",
+            params![now, id],
+        )
+    })
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn update_transaction(
     conn: &Connection,
     id: &str,
@@ -80,49 +220,443 @@ pub fn update_transaction(
     price_usd: f64,
     ts: i64,
     notes: Option<&str>,
+    fee: f64,
+    account_id: Option<&str>,
 ) -> anyhow::Result<()> {
-    let updated = conn.execute(
-        "UPDATE transactions SET tx_type = ?1, quantity = ?2, price_usd = ?3, ts = ?4, notes = ?5 WHERE id = ?6 AND deleted_at IS NULL",
-        params![tx_type.as_str(), quantity, price_usd, ts, notes, id],
-    )?;
-    if updated == 0 {
-        anyhow::bail!("Transaction not found");
+    audited_change(conn, id, AuditAction::Update, None, |conn| {
+        conn.execute(
+            "UPDATE transactions SET tx_type = ?1, quantity = ?2, price_usd = ?3, ts = ?4, notes = ?5, fee = ?6, account_id = ?7 WHERE id = ?8 AND deleted_at IS NULL",
+            params![tx_type.as_str(), quantity, price_usd, ts, notes, fee, account_id, id],
+        )
+    })
+}
+
+fn ensure_none_locked(transactions: &[Transaction]) -> anyhow::Result<()> {
+    let locked = transactions.iter().filter(|t| t.deleted_at.is_none() && t.locked_at.is_some()).count();
+    if locked > 0 {
+        anyhow::bail!("{} of the transactions are locked; unlock them first", locked);
     }
     Ok(())
 }
 
-pub fn soft_delete_transactions_by_asset(conn: &Connection, asset_id: &str) -> anyhow::Result<u64> {
-    let now = Utc::now().timestamp();
-    let count = conn.execute(
+/// Soft-delete every live transaction of an asset and mark them as trashed
+/// with it, so restoring the asset brings back exactly these. Runs inside the
+/// caller's database transaction.
+pub fn soft_delete_transactions_by_asset(conn: &Connection, asset_id: &str, deleted_at: i64) -> anyhow::Result<u64> {
+    let live = list_transactions_by_asset(conn, asset_id)?;
+    ensure_none_locked(&live)?;
+    conn.execute("DELETE FROM asset_trashed_transactions WHERE asset_id = ?1", params![asset_id])?;
+    conn.execute(
+        "INSERT INTO asset_trashed_transactions (transaction_id, asset_id)
+         SELECT id, asset_id FROM transactions WHERE asset_id = ?1 AND deleted_at IS NULL",
+        params![asset_id],
+    )?;
+    conn.execute(
         "UPDATE transactions SET deleted_at = ?1 WHERE asset_id = ?2 AND deleted_at IS NULL",
-        params![now, asset_id],
+        params![deleted_at, asset_id],
     )?;
-    Ok(count as u64)
+    for before in &live {
+        let after = Transaction { deleted_at: Some(deleted_at), ..before.clone() };
+        audit::record(conn, AuditAction::Delete, Some(before), Some(&after), None, None)?;
+    }
+    Ok(live.len() as u64)
 }
 
-pub fn lock_transaction(conn: &Connection, id: &str) -> anyhow::Result<()> {
-    let now = Utc::now().timestamp();
-    let updated = conn.execute(
-        "UPDATE transactions SET locked_at = ?1 WHERE id = ?2 AND deleted_at IS NULL",
-        params![now, id],
+/// Unlink every transaction, deleted ones included, from an account that is
+/// about to be removed, logging each change. Refused while any of them is
+/// locked. Runs inside the caller's database transaction.
+pub fn detach_account(conn: &Connection, account_id: &str) -> anyhow::Result<u64> {
+    let linked = select_transactions(conn, "account_id = ?1", params![account_id])?;
+    ensure_none_locked(&linked)?;
+    conn.execute("UPDATE transactions SET account_id = NULL WHERE account_id = ?1", params![account_id])?;
+    for before in &linked {
+        let after = Transaction { account_id: None, ..before.clone() };
+        audit::record(conn, AuditAction::Update, Some(before), Some(&after), None, None)?;
+    }
+    Ok(linked.len() as u64)
+}
+
+const TRASHED_WITH_ASSET: &str = "asset_id = ?1 AND deleted_at IS NOT NULL
+     AND id IN (SELECT transaction_id FROM asset_trashed_transactions WHERE asset_id = ?1)";
+
+/// The deleted transactions that restoring the asset would bring back.
+pub fn list_trashed_with_asset(conn: &Connection, asset_id: &str) -> anyhow::Result<Vec<Transaction>> {
+    select_transactions(conn, TRASHED_WITH_ASSET, params![asset_id])
+}
+
+/// Bring back the transactions the asset's removal deleted. Ones deleted on
+/// their own stay in the trash. Runs inside the caller's database transaction.
+pub fn restore_transactions_by_asset(conn: &Connection, asset_id: &str) -> anyhow::Result<u64> {
+    let trashed = list_trashed_with_asset(conn, asset_id)?;
+    conn.execute(
+        &format!("UPDATE transactions SET deleted_at = NULL WHERE {}", TRASHED_WITH_ASSET),
+        params![asset_id],
     )?;
-    if updated == 0 {
-        anyhow::bail!("Transaction not found");
+    conn.execute("DELETE FROM asset_trashed_transactions WHERE asset_id = ?1", params![asset_id])?;
+    for before in &trashed {
+        let after = Transaction { deleted_at: None, ..before.clone() };
+        ensure_period_open(conn, Some(before), &after)?;
+        audit::record(conn, AuditAction::Restore, Some(before), Some(&after), None, None)?;
     }
-    Ok(())
+    Ok(trashed.len() as u64)
 }
 
-pub fn unlock_transaction(conn: &Connection, id: &str) -> anyhow::Result<()> {
-    let updated = conn.execute(
-        "UPDATE transactions SET locked_at = NULL WHERE id = ?1 AND deleted_at IS NULL",
+/// Soft-deleted transactions, most recently deleted first.
+pub fn list_deleted_transactions(conn: &Connection) -> anyhow::Result<Vec<Transaction>> {
+    select_transactions(conn, "deleted_at IS NOT NULL ORDER BY deleted_at DESC, ts DESC", [])
+}
+
+/// Take a transaction out of the trash. Its asset has to be active.
+pub fn restore_transaction(conn: &Connection, id: &str) -> anyhow::Result<()> {
+    let asset_active: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM transactions t JOIN assets a ON a.id = t.asset_id
+         WHERE t.id = ?1 AND a.deleted_at IS NULL)",
         params![id],
+        |row| row.get(0),
     )?;
-    if updated == 0 {
-        anyhow::bail!("Transaction not found");
+    if !asset_active {
+        anyhow::bail!("The asset of this transaction is deleted; restore the asset first");
     }
+    audited_change(conn, id, AuditAction::Restore, None, |conn| {
+        conn.execute(
+            "UPDATE transactions SET deleted_at = NULL WHERE id = ?1 AND deleted_at IS NOT NULL",
+            params![id],
+        )
+    })
+}
+
+/// Delete soft-deleted transactions for good, logging each one. With
+/// `deleted_before`, only those trashed before then; otherwise all of them.
+pub fn purge_deleted_transactions(conn: &Connection, deleted_before: Option<i64>) -> anyhow::Result<u64> {
+    let filter = "deleted_at IS NOT NULL AND (?1 IS NULL OR deleted_at < ?1)";
+    let tx = conn.unchecked_transaction()?;
+    let trashed = select_transactions(&tx, filter, params![deleted_before])?;
+    tx.execute(&format!("DELETE FROM transactions WHERE {}", filter), params![deleted_before])?;
+    for before in &trashed {
+        audit::record(&tx, AuditAction::Purge, Some(before), None, None, None)?;
+    }
+    tx.commit()?;
+    Ok(trashed.len() as u64)
+}
+
+/// Delete one soft-deleted transaction for good.
+pub fn purge_transaction(conn: &Connection, id: &str) -> anyhow::Result<()> {
+    let tx = conn.unchecked_transaction()?;
+    let Some(before) = get_transaction(&tx, id)?.filter(|t| t.deleted_at.is_some()) else {
+        anyhow::bail!("Only deleted transactions can be purged");
+    };
+    tx.execute("DELETE FROM transactions WHERE id = ?1", params![id])?;
+    audit::record(&tx, AuditAction::Purge, Some(&before), None, None, None)?;
+    tx.commit()?;
     Ok(())
 }
 
+/// Delete every transaction of an asset for good, logging each one so the
+/// history outlives the rows. Runs inside the caller's database transaction.
+pub fn purge_transactions_by_asset(conn: &Connection, asset_id: &str) -> anyhow::Result<u64> {
+    let rows = select_transactions(conn, "asset_id = ?1", params![asset_id])?;
+    ensure_none_locked(&rows)?;
+    conn.execute("DELETE FROM transactions WHERE asset_id = ?1", params![asset_id])?;
+    for before in &rows {
+        audit::record(conn, AuditAction::Purge, Some(before), None, None, None)?;
+    }
+    Ok(rows.len() as u64)
+}
+
+/// Lock a transaction against edits and deletes. Locking it again keeps the
+/// original lock time.
+pub fn lock_transaction(conn: &Connection, id: &str) -> anyhow::Result<()> {
+    let now = Utc::now().timestamp();
+    audited_change(conn, id, AuditAction::Lock, None, |conn| {
+        conn.execute(
+            "UPDATE transactions SET locked_at = COALESCE(locked_at, ?1) WHERE id = ?2 AND deleted_at IS NULL",
+            params![now, id],
+        )
+    })
+}
+
+/// Close the books before `before_ts`, of one asset or of all: lock every
+/// live transaction dated earlier and store the close, so that none can be
+/// added or moved there until the period is reopened. Returns how many were
+/// newly locked.
+pub fn lock_transactions_before(conn: &Connection, before_ts: i64, asset_id: Option<&str>) -> anyhow::Result<usize> {
+    let now = Utc::now().timestamp();
+    let filter = "deleted_at IS NULL AND locked_at IS NULL AND ts < ?1 AND (?2 IS NULL OR asset_id = ?2)";
+    let tx = conn.unchecked_transaction()?;
+    tx.execute(
+        "INSERT INTO period_closes (asset_id, closed_before, closed_at) VALUES (?1, ?2, ?3)",
+        params![asset_id, before_ts, now],
+    )?;
+    let open = select_transactions(&tx, filter, params![before_ts, asset_id])?;
+    tx.execute(
+        &format!("UPDATE transactions SET locked_at = ?3 WHERE {}", filter),
+        params![before_ts, asset_id, now],
+    )?;
+    for before in &open {
+        let after = Transaction { locked_at: Some(now), ..before.clone() };
+        audit::record(&tx, AuditAction::Lock, Some(before), Some(&after), None, None)?;
+    }
+    tx.commit()?;
+    Ok(open.len())
+}
+
+/// Every period close, reopened ones included, most recent first.
+pub fn list_period_closes(conn: &Connection) -> anyhow::Result<Vec<PeriodClose>> {
+    let mut stmt = conn.prepare(
+        "SELECT id, asset_id, closed_before, closed_at, reopened_at, reopen_reason FROM period_closes
+         ORDER BY closed_at DESC, id DESC",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(PeriodClose {
+            id: row.get(0)?,
+            asset_id: row.get(1)?,
+            closed_before: row.get(2)?,
+            closed_at: row.get(3)?,
+            reopened_at: row.get(4)?,
+            reopen_reason: row.get(5)?,
+        })
+    })?;
+    let mut closes = Vec::new();
+    for row in rows {
+        closes.push(row?);
+    }
+    Ok(closes)
+}
+
+/// Reopen the closed periods of one asset or, with `None`, the global ones,
+/// and unlock the transactions they covered that no other close still
+/// covers. The reason is stored on the closes and on each unlock in the
+/// audit log. Returns how many transactions were unlocked.
+pub fn reopen_period(conn: &Connection, asset_id: Option<&str>, reason: &str) -> anyhow::Result<usize> {
+    let reason = reason.trim();
+    if reason.is_empty() {
+        anyhow::bail!("A reason is required to reopen a period");
+    }
+    let now = Utc::now().timestamp();
+    let tx = conn.unchecked_transaction()?;
+    let reopened_before: Option<i64> = tx.query_row(
+        "SELECT MAX(closed_before) FROM period_closes WHERE reopened_at IS NULL AND asset_id IS ?1",
+        params![asset_id],
+        |row| row.get(0),
+    )?;
+    let Some(reopened_before) = reopened_before else {
+        anyhow::bail!("No closed period to reopen");
+    };
+    tx.execute(
+        "UPDATE period_closes SET reopened_at = ?1, reopen_reason = ?2 WHERE reopened_at IS NULL AND asset_id IS ?3",
+        params![now, reason, asset_id],
+    )?;
+    let locked = select_transactions(
+        &tx,
+        "deleted_at IS NULL AND locked_at IS NOT NULL AND ts < ?1 AND (?2 IS NULL OR asset_id = ?2)",
+        params![reopened_before, asset_id],
+    )?;
+    let mut unlocked = 0;
+    for before in &locked {
+        if closed_before(&tx, &before.asset_id)?.is_some_and(|c| before.ts < c) {
+            continue;
+        }
+        let after = Transaction { locked_at: None, ..before.clone() };
+        tx.execute("UPDATE transactions SET locked_at = NULL WHERE id = ?1", params![before.id])?;
+        audit::record(&tx, AuditAction::Unlock, Some(before), Some(&after), None, Some(reason))?;
+        unlocked += 1;
+    }
+    tx.commit()?;
+    Ok(unlocked)
+}
+
+/// The live transactions a bulk operation applies to. Every listed id has to
+/// be one, and a filter needs at least one condition so that a bulk change
+/// can't hit every transaction by accident.
+pub fn select_for_bulk(conn: &Connection, selection: &TransactionSelection) -> anyhow::Result<Vec<Transaction>> {
+    match selection {
+        TransactionSelection::Ids(ids) => {
+            let mut rows: Vec<Transaction> = Vec::new();
+            for id in ids {
+                if rows.iter().any(|t| &t.id == id) {
+                    continue;
+                }
+                let Some(row) = get_transaction(conn, id)?.filter(|t| t.deleted_at.is_none()) else {
+                    anyhow::bail!("Transaction not found: {}", id);
+                };
+                rows.push(row);
+            }
+            Ok(rows)
+        }
+        TransactionSelection::Filter(filter) => {
+            if *filter == TransactionFilter::default() {
+                anyhow::bail!("A filter needs at least one condition");
+            }
+            select_transactions(
+                conn,
+                "deleted_at IS NULL AND (?1 IS NULL OR asset_id = ?1) AND (?2 IS NULL OR tx_type = ?2)
+                 AND (?3 IS NULL OR ts >= ?3) AND (?4 IS NULL OR ts <= ?4)
+                 AND (?5 IS NULL OR created_at >= ?5) AND (?6 IS NULL OR created_at <= ?6) ORDER BY ts DESC",
+                params![
+                    filter.asset_id,
+                    filter.tx_type.as_ref().map(|t| t.as_str()),
+                    filter.from_ts,
+                    filter.to_ts,
+                    filter.created_from,
+                    filter.created_to
+                ],
+            )
+        }
+    }
+}
+
+/// Write `change` of each selected transaction and log it, all in one
+/// database transaction. Only the asset, date, notes, delete and lock columns
+/// are written, and no row may end up in a closed period. Returns how many
+/// rows actually changed.
+fn bulk_change(
+    conn: &Connection,
+    selection: &TransactionSelection,
+    action: AuditAction,
+    change: impl Fn(&Transaction) -> anyhow::Result<Transaction>,
+) -> anyhow::Result<u64> {
+    let tx = conn.unchecked_transaction()?;
+    let rows = select_for_bulk(&tx, selection)?;
+    if matches!(action, AuditAction::Update | AuditAction::Delete) {
+        ensure_none_locked(&rows)?;
+    }
+    let mut changed = 0;
+    for before in &rows {
+        let after = change(before)?;
+        if after == *before {
+            continue;
+        }
+        ensure_period_open(&tx, Some(before), &after)?;
+        tx.execute(
+            "UPDATE transactions SET asset_id = ?1, ts = ?2, notes = ?3, deleted_at = ?4, locked_at = ?5 WHERE id = ?6",
+            params![after.asset_id, after.ts, after.notes, after.deleted_at, after.locked_at, after.id],
+        )?;
+        audit::record(&tx, action, Some(before), Some(&after), None, None)?;
+        changed += 1;
+    }
+    tx.commit()?;
+    Ok(changed)
+}
+
+/// Soft-delete the selected transactions, or none if any of them is locked.
+pub fn soft_delete_transactions(conn: &Connection, selection: &TransactionSelection) -> anyhow::Result<u64> {
+    let now = Utc::now().timestamp();
+    bulk_change(conn, selection, AuditAction::Delete, |t| Ok(Transaction { deleted_at: Some(now), ..t.clone() }))
+}
+
+/// Lock the selected transactions. Returns how many were newly locked.
+pub fn lock_transactions(conn: &Connection, selection: &TransactionSelection) -> anyhow::Result<u64> {
+    let now = Utc::now().timestamp();
+    bulk_change(conn, selection, AuditAction::Lock, |t| {
+        Ok(Transaction { locked_at: t.locked_at.or(Some(now)), ..t.clone() })
+    })
+}
+
+/// Apply `edit` to the selected transactions, or to none if any of them is
+/// locked. A target asset has to be active.
+pub fn edit_transactions(
+    conn: &Connection,
+    selection: &TransactionSelection,
+    edit: &TransactionEdit,
+) -> anyhow::Result<u64> {
+    if let Some(asset_id) = &edit.asset_id {
+        let asset_active: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM assets WHERE id = ?1 AND deleted_at IS NULL)",
+            params![asset_id],
+            |row| row.get(0),
+        )?;
+        if !asset_active {
+            anyhow::bail!("Asset not found");
+        }
+    }
+    bulk_change(conn, selection, AuditAction::Update, |t| {
+        edit.apply(t).map_err(|e| ValidationError { code: "invalid_input", fields: vec![e] }.into())
+    })
+}
+
+/// Reopen a locked transaction. The reason is kept in the audit log.
+pub fn unlock_transaction(conn: &Connection, id: &str, reason: &str) -> anyhow::Result<()> {
+    let reason = reason.trim();
+    if reason.is_empty() {
+        anyhow::bail!("A reason is required to unlock a transaction");
+    }
+    audited_change(conn, id, AuditAction::Unlock, Some(reason), |conn| {
+        conn.execute(
+            "UPDATE transactions SET locked_at = NULL WHERE id = ?1 AND deleted_at IS NULL",
+            params![id],
+        )
+    })
+}
+
+/// Undo the change logged as `audit_id`, putting the transaction back the way
+/// it was before it. Only allowed while the row still matches what the change
+/// left behind; later changes have to be reverted first. An undone insert
+/// becomes a soft delete, an undone purge re-creates the row. A row only
+/// comes back live while its asset is active. The revert is logged itself
+/// and returned.
+pub fn revert_change(conn: &Connection, audit_id: i64) -> anyhow::Result<AuditEntry> {
+    let tx = conn.unchecked_transaction()?;
+    let Some(entry) = audit::get_audit_entry(&tx, audit_id)? else {
+        anyhow::bail!("Change not found");
+    };
+    let current = get_transaction(&tx, &entry.transaction_id)?;
+    if current != entry.after {
+        anyhow::bail!("Transaction has changed since; revert the later changes first");
+    }
+    if current.as_ref().is_some_and(|t| t.locked_at.is_some()) {
+        anyhow::bail!("Transaction is locked; unlock it before reverting changes");
+    }
+    // A row coming back live needs an active asset, like a restore from the
+    // trash; a deleted one only needs its asset to still exist
+    if let Some(t) = &entry.before {
+        let live = t.deleted_at.is_none();
+        let asset_ok: bool = tx.query_row(
+            "SELECT EXISTS(SELECT 1 FROM assets WHERE id = ?1 AND (NOT ?2 OR deleted_at IS NULL))",
+            params![t.asset_id, live],
+            |row| row.get(0),
+        )?;
+        match (asset_ok, live) {
+            (false, true) => anyhow::bail!("The asset of this transaction is deleted; restore the asset first"),
+            (false, false) => anyhow::bail!("The asset of this transaction no longer exists"),
+            _ => {}
+        }
+    }
+
+    match (&entry.before, &current) {
+        (None, _) => {
+            tx.execute(
+                "UPDATE transactions SET deleted_at = ?1 WHERE id = ?2",
+                params![Utc::now().timestamp(), entry.transaction_id],
+            )?;
+        }
+        (Some(t), Some(_)) => {
+            if t.deleted_at.is_none() {
+                tx.execute("DELETE FROM asset_trashed_transactions WHERE transaction_id = ?1", params![t.id])?;
+            }
+            tx.execute(
+                "UPDATE transactions SET asset_id = ?1, tx_type = ?2, quantity = ?3, price_usd = ?4, ts = ?5, notes = ?6, deleted_at = ?7, locked_at = ?8, fee = ?9, account_id = ?10 WHERE id = ?11",
+                params![t.asset_id, t.tx_type.as_str(), t.quantity, t.price_usd, t.ts, t.notes, t.deleted_at, t.locked_at, t.fee, t.account_id, t.id],
+            )?;
+        }
+        (Some(t), None) => {
+            tx.execute(
+                "INSERT INTO transactions (id, asset_id, tx_type, quantity, price_usd, ts, notes, created_at, deleted_at, locked_at, fee, account_id) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)",
+                params![t.id, t.asset_id, t.tx_type.as_str(), t.quantity, t.price_usd, t.ts, t.notes, t.created_at, t.deleted_at, t.locked_at, t.fee, t.account_id],
+            )?;
+        }
+    }
+
+    let after = get_transaction(&tx, &entry.transaction_id)?;
+    if let Some(after) = &after {
+        ensure_period_open(&tx, current.as_ref(), after)?;
+    }
+    let action = AuditAction::between(current.as_ref(), after.as_ref());
+    let id = audit::record(&tx, action, current.as_ref(), after.as_ref(), Some(entry.id), None)?;
+    let reverted = audit::get_audit_entry(&tx, id)?;
+    tx.commit()?;
+    reverted.ok_or_else(|| anyhow::anyhow!("Change not found"))
+}
+
 pub fn get_holding_summary(
     conn: &Connection,
     asset_id: &str,
@@ -141,13 +675,13 @@ pub fn get_holding_summary(
         )?;
     let total_cost_basis: f64 = conn
         .query_row(
-            "SELECT COALESCE(SUM(quantity * price_usd), 0) FROM transactions WHERE asset_id = ?1 AND tx_type = 'buy' AND deleted_at IS NULL",
+            "SELECT COALESCE(SUM(quantity * price_usd + fee), 0) FROM transactions WHERE asset_id = ?1 AND tx_type = 'buy' AND deleted_at IS NULL",
             params![asset_id],
             |row| row.get(0),
         )?;
     let total_sold_value: f64 = conn
         .query_row(
-            "SELECT COALESCE(SUM(quantity * price_usd), 0) FROM transactions WHERE asset_id = ?1 AND tx_type = 'sell' AND deleted_at IS NULL",
+            "SELECT COALESCE(SUM(quantity * price_usd - fee), 0) FROM transactions WHERE asset_id = ?1 AND tx_type = 'sell' AND deleted_at IS NULL",
             params![asset_id],
             |row| row.get(0),
         )?;
@@ -177,7 +711,7 @@ mod tests {
     use crate::models::AssetType;
 
     fn setup_test_asset(conn: &Connection) -> String {
-        let asset = assets::insert_asset(conn, "BTC", "Bitcoin", &AssetType::Crypto, "USD").unwrap();
+        let asset = assets::insert_asset(conn, "BTC", "Bitcoin", &AssetType::Crypto, "USD", None).unwrap();
         asset.id
     }
 
@@ -186,7 +720,7 @@ mod tests {
         let conn = test_db();
         let asset_id = setup_test_asset(&conn);
 
-        let tx = insert_transaction(&conn, &asset_id, &TxType::Buy, 0.5, 50000.0, 1700000000, Some("First buy")).unwrap();
+        let tx = insert_transaction(&conn, &asset_id, &TxType::Buy, 0.5, 50000.0, 1700000000, Some("First buy"), 0.0, None).unwrap();
         assert_eq!(tx.quantity, 0.5);
         assert_eq!(tx.price_usd, 50000.0);
 
@@ -200,7 +734,7 @@ mod tests {
         let conn = test_db();
         let asset_id = setup_test_asset(&conn);
 
-        let tx = insert_transaction(&conn, &asset_id, &TxType::Buy, 1.0, 40000.0, 1700000000, None).unwrap();
+        let tx = insert_transaction(&conn, &asset_id, &TxType::Buy, 1.0, 40000.0, 1700000000, None, 0.0, None).unwrap();
         soft_delete_transaction(&conn, &tx.id).unwrap();
 
         let txs = list_transactions_by_asset(&conn, &asset_id).unwrap();
@@ -219,9 +753,9 @@ mod tests {
         let conn = test_db();
         let asset_id = setup_test_asset(&conn);
 
-        insert_transaction(&conn, &asset_id, &TxType::Buy, 2.0, 50000.0, 1700000000, None).unwrap();
-        insert_transaction(&conn, &asset_id, &TxType::Buy, 1.0, 60000.0, 1700100000, None).unwrap();
-        insert_transaction(&conn, &asset_id, &TxType::Sell, 0.5, 55000.0, 1700200000, None).unwrap();
+        insert_transaction(&conn, &asset_id, &TxType::Buy, 2.0, 50000.0, 1700000000, None, 0.0, None).unwrap();
+        insert_transaction(&conn, &asset_id, &TxType::Buy, 1.0, 60000.0, 1700100000, None, 0.0, None).unwrap();
+        insert_transaction(&conn, &asset_id, &TxType::Sell, 0.5, 55000.0, 1700200000, None, 0.0, None).unwrap();
 
         let summary = get_holding_summary(&conn, &asset_id).unwrap();
         assert_eq!(summary.total_bought, 3.0);
@@ -235,6 +769,20 @@ mod tests {
         assert!((summary.avg_cost_per_unit - 53333.333333).abs() < 0.01);
     }
 
+    #[test]
+    fn test_holding_summary_includes_fees() {
+        let conn = test_db();
+        let asset_id = setup_test_asset(&conn);
+
+        insert_transaction(&conn, &asset_id, &TxType::Buy, 2.0, 100.0, 1700000000, None, 10.0, None).unwrap();
+        insert_transaction(&conn, &asset_id, &TxType::Sell, 1.0, 150.0, 1700100000, None, 5.0, None).unwrap();
+
+        let summary = get_holding_summary(&conn, &asset_id).unwrap();
+        assert_eq!(summary.total_cost_basis, 210.0);
+        assert_eq!(summary.avg_cost_per_unit, 105.0);
+        assert_eq!(summary.total_sold_value, 145.0);
+    }
+
     #[test]
     fn test_holding_summary_empty() {
         let conn = test_db();
@@ -252,8 +800,8 @@ mod tests {
         let conn = test_db();
         let asset_id = setup_test_asset(&conn);
 
-        let tx = insert_transaction(&conn, &asset_id, &TxType::Buy, 1.0, 40000.0, 1700000000, Some("original")).unwrap();
-        update_transaction(&conn, &tx.id, &TxType::Sell, 2.0, 45000.0, 1700100000, Some("edited")).unwrap();
+        let tx = insert_transaction(&conn, &asset_id, &TxType::Buy, 1.0, 40000.0, 1700000000, Some("original"), 0.0, None).unwrap();
+        update_transaction(&conn, &tx.id, &TxType::Sell, 2.0, 45000.0, 1700100000, Some("edited"), 0.0, None).unwrap();
 
         let txs = list_transactions_by_asset(&conn, &asset_id).unwrap();
         assert_eq!(txs.len(), 1);
@@ -267,7 +815,7 @@ mod tests {
     #[test]
     fn test_update_nonexistent_transaction() {
         let conn = test_db();
-        let result = update_transaction(&conn, "nonexistent-id", &TxType::Buy, 1.0, 50000.0, 1700000000, None);
+        let result = update_transaction(&conn, "nonexistent-id", &TxType::Buy, 1.0, 50000.0, 1700000000, None, 0.0, None);
         assert!(result.is_err());
     }
 
@@ -276,17 +824,17 @@ mod tests {
         let conn = test_db();
         let asset_id = setup_test_asset(&conn);
 
-        insert_transaction(&conn, &asset_id, &TxType::Buy, 1.0, 40000.0, 1700000000, None).unwrap();
-        insert_transaction(&conn, &asset_id, &TxType::Buy, 0.5, 45000.0, 1700100000, None).unwrap();
+        insert_transaction(&conn, &asset_id, &TxType::Buy, 1.0, 40000.0, 1700000000, None, 0.0, None).unwrap();
+        insert_transaction(&conn, &asset_id, &TxType::Buy, 0.5, 45000.0, 1700100000, None, 0.0, None).unwrap();
 
-        let count = soft_delete_transactions_by_asset(&conn, &asset_id).unwrap();
+        let count = soft_delete_transactions_by_asset(&conn, &asset_id, 1800000000).unwrap();
         assert_eq!(count, 2);
 
         let txs = list_transactions_by_asset(&conn, &asset_id).unwrap();
         assert_eq!(txs.len(), 0);
 
         // Calling again should return 0 (already deleted)
-        let count2 = soft_delete_transactions_by_asset(&conn, &asset_id).unwrap();
+        let count2 = soft_delete_transactions_by_asset(&conn, &asset_id, 1800000000).unwrap();
         assert_eq!(count2, 0);
     }
 
@@ -295,10 +843,221 @@ mod tests {
         let conn = test_db();
         let asset_id = setup_test_asset(&conn);
 
-        insert_transaction(&conn, &asset_id, &TxType::Buy, 1.0, 40000.0, 1700000000, None).unwrap();
-        insert_transaction(&conn, &asset_id, &TxType::Buy, 1.0, 50000.0, 1700100000, None).unwrap();
+        insert_transaction(&conn, &asset_id, &TxType::Buy, 1.0, 40000.0, 1700000000, None, 0.0, None).unwrap();
+        insert_transaction(&conn, &asset_id, &TxType::Buy, 1.0, 50000.0, 1700100000, None, 0.0, None).unwrap();
 
         let txs = list_transactions_by_asset(&conn, &asset_id).unwrap();
         assert!(txs[0].ts > txs[1].ts);
     }
+
+    #[test]
+    fn test_changes_are_audited_and_revertible() {
+        let conn = test_db();
+        let asset_id = setup_test_asset(&conn);
+
+        let tx = insert_transaction(&conn, &asset_id, &TxType::Buy, 1.0, 40000.0, 1700000000, None, 0.0, None).unwrap();
+        update_transaction(&conn, &tx.id, &TxType::Buy, 2.0, 40000.0, 1700000000, None, 0.0, None).unwrap();
+        lock_transaction(&conn, &tx.id).unwrap();
+        let history = audit::list_audit_entries(&conn, Some(&tx.id), None).unwrap();
+        let actions: Vec<AuditAction> = history.iter().map(|e| e.action).collect();
+        assert_eq!(actions, vec![AuditAction::Lock, AuditAction::Update, AuditAction::Insert]);
+
+        // The update can only be undone once the later lock is lifted
+        assert!(revert_change(&conn, history[1].id).is_err());
+        assert!(revert_change(&conn, history[0].id).is_err());
+        unlock_transaction(&conn, &tx.id, "Typo in quantity").unwrap();
+        let reverted = revert_change(&conn, history[1].id).unwrap();
+        assert_eq!(reverted.action, AuditAction::Update);
+        assert_eq!(reverted.reverts, Some(history[1].id));
+        assert_eq!(get_transaction(&conn, &tx.id).unwrap(), Some(tx.clone()));
+
+        // Undoing the insert deletes the row; undoing that brings it back
+        let deleted = revert_change(&conn, history[2].id).unwrap();
+        assert_eq!(deleted.action, AuditAction::Delete);
+        assert!(list_transactions_by_asset(&conn, &asset_id).unwrap().is_empty());
+        assert_eq!(revert_change(&conn, deleted.id).unwrap().action, AuditAction::Restore);
+        assert_eq!(list_transactions_by_asset(&conn, &asset_id).unwrap(), vec![tx.clone()]);
+    }
+
+    #[test]
+    fn test_purged_transactions_keep_their_history() {
+        let conn = test_db();
+        let asset_id = setup_test_asset(&conn);
+        let tx = insert_transaction(&conn, &asset_id, &TxType::Buy, 1.0, 40000.0, 1700000000, None, 0.0, None).unwrap();
+        soft_delete_transactions_by_asset(&conn, &asset_id, 1800000000).unwrap();
+        assets::hard_delete_asset(&conn, &asset_id).unwrap();
+
+        assert!(get_transaction(&conn, &tx.id).unwrap().is_none());
+        let history = audit::list_audit_entries(&conn, None, Some(&asset_id)).unwrap();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].action, AuditAction::Purge);
+        assert!(history[0].after.is_none());
+        // Nothing to put the row back under
+        assert!(revert_change(&conn, history[0].id).is_err());
+    }
+
+    #[test]
+    fn test_locked_transactions_are_immutable() {
+        let conn = test_db();
+        let asset_id = setup_test_asset(&conn);
+        let old = insert_transaction(&conn, &asset_id, &TxType::Buy, 1.0, 40000.0, 1700000000, None, 0.0, None).unwrap();
+        let new = insert_transaction(&conn, &asset_id, &TxType::Buy, 1.0, 50000.0, 1800000000, None, 0.0, None).unwrap();
+
+        assert_eq!(lock_transactions_before(&conn, 1750000000, None).unwrap(), 1);
+        assert_eq!(lock_transactions_before(&conn, 1750000000, None).unwrap(), 0);
+        assert!(get_transaction(&conn, &new.id).unwrap().unwrap().locked_at.is_none());
+
+        assert!(update_transaction(&conn, &old.id, &TxType::Buy, 2.0, 40000.0, 1700000000, None, 0.0, None).is_err());
+        assert!(soft_delete_transaction(&conn, &old.id).is_err());
+        assert!(soft_delete_transactions_by_asset(&conn, &asset_id, 1800000000).is_err());
+        assert_eq!(list_transactions_by_asset(&conn, &asset_id).unwrap().len(), 2);
+
+        assert!(unlock_transaction(&conn, &old.id, "  ").is_err());
+        unlock_transaction(&conn, &old.id, "Broker corrected the fill").unwrap();
+        let unlock = &audit::list_audit_entries(&conn, Some(&old.id), None).unwrap()[0];
+        assert_eq!(unlock.action, AuditAction::Unlock);
+        assert_eq!(unlock.reason.as_deref(), Some("Broker corrected the fill"));
+        update_transaction(&conn, &old.id, &TxType::Buy, 2.0, 40000.0, 1700000000, None, 0.0, None).unwrap();
+    }
+
+    #[test]
+    fn test_closed_period_rejects_backdated_changes() {
+        let conn = test_db();
+        let asset_id = setup_test_asset(&conn);
+        let eth = assets::insert_asset(&conn, "ETH", "Ethereum", &AssetType::Crypto, "USD", None).unwrap().id;
+        let old = insert_transaction(&conn, &asset_id, &TxType::Buy, 1.0, 40000.0, 1700000000, None, 0.0, None).unwrap();
+        let new = insert_transaction(&conn, &asset_id, &TxType::Buy, 1.0, 50000.0, 1800000000, None, 0.0, None).unwrap();
+        assert_eq!(lock_transactions_before(&conn, 1750000000, Some(&asset_id)).unwrap(), 1);
+        assert_eq!(closed_before(&conn, &asset_id).unwrap(), Some(1750000000));
+
+        let err = insert_transaction(&conn, &asset_id, &TxType::Buy, 1.0, 40000.0, 1700000500, None, 0.0, None).unwrap_err();
+        assert_eq!(err.downcast_ref::<ValidationError>().unwrap().code, "period_closed");
+        assert!(update_transaction(&conn, &new.id, &TxType::Buy, 1.0, 50000.0, 1700000000, None, 0.0, None).is_err());
+        let ids = TransactionSelection::Ids(vec![new.id.clone()]);
+        let shift = TransactionEdit { shift_secs: Some(-100000000), ..Default::default() };
+        assert!(edit_transactions(&conn, &ids, &shift).is_err());
+        // Other assets and later dates are still open
+        insert_transaction(&conn, &eth, &TxType::Buy, 1.0, 2000.0, 1700000000, None, 0.0, None).unwrap();
+        insert_transaction(&conn, &asset_id, &TxType::Buy, 1.0, 50000.0, 1760000000, None, 0.0, None).unwrap();
+        let move_in = TransactionSelection::Filter(TransactionFilter { asset_id: Some(eth.clone()), ..Default::default() });
+        let to_btc = TransactionEdit { asset_id: Some(asset_id.clone()), ..Default::default() };
+        assert!(edit_transactions(&conn, &move_in, &to_btc).is_err());
+
+        assert!(reopen_period(&conn, Some(&asset_id), " ").is_err());
+        assert!(reopen_period(&conn, None, "Late statement").is_err());
+        assert_eq!(reopen_period(&conn, Some(&asset_id), "Late statement").unwrap(), 1);
+        assert!(get_transaction(&conn, &old.id).unwrap().unwrap().locked_at.is_none());
+        let unlock = &audit::list_audit_entries(&conn, Some(&old.id), None).unwrap()[0];
+        assert_eq!(unlock.action, AuditAction::Unlock);
+        assert_eq!(unlock.reason.as_deref(), Some("Late statement"));
+        let closes = list_period_closes(&conn).unwrap();
+        assert_eq!(closes[0].reopen_reason.as_deref(), Some("Late statement"));
+        assert!(closes[0].reopened_at.is_some());
+        insert_transaction(&conn, &asset_id, &TxType::Buy, 1.0, 40000.0, 1700000500, None, 0.0, None).unwrap();
+    }
+
+    #[test]
+    fn test_restore_and_purge_deleted_transactions() {
+        let conn = test_db();
+        let asset_id = setup_test_asset(&conn);
+        let tx = insert_transaction(&conn, &asset_id, &TxType::Buy, 1.0, 40000.0, 1700000000, None, 0.0, None).unwrap();
+        let other = insert_transaction(&conn, &asset_id, &TxType::Buy, 2.0, 40000.0, 1700000000, None, 0.0, None).unwrap();
+
+        assert!(restore_transaction(&conn, &tx.id).is_err());
+        assert!(purge_transaction(&conn, &tx.id).is_err());
+        soft_delete_transaction(&conn, &tx.id).unwrap();
+        soft_delete_transaction(&conn, &other.id).unwrap();
+        assert_eq!(list_deleted_transactions(&conn).unwrap().len(), 2);
+
+        restore_transaction(&conn, &tx.id).unwrap();
+        assert_eq!(list_transactions_by_asset(&conn, &asset_id).unwrap(), vec![tx.clone()]);
+        let history = audit::list_audit_entries(&conn, Some(&tx.id), None).unwrap();
+        assert_eq!(history[0].action, AuditAction::Restore);
+
+        assert_eq!(purge_deleted_transactions(&conn, Some(0)).unwrap(), 0);
+        purge_transaction(&conn, &other.id).unwrap();
+        assert!(get_transaction(&conn, &other.id).unwrap().is_none());
+        assert!(list_deleted_transactions(&conn).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_bulk_operations_are_all_or_nothing() {
+        let conn = test_db();
+        let asset_id = setup_test_asset(&conn);
+        let row = |asset_id: &str, ts: i64| NewTransaction {
+            asset_id: asset_id.to_string(),
+            tx_type: TxType::Buy,
+            quantity: 1.0,
+            price_usd: 100.0,
+            ts,
+            notes: None,
+            fee: 0.0,
+            account_id: None,
+        };
+
+        // The unknown asset fails the second row, so the first isn't kept either
+        assert!(insert_transactions(&conn, &[row(&asset_id, 100), row("missing", 200)]).is_err());
+        assert!(list_transactions_by_asset(&conn, &asset_id).unwrap().is_empty());
+        let inserted = insert_transactions(&conn, &[row(&asset_id, 100), row(&asset_id, 200), row(&asset_id, 0)]).unwrap();
+        let ids = TransactionSelection::Ids(inserted.iter().map(|t| t.id.clone()).collect());
+
+        let edit = TransactionEdit { shift_secs: Some(50), notes: Some(" bad import ".to_string()), ..Default::default() };
+        assert_eq!(edit_transactions(&conn, &ids, &edit).unwrap(), 3);
+        let dates: Vec<i64> = list_transactions_by_asset(&conn, &asset_id).unwrap().iter().map(|t| t.ts).collect();
+        assert_eq!(dates, vec![250, 150, 0]);
+        assert_eq!(get_transaction(&conn, &inserted[0].id).unwrap().unwrap().notes.as_deref(), Some("bad import"));
+        let overflow = TransactionEdit { shift_secs: Some(i64::MAX), ..Default::default() };
+        let err = edit_transactions(&conn, &ids, &overflow).unwrap_err();
+        assert_eq!(err.downcast_ref::<ValidationError>().unwrap().fields[0].field, "shift_secs");
+
+        let early = TransactionSelection::Filter(TransactionFilter { to_ts: Some(200), ..Default::default() });
+        assert!(select_for_bulk(&conn, &TransactionSelection::Filter(TransactionFilter::default())).is_err());
+        assert_eq!(lock_transactions(&conn, &early).unwrap(), 2);
+        assert_eq!(lock_transactions(&conn, &early).unwrap(), 0);
+
+        // One locked row blocks the whole delete
+        assert!(soft_delete_transactions(&conn, &ids).is_err());
+        assert_eq!(list_transactions_by_asset(&conn, &asset_id).unwrap().len(), 3);
+        let unlocked = TransactionSelection::Ids(vec![inserted[1].id.clone()]);
+        assert_eq!(soft_delete_transactions(&conn, &unlocked).unwrap(), 1);
+        assert!(soft_delete_transactions(&conn, &unlocked).is_err());
+        let deletes = audit::list_audit_entries(&conn, None, Some(&asset_id)).unwrap();
+        assert_eq!(deletes[0].action, AuditAction::Delete);
+    }
+
+    #[test]
+    fn test_revert_bulk_move() {
+        let conn = test_db();
+        let btc = setup_test_asset(&conn);
+        let eth = assets::insert_asset(&conn, "ETH", "Ethereum", &AssetType::Crypto, "USD", None).unwrap().id;
+        let tx = insert_transaction(&conn, &btc, &TxType::Buy, 1.0, 100.0, 100, None, 0.0, None).unwrap();
+
+        let ids = TransactionSelection::Ids(vec![tx.id.clone()]);
+        let edit = TransactionEdit { asset_id: Some(eth.clone()), ..Default::default() };
+        assert_eq!(edit_transactions(&conn, &ids, &edit).unwrap(), 1);
+        assert_eq!(list_transactions_by_asset(&conn, &eth).unwrap().len(), 1);
+        // The move is in both assets' history
+        assert_eq!(audit::list_audit_entries(&conn, None, Some(&btc)).unwrap().len(), 2);
+        assert_eq!(audit::list_audit_entries(&conn, None, Some(&eth)).unwrap().len(), 1);
+
+        let moved = &audit::list_audit_entries(&conn, Some(&tx.id), None).unwrap()[0];
+        revert_change(&conn, moved.id).unwrap();
+        assert!(list_transactions_by_asset(&conn, &eth).unwrap().is_empty());
+        assert_eq!(list_transactions_by_asset(&conn, &btc).unwrap(), vec![tx]);
+    }
+
+    #[test]
+    fn test_revert_into_trashed_asset_is_refused() {
+        let conn = test_db();
+        let asset_id = setup_test_asset(&conn);
+        let tx = insert_transaction(&conn, &asset_id, &TxType::Buy, 1.0, 100.0, 100, None, 0.0, None).unwrap();
+        assets::soft_delete_asset(&conn, &asset_id).unwrap();
+
+        // Undoing the delete the asset's removal logged would revive it inside the trash
+        let cascaded = &audit::list_audit_entries(&conn, Some(&tx.id), None).unwrap()[0];
+        assert_eq!(cascaded.action, AuditAction::Delete);
+        assert!(revert_change(&conn, cascaded.id).is_err());
+        assert_eq!(list_trashed_with_asset(&conn, &asset_id).unwrap().len(), 1);
+    }
 }
+
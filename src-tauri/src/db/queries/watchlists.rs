@@ -0,0 +1,246 @@
+use crate::models::{Watchlist, WatchlistItem};
+use chrono::Utc;
+use rusqlite::{params, Connection};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+pub fn insert_watchlist(conn: &Connection, name: &str) -> anyhow::Result<Watchlist> {
+    let id = Uuid::new_v4().to_string();
+    let now = Utc::now().timestamp();
+    let position: i64 =
+        conn.query_row("SELECT COALESCE(MAX(position) + 1, 0) FROM watchlists", [], |row| row.get(0))?;
+    conn.execute(
+        "INSERT INTO watchlists (id, name, position, created_at) VALUES (?1, ?2, ?3, ?4)",
+        params![id, name, position, now],
+    )?;
+    Ok(Watchlist {
+        id,
+        name: name.to_string(),
+        position,
+        created_at: now,
+        items: Vec::new(),
+    })
+}
+
+pub fn rename_watchlist(conn: &Connection, id: &str, name: &str) -> anyhow::Result<()> {
+    let changed = conn.execute("UPDATE watchlists SET name = ?2 WHERE id = ?1", params![id, name])?;
+    if changed == 0 {
+        anyhow::bail!("Watchlist not found: {}", id);
+    }
+    Ok(())
+}
+
+pub fn delete_watchlist(conn: &Connection, id: &str) -> anyhow::Result<()> {
+    conn.execute("DELETE FROM watchlists WHERE id = ?1", params![id])?;
+    Ok(())
+}
+
+/// Watchlists in display order, each with its items on active assets.
+pub fn list_watchlists(conn: &Connection) -> anyhow::Result<Vec<Watchlist>> {
+    let mut items: HashMap<String, Vec<WatchlistItem>> = HashMap::new();
+    let mut stmt = conn.prepare(
+        "SELECT i.watchlist_id, i.asset_id, i.position, i.notes, i.added_at FROM watchlist_items i
+         JOIN assets a ON a.id = i.asset_id WHERE a.deleted_at IS NULL ORDER BY i.position, i.added_at",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            WatchlistItem {
+                asset_id: row.get(1)?,
+                position: row.get(2)?,
+                notes: row.get(3)?,
+                added_at: row.get(4)?,
+            },
+        ))
+    })?;
+    for row in rows {
+        let (watchlist_id, item) = row?;
+        items.entry(watchlist_id).or_default().push(item);
+    }
+
+    let mut stmt = conn.prepare("SELECT id, name, position, created_at FROM watchlists ORDER BY position, name")?;
+    let rows = stmt.query_map([], |row| {
+        Ok(Watchlist {
+            id: row.get(0)?,
+            name: row.get(1)?,
+            position: row.get(2)?,
+            created_at: row.get(3)?,
+            items: Vec::new(),
+        })
+    })?;
+
+    let mut result = Vec::new();
+    for row in rows {
+        let mut watchlist = row?;
+        watchlist.items = items.remove(&watchlist.id).unwrap_or_default();
+        result.push(watchlist);
+    }
+    Ok(result)
+}
+
+/// Add an asset at the end of a watchlist. Adding it again only updates the notes.
+pub fn add_watchlist_item(
+    conn: &Connection,
+    watchlist_id: &str,
+    asset_id: &str,
+    notes: Option<&str>,
+) -> anyhow::Result<()> {
+    let now = Utc::now().timestamp();
+    conn.execute(
+        "INSERT INTO watchlist_items (watchlist_id, asset_id, position, notes, added_at)
+         VALUES (?1, ?2, (SELECT COALESCE(MAX(position) + 1, 0) FROM watchlist_items WHERE watchlist_id = ?1), ?3, ?4)
+         ON CONFLICT(watchlist_id, asset_id) DO UPDATE SET notes = COALESCE(excluded.notes, notes)",
+        params![watchlist_id, asset_id, notes, now],
+    )?;
+    Ok(())
+}
+
+pub fn update_watchlist_item_notes(
+    conn: &Connection,
+    watchlist_id: &str,
+    asset_id: &str,
+    notes: Option<&str>,
+) -> anyhow::Result<()> {
+    let changed = conn.execute(
+        "UPDATE watchlist_items SET notes = ?3 WHERE watchlist_id = ?1 AND asset_id = ?2",
+        params![watchlist_id, asset_id, notes],
+    )?;
+    if changed == 0 {
+        anyhow::bail!("Asset is not on this watchlist");
+    }
+    Ok(())
+}
+
+pub fn remove_watchlist_item(conn: &Connection, watchlist_id: &str, asset_id: &str) -> anyhow::Result<()> {
+    conn.execute(
+        "DELETE FROM watchlist_items WHERE watchlist_id = ?1 AND asset_id = ?2",
+        params![watchlist_id, asset_id],
+    )?;
+    Ok(())
+}
+
+/// Number items in the order given. Items left out keep their relative
+/// order after the listed ones.
+pub fn reorder_watchlist_items(conn: &Connection, watchlist_id: &str, asset_ids: &[String]) -> anyhow::Result<()> {
+    conn.execute(
+        "UPDATE watchlist_items SET position = position + ?2 WHERE watchlist_id = ?1",
+        params![watchlist_id, asset_ids.len() as i64],
+    )?;
+    for (position, asset_id) in asset_ids.iter().enumerate() {
+        conn.execute(
+            "UPDATE watchlist_items SET position = ?3 WHERE watchlist_id = ?1 AND asset_id = ?2",
+            params![watchlist_id, asset_id, position as i64],
+        )?;
+    }
+    Ok(())
+}
+
+/// Number watchlists in the order given, like `reorder_watchlist_items`.
+pub fn reorder_watchlists(conn: &Connection, ids: &[String]) -> anyhow::Result<()> {
+    conn.execute("UPDATE watchlists SET position = position + ?1", params![ids.len() as i64])?;
+    for (position, id) in ids.iter().enumerate() {
+        conn.execute("UPDATE watchlists SET position = ?2 WHERE id = ?1", params![id, position as i64])?;
+    }
+    Ok(())
+}
+
+pub fn mark_watch_only(conn: &Connection, asset_id: &str) -> anyhow::Result<()> {
+    conn.execute("INSERT OR IGNORE INTO watch_only_assets (asset_id) VALUES (?1)", params![asset_id])?;
+    Ok(())
+}
+
+/// Move an asset into the portfolio. Returns whether it was watch-only.
+pub fn clear_watch_only(conn: &Connection, asset_id: &str) -> anyhow::Result<bool> {
+    let deleted = conn.execute("DELETE FROM watch_only_assets WHERE asset_id = ?1", params![asset_id])?;
+    Ok(deleted > 0)
+}
+
+/// Soft-delete watch-only assets that are on no watchlist and were never
+/// traded, so they stop being refreshed. Returns how many were removed.
+pub fn prune_watch_only_assets(conn: &Connection) -> anyhow::Result<usize> {
+    let now = Utc::now().timestamp();
+    let pruned = conn.execute(
+        "UPDATE assets SET deleted_at = ?1
+         WHERE deleted_at IS NULL
+           AND id IN (SELECT asset_id FROM watch_only_assets)
+           AND id NOT IN (SELECT asset_id FROM watchlist_items)
+           AND id NOT IN (SELECT asset_id FROM transactions WHERE deleted_at IS NULL)",
+        params![now],
+    )?;
+    Ok(pruned)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::queries::{assets, transactions};
+    use crate::db::test_db;
+    use crate::models::{AssetType, TxType};
+
+    #[test]
+    fn test_watchlist_items_and_order() {
+        let conn = test_db();
+        let tech = insert_watchlist(&conn, "Tech").unwrap();
+        let energy = insert_watchlist(&conn, "Energy").unwrap();
+        assert!(insert_watchlist(&conn, "tech").is_err());
+        let aapl = assets::insert_asset(&conn, "AAPL", "Apple", &AssetType::Stock, "USD", None).unwrap();
+        let msft = assets::insert_asset(&conn, "MSFT", "Microsoft", &AssetType::Stock, "USD", None).unwrap();
+        let nvda = assets::insert_asset(&conn, "NVDA", "Nvidia", &AssetType::Stock, "USD", None).unwrap();
+
+        add_watchlist_item(&conn, &tech.id, &aapl.id, Some("Wait for earnings")).unwrap();
+        add_watchlist_item(&conn, &tech.id, &msft.id, None).unwrap();
+        add_watchlist_item(&conn, &tech.id, &nvda.id, None).unwrap();
+        add_watchlist_item(&conn, &tech.id, &aapl.id, None).unwrap();
+        reorder_watchlist_items(&conn, &tech.id, &[nvda.id.clone(), aapl.id.clone()]).unwrap();
+        reorder_watchlists(&conn, std::slice::from_ref(&energy.id)).unwrap();
+
+        let lists = list_watchlists(&conn).unwrap();
+        assert_eq!(lists[0].name, "Energy");
+        let order: Vec<&str> = lists[1].items.iter().map(|i| i.asset_id.as_str()).collect();
+        assert_eq!(order, vec![nvda.id.as_str(), aapl.id.as_str(), msft.id.as_str()]);
+        assert_eq!(lists[1].items[1].notes.as_deref(), Some("Wait for earnings"));
+
+        update_watchlist_item_notes(&conn, &tech.id, &msft.id, Some("Cloud")).unwrap();
+        assert!(update_watchlist_item_notes(&conn, &energy.id, &msft.id, None).is_err());
+        remove_watchlist_item(&conn, &tech.id, &nvda.id).unwrap();
+        assets::soft_delete_asset(&conn, &aapl.id).unwrap();
+        let lists = list_watchlists(&conn).unwrap();
+        assert_eq!(lists[1].items.len(), 1);
+        assert_eq!(lists[1].items[0].notes.as_deref(), Some("Cloud"));
+
+        delete_watchlist(&conn, &tech.id).unwrap();
+        assert_eq!(list_watchlists(&conn).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_watch_only_assets_stay_out_of_portfolio() {
+        let conn = test_db();
+        let list = insert_watchlist(&conn, "Ideas").unwrap();
+        let held = assets::insert_asset(&conn, "VTI", "Vanguard Total", &AssetType::Etf, "USD", None).unwrap();
+        let idea = assets::insert_asset(&conn, "TSLA", "Tesla", &AssetType::Stock, "USD", None).unwrap();
+        let bought = assets::insert_asset(&conn, "AMD", "AMD", &AssetType::Stock, "USD", None).unwrap();
+        for asset in [&idea, &bought] {
+            mark_watch_only(&conn, &asset.id).unwrap();
+            add_watchlist_item(&conn, &list.id, &asset.id, None).unwrap();
+        }
+        add_watchlist_item(&conn, &list.id, &held.id, None).unwrap();
+        transactions::insert_transaction(&conn, &bought.id, &TxType::Buy, 1.0, 100.0, 0, None, 0.0, None).unwrap();
+
+        let portfolio: Vec<String> =
+            assets::list_portfolio_assets(&conn).unwrap().into_iter().map(|a| a.symbol).collect();
+        assert_eq!(portfolio.len(), 2);
+        assert!(!portfolio.contains(&"TSLA".to_string()));
+        assert_eq!(assets::list_assets(&conn).unwrap().len(), 3);
+
+        // Only untraded watch-only assets go once they leave every list
+        for asset in [&idea, &bought, &held] {
+            remove_watchlist_item(&conn, &list.id, &asset.id).unwrap();
+        }
+        assert_eq!(prune_watch_only_assets(&conn).unwrap(), 1);
+        assert!(assets::get_asset(&conn, &idea.id).unwrap().is_none());
+        assert!(assets::get_asset(&conn, &held.id).unwrap().is_some());
+
+        assert!(clear_watch_only(&conn, &bought.id).unwrap());
+        assert!(!clear_watch_only(&conn, &held.id).unwrap());
+    }
+}
@@ -7,29 +7,48 @@ pub fn run_migrations(conn: &Connection) -> anyhow::Result<()> {
             id          TEXT PRIMARY KEY,
             symbol      TEXT NOT NULL,
             name        TEXT NOT NULL,
-            asset_type  TEXT NOT NULL CHECK(asset_type IN ('stock','crypto','commodity')),
+            asset_type  TEXT NOT NULL CHECK(asset_type IN ('stock','etf','mutual_fund','bond','forex','index','crypto','commodity','cash')),
             currency    TEXT NOT NULL DEFAULT 'USD',
             added_at    INTEGER NOT NULL,
             deleted_at  INTEGER,
-            UNIQUE(symbol) ON CONFLICT ABORT
+            exchange    TEXT NOT NULL DEFAULT '',
+            UNIQUE(symbol, exchange, asset_type) ON CONFLICT ABORT
         );
 
         CREATE TABLE IF NOT EXISTS historical_prices (
             id          INTEGER PRIMARY KEY AUTOINCREMENT,
             asset_id    TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+            interval    TEXT NOT NULL DEFAULT '1d' CHECK(interval IN ('1m','5m','1h','4h','1d','1w')),
             ts          INTEGER NOT NULL,
             open        REAL,
             high        REAL,
             low         REAL,
             close       REAL NOT NULL,
             volume      REAL,
-            UNIQUE(asset_id, ts)
+            provider    TEXT,
+            UNIQUE(asset_id, interval, ts)
+        );
+
+        CREATE TABLE IF NOT EXISTS reference_prices (
+            asset_id    TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+            interval    TEXT NOT NULL,
+            ts          INTEGER NOT NULL,
+            provider    TEXT NOT NULL,
+            open        REAL,
+            high        REAL,
+            low         REAL,
+            close       REAL NOT NULL,
+            volume      REAL,
+            fetched_at  INTEGER NOT NULL,
+            PRIMARY KEY(asset_id, interval, ts, provider)
         );
 
         CREATE TABLE IF NOT EXISTS price_cache_meta (
-            asset_id      TEXT PRIMARY KEY REFERENCES assets(id) ON DELETE CASCADE,
+            asset_id      TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+            interval      TEXT NOT NULL DEFAULT '1d',
             provider      TEXT NOT NULL,
-            last_fetched  INTEGER NOT NULL
+            last_fetched  INTEGER NOT NULL,
+            PRIMARY KEY(asset_id, interval)
         );
 
         CREATE TABLE IF NOT EXISTS settings (
@@ -47,7 +66,145 @@ pub fn run_migrations(conn: &Connection) -> anyhow::Result<()> {
             notes       TEXT,
             created_at  INTEGER NOT NULL,
             deleted_at  INTEGER,
-            locked_at   INTEGER
+            locked_at   INTEGER,
+            fee         REAL NOT NULL DEFAULT 0,
+            account_id  TEXT REFERENCES accounts(id) ON DELETE SET NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS accounts (
+            id          TEXT PRIMARY KEY,
+            name        TEXT NOT NULL UNIQUE,
+            currency    TEXT NOT NULL DEFAULT 'USD',
+            created_at  INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS cash_ledger (
+            id          TEXT PRIMARY KEY,
+            account_id  TEXT NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+            kind        TEXT NOT NULL CHECK(kind IN ('deposit','withdrawal','dividend')),
+            amount      REAL NOT NULL CHECK(amount > 0),
+            currency    TEXT NOT NULL,
+            ts          INTEGER NOT NULL,
+            asset_id    TEXT REFERENCES assets(id) ON DELETE SET NULL,
+            notes       TEXT,
+            created_at  INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS price_issues (
+            id          INTEGER PRIMARY KEY AUTOINCREMENT,
+            asset_id    TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+            interval    TEXT NOT NULL,
+            ts          INTEGER NOT NULL,
+            open        REAL,
+            high        REAL,
+            low         REAL,
+            close       REAL NOT NULL,
+            volume      REAL,
+            provider    TEXT,
+            kind        TEXT NOT NULL CHECK(kind IN ('non_positive','ohlc_inconsistent','spike')),
+            detail      TEXT NOT NULL,
+            status      TEXT NOT NULL DEFAULT 'pending' CHECK(status IN ('pending','accepted','rejected')),
+            detected_at INTEGER NOT NULL,
+            resolved_at INTEGER,
+            UNIQUE(asset_id, interval, ts)
+        );
+
+        CREATE TABLE IF NOT EXISTS asset_provider_symbols (
+            asset_id        TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+            provider        TEXT NOT NULL,
+            provider_symbol TEXT NOT NULL,
+            PRIMARY KEY(asset_id, provider)
+        );
+
+        CREATE TABLE IF NOT EXISTS asset_metadata (
+            asset_id            TEXT PRIMARY KEY REFERENCES assets(id) ON DELETE CASCADE,
+            sector              TEXT,
+            industry            TEXT,
+            country             TEXT,
+            asset_class         TEXT,
+            profile_fetched_at  INTEGER
+        );
+
+        CREATE TABLE IF NOT EXISTS allocation_targets (
+            asset_id    TEXT PRIMARY KEY REFERENCES assets(id) ON DELETE CASCADE,
+            target_pct  REAL NOT NULL CHECK(target_pct >= 0 AND target_pct <= 100),
+            lot_size    REAL NOT NULL DEFAULT 0 CHECK(lot_size >= 0),
+            no_sell     INTEGER NOT NULL DEFAULT 0
+        );
+
+        CREATE TABLE IF NOT EXISTS alerts (
+            id                  TEXT PRIMARY KEY,
+            asset_id            TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+            kind                TEXT NOT NULL CHECK(kind IN ('price_above','price_below','pct_move','drawdown','position_above','position_below')),
+            threshold           REAL NOT NULL,
+            window_days         INTEGER CHECK(window_days IS NULL OR window_days > 0),
+            enabled             INTEGER NOT NULL DEFAULT 1,
+            triggered           INTEGER NOT NULL DEFAULT 0,
+            last_triggered_at   INTEGER,
+            created_at          INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS watchlists (
+            id          TEXT PRIMARY KEY,
+            name        TEXT NOT NULL UNIQUE COLLATE NOCASE,
+            position    INTEGER NOT NULL,
+            created_at  INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS watchlist_items (
+            watchlist_id    TEXT NOT NULL REFERENCES watchlists(id) ON DELETE CASCADE,
+            asset_id        TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+            position        INTEGER NOT NULL,
+            notes           TEXT,
+            added_at        INTEGER NOT NULL,
+            PRIMARY KEY(watchlist_id, asset_id)
+        );
+
+        CREATE TABLE IF NOT EXISTS watch_only_assets (
+            asset_id    TEXT PRIMARY KEY REFERENCES assets(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS short_selling_assets (
+            asset_id    TEXT PRIMARY KEY REFERENCES assets(id) ON DELETE CASCADE
+        );
+
+        CREATE TABLE IF NOT EXISTS transaction_audit (
+            id              INTEGER PRIMARY KEY AUTOINCREMENT,
+            transaction_id  TEXT NOT NULL,
+            asset_id        TEXT NOT NULL,
+            action          TEXT NOT NULL CHECK(action IN ('insert','update','delete','restore','lock','unlock','purge')),
+            before          TEXT,
+            after           TEXT,
+            ts              INTEGER NOT NULL,
+            reverts         INTEGER REFERENCES transaction_audit(id)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_transaction_audit_transaction ON transaction_audit(transaction_id);
+        CREATE INDEX IF NOT EXISTS idx_transaction_audit_asset ON transaction_audit(asset_id);
+
+        CREATE TRIGGER IF NOT EXISTS transaction_audit_no_update BEFORE UPDATE ON transaction_audit
+        BEGIN
+            SELECT RAISE(ABORT, 'transaction_audit is append-only');
+        END;
+
+        CREATE TRIGGER IF NOT EXISTS transaction_audit_no_delete BEFORE DELETE ON transaction_audit
+        BEGIN
+            SELECT RAISE(ABORT, 'transaction_audit is append-only');
+        END;
+
+        CREATE TABLE IF NOT EXISTS asset_tags (
+            asset_id    TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+            tag         TEXT NOT NULL COLLATE NOCASE,
+            PRIMARY KEY(asset_id, tag)
+        );
+
+        CREATE TABLE IF NOT EXISTS period_closes (
+            id             INTEGER PRIMARY KEY AUTOINCREMENT,
+            asset_id       TEXT REFERENCES assets(id) ON DELETE CASCADE,
+            closed_before  INTEGER NOT NULL,
+            closed_at      INTEGER NOT NULL,
+            reopened_at    INTEGER,
+            reopen_reason  TEXT
         );
         ",
     )?;
@@ -62,6 +219,16 @@ pub fn run_migrations(conn: &Connection) -> anyhow::Result<()> {
         conn.execute("ALTER TABLE transactions ADD COLUMN locked_at INTEGER", [])?;
     }
 
+    // Audit reason migration: unlocks record why a closed transaction was reopened
+    let has_reason: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('transaction_audit') WHERE name='reason'",
+        [],
+        |row| row.get::<_, i64>(0),
+    ).unwrap_or(0) > 0;
+    if !has_reason {
+        conn.execute("ALTER TABLE transaction_audit ADD COLUMN reason TEXT", [])?;
+    }
+
     // Reverse migration: remove 'snapshot' tx_type, convert existing snapshots to 'buy'
     let table_sql: String = conn.query_row(
         "SELECT sql FROM sqlite_master WHERE type='table' AND name='transactions'",
@@ -94,5 +261,203 @@ pub fn run_migrations(conn: &Connection) -> anyhow::Result<()> {
         ")?;
     }
 
+    // Interval migration: existing price rows and cache entries are all daily
+    let has_interval: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('historical_prices') WHERE name='interval'",
+        [],
+        |row| row.get::<_, i64>(0),
+    ).unwrap_or(0) > 0;
+    if !has_interval {
+        conn.execute_batch("
+            PRAGMA foreign_keys = OFF;
+            CREATE TABLE historical_prices_v2 (
+                id          INTEGER PRIMARY KEY AUTOINCREMENT,
+                asset_id    TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+                interval    TEXT NOT NULL DEFAULT '1d' CHECK(interval IN ('1m','5m','1h','4h','1d','1w')),
+                ts          INTEGER NOT NULL,
+                open        REAL,
+                high        REAL,
+                low         REAL,
+                close       REAL NOT NULL,
+                volume      REAL,
+                UNIQUE(asset_id, interval, ts)
+            );
+            INSERT INTO historical_prices_v2 (id, asset_id, interval, ts, open, high, low, close, volume)
+                SELECT id, asset_id, '1d', ts, open, high, low, close, volume FROM historical_prices;
+            DROP TABLE historical_prices;
+            ALTER TABLE historical_prices_v2 RENAME TO historical_prices;
+
+            CREATE TABLE price_cache_meta_v2 (
+                asset_id      TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+                interval      TEXT NOT NULL DEFAULT '1d',
+                provider      TEXT NOT NULL,
+                last_fetched  INTEGER NOT NULL,
+                PRIMARY KEY(asset_id, interval)
+            );
+            INSERT INTO price_cache_meta_v2 (asset_id, interval, provider, last_fetched)
+                SELECT asset_id, '1d', provider, last_fetched FROM price_cache_meta;
+            DROP TABLE price_cache_meta;
+            ALTER TABLE price_cache_meta_v2 RENAME TO price_cache_meta;
+            PRAGMA foreign_keys = ON;
+        ")?;
+    }
+
+    // Provenance migration: existing rows came from the provider in their cache entry
+    let has_provider: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('historical_prices') WHERE name='provider'",
+        [],
+        |row| row.get::<_, i64>(0),
+    ).unwrap_or(0) > 0;
+    if !has_provider {
+        conn.execute_batch("
+            ALTER TABLE historical_prices ADD COLUMN provider TEXT;
+            UPDATE historical_prices SET provider = (
+                SELECT m.provider FROM price_cache_meta m
+                WHERE m.asset_id = historical_prices.asset_id AND m.interval = historical_prices.interval
+            );
+        ")?;
+    }
+    let issues_have_provider: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('price_issues') WHERE name='provider'",
+        [],
+        |row| row.get::<_, i64>(0),
+    ).unwrap_or(0) > 0;
+    if !issues_have_provider {
+        conn.execute("ALTER TABLE price_issues ADD COLUMN provider TEXT", [])?;
+    }
+
+    // Corporate actions. Twelve Data candles used to be stored split-adjusted as
+    // of fetch time, so a later split left older rows on the old scale. Candles
+    // are now stored raw and adjusted on read; the first time through, drop
+    // Twelve Data history so it is refetched raw.
+    let has_actions: bool = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='corporate_actions'",
+        [],
+        |row| row.get::<_, i64>(0),
+    ).unwrap_or(0) > 0;
+    if !has_actions {
+        conn.execute_batch("
+            DELETE FROM historical_prices WHERE asset_id IN
+                (SELECT asset_id FROM price_cache_meta WHERE provider = 'twelve_data');
+            DELETE FROM price_cache_meta WHERE provider = 'twelve_data';
+
+            CREATE TABLE IF NOT EXISTS corporate_actions (
+                id          INTEGER PRIMARY KEY AUTOINCREMENT,
+                asset_id    TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+                action_type TEXT NOT NULL CHECK(action_type IN ('split','dividend')),
+                ts          INTEGER NOT NULL,
+                value       REAL NOT NULL,
+                provider    TEXT NOT NULL,
+                UNIQUE(asset_id, action_type, ts)
+            );
+
+            CREATE TABLE IF NOT EXISTS corporate_actions_meta (
+                asset_id      TEXT PRIMARY KEY REFERENCES assets(id) ON DELETE CASCADE,
+                last_fetched  INTEGER NOT NULL
+            );
+        ")?;
+    }
+
+    // Asset identity migration: UNIQUE(symbol) becomes UNIQUE(symbol, exchange,
+    // asset_type). Existing assets get no exchange, except where a Twelve Data
+    // mapping already names the listing ("SHOP:TSX").
+    let assets_have_exchange: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('assets') WHERE name='exchange'",
+        [],
+        |row| row.get::<_, i64>(0),
+    ).unwrap_or(0) > 0;
+    if !assets_have_exchange {
+        conn.execute_batch("
+            PRAGMA foreign_keys = OFF;
+            CREATE TABLE assets_v2 (
+                id          TEXT PRIMARY KEY,
+                symbol      TEXT NOT NULL,
+                name        TEXT NOT NULL,
+                asset_type  TEXT NOT NULL CHECK(asset_type IN ('stock','crypto','commodity')),
+                currency    TEXT NOT NULL DEFAULT 'USD',
+                added_at    INTEGER NOT NULL,
+                deleted_at  INTEGER,
+                exchange    TEXT NOT NULL DEFAULT '',
+                UNIQUE(symbol, exchange, asset_type) ON CONFLICT ABORT
+            );
+            INSERT INTO assets_v2 (id, symbol, name, asset_type, currency, added_at, deleted_at)
+                SELECT id, symbol, name, asset_type, currency, added_at, deleted_at FROM assets;
+            UPDATE assets_v2 SET exchange = (
+                SELECT UPPER(SUBSTR(p.provider_symbol, INSTR(p.provider_symbol, ':') + 1))
+                FROM asset_provider_symbols p
+                WHERE p.asset_id = assets_v2.id AND p.provider = 'twelve_data' AND INSTR(p.provider_symbol, ':') > 0
+            )
+            WHERE id IN (
+                SELECT asset_id FROM asset_provider_symbols
+                WHERE provider = 'twelve_data' AND INSTR(provider_symbol, ':') > 0
+            );
+            DROP TABLE assets;
+            ALTER TABLE assets_v2 RENAME TO assets;
+            PRAGMA foreign_keys = ON;
+        ")?;
+    }
+
+    // Cash ledger columns: existing transactions have no fee and no account,
+    // so they leave cash balances untouched
+    let has_fee: bool = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('transactions') WHERE name='fee'",
+        [],
+        |row| row.get::<_, i64>(0),
+    ).unwrap_or(0) > 0;
+    if !has_fee {
+        conn.execute_batch("
+            ALTER TABLE transactions ADD COLUMN fee REAL NOT NULL DEFAULT 0;
+            ALTER TABLE transactions ADD COLUMN account_id TEXT REFERENCES accounts(id) ON DELETE SET NULL;
+        ")?;
+    }
+
+    // Asset type migration: widen the asset_type CHECK for the new types
+    let assets_sql: String = conn.query_row(
+        "SELECT sql FROM sqlite_master WHERE type='table' AND name='assets'",
+        [],
+        |row| row.get(0),
+    ).unwrap_or_default();
+    if !assets_sql.contains("'cash'") {
+        conn.execute_batch("
+            PRAGMA foreign_keys = OFF;
+            CREATE TABLE assets_v2 (
+                id          TEXT PRIMARY KEY,
+                symbol      TEXT NOT NULL,
+                name        TEXT NOT NULL,
+                asset_type  TEXT NOT NULL CHECK(asset_type IN ('stock','etf','mutual_fund','bond','forex','index','crypto','commodity','cash')),
+                currency    TEXT NOT NULL DEFAULT 'USD',
+                added_at    INTEGER NOT NULL,
+                deleted_at  INTEGER,
+                exchange    TEXT NOT NULL DEFAULT '',
+                UNIQUE(symbol, exchange, asset_type) ON CONFLICT ABORT
+            );
+            INSERT INTO assets_v2 (id, symbol, name, asset_type, currency, added_at, deleted_at, exchange)
+                SELECT id, symbol, name, asset_type, currency, added_at, deleted_at, exchange FROM assets;
+            DROP TABLE assets;
+            ALTER TABLE assets_v2 RENAME TO assets;
+            PRAGMA foreign_keys = ON;
+        ")?;
+    }
+
+    // Cascade marker: transactions trashed together with their asset. They
+    // used to be recognized by sharing the asset's deleted_at, so assets
+    // already in the trash are backfilled that way once.
+    let has_cascade: bool = conn.query_row(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type='table' AND name='asset_trashed_transactions'",
+        [],
+        |row| row.get::<_, i64>(0),
+    ).unwrap_or(0) > 0;
+    if !has_cascade {
+        conn.execute_batch("
+            CREATE TABLE asset_trashed_transactions (
+                transaction_id  TEXT PRIMARY KEY REFERENCES transactions(id) ON DELETE CASCADE,
+                asset_id        TEXT NOT NULL REFERENCES assets(id) ON DELETE CASCADE
+            );
+            INSERT INTO asset_trashed_transactions (transaction_id, asset_id)
+                SELECT t.id, t.asset_id FROM transactions t JOIN assets a ON a.id = t.asset_id
+                WHERE a.deleted_at IS NOT NULL AND t.deleted_at = a.deleted_at;
+        ")?;
+    }
+
     Ok(())
 }
@@ -0,0 +1,187 @@
+use crate::models::{CorporateAction, CorporateActionType, OHLCVRow, PriceAdjustment, Transaction, TxType};
+
+/// Apply `mode` to raw candles (sorted ascending) in place. Each action scales
+/// every candle before its ex-date, so the latest prices always stay as traded.
+///
+/// Splits divide prices by the ratio and multiply volume by it. Dividends
+/// (`Total` only) scale by `1 - dividend / close` using the raw close of the
+/// last candle before the ex-date, the usual total-return convention.
+pub fn adjust_prices(rows: &mut [OHLCVRow], actions: &[CorporateAction], mode: PriceAdjustment) {
+    if mode == PriceAdjustment::Raw || actions.is_empty() {
+        return;
+    }
+    let mut newest_first: Vec<&CorporateAction> = actions.iter().collect();
+    newest_first.sort_by_key(|a| std::cmp::Reverse(a.ts));
+    let mut pending = newest_first.into_iter().peekable();
+
+    let mut price_factor = 1.0;
+    let mut volume_factor = 1.0;
+    for row in rows.iter_mut().rev() {
+        // Walking backwards, the first candle before an ex-date is the one the
+        // dividend is measured against; its close is still raw here.
+        while let Some(action) = pending.next_if(|a| a.ts > row.ts) {
+            match action.action_type {
+                CorporateActionType::Split if action.value > 0.0 => {
+                    price_factor /= action.value;
+                    volume_factor *= action.value;
+                }
+                CorporateActionType::Dividend
+                    if mode == PriceAdjustment::Total && action.value < row.close =>
+                {
+                    price_factor *= 1.0 - action.value / row.close;
+                }
+                _ => {}
+            }
+        }
+        if price_factor != 1.0 {
+            row.open = row.open.map(|p| p * price_factor);
+            row.high = row.high.map(|p| p * price_factor);
+            row.low = row.low.map(|p| p * price_factor);
+            row.close *= price_factor;
+        }
+        if volume_factor != 1.0 {
+            row.volume = row.volume.map(|v| v * volume_factor);
+        }
+    }
+}
+
+/// Combined ratio of the splits taking effect after `ts`: how many shares one
+/// share held at `ts` has become.
+pub fn split_ratio_after(actions: &[CorporateAction], ts: i64) -> f64 {
+    actions
+        .iter()
+        .filter(|a| a.action_type == CorporateActionType::Split && a.ts > ts && a.value > 0.0)
+        .map(|a| a.value)
+        .product()
+}
+
+/// Restate transactions in today's share terms: quantity times the later
+/// split ratio, price divided by it. Trade value is unchanged.
+pub fn split_adjust_transactions(transactions: &mut [Transaction], actions: &[CorporateAction]) {
+    for tx in transactions {
+        let ratio = split_ratio_after(actions, tx.ts);
+        if ratio != 1.0 {
+            tx.quantity *= ratio;
+            tx.price_usd /= ratio;
+        }
+    }
+}
+
+/// Net quantity bought minus sold, in today's share terms, so that it can be
+/// valued at a current price.
+pub fn split_adjusted_net_quantity(transactions: &[Transaction], actions: &[CorporateAction]) -> f64 {
+    transactions
+        .iter()
+        .map(|tx| {
+            let quantity = tx.quantity * split_ratio_after(actions, tx.ts);
+            match tx.tx_type {
+                TxType::Buy => quantity,
+                TxType::Sell => -quantity,
+            }
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Interval;
+
+    const DAY: i64 = 86400;
+
+    fn candle(ts: i64, close: f64) -> OHLCVRow {
+        OHLCVRow {
+            id: None,
+            asset_id: "a1".to_string(),
+            interval: Interval::OneDay,
+            ts,
+            open: Some(close),
+            high: Some(close),
+            low: Some(close),
+            close,
+            volume: Some(100.0),
+            provider: None,
+        }
+    }
+
+    fn action(action_type: CorporateActionType, ts: i64, value: f64) -> CorporateAction {
+        CorporateAction {
+            id: None,
+            asset_id: "a1".to_string(),
+            action_type,
+            ts,
+            value,
+            provider: "twelve_data".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_split_adjustment_removes_crash() {
+        let mut rows = vec![candle(0, 400.0), candle(DAY, 404.0), candle(2 * DAY, 101.0)];
+        let actions = vec![action(CorporateActionType::Split, 2 * DAY, 4.0)];
+
+        let mut raw = rows.clone();
+        adjust_prices(&mut raw, &actions, PriceAdjustment::Raw);
+        assert_eq!(raw[1].close, 404.0);
+
+        adjust_prices(&mut rows, &actions, PriceAdjustment::Split);
+        let closes: Vec<f64> = rows.iter().map(|r| r.close).collect();
+        assert_eq!(closes, vec![100.0, 101.0, 101.0]);
+        assert_eq!(rows[0].high, Some(100.0));
+        assert_eq!(rows[0].volume, Some(400.0));
+        assert_eq!(rows[2].volume, Some(100.0));
+    }
+
+    #[test]
+    fn test_total_return_adjustment() {
+        let mut rows = vec![candle(0, 50.0), candle(DAY, 100.0), candle(2 * DAY, 99.0)];
+        let actions = vec![
+            action(CorporateActionType::Dividend, 2 * DAY, 1.0),
+            action(CorporateActionType::Split, DAY, 2.0),
+        ];
+
+        let mut split_only = rows.clone();
+        adjust_prices(&mut split_only, &actions, PriceAdjustment::Split);
+        assert_eq!(split_only[1].close, 100.0);
+
+        adjust_prices(&mut rows, &actions, PriceAdjustment::Total);
+        // Dividend of 1 against the 100 close before the ex-date: factor 0.99
+        assert!((rows[1].close - 99.0).abs() < 1e-9);
+        // The split and the dividend compound for older candles
+        assert!((rows[0].close - 50.0 * 0.99 / 2.0).abs() < 1e-9);
+        assert_eq!(rows[2].close, 99.0);
+    }
+
+    #[test]
+    fn test_split_adjust_transactions() {
+        let tx = |ts: i64, quantity: f64, price_usd: f64| Transaction {
+            id: ts.to_string(),
+            asset_id: "a1".to_string(),
+            tx_type: TxType::Buy,
+            quantity,
+            price_usd,
+            ts,
+            notes: None,
+            created_at: 0,
+            deleted_at: None,
+            locked_at: None,
+            fee: 0.0,
+            account_id: None,
+        };
+        let actions = vec![
+            action(CorporateActionType::Split, 10 * DAY, 2.0),
+            action(CorporateActionType::Split, 20 * DAY, 3.0),
+            action(CorporateActionType::Dividend, 15 * DAY, 0.5),
+        ];
+        let mut txs = vec![tx(DAY, 10.0, 600.0), tx(12 * DAY, 10.0, 300.0), tx(20 * DAY, 10.0, 100.0)];
+        let sold = Transaction { tx_type: TxType::Sell, ..tx(25 * DAY, 20.0, 100.0) };
+        let raw: Vec<Transaction> = txs.iter().cloned().chain(Some(sold)).collect();
+        assert_eq!(split_adjusted_net_quantity(&raw, &actions), 60.0 + 30.0 + 10.0 - 20.0);
+        split_adjust_transactions(&mut txs, &actions);
+
+        assert_eq!((txs[0].quantity, txs[0].price_usd), (60.0, 100.0));
+        assert_eq!((txs[1].quantity, txs[1].price_usd), (30.0, 100.0));
+        // A trade on the ex-date is already in post-split terms
+        assert_eq!((txs[2].quantity, txs[2].price_usd), (10.0, 100.0));
+    }
+}
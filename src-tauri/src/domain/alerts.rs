@@ -0,0 +1,142 @@
+use crate::models::{Alert, AlertKind, OHLCVRow};
+
+const DAY_SECS: i64 = 86_400;
+
+/// The value an alert watches and whether it is past the threshold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Observation {
+    pub value: f64,
+    pub holds: bool,
+}
+
+/// Check `alert` against daily `candles` (ascending) and the held `quantity`.
+/// `None` when there isn't enough data to tell: no candles, no close
+/// `window_days` before the latest one, or no holding for a position alert.
+pub fn observe(alert: &Alert, candles: &[OHLCVRow], quantity: f64) -> Option<Observation> {
+    let latest = candles.last()?;
+    let price = latest.close;
+    let value = match alert.kind {
+        AlertKind::PriceAbove | AlertKind::PriceBelow => price,
+        AlertKind::PctMove => {
+            let cutoff = latest.ts - alert.window_days.unwrap_or(1) * DAY_SECS;
+            let base = candles.iter().rev().find(|c| c.ts <= cutoff)?.close;
+            if base <= 0.0 {
+                return None;
+            }
+            (price / base - 1.0) * 100.0
+        }
+        AlertKind::Drawdown => {
+            let from = alert.window_days.map(|days| latest.ts - days * DAY_SECS);
+            let high = candles
+                .iter()
+                .filter(|c| from.is_none_or(|from| c.ts >= from))
+                .map(|c| c.close)
+                .fold(f64::MIN, f64::max);
+            if high <= 0.0 {
+                return None;
+            }
+            (1.0 - price / high) * 100.0
+        }
+        AlertKind::PositionAbove | AlertKind::PositionBelow => {
+            if quantity == 0.0 {
+                return None;
+            }
+            quantity * price
+        }
+    };
+    let holds = match alert.kind {
+        AlertKind::PriceAbove | AlertKind::PositionAbove | AlertKind::Drawdown => value >= alert.threshold,
+        AlertKind::PriceBelow | AlertKind::PositionBelow => value <= alert.threshold,
+        AlertKind::PctMove => value.abs() >= alert.threshold,
+    };
+    Some(Observation { value, holds })
+}
+
+/// One-line notification text, e.g. "AAPL rose above $200.00 (now $201.50)".
+pub fn describe(alert: &Alert, symbol: &str, value: f64) -> String {
+    let window = alert.window_days.unwrap_or(1);
+    match alert.kind {
+        AlertKind::PriceAbove => format!("{symbol} rose above ${:.2} (now ${value:.2})", alert.threshold),
+        AlertKind::PriceBelow => format!("{symbol} fell below ${:.2} (now ${value:.2})", alert.threshold),
+        AlertKind::PctMove => format!("{symbol} moved {value:+.1}% in {window} day(s)"),
+        AlertKind::Drawdown => match alert.window_days {
+            Some(days) => format!("{symbol} is {value:.1}% below its {days}-day high"),
+            None => format!("{symbol} is {value:.1}% below its high"),
+        },
+        AlertKind::PositionAbove => {
+            format!("{symbol} position rose above ${:.2} (now ${value:.2})", alert.threshold)
+        }
+        AlertKind::PositionBelow => {
+            format!("{symbol} position fell below ${:.2} (now ${value:.2})", alert.threshold)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Interval;
+
+    fn candle(day: i64, close: f64) -> OHLCVRow {
+        OHLCVRow {
+            id: None,
+            asset_id: "a".to_string(),
+            interval: Interval::OneDay,
+            ts: day * DAY_SECS,
+            open: None,
+            high: None,
+            low: None,
+            close,
+            volume: None,
+            provider: None,
+        }
+    }
+
+    fn alert(kind: AlertKind, threshold: f64, window_days: Option<i64>) -> Alert {
+        Alert {
+            id: "x".to_string(),
+            asset_id: "a".to_string(),
+            kind,
+            threshold,
+            window_days,
+            enabled: true,
+            triggered: false,
+            last_triggered_at: None,
+            created_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_price_and_position_thresholds() {
+        let candles = vec![candle(1, 90.0), candle(2, 105.0)];
+        let above = observe(&alert(AlertKind::PriceAbove, 100.0, None), &candles, 0.0).unwrap();
+        assert!(above.holds);
+        assert_eq!(above.value, 105.0);
+        assert!(!observe(&alert(AlertKind::PriceBelow, 100.0, None), &candles, 0.0).unwrap().holds);
+
+        let position = alert(AlertKind::PositionAbove, 1000.0, None);
+        assert!(observe(&position, &candles, 10.0).unwrap().holds);
+        assert!(observe(&position, &candles, 0.0).is_none());
+        assert!(observe(&position, &[], 10.0).is_none());
+    }
+
+    #[test]
+    fn test_pct_move_and_drawdown() {
+        let candles = vec![candle(1, 100.0), candle(5, 120.0), candle(8, 90.0), candle(10, 99.0)];
+
+        // Day 10 vs the last close at or before day 3 (day 1): -1%
+        let week = observe(&alert(AlertKind::PctMove, 5.0, Some(7)), &candles, 0.0).unwrap();
+        assert!((week.value + 1.0).abs() < 1e-9);
+        assert!(!week.holds);
+        // Day 10 vs day 8: +10%
+        assert!(observe(&alert(AlertKind::PctMove, 5.0, Some(2)), &candles, 0.0).unwrap().holds);
+        assert!(observe(&alert(AlertKind::PctMove, 5.0, Some(30)), &candles, 0.0).is_none());
+
+        // 99 is 17.5% below the 120 high
+        let drawdown = observe(&alert(AlertKind::Drawdown, 15.0, None), &candles, 0.0).unwrap();
+        assert!((drawdown.value - 17.5).abs() < 1e-9);
+        assert!(drawdown.holds);
+        // Within the last 2 days the high is 99
+        assert_eq!(observe(&alert(AlertKind::Drawdown, 15.0, Some(2)), &candles, 0.0).unwrap().value, 0.0);
+    }
+}
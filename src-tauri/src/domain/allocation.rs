@@ -0,0 +1,48 @@
+use crate::models::AllocationSlice;
+use std::collections::HashMap;
+
+/// Sum `(key, label, value)` items into slices, largest first, with each
+/// slice's share of the summed value. Items sharing a key are merged.
+pub fn slices<I>(items: I) -> Vec<AllocationSlice>
+where
+    I: IntoIterator<Item = (String, String, f64)>,
+{
+    let mut totals: HashMap<String, (String, f64)> = HashMap::new();
+    for (key, label, value) in items {
+        totals.entry(key).or_insert((label, 0.0)).1 += value;
+    }
+    let total: f64 = totals.values().map(|(_, v)| v).sum();
+
+    let mut result: Vec<AllocationSlice> = totals
+        .into_iter()
+        .map(|(key, (label, value))| AllocationSlice {
+            key,
+            label,
+            value,
+            pct: if total != 0.0 { value / total * 100.0 } else { 0.0 },
+        })
+        .collect();
+    result.sort_by(|a, b| b.value.total_cmp(&a.value).then_with(|| a.label.cmp(&b.label)));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slices_merge_and_sort() {
+        let items = vec![
+            ("tech".to_string(), "Technology".to_string(), 300.0),
+            ("".to_string(), "Unclassified".to_string(), 250.0),
+            ("tech".to_string(), "Technology".to_string(), 450.0),
+        ];
+        let result = slices(items);
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].key, "tech");
+        assert_eq!(result[0].value, 750.0);
+        assert_eq!(result[0].pct, 75.0);
+        assert_eq!(result[1].pct, 25.0);
+        assert!(slices(Vec::new()).is_empty());
+    }
+}
@@ -0,0 +1,504 @@
+use crate::models::{Asset, AssetType};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, Weekday};
+
+const DAY_SECS: i64 = 86400;
+/// Longest run of days a market can stay closed (weekend plus holidays), with room to spare.
+const MAX_CLOSED_DAYS: i64 = 14;
+/// Time after the close before a session's final candle is reliably published.
+const CLOSE_SETTLE_SECS: i64 = 15 * 60;
+
+/// Which days a market produces candles on, so a missing day can be told
+/// apart from a day the market was closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradingCalendar {
+    /// Trades around the clock, every day (crypto).
+    Continuous,
+    /// Monday to Friday (UTC), no holidays (commodities, FX quotes, and stocks
+    /// on exchanges we don't model).
+    Weekdays,
+    /// An exchange's regular sessions: weekdays minus its holidays.
+    Exchange(Exchange),
+}
+
+impl TradingCalendar {
+    pub fn for_asset(asset: &Asset) -> Self {
+        match asset.asset_type {
+            // Cash has no market; continuous keeps it out of open/closed reporting
+            AssetType::Crypto | AssetType::Cash => TradingCalendar::Continuous,
+            AssetType::Commodity | AssetType::Forex => TradingCalendar::Weekdays,
+            AssetType::Stock | AssetType::Etf | AssetType::MutualFund | AssetType::Bond | AssetType::Index => asset
+                .exchange
+                .as_deref()
+                .and_then(Exchange::for_venue)
+                .or_else(|| Exchange::for_currency(&asset.currency))
+                .map_or(TradingCalendar::Weekdays, TradingCalendar::Exchange),
+        }
+    }
+
+    /// Short label for the UI: the exchange code, or how a sessionless market trades.
+    pub fn name(&self) -> &'static str {
+        match self {
+            TradingCalendar::Continuous => "24/7",
+            TradingCalendar::Weekdays => "Weekdays",
+            TradingCalendar::Exchange(exchange) => exchange.code(),
+        }
+    }
+
+    pub fn is_trading_day(&self, date: NaiveDate) -> bool {
+        let weekday = !matches!(date.weekday(), Weekday::Sat | Weekday::Sun);
+        match self {
+            TradingCalendar::Continuous => true,
+            TradingCalendar::Weekdays => weekday,
+            TradingCalendar::Exchange(exchange) => weekday && !exchange.is_holiday(date),
+        }
+    }
+
+    /// Open and close timestamps of the regular session on `date`, if the
+    /// market trades that day. Continuous markets have no sessions.
+    fn session(&self, date: NaiveDate) -> Option<(i64, i64)> {
+        if *self == TradingCalendar::Continuous || !self.is_trading_day(date) {
+            return None;
+        }
+        let midnight = date.and_hms_opt(0, 0, 0)?.and_utc().timestamp();
+        match self {
+            TradingCalendar::Exchange(exchange) => {
+                let (open, close) = exchange.session_secs();
+                let offset = exchange.utc_offset_secs(date);
+                Some((midnight + open - offset, midnight + close - offset))
+            }
+            _ => Some((midnight, midnight + DAY_SECS)),
+        }
+    }
+
+    /// Sessions on the days from `ts`'s date onwards (or backwards), as far
+    /// as the longest possible closure.
+    fn sessions_from(&self, ts: i64, step: i64) -> impl Iterator<Item = (i64, i64)> + '_ {
+        let start = date_of(ts);
+        (-1..=MAX_CLOSED_DAYS).filter_map(move |d| self.session(start + Duration::days(d * step)))
+    }
+
+    /// Whether the market is in its regular session at `ts`.
+    pub fn is_open(&self, ts: i64) -> bool {
+        *self == TradingCalendar::Continuous
+            || self.session(date_of(ts)).is_some_and(|(open, close)| open <= ts && ts < close)
+    }
+
+    /// Start of the next session after `ts`; `None` for continuous markets.
+    pub fn next_open(&self, ts: i64) -> Option<i64> {
+        self.sessions_from(ts, 1).map(|(open, _)| open).find(|&open| open > ts)
+    }
+
+    /// End of the session open at `ts` (or the next one); `None` for
+    /// continuous markets. Back-to-back sessions count as one.
+    pub fn next_close(&self, ts: i64) -> Option<i64> {
+        let mut close = self.sessions_from(ts, 1).map(|(_, close)| close).find(|&close| close > ts)?;
+        while let Some((open, next_close)) = self.session(date_of(close)) {
+            if open != close {
+                break;
+            }
+            close = next_close;
+        }
+        Some(close)
+    }
+
+    /// End of the most recent session that closed at or before `ts`.
+    pub fn last_close(&self, ts: i64) -> Option<i64> {
+        self.sessions_from(ts, -1).map(|(_, close)| close).find(|&close| close <= ts)
+    }
+
+    /// Whether data fetched at `last_fetched` should be refetched at `now`.
+    /// While the market trades, anything older than `max_age_secs` is stale.
+    /// Once it has closed, data fetched after the close has settled stays
+    /// fresh until the next session.
+    pub fn is_stale(&self, last_fetched: i64, now: i64, max_age_secs: i64) -> bool {
+        if now - last_fetched <= max_age_secs {
+            return false;
+        }
+        if self.is_open(now) {
+            return true;
+        }
+        self.last_close(now).is_none_or(|close| last_fetched < close + CLOSE_SETTLE_SECS)
+    }
+}
+
+/// Stock exchanges whose sessions and holidays are modeled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Exchange {
+    /// NYSE and Nasdaq share hours and holidays.
+    Nyse,
+    /// Toronto Stock Exchange.
+    Tsx,
+    /// London Stock Exchange.
+    Lse,
+    /// Deutsche Börse Xetra.
+    Xetra,
+}
+
+impl Exchange {
+    /// The modeled exchange for a listing venue code, including venues that
+    /// share its sessions and holidays (Nasdaq trades NYSE hours).
+    pub fn for_venue(venue: &str) -> Option<Self> {
+        match venue.to_ascii_uppercase().as_str() {
+            "NYSE" | "NASDAQ" | "NYSE ARCA" | "NYSE AMERICAN" | "AMEX" | "CBOE" | "BATS" => Some(Exchange::Nyse),
+            "TSX" | "TSXV" => Some(Exchange::Tsx),
+            "LSE" => Some(Exchange::Lse),
+            "XETR" | "XETRA" | "FSX" => Some(Exchange::Xetra),
+            _ => None,
+        }
+    }
+
+    /// Best guess from the quote currency, for assets with no known exchange.
+    pub fn for_currency(currency: &str) -> Option<Self> {
+        match currency.to_ascii_uppercase().as_str() {
+            "USD" => Some(Exchange::Nyse),
+            "CAD" => Some(Exchange::Tsx),
+            "GBP" | "GBX" => Some(Exchange::Lse),
+            "EUR" => Some(Exchange::Xetra),
+            _ => None,
+        }
+    }
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Exchange::Nyse => "NYSE",
+            Exchange::Tsx => "TSX",
+            Exchange::Lse => "LSE",
+            Exchange::Xetra => "XETRA",
+        }
+    }
+
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.holidays(date.year()).contains(&date)
+    }
+
+    /// Full-day closures in `year`. Half days trade and count as sessions.
+    pub fn holidays(&self, year: i32) -> Vec<NaiveDate> {
+        match self {
+            Exchange::Nyse => us_market_holidays(year),
+            Exchange::Tsx => tsx_holidays(year),
+            Exchange::Lse => lse_holidays(year),
+            Exchange::Xetra => xetra_holidays(year),
+        }
+    }
+
+    /// Regular session as seconds after local midnight.
+    fn session_secs(&self) -> (i64, i64) {
+        let hm = |h: i64, m: i64| h * 3600 + m * 60;
+        match self {
+            Exchange::Nyse | Exchange::Tsx => (hm(9, 30), hm(16, 0)),
+            Exchange::Lse => (hm(8, 0), hm(16, 30)),
+            Exchange::Xetra => (hm(9, 0), hm(17, 30)),
+        }
+    }
+
+    /// Local time minus UTC during the session on `date`. Sessions are
+    /// mid-day, so only which side of a DST change the date is on matters.
+    fn utc_offset_secs(&self, date: NaiveDate) -> i64 {
+        let year = date.year();
+        let (standard, dst) = match self {
+            // North American DST: second Sunday of March to first Sunday of November
+            Exchange::Nyse | Exchange::Tsx => (
+                -5,
+                nth_weekday(year, 3, Weekday::Sun, 2) <= date && date < nth_weekday(year, 11, Weekday::Sun, 1),
+            ),
+            // European summer time: last Sunday of March to last Sunday of October
+            Exchange::Lse | Exchange::Xetra => (
+                if *self == Exchange::Lse { 0 } else { 1 },
+                last_weekday(year, 3, Weekday::Sun) <= date && date < last_weekday(year, 10, Weekday::Sun),
+            ),
+        };
+        (standard + dst as i64) * 3600
+    }
+}
+
+fn date_of(ts: i64) -> NaiveDate {
+    DateTime::from_timestamp(ts, 0).unwrap_or_default().date_naive()
+}
+
+/// Full-day NYSE closures. Holidays are computed from the rules rather than a
+/// table so they never run out; one-off closures (national days of mourning)
+/// aren't known.
+pub fn us_market_holidays(year: i32) -> Vec<NaiveDate> {
+    let mut holidays = Vec::with_capacity(10);
+
+    // New Year's Day: a Saturday holiday is not moved back into the prior year
+    let new_year = ymd(year, 1, 1);
+    if new_year.weekday() != Weekday::Sat {
+        holidays.push(observed(new_year));
+    }
+    holidays.push(nth_weekday(year, 1, Weekday::Mon, 3)); // Martin Luther King Jr. Day
+    holidays.push(nth_weekday(year, 2, Weekday::Mon, 3)); // Washington's Birthday
+    holidays.push(easter_sunday(year) - Duration::days(2)); // Good Friday
+    holidays.push(last_weekday(year, 5, Weekday::Mon)); // Memorial Day
+    if year >= 2022 {
+        holidays.push(observed(ymd(year, 6, 19))); // Juneteenth
+    }
+    holidays.push(observed(ymd(year, 7, 4))); // Independence Day
+    holidays.push(nth_weekday(year, 9, Weekday::Mon, 1)); // Labor Day
+    holidays.push(nth_weekday(year, 11, Weekday::Thu, 4)); // Thanksgiving
+    holidays.push(observed(ymd(year, 12, 25))); // Christmas
+    holidays
+}
+
+/// TSX closures. A holiday on a weekend moves to the next free weekday.
+pub fn tsx_holidays(year: i32) -> Vec<NaiveDate> {
+    let mut holidays = substitute_weekends(&[
+        ymd(year, 1, 1),  // New Year's Day
+        ymd(year, 7, 1),  // Canada Day
+        ymd(year, 12, 25), // Christmas
+        ymd(year, 12, 26), // Boxing Day
+    ]);
+    holidays.push(nth_weekday(year, 2, Weekday::Mon, 3)); // Family Day
+    holidays.push(easter_sunday(year) - Duration::days(2)); // Good Friday
+    holidays.push(last_weekday_on_or_before(ymd(year, 5, 24), Weekday::Mon)); // Victoria Day
+    holidays.push(nth_weekday(year, 8, Weekday::Mon, 1)); // Civic Holiday
+    holidays.push(nth_weekday(year, 9, Weekday::Mon, 1)); // Labour Day
+    holidays.push(nth_weekday(year, 10, Weekday::Mon, 2)); // Thanksgiving
+    holidays.sort();
+    holidays
+}
+
+/// LSE closures: English bank holidays. One-off bank holidays (jubilees,
+/// coronations) aren't known.
+pub fn lse_holidays(year: i32) -> Vec<NaiveDate> {
+    let easter = easter_sunday(year);
+    let mut holidays = substitute_weekends(&[ymd(year, 1, 1), ymd(year, 12, 25), ymd(year, 12, 26)]);
+    holidays.push(easter - Duration::days(2)); // Good Friday
+    holidays.push(easter + Duration::days(1)); // Easter Monday
+    holidays.push(nth_weekday(year, 5, Weekday::Mon, 1)); // Early May bank holiday
+    holidays.push(last_weekday(year, 5, Weekday::Mon)); // Spring bank holiday
+    holidays.push(last_weekday(year, 8, Weekday::Mon)); // Summer bank holiday
+    holidays.sort();
+    holidays
+}
+
+/// Xetra closures. Holidays on a weekend are not made up.
+pub fn xetra_holidays(year: i32) -> Vec<NaiveDate> {
+    let easter = easter_sunday(year);
+    let mut holidays = vec![
+        ymd(year, 1, 1),
+        easter - Duration::days(2), // Good Friday
+        easter + Duration::days(1), // Easter Monday
+        ymd(year, 5, 1),            // Labour Day
+        ymd(year, 12, 24),
+        ymd(year, 12, 25),
+        ymd(year, 12, 26),
+        ymd(year, 12, 31),
+    ];
+    holidays.retain(|d| !matches!(d.weekday(), Weekday::Sat | Weekday::Sun));
+    holidays.sort();
+    holidays
+}
+
+fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).expect("valid calendar date")
+}
+
+/// Saturday holidays are observed on Friday, Sunday holidays on Monday.
+fn observed(date: NaiveDate) -> NaiveDate {
+    match date.weekday() {
+        Weekday::Sat => date - Duration::days(1),
+        Weekday::Sun => date + Duration::days(1),
+        _ => date,
+    }
+}
+
+/// Weekend holidays move to the next weekday not already a holiday
+/// (Christmas on Saturday → Monday, Boxing Day → Tuesday).
+fn substitute_weekends(dates: &[NaiveDate]) -> Vec<NaiveDate> {
+    let mut observed: Vec<NaiveDate> = Vec::with_capacity(dates.len());
+    for &date in dates {
+        let mut day = date;
+        while matches!(day.weekday(), Weekday::Sat | Weekday::Sun) || observed.contains(&day) {
+            day += Duration::days(1);
+        }
+        observed.push(day);
+    }
+    observed
+}
+
+fn nth_weekday(year: i32, month: u32, weekday: Weekday, n: u32) -> NaiveDate {
+    NaiveDate::from_weekday_of_month_opt(year, month, weekday, n as u8).expect("nth weekday exists")
+}
+
+fn last_weekday(year: i32, month: u32, weekday: Weekday) -> NaiveDate {
+    let first_of_next = if month == 12 { ymd(year + 1, 1, 1) } else { ymd(year, month + 1, 1) };
+    last_weekday_on_or_before(first_of_next - Duration::days(1), weekday)
+}
+
+fn last_weekday_on_or_before(mut date: NaiveDate, weekday: Weekday) -> NaiveDate {
+    while date.weekday() != weekday {
+        date -= Duration::days(1);
+    }
+    date
+}
+
+/// Gregorian Easter Sunday (anonymous Gregorian / Meeus algorithm).
+fn easter_sunday(year: i32) -> NaiveDate {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    ymd(year, month as u32, day as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_easter() {
+        assert_eq!(easter_sunday(2024), ymd(2024, 3, 31));
+        assert_eq!(easter_sunday(2025), ymd(2025, 4, 20));
+        assert_eq!(easter_sunday(2019), ymd(2019, 4, 21));
+    }
+
+    #[test]
+    fn test_us_holidays_2024() {
+        let expected = vec![
+            ymd(2024, 1, 1),
+            ymd(2024, 1, 15),
+            ymd(2024, 2, 19),
+            ymd(2024, 3, 29),
+            ymd(2024, 5, 27),
+            ymd(2024, 6, 19),
+            ymd(2024, 7, 4),
+            ymd(2024, 9, 2),
+            ymd(2024, 11, 28),
+            ymd(2024, 12, 25),
+        ];
+        assert_eq!(us_market_holidays(2024), expected);
+    }
+
+    #[test]
+    fn test_observed_holidays() {
+        // 2022: New Year's on Saturday is not observed; Juneteenth and Christmas on Sunday move to Monday
+        let h2022 = us_market_holidays(2022);
+        assert!(!h2022.contains(&ymd(2021, 12, 31)));
+        assert!(h2022.contains(&ymd(2022, 6, 20)));
+        assert!(h2022.contains(&ymd(2022, 12, 26)));
+        // 2021: Independence Day on Sunday → Monday, Christmas on Saturday → Friday
+        let h2021 = us_market_holidays(2021);
+        assert!(h2021.contains(&ymd(2021, 7, 5)));
+        assert!(h2021.contains(&ymd(2021, 12, 24)));
+        assert!(!h2021.iter().any(|d| d.month() == 6 && d.day() == 19));
+    }
+
+    #[test]
+    fn test_trading_days_by_calendar() {
+        let saturday = ymd(2024, 7, 6);
+        let independence_day = ymd(2024, 7, 4);
+        let regular = ymd(2024, 7, 5);
+        assert!(TradingCalendar::Continuous.is_trading_day(saturday));
+        assert!(!TradingCalendar::Weekdays.is_trading_day(saturday));
+        assert!(TradingCalendar::Weekdays.is_trading_day(independence_day));
+        let nyse = TradingCalendar::Exchange(Exchange::Nyse);
+        assert!(!nyse.is_trading_day(independence_day));
+        assert!(nyse.is_trading_day(regular));
+        assert!(TradingCalendar::Exchange(Exchange::Lse).is_trading_day(independence_day));
+    }
+
+    #[test]
+    fn test_other_exchange_holidays() {
+        // 2022: Christmas on Sunday, Boxing Day on Monday
+        let lse = lse_holidays(2022);
+        assert!(lse.contains(&ymd(2022, 12, 26)) && lse.contains(&ymd(2022, 12, 27)));
+        assert!(lse.contains(&ymd(2022, 4, 18))); // Easter Monday
+        assert_eq!(lse.len(), 8);
+
+        let tsx = tsx_holidays(2024);
+        assert!(tsx.contains(&ymd(2024, 5, 20))); // Victoria Day
+        assert!(tsx.contains(&ymd(2024, 10, 14))); // Thanksgiving
+        // Canada Day 2023 fell on a Saturday
+        assert!(tsx_holidays(2023).contains(&ymd(2023, 7, 3)));
+
+        // Xetra closes on New Year's Eve but doesn't make up weekend holidays
+        assert!(xetra_holidays(2024).contains(&ymd(2024, 12, 31)));
+        assert!(!xetra_holidays(2022).contains(&ymd(2022, 12, 27)));
+    }
+
+    #[test]
+    fn test_sessions_follow_dst() {
+        let nyse = TradingCalendar::Exchange(Exchange::Nyse);
+        let ts = |y, m, d, h, min| ymd(y, m, d).and_hms_opt(h, min, 0).unwrap().and_utc().timestamp();
+        // 9:30 New York is 14:30 UTC in winter and 13:30 UTC in summer
+        assert_eq!(nyse.next_open(ts(2024, 1, 8, 0, 0)), Some(ts(2024, 1, 8, 14, 30)));
+        assert_eq!(nyse.next_open(ts(2024, 7, 8, 0, 0)), Some(ts(2024, 7, 8, 13, 30)));
+        // The US switches two weeks before Europe, so London opens 12:00 New York time then
+        let lse = TradingCalendar::Exchange(Exchange::Lse);
+        assert_eq!(lse.next_open(ts(2024, 3, 18, 0, 0)), Some(ts(2024, 3, 18, 8, 0)));
+        assert_eq!(lse.next_open(ts(2024, 4, 2, 0, 0)), Some(ts(2024, 4, 2, 7, 0)));
+    }
+
+    #[test]
+    fn test_market_open_and_next_transitions() {
+        let nyse = TradingCalendar::Exchange(Exchange::Nyse);
+        let ts = |d, h, min| ymd(2024, 7, d).and_hms_opt(h, min, 0).unwrap().and_utc().timestamp();
+        // Tuesday 2 July, 15:00 UTC: open until 20:00 UTC
+        assert!(nyse.is_open(ts(2, 15, 0)));
+        assert_eq!(nyse.next_close(ts(2, 15, 0)), Some(ts(2, 20, 0)));
+        assert!(!nyse.is_open(ts(2, 21, 0)));
+        assert_eq!(nyse.last_close(ts(2, 21, 0)), Some(ts(2, 20, 0)));
+        // After Wednesday's close the next session skips Independence Day
+        assert_eq!(nyse.next_open(ts(3, 21, 0)), Some(ts(5, 13, 30)));
+
+        // Weekday markets run Monday to Friday without closing in between
+        let weekdays = TradingCalendar::Weekdays;
+        assert!(weekdays.is_open(ts(3, 23, 59)));
+        assert_eq!(weekdays.next_close(ts(3, 12, 0)), Some(ts(6, 0, 0)));
+        assert_eq!(weekdays.next_open(ts(6, 12, 0)), Some(ts(8, 0, 0)));
+
+        assert!(TradingCalendar::Continuous.is_open(ts(6, 12, 0)));
+        assert_eq!(TradingCalendar::Continuous.next_close(ts(6, 12, 0)), None);
+    }
+
+    #[test]
+    fn test_staleness_across_close() {
+        let nyse = TradingCalendar::Exchange(Exchange::Nyse);
+        let ts = |d, h, min| ymd(2024, 7, d).and_hms_opt(h, min, 0).unwrap().and_utc().timestamp();
+        let hour = 3600;
+        // During the session the max age applies
+        assert!(!nyse.is_stale(ts(2, 15, 0), ts(2, 15, 30), hour));
+        assert!(nyse.is_stale(ts(2, 15, 0), ts(2, 16, 30), hour));
+        // Fetched before the close: stale once, to pick up the closing candle
+        assert!(nyse.is_stale(ts(2, 19, 0), ts(2, 22, 0), hour));
+        // Fetched after the close: fresh through the holiday until Friday's open
+        assert!(!nyse.is_stale(ts(3, 21, 0), ts(5, 13, 0), hour));
+        assert!(nyse.is_stale(ts(3, 21, 0), ts(5, 14, 0), hour));
+        // Crypto never closes
+        assert!(TradingCalendar::Continuous.is_stale(ts(6, 10, 0), ts(6, 12, 0), hour));
+    }
+
+    #[test]
+    fn test_calendar_for_asset_prefers_exchange() {
+        let asset = |asset_type, currency: &str, exchange: Option<&str>| Asset {
+            id: "a1".to_string(),
+            symbol: "SHOP".to_string(),
+            name: "Shopify".to_string(),
+            asset_type,
+            currency: currency.to_string(),
+            exchange: exchange.map(str::to_string),
+            added_at: 0,
+            deleted_at: None,
+        };
+        let calendar = |a: &Asset| TradingCalendar::for_asset(a).name();
+        assert_eq!(calendar(&asset(AssetType::Stock, "USD", Some("TSX"))), "TSX");
+        assert_eq!(calendar(&asset(AssetType::Stock, "USD", Some("NASDAQ"))), "NYSE");
+        // Unknown venues and missing exchanges fall back to the currency
+        assert_eq!(calendar(&asset(AssetType::Stock, "CAD", Some("OTC"))), "TSX");
+        assert_eq!(calendar(&asset(AssetType::Stock, "USD", None)), "NYSE");
+        assert_eq!(calendar(&asset(AssetType::Etf, "USD", Some("LSE"))), "LSE");
+        assert_eq!(calendar(&asset(AssetType::Forex, "USD", None)), "Weekdays");
+        assert_eq!(calendar(&asset(AssetType::Crypto, "USD", Some("Binance"))), "24/7");
+    }
+}
@@ -0,0 +1,39 @@
+use crate::models::{Interval, OHLCVRow};
+
+/// Candles for a cash balance from `from` to `to`: a flat 1.0 per unit, so
+/// cash values and charts like any priced asset without a provider.
+pub fn flat_candles(asset_id: &str, interval: Interval, from: i64, to: i64) -> Vec<OHLCVRow> {
+    let mut rows = Vec::new();
+    let mut ts = interval.align(from);
+    while ts <= to {
+        rows.push(OHLCVRow {
+            id: None,
+            asset_id: asset_id.to_string(),
+            interval,
+            ts,
+            open: Some(1.0),
+            high: Some(1.0),
+            low: Some(1.0),
+            close: 1.0,
+            volume: None,
+            provider: None,
+        });
+        ts += interval.secs();
+    }
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flat_candles() {
+        let day = 86_400;
+        let rows = flat_candles("cash", Interval::OneDay, 10 * day + 3600, 13 * day);
+        let timestamps: Vec<i64> = rows.iter().map(|r| r.ts).collect();
+        assert_eq!(timestamps, vec![10 * day, 11 * day, 12 * day, 13 * day]);
+        assert!(rows.iter().all(|r| r.close == 1.0 && r.provider.is_none()));
+        assert!(flat_candles("cash", Interval::OneDay, 2 * day, day).is_empty());
+    }
+}
@@ -0,0 +1,157 @@
+use crate::domain::calendar::TradingCalendar;
+use crate::models::Interval;
+use chrono::DateTime;
+use serde::Serialize;
+
+const DAY_SECS: i64 = 86400;
+
+/// A run of missing candles between two stored ones. `from` and `to` are the
+/// first and last missing candle timestamps (inclusive).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Gap {
+    pub from: i64,
+    pub to: i64,
+    /// Missing candles; whole trading days for intraday data on session calendars.
+    pub missing: usize,
+}
+
+impl Gap {
+    /// Last second covered by the gap, for requesting it from a provider.
+    pub fn end(&self, interval: Interval) -> i64 {
+        self.to + interval.secs() - 1
+    }
+}
+
+/// Find gaps inside stored history. Only the span between the first and last
+/// stored candle is scanned; anything after the last one is the incremental
+/// fetch's job. `timestamps` must be sorted ascending.
+pub fn find_gaps(timestamps: &[i64], interval: Interval, calendar: TradingCalendar) -> Vec<Gap> {
+    timestamps
+        .windows(2)
+        .filter_map(|pair| {
+            let missing = missing_between(pair[0], pair[1], interval, calendar);
+            let (&from, &last) = (missing.first()?, missing.last()?);
+            let to = if session_days(interval, calendar) {
+                last + DAY_SECS - interval.secs()
+            } else {
+                last
+            };
+            Some(Gap { from, to, missing: missing.len() })
+        })
+        .collect()
+}
+
+/// Intraday candles on a session calendar only exist during market hours,
+/// which we don't model; such data is checked for missing whole days instead.
+fn session_days(interval: Interval, calendar: TradingCalendar) -> bool {
+    interval.is_intraday() && calendar != TradingCalendar::Continuous
+}
+
+/// Expected candle (or trading day) timestamps strictly between `a` and `b`.
+fn missing_between(a: i64, b: i64, interval: Interval, calendar: TradingCalendar) -> Vec<i64> {
+    let is_trading_day = |ts: i64| {
+        DateTime::from_timestamp(ts, 0).is_some_and(|dt| calendar.is_trading_day(dt.date_naive()))
+    };
+
+    if session_days(interval, calendar) {
+        let (first_day, last_day) = (a - a.rem_euclid(DAY_SECS), b - b.rem_euclid(DAY_SECS));
+        return (first_day + DAY_SECS..last_day)
+            .step_by(DAY_SECS as usize)
+            .filter(|&day| is_trading_day(day))
+            .collect();
+    }
+
+    let step = interval.secs();
+    (a + step..b)
+        .step_by(step as usize)
+        // Weekly candles are expected every week regardless of holidays
+        .filter(|&ts| interval != Interval::OneDay || is_trading_day(ts))
+        .collect()
+}
+
+/// Merge gaps that are close enough to fetch in one provider request, so a
+/// history with many single missing days doesn't cost one request per day.
+pub fn coalesce(gaps: &[Gap], interval: Interval, max_span_candles: i64) -> Vec<Gap> {
+    let max_span = max_span_candles * interval.secs();
+    let mut merged: Vec<Gap> = Vec::new();
+    for gap in gaps {
+        match merged.last_mut() {
+            Some(last) if gap.to - last.from < max_span => {
+                last.to = gap.to;
+                last.missing += gap.missing;
+            }
+            _ => merged.push(gap.clone()),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::calendar::Exchange;
+
+    // Monday 2024-07-01 00:00 UTC
+    const MON: i64 = 1719792000;
+
+    fn day(n: i64) -> i64 {
+        MON + n * DAY_SECS
+    }
+
+    #[test]
+    fn test_continuous_daily_gap() {
+        let ts = vec![day(0), day(1), day(4), day(5)];
+        let gaps = find_gaps(&ts, Interval::OneDay, TradingCalendar::Continuous);
+        assert_eq!(gaps, vec![Gap { from: day(2), to: day(3), missing: 2 }]);
+    }
+
+    #[test]
+    fn test_stock_daily_skips_weekends_and_holidays() {
+        // Mon 1, Tue 2, Wed 3, (Thu 4 = Independence Day), Fri 5, (weekend), Mon 8
+        let ts = vec![day(0), day(1), day(2), day(4), day(7)];
+        assert!(find_gaps(&ts, Interval::OneDay, TradingCalendar::Exchange(Exchange::Nyse)).is_empty());
+        // The same data traded as 24/7 would have gaps
+        assert_eq!(find_gaps(&ts, Interval::OneDay, TradingCalendar::Continuous).len(), 2);
+        // Missing Tuesday is a real gap
+        let ts = vec![day(0), day(2)];
+        let gaps = find_gaps(&ts, Interval::OneDay, TradingCalendar::Exchange(Exchange::Nyse));
+        assert_eq!(gaps, vec![Gap { from: day(1), to: day(1), missing: 1 }]);
+    }
+
+    #[test]
+    fn test_intraday_stock_checks_whole_days() {
+        let hour = 3600;
+        // Candles Mon 14:00 and Wed 14:00: Tuesday had no session at all
+        let ts = vec![day(0) + 14 * hour, day(0) + 15 * hour, day(2) + 14 * hour];
+        let gaps = find_gaps(&ts, Interval::OneHour, TradingCalendar::Exchange(Exchange::Nyse));
+        assert_eq!(gaps, vec![Gap { from: day(1), to: day(2) - hour, missing: 1 }]);
+        // Overnight between sessions is not a gap
+        let ts = vec![day(0) + 20 * hour, day(1) + 14 * hour];
+        assert!(find_gaps(&ts, Interval::OneHour, TradingCalendar::Exchange(Exchange::Nyse)).is_empty());
+    }
+
+    #[test]
+    fn test_intraday_crypto_checks_every_candle() {
+        let hour = 3600;
+        let ts = vec![MON, MON + hour, MON + 4 * hour];
+        let gaps = find_gaps(&ts, Interval::OneHour, TradingCalendar::Continuous);
+        assert_eq!(gaps, vec![Gap { from: MON + 2 * hour, to: MON + 3 * hour, missing: 2 }]);
+    }
+
+    #[test]
+    fn test_coalesce_nearby_gaps() {
+        let gaps = vec![
+            Gap { from: day(2), to: day(2), missing: 1 },
+            Gap { from: day(5), to: day(6), missing: 2 },
+            Gap { from: day(40), to: day(40), missing: 1 },
+        ];
+        let merged = coalesce(&gaps, Interval::OneDay, 10);
+        assert_eq!(
+            merged,
+            vec![
+                Gap { from: day(2), to: day(6), missing: 3 },
+                Gap { from: day(40), to: day(40), missing: 1 },
+            ]
+        );
+    }
+}
@@ -1,2 +1,14 @@
 // Domain logic module — pure functions, no I/O
 // Will be expanded as analytics features are added
+
+pub mod adjustments;
+pub mod alerts;
+pub mod allocation;
+pub mod calendar;
+pub mod cash;
+pub mod gaps;
+pub mod quality;
+pub mod rebalance;
+pub mod reconcile;
+pub mod retention;
+pub mod validation;
@@ -0,0 +1,205 @@
+use crate::models::{OHLCVRow, PriceIssueKind};
+use std::collections::HashSet;
+
+/// Jumps beyond this many standard deviations of nearby returns count as spikes.
+pub const SPIKE_SIGMAS: f64 = 6.0;
+
+/// Returns on each side of a candle used to estimate its local volatility.
+const SPIKE_WINDOW: usize = 20;
+/// Below this many returns the volatility estimate is too noisy to judge by.
+const MIN_WINDOW: usize = 10;
+/// Volatility floor (1% per candle) so near-flat series don't flag every move.
+const MIN_SIGMA: f64 = 0.01;
+
+/// An incoming candle that failed a check, identified by its timestamp.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CandleIssue {
+    pub ts: i64,
+    pub kind: PriceIssueKind,
+    pub detail: String,
+}
+
+/// Check `incoming` candles before they are stored. `context` is stored
+/// history around them (any order); it gives spike detection its neighbors
+/// but is never flagged itself. Both must be the same asset and interval.
+pub fn check_candles(context: &[OHLCVRow], incoming: &[OHLCVRow], sigmas: f64) -> Vec<CandleIssue> {
+    let mut issues: Vec<CandleIssue> = incoming.iter().filter_map(check_row).collect();
+    let invalid: HashSet<i64> = issues.iter().map(|i| i.ts).collect();
+
+    // Incoming candles replace stored ones at the same timestamp
+    let incoming_ts: HashSet<i64> = incoming.iter().map(|r| r.ts).collect();
+    let mut series: Vec<(&OHLCVRow, bool)> = context
+        .iter()
+        .filter(|r| !incoming_ts.contains(&r.ts))
+        .map(|r| (r, false))
+        .chain(incoming.iter().filter(|r| !invalid.contains(&r.ts)).map(|r| (r, true)))
+        .collect();
+    series.sort_by_key(|(r, _)| r.ts);
+
+    let returns: Vec<f64> = series.windows(2).map(|w| (w[1].0.close / w[0].0.close).ln()).collect();
+    for i in 1..series.len().saturating_sub(1) {
+        let (row, is_new) = series[i];
+        if !is_new {
+            continue;
+        }
+        let Some(sigma) = local_sigma(&returns, i) else {
+            continue;
+        };
+        // Returns into and out of candle i
+        let (jump, revert) = (returns[i - 1], returns[i]);
+        if jump.abs() > sigmas * sigma && revert.abs() > sigmas * sigma && jump.signum() != revert.signum() {
+            issues.push(CandleIssue {
+                ts: row.ts,
+                kind: PriceIssueKind::Spike,
+                detail: format!(
+                    "Close {} moves {:+.1}% from the previous candle and {:+.1}% to the next ({:.0}σ)",
+                    row.close,
+                    (jump.exp() - 1.0) * 100.0,
+                    (revert.exp() - 1.0) * 100.0,
+                    jump.abs().min(revert.abs()) / sigma,
+                ),
+            });
+        }
+    }
+
+    issues.sort_by_key(|i| i.ts);
+    issues
+}
+
+/// Single-candle checks: positive prices and a consistent OHLC range.
+fn check_row(row: &OHLCVRow) -> Option<CandleIssue> {
+    let issue = |kind, detail| Some(CandleIssue { ts: row.ts, kind, detail });
+
+    let prices = [("open", row.open), ("high", row.high), ("low", row.low), ("close", Some(row.close))];
+    for (field, value) in prices {
+        if let Some(value) = value.filter(|v| v.is_nan() || *v <= 0.0) {
+            return issue(PriceIssueKind::NonPositive, format!("{} is {}", field, value));
+        }
+    }
+    if let Some(volume) = row.volume.filter(|v| *v < 0.0) {
+        return issue(PriceIssueKind::NonPositive, format!("volume is {}", volume));
+    }
+
+    if let (Some(high), Some(low)) = (row.high, row.low) {
+        if high < low {
+            return issue(PriceIssueKind::OhlcInconsistent, format!("high {} is below low {}", high, low));
+        }
+        for (field, value) in [("open", row.open), ("close", Some(row.close))] {
+            if let Some(value) = value.filter(|v| *v > high || *v < low) {
+                return issue(
+                    PriceIssueKind::OhlcInconsistent,
+                    format!("{} {} is outside the high-low range {}–{}", field, value, low, high),
+                );
+            }
+        }
+    }
+    None
+}
+
+/// Robust volatility of the returns around candle `i`, leaving out the two
+/// returns that touch it: 1.4826 × median absolute return estimates σ for
+/// roughly normal returns without letting other outliers inflate it.
+fn local_sigma(returns: &[f64], i: usize) -> Option<f64> {
+    let start = i.saturating_sub(1 + SPIKE_WINDOW);
+    let end = (i + 1 + SPIKE_WINDOW).min(returns.len());
+    let mut nearby: Vec<f64> = (start..end)
+        .filter(|&j| j != i - 1 && j != i)
+        .map(|j| returns[j].abs())
+        .collect();
+    if nearby.len() < MIN_WINDOW {
+        return None;
+    }
+    nearby.sort_by(f64::total_cmp);
+    Some((1.4826 * nearby[nearby.len() / 2]).max(MIN_SIGMA))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Interval;
+
+    const DAY: i64 = 86400;
+
+    fn candle(ts: i64, close: f64) -> OHLCVRow {
+        OHLCVRow {
+            id: None,
+            asset_id: "a1".to_string(),
+            interval: Interval::OneDay,
+            ts,
+            open: Some(close),
+            high: Some(close),
+            low: Some(close),
+            close,
+            volume: Some(10.0),
+            provider: None,
+        }
+    }
+
+    /// A gently zig-zagging series around 100 (±2%).
+    fn history(days: i64) -> Vec<OHLCVRow> {
+        (0..days).map(|d| candle(d * DAY, if d % 2 == 0 { 100.0 } else { 102.0 })).collect()
+    }
+
+    #[test]
+    fn test_non_positive_and_inconsistent_rows() {
+        let mut zero = candle(0, 0.0);
+        zero.open = Some(0.0);
+        let mut inverted = candle(DAY, 100.0);
+        inverted.high = Some(90.0);
+        inverted.low = Some(110.0);
+        let mut outside = candle(2 * DAY, 100.0);
+        outside.high = Some(99.0);
+        let mut negative_volume = candle(3 * DAY, 100.0);
+        negative_volume.volume = Some(-1.0);
+
+        let issues = check_candles(&[], &[zero, inverted, outside, negative_volume, candle(4 * DAY, 100.0)], SPIKE_SIGMAS);
+        let kinds: Vec<(i64, PriceIssueKind)> = issues.iter().map(|i| (i.ts, i.kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (0, PriceIssueKind::NonPositive),
+                (DAY, PriceIssueKind::OhlcInconsistent),
+                (2 * DAY, PriceIssueKind::OhlcInconsistent),
+                (3 * DAY, PriceIssueKind::NonPositive),
+            ]
+        );
+        assert_eq!(issues[0].detail, "open is 0");
+    }
+
+    #[test]
+    fn test_isolated_spike_flagged_against_stored_neighbors() {
+        let context = history(30);
+        // Incoming day 15 replaces the stored candle with a 10x print
+        let issues = check_candles(&context, &[candle(15 * DAY, 1020.0)], SPIKE_SIGMAS);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].ts, 15 * DAY);
+        assert_eq!(issues[0].kind, PriceIssueKind::Spike);
+        // Stored candles are never flagged, even next to an incoming spike
+        assert!(check_candles(&context, &[candle(15 * DAY, 102.0)], SPIKE_SIGMAS).is_empty());
+
+        // The newest candle can't be judged alone, but is once it is re-fetched
+        // together with the next one
+        let mut context = history(30);
+        context[29] = candle(29 * DAY, 1000.0);
+        let spike = context[29].clone();
+        assert!(check_candles(&context[..29], std::slice::from_ref(&spike), SPIKE_SIGMAS).is_empty());
+        let issues = check_candles(&context, &[spike, candle(30 * DAY, 100.0)], SPIKE_SIGMAS);
+        assert_eq!(issues.iter().map(|i| i.ts).collect::<Vec<_>>(), vec![29 * DAY]);
+    }
+
+    #[test]
+    fn test_level_shift_and_short_history_not_flagged() {
+        // A crash that doesn't revert is real price action
+        let mut rows = history(30);
+        for row in rows.iter_mut().skip(15) {
+            row.close /= 4.0;
+            row.open = Some(row.close);
+            row.high = Some(row.close);
+            row.low = Some(row.close);
+        }
+        assert!(check_candles(&[], &rows, SPIKE_SIGMAS).is_empty());
+        // Too few neighbors to estimate volatility
+        let rows = vec![candle(0, 100.0), candle(DAY, 1000.0), candle(2 * DAY, 100.0)];
+        assert!(check_candles(&[], &rows, SPIKE_SIGMAS).is_empty());
+    }
+}
@@ -0,0 +1,211 @@
+use crate::models::{AllocationTarget, RebalancePlan, RebalanceTrade, TxType};
+
+/// A targeted asset with its current holding and latest price.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Position {
+    pub target: AllocationTarget,
+    pub quantity: f64,
+    pub price: f64,
+}
+
+/// Buys and sells that move the targeted assets toward their weights.
+///
+/// The total is the value of the targeted positions plus `cash`; weights
+/// below 100% in sum leave the rest in cash. A no-sell position above its
+/// target keeps its value and the other targets shrink proportionally to make
+/// room. Quantities are rounded toward zero to whole lots, trades worth less
+/// than `min_trade_value` are dropped, and buys are scaled down if sells and
+/// cash can't pay for them.
+pub fn plan_rebalance(positions: &[Position], cash: f64, min_trade_value: f64) -> RebalancePlan {
+    let values: Vec<f64> = positions.iter().map(|p| p.quantity * p.price).collect();
+    let total = values.iter().sum::<f64>() + cash;
+    let targets = effective_targets(positions, &values, total);
+
+    let mut sells = Vec::new();
+    let mut buys = Vec::new();
+    for (i, position) in positions.iter().enumerate() {
+        let diff = targets[i] - values[i];
+        if diff < 0.0 && position.target.no_sell {
+            continue;
+        }
+        let quantity = round_to_lot(diff / position.price, position.target.lot_size);
+        if quantity == 0.0 || (quantity * position.price).abs() < min_trade_value {
+            continue;
+        }
+        let trade = (i, quantity);
+        if quantity < 0.0 {
+            sells.push(trade);
+        } else {
+            buys.push(trade);
+        }
+    }
+
+    let proceeds: f64 = sells.iter().map(|&(i, q)| -q * positions[i].price).sum();
+    let cost: f64 = buys.iter().map(|&(i, q)| q * positions[i].price).sum();
+    let available = (cash + proceeds).max(0.0);
+    if cost > available {
+        let scale = available / cost;
+        buys = buys
+            .into_iter()
+            .map(|(i, q)| (i, round_to_lot(q * scale, positions[i].target.lot_size)))
+            .filter(|&(i, q)| q > 0.0 && q * positions[i].price >= min_trade_value)
+            .collect();
+    }
+
+    let pct = |value: f64| if total > 0.0 { value / total * 100.0 } else { 0.0 };
+    let trades: Vec<RebalanceTrade> = sells
+        .into_iter()
+        .chain(buys)
+        .map(|(i, quantity)| {
+            let position = &positions[i];
+            RebalanceTrade {
+                asset_id: position.target.asset_id.clone(),
+                action: if quantity < 0.0 { TxType::Sell } else { TxType::Buy },
+                quantity: quantity.abs(),
+                price: position.price,
+                value: quantity.abs() * position.price,
+                current_pct: pct(values[i]),
+                target_pct: pct(targets[i]),
+            }
+        })
+        .collect();
+
+    let cash_after = cash
+        + trades
+            .iter()
+            .map(|t| if t.action == TxType::Sell { t.value } else { -t.value })
+            .sum::<f64>();
+    RebalancePlan {
+        total_value: total,
+        cash_before: cash,
+        cash_after,
+        trades,
+        unpriced_asset_ids: Vec::new(),
+    }
+}
+
+/// Target value per position. No-sell positions already above their target
+/// are frozen at their current value, and the remaining targets are scaled so
+/// the sum of targets stays the same; repeated until no other position ends
+/// up frozen.
+fn effective_targets(positions: &[Position], values: &[f64], total: f64) -> Vec<f64> {
+    let desired: Vec<f64> = positions
+        .iter()
+        .map(|p| p.target.target_pct.max(0.0) / 100.0 * total)
+        .collect();
+    let desired_total: f64 = desired.iter().sum();
+    let mut frozen = vec![false; positions.len()];
+
+    loop {
+        let frozen_value: f64 = (0..positions.len()).filter(|&i| frozen[i]).map(|i| values[i]).sum();
+        let free_desired: f64 = (0..positions.len()).filter(|&i| !frozen[i]).map(|i| desired[i]).sum();
+        let scale = if free_desired > 0.0 {
+            ((desired_total - frozen_value) / free_desired).max(0.0)
+        } else {
+            0.0
+        };
+        let targets: Vec<f64> = (0..positions.len())
+            .map(|i| if frozen[i] { values[i] } else { desired[i] * scale })
+            .collect();
+
+        let mut changed = false;
+        for (i, position) in positions.iter().enumerate() {
+            if !frozen[i] && position.target.no_sell && values[i] > targets[i] {
+                frozen[i] = true;
+                changed = true;
+            }
+        }
+        if !changed {
+            return targets;
+        }
+    }
+}
+
+fn round_to_lot(quantity: f64, lot_size: f64) -> f64 {
+    if lot_size > 0.0 {
+        (quantity / lot_size).trunc() * lot_size
+    } else {
+        quantity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(asset_id: &str, target_pct: f64, quantity: f64, price: f64) -> Position {
+        Position {
+            target: AllocationTarget {
+                asset_id: asset_id.to_string(),
+                target_pct,
+                lot_size: 0.0,
+                no_sell: false,
+            },
+            quantity,
+            price,
+        }
+    }
+
+    fn trade<'a>(plan: &'a RebalancePlan, asset_id: &str) -> Option<&'a RebalanceTrade> {
+        plan.trades.iter().find(|t| t.asset_id == asset_id)
+    }
+
+    #[test]
+    fn test_rebalance_to_targets() {
+        // 8000 in A, 2000 in B, 0 cash; target 50/50
+        let positions = vec![position("a", 50.0, 80.0, 100.0), position("b", 50.0, 20.0, 100.0)];
+        let plan = plan_rebalance(&positions, 0.0, 0.0);
+        assert_eq!(plan.total_value, 10000.0);
+        let a = trade(&plan, "a").unwrap();
+        assert_eq!(a.action, TxType::Sell);
+        assert!((a.quantity - 30.0).abs() < 1e-9);
+        assert_eq!(a.current_pct, 80.0);
+        let b = trade(&plan, "b").unwrap();
+        assert_eq!(b.action, TxType::Buy);
+        assert!((b.quantity - 30.0).abs() < 1e-9);
+        assert!(plan.cash_after.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rebalance_lots_and_minimum_value() {
+        // Cash to invest; whole shares of A, and B's small top-up is skipped
+        let mut a = position("a", 60.0, 0.0, 30.0);
+        a.target.lot_size = 1.0;
+        let b = position("b", 40.0, 3.9, 100.0);
+        let plan = plan_rebalance(&[a, b], 610.0, 50.0);
+        // Total 1000: A wants 600 → 20 shares; B wants 400, holds 390
+        let a = trade(&plan, "a").unwrap();
+        assert_eq!(a.quantity, 20.0);
+        assert!(trade(&plan, "b").is_none());
+        assert!((plan.cash_after - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rebalance_no_sell_shrinks_other_targets() {
+        // A is 70% but can't be sold; B and C share the remaining 30%
+        let mut a = position("a", 50.0, 70.0, 100.0);
+        a.target.no_sell = true;
+        let b = position("b", 25.0, 20.0, 100.0);
+        let c = position("c", 25.0, 0.0, 100.0);
+        let plan = plan_rebalance(&[a, b, c], 1000.0, 0.0);
+        assert!(trade(&plan, "a").is_none());
+        let b = trade(&plan, "b").unwrap();
+        assert_eq!(b.action, TxType::Sell);
+        assert!((b.quantity - 5.0).abs() < 1e-9);
+        assert!((b.target_pct - 15.0).abs() < 1e-9);
+        let c = trade(&plan, "c").unwrap();
+        assert!((c.quantity - 15.0).abs() < 1e-9);
+        assert!(plan.cash_after.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rebalance_scales_buys_to_available_cash() {
+        // B's sell falls under the minimum, so A's buy must fit in cash alone
+        let positions = vec![position("a", 50.0, 0.0, 10.0), position("b", 50.0, 10.2, 10.0)];
+        let plan = plan_rebalance(&positions, 98.0, 5.0);
+        assert!(trade(&plan, "b").is_none());
+        let a = trade(&plan, "a").unwrap();
+        assert!((a.value - 98.0).abs() < 1e-9);
+        assert!(plan.cash_after.abs() < 1e-9);
+    }
+}
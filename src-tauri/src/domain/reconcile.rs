@@ -0,0 +1,99 @@
+use crate::models::OHLCVRow;
+use std::collections::HashMap;
+
+/// A candle whose close differs between the stored series and a reference provider.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence {
+    pub ts: i64,
+    pub provider: Option<String>,
+    pub close: f64,
+    pub reference_close: f64,
+    /// Stored close relative to the reference, in percent.
+    pub diff_pct: f64,
+}
+
+/// Compare closes candle by candle. Returns how many candles were compared
+/// and those differing by more than `threshold_pct`. Stored candles that
+/// came from `reference_provider` itself are skipped.
+pub fn find_divergences(
+    primary: &[OHLCVRow],
+    reference: &[OHLCVRow],
+    reference_provider: &str,
+    threshold_pct: f64,
+) -> (usize, Vec<Divergence>) {
+    let reference_by_ts: HashMap<i64, f64> = reference
+        .iter()
+        .filter(|r| r.close > 0.0)
+        .map(|r| (r.ts, r.close))
+        .collect();
+
+    let mut compared = 0;
+    let mut divergences = Vec::new();
+    for row in primary {
+        if row.provider.as_deref() == Some(reference_provider) {
+            continue;
+        }
+        let Some(&reference_close) = reference_by_ts.get(&row.ts) else {
+            continue;
+        };
+        compared += 1;
+        let diff_pct = (row.close - reference_close) / reference_close * 100.0;
+        if diff_pct.abs() > threshold_pct {
+            divergences.push(Divergence {
+                ts: row.ts,
+                provider: row.provider.clone(),
+                close: row.close,
+                reference_close,
+                diff_pct,
+            });
+        }
+    }
+    (compared, divergences)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Interval;
+
+    fn candle(ts: i64, close: f64, provider: &str) -> OHLCVRow {
+        OHLCVRow {
+            id: None,
+            asset_id: "a1".to_string(),
+            interval: Interval::OneDay,
+            ts,
+            open: None,
+            high: None,
+            low: None,
+            close,
+            volume: None,
+            provider: Some(provider.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_divergences_above_threshold() {
+        let primary = vec![
+            candle(1, 100.0, "binance"),
+            candle(2, 103.0, "binance"),
+            candle(3, 97.0, "binance"),
+            candle(4, 100.0, "binance"), // no reference candle
+        ];
+        let reference = vec![candle(1, 100.5, "coingecko"), candle(2, 100.0, "coingecko"), candle(3, 100.0, "coingecko")];
+
+        let (compared, divergences) = find_divergences(&primary, &reference, "coingecko", 1.0);
+        assert_eq!(compared, 3);
+        let found: Vec<(i64, f64)> = divergences.iter().map(|d| (d.ts, d.diff_pct.round())).collect();
+        assert_eq!(found, vec![(2, 3.0), (3, -3.0)]);
+    }
+
+    #[test]
+    fn test_skips_candles_from_reference_provider() {
+        // After a Binance→CoinGecko fallback the stored series is already CoinGecko's
+        let primary = vec![candle(1, 100.0, "coingecko"), candle(2, 110.0, "binance")];
+        let reference = vec![candle(1, 90.0, "coingecko"), candle(2, 100.0, "coingecko")];
+        let (compared, divergences) = find_divergences(&primary, &reference, "coingecko", 1.0);
+        assert_eq!(compared, 1);
+        assert_eq!(divergences[0].ts, 2);
+    }
+}
@@ -0,0 +1,106 @@
+use crate::models::{Interval, OHLCVRow};
+
+/// Aggregate candles (sorted ascending) into daily UTC candles: first open,
+/// highest high, lowest low, last close and summed volume. Provenance is the
+/// provider of the day's last candle.
+pub fn downsample_daily(rows: &[OHLCVRow]) -> Vec<OHLCVRow> {
+    let mut days: Vec<OHLCVRow> = Vec::new();
+    for row in rows {
+        let day = Interval::OneDay.align(row.ts);
+        match days.last_mut() {
+            Some(daily) if daily.ts == day => {
+                daily.high = max_opt(daily.high, row.high.or(Some(row.close)));
+                daily.low = min_opt(daily.low, row.low.or(Some(row.close)));
+                daily.close = row.close;
+                daily.volume = match (daily.volume, row.volume) {
+                    (Some(a), Some(b)) => Some(a + b),
+                    (a, b) => a.or(b),
+                };
+                daily.provider = row.provider.clone();
+            }
+            _ => days.push(OHLCVRow {
+                id: None,
+                asset_id: row.asset_id.clone(),
+                interval: Interval::OneDay,
+                ts: day,
+                open: row.open.or(Some(row.close)),
+                high: row.high.or(Some(row.close)),
+                low: row.low.or(Some(row.close)),
+                close: row.close,
+                volume: row.volume,
+                provider: row.provider.clone(),
+            }),
+        }
+    }
+    days
+}
+
+fn max_opt(a: Option<f64>, b: Option<f64>) -> Option<f64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+fn min_opt(a: Option<f64>, b: Option<f64>) -> Option<f64> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: i64 = 86400;
+
+    fn candle(ts: i64, open: f64, high: f64, low: f64, close: f64, volume: f64) -> OHLCVRow {
+        OHLCVRow {
+            id: None,
+            asset_id: "a1".to_string(),
+            interval: Interval::OneHour,
+            ts,
+            open: Some(open),
+            high: Some(high),
+            low: Some(low),
+            close,
+            volume: Some(volume),
+            provider: Some("binance".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_downsample_daily() {
+        let rows = vec![
+            candle(DAY, 10.0, 12.0, 9.0, 11.0, 1.0),
+            candle(DAY + 3600, 11.0, 15.0, 10.0, 14.0, 2.0),
+            candle(DAY + 7200, 14.0, 14.5, 8.0, 13.0, 3.0),
+            candle(2 * DAY + 3600, 13.0, 13.0, 12.0, 12.5, 4.0),
+        ];
+        let days = downsample_daily(&rows);
+        assert_eq!(days.len(), 2);
+
+        let first = &days[0];
+        assert_eq!((first.ts, first.interval), (DAY, Interval::OneDay));
+        assert_eq!((first.open, first.high, first.low), (Some(10.0), Some(15.0), Some(8.0)));
+        assert_eq!((first.close, first.volume), (13.0, Some(6.0)));
+        // A day that starts mid-way still lands on midnight
+        assert_eq!(days[1].ts, 2 * DAY);
+    }
+
+    #[test]
+    fn test_downsample_close_only_rows() {
+        let mut a = candle(DAY, 0.0, 0.0, 0.0, 100.0, 0.0);
+        let mut b = candle(DAY + 60, 0.0, 0.0, 0.0, 90.0, 0.0);
+        for row in [&mut a, &mut b] {
+            row.open = None;
+            row.high = None;
+            row.low = None;
+            row.volume = None;
+        }
+        let days = downsample_daily(&[a, b]);
+        assert_eq!((days[0].open, days[0].high, days[0].low), (Some(100.0), Some(100.0), Some(90.0)));
+        assert_eq!(days[0].volume, None);
+    }
+}
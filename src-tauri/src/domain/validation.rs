@@ -0,0 +1,169 @@
+use crate::models::{FieldError, Transaction, TxType};
+use std::fmt;
+
+/// How far past the clock a transaction may be dated, so a trade entered late
+/// in the day in an earlier time zone isn't rejected.
+const FUTURE_SLACK_SECS: i64 = 86_400;
+
+/// Quantities within this of zero count as a closed position.
+const EPSILON: f64 = 1e-9;
+
+/// Field-level problems with a transaction's values. `ts` 0 is the
+/// "unknown date" imports use and is accepted.
+pub fn validate_transaction(quantity: f64, price_usd: f64, ts: i64, fee: f64, now: i64) -> Vec<FieldError> {
+    let mut errors = Vec::new();
+    if !quantity.is_finite() || quantity <= 0.0 {
+        errors.push(FieldError::new("quantity", "Quantity must be a positive number"));
+    }
+    if !price_usd.is_finite() || price_usd <= 0.0 {
+        errors.push(FieldError::new("price_usd", "Price must be a positive number"));
+    }
+    if ts < 0 {
+        errors.push(FieldError::new("ts", "Date is invalid"));
+    } else if ts > now + FUTURE_SLACK_SECS {
+        errors.push(FieldError::new("ts", "Date can't be in the future"));
+    }
+    if !fee.is_finite() || fee < 0.0 {
+        errors.push(FieldError::new("fee", "Fee must be zero or positive"));
+    }
+    errors
+}
+
+/// Rejected input, carried through `anyhow` so commands can still report the
+/// fields (see `CommandError`'s conversion). `code` is `invalid_input`,
+/// `oversell` or `period_closed`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    pub code: &'static str,
+    pub fields: Vec<FieldError>,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let messages: Vec<&str> = self.fields.iter().map(|e| e.message.as_str()).collect();
+        write!(f, "{}", messages.join("; "))
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+/// A sell that takes the running position below zero.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Oversell {
+    pub transaction_id: String,
+    pub ts: i64,
+    /// Position right after the sell (negative).
+    pub balance: f64,
+}
+
+/// Replay live transactions in date order and return the first sell that
+/// leaves the position negative and the one that leaves it lowest. On the
+/// same date buys count first, so a day's round trip entered in any order is
+/// fine.
+fn replay(transactions: &[Transaction]) -> Option<(Oversell, Oversell)> {
+    let mut ordered: Vec<&Transaction> = transactions.iter().filter(|t| t.deleted_at.is_none()).collect();
+    ordered.sort_by_key(|t| (t.ts, t.tx_type == TxType::Sell));
+
+    let mut balance = 0.0;
+    let mut found: Option<(Oversell, Oversell)> = None;
+    for tx in ordered {
+        match tx.tx_type {
+            TxType::Buy => balance += tx.quantity,
+            TxType::Sell => {
+                balance -= tx.quantity;
+                if balance < -EPSILON {
+                    let oversell = Oversell { transaction_id: tx.id.clone(), ts: tx.ts, balance };
+                    match &mut found {
+                        None => found = Some((oversell.clone(), oversell)),
+                        Some((_, lowest)) if balance < lowest.balance => *lowest = oversell,
+                        Some(_) => {}
+                    }
+                }
+            }
+        }
+    }
+    found
+}
+
+/// The oversell to report when going from `before` to `after` makes the
+/// position worse: a first oversell where there was none, or one that comes
+/// earlier or goes deeper, or a lower low than before. Changes that leave an
+/// already negative history no worse, such as fixing a price, pass.
+pub fn worsened_oversell(before: &[Transaction], after: &[Transaction]) -> Option<Oversell> {
+    let (first, lowest) = replay(after)?;
+    let Some((was_first, was_lowest)) = replay(before) else {
+        return Some(first);
+    };
+    if first.ts < was_first.ts || (first.ts == was_first.ts && first.balance < was_first.balance - EPSILON) {
+        return Some(first);
+    }
+    (lowest.balance < was_lowest.balance - EPSILON).then_some(lowest)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(id: &str, tx_type: TxType, quantity: f64, ts: i64) -> Transaction {
+        Transaction {
+            id: id.to_string(),
+            asset_id: "a".to_string(),
+            tx_type,
+            quantity,
+            price_usd: 1.0,
+            ts,
+            notes: None,
+            created_at: 0,
+            deleted_at: None,
+            locked_at: None,
+            fee: 0.0,
+            account_id: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_transaction() {
+        let now = 1_700_000_000;
+        assert!(validate_transaction(1.0, 10.0, now, 0.0, now).is_empty());
+        assert!(validate_transaction(1.0, 10.0, 0, 0.0, now).is_empty());
+
+        let fields: Vec<String> = validate_transaction(-1.0, 0.0, now + 2 * 86_400, f64::NAN, now)
+            .into_iter()
+            .map(|e| e.field)
+            .collect();
+        assert_eq!(fields, vec!["quantity", "price_usd", "ts", "fee"]);
+    }
+
+    #[test]
+    fn test_oversell_is_chronological() {
+        // Entered out of order, but the sell happens before the second buy
+        let history = vec![
+            tx("b2", TxType::Buy, 5.0, 300),
+            tx("b1", TxType::Buy, 2.0, 100),
+            tx("s1", TxType::Sell, 3.0, 200),
+        ];
+        let oversell = worsened_oversell(&[], &history).unwrap();
+        assert_eq!(oversell.transaction_id, "s1");
+        assert_eq!(oversell.balance, -1.0);
+
+        // Same-day buy and sell, and selling out exactly
+        let history = vec![tx("s", TxType::Sell, 2.0, 100), tx("b", TxType::Buy, 2.0, 100)];
+        assert_eq!(worsened_oversell(&[], &history), None);
+
+        // Already short from the first sell: fixing that is fine, selling more is not
+        let short = vec![tx("b", TxType::Buy, 1.0, 100), tx("s1", TxType::Sell, 2.0, 200)];
+        let mut fixed = short.clone();
+        fixed.push(tx("b2", TxType::Buy, 0.5, 150));
+        assert_eq!(worsened_oversell(&short, &fixed), None);
+        let mut deeper = short.clone();
+        deeper.push(tx("s2", TxType::Sell, 5.0, 300));
+        assert_eq!(worsened_oversell(&short, &deeper).unwrap().transaction_id, "s2");
+        let mut earlier = short.clone();
+        earlier.push(tx("s0", TxType::Sell, 3.0, 50));
+        assert_eq!(worsened_oversell(&short, &earlier).unwrap().transaction_id, "s0");
+
+        let mut deleted = tx("s2", TxType::Sell, 9.0, 400);
+        deleted.deleted_at = Some(500);
+        assert_eq!(worsened_oversell(&[], &[tx("b", TxType::Buy, 1.0, 100), deleted]), None);
+    }
+}
@@ -3,7 +3,10 @@ mod db;
 mod domain;
 mod models;
 mod providers;
+mod rate_limit;
+mod scheduler;
 mod state;
+mod streaming;
 
 use state::AppState;
 use tauri::Manager;
@@ -26,14 +29,34 @@ pub fn run() {
             commands::auth::check_first_run,
             commands::auth::setup_db,
             commands::auth::unlock_db,
+            commands::alerts::list_alerts,
+            commands::alerts::create_alert,
+            commands::alerts::update_alert,
+            commands::alerts::delete_alert,
             commands::assets::add_asset,
             commands::assets::remove_asset,
             commands::assets::list_assets,
             commands::assets::list_all_assets,
             commands::assets::purge_asset,
+            commands::assets::list_market_status,
+            commands::assets::list_provider_symbols,
+            commands::assets::set_provider_symbol,
+            commands::assets::list_asset_metadata,
+            commands::assets::update_asset_metadata,
             commands::prices::fetch_prices,
             commands::prices::refresh_asset,
             commands::prices::list_cache_meta,
+            commands::prices::fetch_quotes,
+            commands::prices::scan_price_gaps,
+            commands::prices::backfill_price_gaps,
+            commands::prices::list_corporate_actions,
+            commands::prices::reconcile_prices,
+            commands::prices::refresh_asset_profile,
+            commands::price_issues::list_price_issues,
+            commands::price_issues::accept_price_issue,
+            commands::price_issues::reject_price_issue,
+            commands::storage::get_storage_report,
+            commands::storage::optimize_database,
             commands::settings::save_api_key,
             commands::settings::has_api_key,
             commands::settings::remove_api_key,
@@ -43,10 +66,48 @@ pub fn run() {
             commands::transactions::list_transactions,
             commands::transactions::update_transaction,
             commands::transactions::delete_transaction,
+            commands::transactions::bulk_add_transactions,
+            commands::transactions::bulk_delete_transactions,
+            commands::transactions::bulk_lock_transactions,
+            commands::transactions::bulk_edit_transactions,
             commands::transactions::lock_transaction,
+            commands::transactions::lock_transactions_before,
+            commands::transactions::reopen_period,
+            commands::transactions::list_period_closes,
             commands::transactions::unlock_transaction,
+            commands::transactions::list_transaction_history,
+            commands::transactions::revert_transaction_change,
             commands::transactions::get_holding_summary,
+            commands::trash::list_trash,
+            commands::trash::restore_deleted_asset,
+            commands::trash::restore_transaction,
+            commands::trash::purge_transaction,
+            commands::trash::empty_trash,
+            commands::cash::create_account,
+            commands::cash::list_accounts,
+            commands::cash::delete_account,
+            commands::cash::add_cash_entry,
+            commands::cash::delete_cash_entry,
+            commands::cash::list_cash_ledger,
+            commands::cash::get_cash_balances,
+            commands::portfolio::get_allocation,
+            commands::portfolio::list_allocation_targets,
+            commands::portfolio::set_allocation_target,
+            commands::portfolio::delete_allocation_target,
+            commands::portfolio::plan_rebalance,
+            commands::watchlists::list_watchlists,
+            commands::watchlists::create_watchlist,
+            commands::watchlists::rename_watchlist,
+            commands::watchlists::delete_watchlist,
+            commands::watchlists::reorder_watchlists,
+            commands::watchlists::add_watchlist_item,
+            commands::watchlists::watch_symbol,
+            commands::watchlists::update_watchlist_item_notes,
+            commands::watchlists::remove_watchlist_item,
+            commands::watchlists::reorder_watchlist_items,
             commands::search::search_symbols,
+            commands::streaming::start_price_stream,
+            commands::streaming::stop_price_stream,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
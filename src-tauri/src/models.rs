@@ -1,30 +1,74 @@
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-#[serde(rename_all = "lowercase")]
+#[serde(rename_all = "snake_case")]
 pub enum AssetType {
     Stock,
+    Etf,
+    MutualFund,
+    Bond,
+    /// A currency pair such as "EUR/USD".
+    Forex,
+    Index,
     Crypto,
     Commodity,
+    /// A cash balance, worth 1 per unit of its currency. Never priced by a provider.
+    Cash,
 }
 
 impl AssetType {
     pub fn as_str(&self) -> &'static str {
         match self {
             AssetType::Stock => "stock",
+            AssetType::Etf => "etf",
+            AssetType::MutualFund => "mutual_fund",
+            AssetType::Bond => "bond",
+            AssetType::Forex => "forex",
+            AssetType::Index => "index",
             AssetType::Crypto => "crypto",
             AssetType::Commodity => "commodity",
+            AssetType::Cash => "cash",
         }
     }
 
     pub fn from_str(s: &str) -> anyhow::Result<Self> {
         match s {
             "stock" => Ok(AssetType::Stock),
+            "etf" => Ok(AssetType::Etf),
+            "mutual_fund" => Ok(AssetType::MutualFund),
+            "bond" => Ok(AssetType::Bond),
+            "forex" => Ok(AssetType::Forex),
+            "index" => Ok(AssetType::Index),
             "crypto" => Ok(AssetType::Crypto),
             "commodity" => Ok(AssetType::Commodity),
+            "cash" => Ok(AssetType::Cash),
             _ => anyhow::bail!("Invalid asset type: {}", s),
         }
     }
+
+    /// Whether the type has splits and dividends to fetch.
+    pub fn has_corporate_actions(&self) -> bool {
+        matches!(self, AssetType::Stock | AssetType::Etf | AssetType::MutualFund)
+    }
+
+    /// Allocation bucket used until the user or a provider profile sets one.
+    pub fn default_asset_class(&self) -> &'static str {
+        match self {
+            AssetType::Stock | AssetType::Etf | AssetType::MutualFund | AssetType::Index => "Equity",
+            AssetType::Bond => "Fixed Income",
+            AssetType::Forex | AssetType::Cash => "Cash",
+            AssetType::Crypto => "Crypto",
+            AssetType::Commodity => "Commodity",
+        }
+    }
+
+    /// Whether the type trades on a listing exchange with its sessions and holidays.
+    pub fn is_exchange_listed(&self) -> bool {
+        matches!(
+            self,
+            AssetType::Stock | AssetType::Etf | AssetType::MutualFund | AssetType::Bond | AssetType::Index
+        )
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,29 +78,390 @@ pub struct Asset {
     pub name: String,
     pub asset_type: AssetType,
     pub currency: String,
+    /// Listing venue ("NYSE", "TSX", ...). Part of the asset's identity with
+    /// symbol and type, so one ticker can be tracked on several exchanges.
+    pub exchange: Option<String>,
     pub added_at: i64,
     pub deleted_at: Option<i64>,
 }
 
+/// Descriptive fields used for allocation breakdowns. User edits and provider
+/// profiles both write here; `tags` double as user-defined groups.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AssetMetadata {
+    pub asset_id: String,
+    pub sector: Option<String>,
+    pub industry: Option<String>,
+    pub country: Option<String>,
+    /// Falls back to the asset type's default class when unset.
+    pub asset_class: Option<String>,
+    pub tags: Vec<String>,
+    /// Sells may take the position below zero.
+    pub allow_short: bool,
+}
+
+/// Company or fund profile as reported by a provider.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AssetProfile {
+    pub sector: Option<String>,
+    pub industry: Option<String>,
+    pub country: Option<String>,
+    pub asset_class: Option<String>,
+}
+
+/// Candle resolution for stored and fetched price history.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, Default)]
+pub enum Interval {
+    #[serde(rename = "1m")]
+    OneMinute,
+    #[serde(rename = "5m")]
+    FiveMinutes,
+    #[serde(rename = "1h")]
+    OneHour,
+    #[serde(rename = "4h")]
+    FourHours,
+    #[default]
+    #[serde(rename = "1d")]
+    OneDay,
+    #[serde(rename = "1w")]
+    OneWeek,
+}
+
+impl Interval {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Interval::OneMinute => "1m",
+            Interval::FiveMinutes => "5m",
+            Interval::OneHour => "1h",
+            Interval::FourHours => "4h",
+            Interval::OneDay => "1d",
+            Interval::OneWeek => "1w",
+        }
+    }
+
+    pub fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "1m" => Ok(Interval::OneMinute),
+            "5m" => Ok(Interval::FiveMinutes),
+            "1h" => Ok(Interval::OneHour),
+            "4h" => Ok(Interval::FourHours),
+            "1d" => Ok(Interval::OneDay),
+            "1w" => Ok(Interval::OneWeek),
+            _ => anyhow::bail!("Invalid interval: {}", s),
+        }
+    }
+
+    /// Length of one candle in seconds.
+    pub fn secs(&self) -> i64 {
+        match self {
+            Interval::OneMinute => 60,
+            Interval::FiveMinutes => 300,
+            Interval::OneHour => 3600,
+            Interval::FourHours => 14_400,
+            Interval::OneDay => 86_400,
+            Interval::OneWeek => 604_800,
+        }
+    }
+
+    pub fn is_intraday(&self) -> bool {
+        self.secs() < 86_400
+    }
+
+    /// Snap a Unix timestamp to the start of its candle (UTC).
+    /// Weekly candles start on Monday, matching Binance and Twelve Data.
+    pub fn align(&self, ts: i64) -> i64 {
+        match self {
+            // 1970-01-01 was a Thursday; the first Monday is 4 days later.
+            Interval::OneWeek => ts - (ts - 4 * 86_400).rem_euclid(604_800),
+            _ => ts - ts.rem_euclid(self.secs()),
+        }
+    }
+
+    /// How long rows at this resolution are kept. `None` means forever.
+    /// Older intraday candles are folded into daily ones so a few hourly
+    /// charts don't bloat the DB.
+    pub fn retention_secs(&self) -> Option<i64> {
+        match self {
+            Interval::OneMinute => Some(7 * 86_400),
+            Interval::FiveMinutes => Some(30 * 86_400),
+            Interval::OneHour => Some(180 * 86_400),
+            Interval::FourHours => Some(730 * 86_400),
+            Interval::OneDay | Interval::OneWeek => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OHLCVRow {
     pub id: Option<i64>,
     pub asset_id: String,
+    #[serde(default)]
+    pub interval: Interval,
     pub ts: i64,
     pub open: Option<f64>,
     pub high: Option<f64>,
     pub low: Option<f64>,
     pub close: f64,
     pub volume: Option<f64>,
+    /// Provider the candle came from; `None` for rows stored before provenance was tracked.
+    #[serde(default)]
+    pub provider: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PriceCacheMeta {
     pub asset_id: String,
+    pub interval: Interval,
     pub provider: String,
     pub last_fetched: i64,
 }
 
+/// A live price update pushed to the frontend by the streaming subsystem,
+/// also returned by batch quote fetches.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PriceTick {
+    pub asset_id: String,
+    pub symbol: String,
+    pub price: f64,
+    pub ts: i64,
+    pub provider: String,
+}
+
+/// A run of missing candles in stored history, found by the gap scan.
+/// `from`/`to` are the first and last missing candle timestamps.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PriceGap {
+    pub asset_id: String,
+    pub symbol: String,
+    pub interval: Interval,
+    pub from: i64,
+    pub to: i64,
+    pub missing: usize,
+}
+
+/// A candle whose stored close differs from a second provider's.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceDivergence {
+    pub ts: i64,
+    /// Provider of the stored candle.
+    pub provider: Option<String>,
+    pub close: f64,
+    pub reference_close: f64,
+    /// Stored close relative to the reference, in percent.
+    pub diff_pct: f64,
+}
+
+/// Result of comparing an asset's stored history against another provider.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconciliationReport {
+    pub asset_id: String,
+    pub interval: Interval,
+    pub reference_provider: String,
+    pub from: i64,
+    pub to: i64,
+    /// Candles present in both series (and not already from the reference provider).
+    pub compared: usize,
+    pub threshold_pct: f64,
+    pub divergences: Vec<PriceDivergence>,
+}
+
+/// Why an incoming candle was quarantined instead of stored.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PriceIssueKind {
+    /// A zero or negative price, or negative volume.
+    NonPositive,
+    /// High below low, or open/close outside the high-low range.
+    OhlcInconsistent,
+    /// An isolated jump away from both neighbors that reverts straight back.
+    Spike,
+}
+
+impl PriceIssueKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PriceIssueKind::NonPositive => "non_positive",
+            PriceIssueKind::OhlcInconsistent => "ohlc_inconsistent",
+            PriceIssueKind::Spike => "spike",
+        }
+    }
+
+    pub fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "non_positive" => Ok(PriceIssueKind::NonPositive),
+            "ohlc_inconsistent" => Ok(PriceIssueKind::OhlcInconsistent),
+            "spike" => Ok(PriceIssueKind::Spike),
+            _ => anyhow::bail!("Invalid price issue kind: {}", s),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PriceIssueStatus {
+    /// Quarantined, waiting for review.
+    Pending,
+    /// Reviewed as genuine and stored in price history.
+    Accepted,
+    /// Reviewed as bad data; the same candle is dropped if fetched again.
+    Rejected,
+}
+
+impl PriceIssueStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PriceIssueStatus::Pending => "pending",
+            PriceIssueStatus::Accepted => "accepted",
+            PriceIssueStatus::Rejected => "rejected",
+        }
+    }
+
+    pub fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "pending" => Ok(PriceIssueStatus::Pending),
+            "accepted" => Ok(PriceIssueStatus::Accepted),
+            "rejected" => Ok(PriceIssueStatus::Rejected),
+            _ => anyhow::bail!("Invalid price issue status: {}", s),
+        }
+    }
+}
+
+/// A suspicious candle held out of `historical_prices` by the ingest checks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriceIssue {
+    pub id: i64,
+    pub candle: OHLCVRow,
+    pub kind: PriceIssueKind,
+    pub detail: String,
+    pub status: PriceIssueStatus,
+    pub detected_at: i64,
+    pub resolved_at: Option<i64>,
+}
+
+/// Progress of a background refresh run, emitted once per asset and once when done.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshProgress {
+    pub completed: usize,
+    pub total: usize,
+    pub asset_id: Option<String>,
+    pub symbol: Option<String>,
+    pub error: Option<String>,
+    pub done: bool,
+}
+
+/// Rows and estimated disk usage of one asset's price data.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AssetStorage {
+    pub asset_id: String,
+    pub symbol: String,
+    /// Soft-deleted assets keep their data until purged.
+    pub deleted: bool,
+    pub price_rows: i64,
+    /// Of `price_rows`, candles below daily resolution.
+    pub intraday_rows: i64,
+    pub reference_rows: i64,
+    /// Share of the price tables' pages, by row count.
+    pub bytes: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageReport {
+    /// Size of the database file.
+    pub total_bytes: i64,
+    /// Pages that are allocated but unused; `VACUUM` returns them.
+    pub free_bytes: i64,
+    /// Largest first.
+    pub assets: Vec<AssetStorage>,
+    /// Price rows whose asset no longer exists.
+    pub orphan_rows: i64,
+}
+
+/// What `optimize_database` did.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OptimizeResult {
+    /// Daily candles created from intraday candles past retention.
+    pub daily_rows_added: usize,
+    /// Intraday candles past retention removed.
+    pub intraday_rows_removed: usize,
+    pub orphan_rows_removed: usize,
+    pub bytes_before: i64,
+    pub bytes_after: i64,
+}
+
+/// Whether an asset's market is in session, for display.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarketStatus {
+    pub asset_id: String,
+    /// Exchange code ("NYSE", "TSX", "LSE", "XETRA"), "24/7" or "Weekdays".
+    pub market: String,
+    pub is_open: bool,
+    /// Start of the next session; `None` for markets that never close.
+    pub next_open: Option<i64>,
+    /// End of the current (or next) session; `None` for markets that never close.
+    pub next_close: Option<i64>,
+}
+
+/// Which corrections `fetch_prices` applies to stored (raw) candles.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PriceAdjustment {
+    /// Prices as traded.
+    #[default]
+    Raw,
+    /// Earlier candles scaled by later splits, so splits don't show as crashes.
+    Split,
+    /// Split-adjusted and with dividends reinvested, for total-return comparisons.
+    Total,
+}
+
+impl PriceAdjustment {
+    pub fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "raw" => Ok(PriceAdjustment::Raw),
+            "split" => Ok(PriceAdjustment::Split),
+            "total" => Ok(PriceAdjustment::Total),
+            _ => anyhow::bail!("Invalid price adjustment: {}", s),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum CorporateActionType {
+    Split,
+    Dividend,
+}
+
+impl CorporateActionType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CorporateActionType::Split => "split",
+            CorporateActionType::Dividend => "dividend",
+        }
+    }
+
+    pub fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "split" => Ok(CorporateActionType::Split),
+            "dividend" => Ok(CorporateActionType::Dividend),
+            _ => anyhow::bail!("Invalid corporate action type: {}", s),
+        }
+    }
+}
+
+/// A split or cash dividend taking effect at `ts` (00:00 UTC of the ex-date).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CorporateAction {
+    pub id: Option<i64>,
+    pub asset_id: String,
+    pub action_type: CorporateActionType,
+    pub ts: i64,
+    /// Splits: shares held after per share before (4.0 for a 4-for-1 split).
+    /// Dividends: cash per share, as paid at the time.
+    pub value: f64,
+    pub provider: String,
+}
+
 #[derive(Debug, Clone)]
 pub struct DateRange {
     pub from: i64,
@@ -87,7 +492,7 @@ impl TxType {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Transaction {
     pub id: String,
     pub asset_id: String,
@@ -99,6 +504,409 @@ pub struct Transaction {
     pub created_at: i64,
     pub deleted_at: Option<i64>,
     pub locked_at: Option<i64>,
+    /// Commission in USD: added to a buy's cost, taken from a sell's proceeds.
+    pub fee: f64,
+    /// Account whose cash pays for a buy or receives a sell. `None` leaves cash untouched.
+    pub account_id: Option<String>,
+}
+
+/// One row of a bulk insert.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NewTransaction {
+    pub asset_id: String,
+    pub tx_type: TxType,
+    pub quantity: f64,
+    pub price_usd: f64,
+    pub ts: i64,
+    pub notes: Option<String>,
+    #[serde(default)]
+    pub fee: f64,
+    pub account_id: Option<String>,
+}
+
+/// Live transactions matching every given condition. Date bounds are
+/// inclusive; `created_*` narrow down rows entered in one sitting, e.g. a bad
+/// import.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TransactionFilter {
+    pub asset_id: Option<String>,
+    pub tx_type: Option<TxType>,
+    pub from_ts: Option<i64>,
+    pub to_ts: Option<i64>,
+    pub created_from: Option<i64>,
+    pub created_to: Option<i64>,
+}
+
+/// The transactions a bulk operation applies to.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionSelection {
+    Ids(Vec<String>),
+    Filter(TransactionFilter),
+}
+
+/// Changes a bulk edit makes to every selected transaction. Unset fields are
+/// left alone; blank `notes` clears them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct TransactionEdit {
+    /// Move the transactions to this asset.
+    pub asset_id: Option<String>,
+    /// Seconds to add to each date, negative to move them earlier. Unknown
+    /// dates (`ts` 0) stay unknown.
+    pub shift_secs: Option<i64>,
+    pub notes: Option<String>,
+}
+
+impl TransactionEdit {
+    /// `tx` with the edit applied, or a `shift_secs` error when the shifted
+    /// date would overflow.
+    pub fn apply(&self, tx: &Transaction) -> Result<Transaction, FieldError> {
+        let mut edited = tx.clone();
+        if let Some(asset_id) = &self.asset_id {
+            edited.asset_id = asset_id.clone();
+        }
+        if let Some(shift) = self.shift_secs.filter(|_| tx.ts != 0) {
+            edited.ts = tx
+                .ts
+                .checked_add(shift)
+                .ok_or_else(|| FieldError::new("shift_secs", "Date shift is out of range"))?;
+        }
+        if let Some(notes) = &self.notes {
+            let notes = notes.trim();
+            edited.notes = (!notes.is_empty()).then(|| notes.to_string());
+        }
+        Ok(edited)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Insert,
+    Update,
+    /// Soft delete.
+    Delete,
+    /// Undo of a soft delete.
+    Restore,
+    Lock,
+    Unlock,
+    /// Hard delete, e.g. when its asset is purged.
+    Purge,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Insert => "insert",
+            AuditAction::Update => "update",
+            AuditAction::Delete => "delete",
+            AuditAction::Restore => "restore",
+            AuditAction::Lock => "lock",
+            AuditAction::Unlock => "unlock",
+            AuditAction::Purge => "purge",
+        }
+    }
+
+    pub fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "insert" => Ok(AuditAction::Insert),
+            "update" => Ok(AuditAction::Update),
+            "delete" => Ok(AuditAction::Delete),
+            "restore" => Ok(AuditAction::Restore),
+            "lock" => Ok(AuditAction::Lock),
+            "unlock" => Ok(AuditAction::Unlock),
+            "purge" => Ok(AuditAction::Purge),
+            _ => anyhow::bail!("Invalid audit action: {}", s),
+        }
+    }
+
+    /// The action that turns `before` into `after`.
+    pub fn between(before: Option<&Transaction>, after: Option<&Transaction>) -> Self {
+        let (Some(b), Some(a)) = (before, after) else {
+            return if before.is_none() { AuditAction::Insert } else { AuditAction::Purge };
+        };
+        let only_lock_changed = Transaction { locked_at: b.locked_at, ..a.clone() } == *b;
+        match (b.deleted_at, a.deleted_at) {
+            (None, Some(_)) => AuditAction::Delete,
+            (Some(_), None) => AuditAction::Restore,
+            _ if only_lock_changed && a.locked_at.is_some() => AuditAction::Lock,
+            _ if only_lock_changed => AuditAction::Unlock,
+            _ => AuditAction::Update,
+        }
+    }
+}
+
+/// One change to a transaction. The log is append-only; `before` is `None`
+/// for an insert and `after` is `None` for a purge.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AuditEntry {
+    pub id: i64,
+    pub transaction_id: String,
+    pub asset_id: String,
+    pub action: AuditAction,
+    pub before: Option<Transaction>,
+    pub after: Option<Transaction>,
+    pub ts: i64,
+    /// The entry this change reverted, if it was a revert.
+    pub reverts: Option<i64>,
+    /// Why the change was made; required to unlock a transaction.
+    pub reason: Option<String>,
+}
+
+/// The books closed before `closed_before`, for one asset or (`asset_id`
+/// `None`) all. No transaction may be added or moved into a closed period
+/// until it is reopened.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PeriodClose {
+    pub id: i64,
+    pub asset_id: Option<String>,
+    pub closed_before: i64,
+    pub closed_at: i64,
+    pub reopened_at: Option<i64>,
+    /// Why the period was reopened; required to reopen it.
+    pub reopen_reason: Option<String>,
+}
+
+/// One rejected input field and why, e.g. `quantity`: "Quantity must be positive".
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FieldError {
+    pub field: String,
+    pub message: String,
+}
+
+impl FieldError {
+    pub fn new(field: &str, message: impl Into<String>) -> Self {
+        Self { field: field.to_string(), message: message.into() }
+    }
+}
+
+/// Soft-deleted assets and transactions that can still be restored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Trash {
+    pub assets: Vec<Asset>,
+    pub transactions: Vec<Transaction>,
+}
+
+/// How much a purge of the trash removed for good.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TrashPurge {
+    pub assets: u64,
+    pub transactions: u64,
+}
+
+/// A brokerage or bank account holding cash.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Account {
+    pub id: String,
+    pub name: String,
+    pub currency: String,
+    pub created_at: i64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CashEntryKind {
+    Deposit,
+    Withdrawal,
+    Dividend,
+    /// Derived from a transaction with an account; not stored in the ledger.
+    Buy,
+    /// Derived from a transaction with an account; not stored in the ledger.
+    Sell,
+}
+
+impl CashEntryKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CashEntryKind::Deposit => "deposit",
+            CashEntryKind::Withdrawal => "withdrawal",
+            CashEntryKind::Dividend => "dividend",
+            CashEntryKind::Buy => "buy",
+            CashEntryKind::Sell => "sell",
+        }
+    }
+
+    pub fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "deposit" => Ok(CashEntryKind::Deposit),
+            "withdrawal" => Ok(CashEntryKind::Withdrawal),
+            "dividend" => Ok(CashEntryKind::Dividend),
+            "buy" => Ok(CashEntryKind::Buy),
+            "sell" => Ok(CashEntryKind::Sell),
+            _ => anyhow::bail!("Invalid cash entry kind: {}", s),
+        }
+    }
+}
+
+/// One movement of cash in an account. `amount` is signed: credits are
+/// positive, debits negative.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CashLedgerEntry {
+    pub id: String,
+    pub account_id: String,
+    pub kind: CashEntryKind,
+    pub amount: f64,
+    pub currency: String,
+    pub ts: i64,
+    pub asset_id: Option<String>,
+    /// Set for buy and sell entries, which mirror a transaction.
+    pub transaction_id: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CashBalance {
+    pub account_id: String,
+    pub account_name: String,
+    pub currency: String,
+    pub balance: f64,
+}
+
+/// One bucket of an allocation breakdown.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AllocationSlice {
+    /// Asset id, asset type, tag, sector or account id; empty for "none".
+    pub key: String,
+    pub label: String,
+    pub value: f64,
+    pub pct: f64,
+}
+
+/// Current portfolio value split several ways. Values are in USD at the
+/// latest stored price; USD cash balances count as "Cash".
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AllocationReport {
+    pub total_value: f64,
+    pub by_asset: Vec<AllocationSlice>,
+    pub by_type: Vec<AllocationSlice>,
+    pub by_tag: Vec<AllocationSlice>,
+    pub by_sector: Vec<AllocationSlice>,
+    pub by_account: Vec<AllocationSlice>,
+}
+
+/// The weight the user wants an asset to have, and how it may be traded.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AllocationTarget {
+    pub asset_id: String,
+    /// Percent of the rebalanced total (targeted assets plus cash).
+    pub target_pct: f64,
+    /// Trades are whole multiples of this quantity; 0 allows any fraction.
+    pub lot_size: f64,
+    /// Never sell this asset to reach the target.
+    pub no_sell: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RebalanceTrade {
+    pub asset_id: String,
+    pub action: TxType,
+    pub quantity: f64,
+    pub price: f64,
+    pub value: f64,
+    pub current_pct: f64,
+    /// Target after constraints; can differ from the stored target when
+    /// no-sell positions are overweight.
+    pub target_pct: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RebalancePlan {
+    /// Value of the targeted assets plus the cash available to invest.
+    pub total_value: f64,
+    pub cash_before: f64,
+    pub cash_after: f64,
+    pub trades: Vec<RebalanceTrade>,
+    /// Targeted assets left out because they have no stored price.
+    pub unpriced_asset_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertKind {
+    PriceAbove,
+    PriceBelow,
+    /// Absolute % change of the close over `window_days`, either direction.
+    PctMove,
+    /// % below the highest close over `window_days` (all stored history if unset).
+    Drawdown,
+    PositionAbove,
+    PositionBelow,
+}
+
+impl AlertKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AlertKind::PriceAbove => "price_above",
+            AlertKind::PriceBelow => "price_below",
+            AlertKind::PctMove => "pct_move",
+            AlertKind::Drawdown => "drawdown",
+            AlertKind::PositionAbove => "position_above",
+            AlertKind::PositionBelow => "position_below",
+        }
+    }
+
+    pub fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "price_above" => Ok(AlertKind::PriceAbove),
+            "price_below" => Ok(AlertKind::PriceBelow),
+            "pct_move" => Ok(AlertKind::PctMove),
+            "drawdown" => Ok(AlertKind::Drawdown),
+            "position_above" => Ok(AlertKind::PositionAbove),
+            "position_below" => Ok(AlertKind::PositionBelow),
+            _ => anyhow::bail!("Invalid alert kind: {}", s),
+        }
+    }
+}
+
+/// A threshold on one asset. An alert fires once when its condition becomes
+/// true and re-arms after the condition clears.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Alert {
+    pub id: String,
+    pub asset_id: String,
+    pub kind: AlertKind,
+    /// Price or position value in USD, or a percentage for `PctMove` and `Drawdown`.
+    pub threshold: f64,
+    pub window_days: Option<i64>,
+    pub enabled: bool,
+    /// The condition held at the last evaluation.
+    pub triggered: bool,
+    pub last_triggered_at: Option<i64>,
+    pub created_at: i64,
+}
+
+/// Payload of the alert event, sent once per firing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertTriggered {
+    pub alert_id: String,
+    pub asset_id: String,
+    pub symbol: String,
+    pub kind: AlertKind,
+    pub threshold: f64,
+    /// The observed price, value or percentage that crossed the threshold.
+    pub value: f64,
+    pub message: String,
+    pub ts: i64,
+}
+
+/// An asset on a watchlist, with the user's research notes.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WatchlistItem {
+    pub asset_id: String,
+    pub position: i64,
+    pub notes: Option<String>,
+    pub added_at: i64,
+}
+
+/// A named, ordered group of assets to follow without holding them.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Watchlist {
+    pub id: String,
+    pub name: String,
+    pub position: i64,
+    pub created_at: i64,
+    /// In display order.
+    pub items: Vec<WatchlistItem>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -106,10 +914,23 @@ pub struct SymbolSearchResult {
     pub symbol: String,
     pub name: String,
     pub asset_type: String,
+    /// Provider that returned the result ("binance", "coingecko", "twelve_data").
     pub provider: String,
+    /// The exact identifier `provider` knows the instrument by, stored with the
+    /// asset so fetches don't have to guess it from the ticker.
+    pub provider_id: String,
     pub exchange: Option<String>,
 }
 
+/// The identifier a provider knows an asset by: a Binance pair ("UNIUSDT"),
+/// a CoinGecko coin id ("uniswap"), or a Twelve Data `TICKER:EXCHANGE`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProviderSymbol {
+    pub asset_id: String,
+    pub provider: String,
+    pub provider_symbol: String,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AssetHoldingSummary {
     pub total_bought: f64,
@@ -1,11 +1,22 @@
-use crate::models::{DateRange, OHLCVRow, SymbolSearchResult};
-use crate::providers::MarketDataProvider;
+use crate::models::{Asset, DateRange, Interval, OHLCVRow, SymbolSearchResult};
+use crate::providers::error::{ProviderError, ProviderResult};
+use crate::providers::{self, MarketDataProvider};
+use crate::rate_limit::{RateLimiter, Throttle};
 use async_trait::async_trait;
 use serde::Deserialize;
 use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Request weights from the Binance API docs; the limit is counted in weight.
+const KLINES_WEIGHT: u32 = 2;
+const TICKER_WEIGHT: u32 = 2;
+const ALL_TICKERS_WEIGHT: u32 = 4;
 
 pub struct BinanceProvider {
     client: reqwest::Client,
+    throttle: Option<Throttle>,
 }
 
 impl BinanceProvider {
@@ -15,9 +26,40 @@ impl BinanceProvider {
                 .user_agent("atlas/0.1")
                 .build()
                 .unwrap_or_else(|_| reqwest::Client::new()),
+            throttle: None,
         }
     }
 
+    /// Draw every request from `limiter`, waiting at most `max_wait` for budget.
+    pub fn with_rate_limiter(
+        mut self,
+        limiter: Arc<RateLimiter>,
+        max_wait: Option<Duration>,
+    ) -> Self {
+        self.throttle = Some(Throttle::new(limiter, max_wait));
+        self
+    }
+
+    async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+        weight: u32,
+    ) -> ProviderResult<reqwest::Response> {
+        providers::send(self.throttle.as_ref(), self.name(), weight, request).await
+    }
+
+    async fn fetch_all_tickers(&self) -> ProviderResult<Vec<TickerPrice>> {
+        Ok(self
+            .send(
+                self.client.get("https://api.binance.com/api/v3/ticker/price"),
+                ALL_TICKERS_WEIGHT,
+            )
+            .await?
+            .error_for_status()?
+            .json()
+            .await?)
+    }
+
     fn to_symbol(ticker: &str) -> String {
         let upper = ticker.to_uppercase();
         let base = upper.trim_end_matches("USDT").trim_end_matches("BUSD");
@@ -37,6 +79,28 @@ fn strip_partial_usdt(s: &str) -> &str {
     s
 }
 
+/// Error body, e.g. {"code":-1121,"msg":"Invalid symbol."}
+#[derive(Deserialize)]
+struct ApiError {
+    code: i64,
+    msg: String,
+}
+
+const INVALID_SYMBOL: i64 = -1121;
+
+/// Binance answers unknown symbols with HTTP 400 and code -1121.
+async fn check_symbol(resp: reqwest::Response, symbol: &str) -> ProviderResult<reqwest::Response> {
+    if resp.status().as_u16() != 400 {
+        return Ok(resp);
+    }
+    let err: ApiError = resp.json().await?;
+    if err.code == INVALID_SYMBOL {
+        Err(ProviderError::NotFound(symbol.to_string()))
+    } else {
+        Err(ProviderError::Parse(err.msg))
+    }
+}
+
 #[derive(Deserialize)]
 struct TickerPrice {
     symbol: String,
@@ -49,9 +113,16 @@ impl MarketDataProvider for BinanceProvider {
         "binance"
     }
 
-    async fn fetch_ohlcv(&self, symbol: &str, range: &DateRange) -> anyhow::Result<Vec<OHLCVRow>> {
-        let binance_symbol = Self::to_symbol(symbol);
+    fn default_symbol(&self, asset: &Asset) -> String {
+        Self::to_symbol(&asset.symbol)
+    }
 
+    async fn fetch_ohlcv(
+        &self,
+        symbol: &str,
+        interval: Interval,
+        range: &DateRange,
+    ) -> ProviderResult<Vec<OHLCVRow>> {
         // For first fetch (range.from == 0), start from 2017-01-01.
         // Binance BTC data begins 2017-08-17; earlier start just returns from listing date.
         let mut start_ms: i64 = if range.from == 0 {
@@ -66,16 +137,15 @@ impl MarketDataProvider for BinanceProvider {
         loop {
             // Klines response: [[open_time_ms, open, high, low, close, volume, close_time_ms, ...], ...]
             // All price/volume fields are JSON strings; open_time and close_time are integers.
-            let candles: Vec<Vec<Value>> = self
-                .client
-                .get("https://api.binance.com/api/v3/klines")
-                .query(&[
-                    ("symbol", binance_symbol.as_str()),
-                    ("interval", "1d"),
-                    ("startTime", &start_ms.to_string()),
-                    ("limit", "1000"),
-                ])
-                .send()
+            let request = self.client.get("https://api.binance.com/api/v3/klines").query(&[
+                ("symbol", symbol),
+                // Binance interval names match ours exactly ("1m", "1h", "1d", ...)
+                ("interval", interval.as_str()),
+                ("startTime", &start_ms.to_string()),
+                ("limit", "1000"),
+            ]);
+            let resp = self.send(request, KLINES_WEIGHT).await?;
+            let candles: Vec<Vec<Value>> = check_symbol(resp, symbol)
                 .await?
                 .error_for_status()?
                 .json()
@@ -104,25 +174,26 @@ impl MarketDataProvider for BinanceProvider {
                     break;
                 }
 
-                let parse_str = |v: Option<&Value>| -> anyhow::Result<f64> {
-                    v.and_then(|v| v.as_str())
-                        .ok_or_else(|| anyhow::anyhow!("missing field"))?
-                        .parse::<f64>()
-                        .map_err(Into::into)
+                let parse_str = |v: Option<&Value>| -> ProviderResult<f64> {
+                    Ok(v.and_then(|v| v.as_str())
+                        .ok_or_else(|| ProviderError::Parse("missing kline field".to_string()))?
+                        .parse::<f64>()?)
                 };
 
-                let ts_sec = open_ms / 1000;
-                let ts_day = ts_sec - (ts_sec % 86400); // normalize to UTC midnight
+                // Normalize to the candle start (UTC midnight for daily)
+                let ts = interval.align(open_ms / 1000);
 
                 all_rows.push(OHLCVRow {
                     id: None,
                     asset_id: String::new(),
-                    ts: ts_day,
+                    interval,
+                    ts,
                     open: Some(parse_str(candle.get(1))?),
                     high: Some(parse_str(candle.get(2))?),
                     low: Some(parse_str(candle.get(3))?),
                     close: parse_str(candle.get(4))?,
                     volume: parse_str(candle.get(5)).ok(),
+                    provider: None,
                 });
             }
 
@@ -140,39 +211,46 @@ impl MarketDataProvider for BinanceProvider {
         Ok(all_rows)
     }
 
-    async fn fetch_current_price(&self, symbol: &str) -> anyhow::Result<f64> {
-        let binance_symbol = Self::to_symbol(symbol);
-
-        let ticker: TickerPrice = self
+    async fn fetch_current_price(&self, symbol: &str) -> ProviderResult<f64> {
+        let request = self
             .client
             .get("https://api.binance.com/api/v3/ticker/price")
-            .query(&[("symbol", binance_symbol.as_str())])
-            .send()
+            .query(&[("symbol", symbol)]);
+        let resp = self.send(request, TICKER_WEIGHT).await?;
+        let ticker: TickerPrice = check_symbol(resp, symbol)
             .await?
             .error_for_status()?
             .json()
             .await?;
 
-        ticker
-            .price
-            .parse::<f64>()
-            .map_err(|e| anyhow::anyhow!("Failed to parse price: {}", e))
+        Ok(ticker.price.parse::<f64>()?)
+    }
+
+    async fn fetch_current_prices(&self, symbols: &[String]) -> ProviderResult<HashMap<String, f64>> {
+        // ticker/price without a symbol returns every pair in one request
+        let tickers: HashMap<String, String> = self
+            .fetch_all_tickers()
+            .await?
+            .into_iter()
+            .map(|t| (t.symbol, t.price))
+            .collect();
+
+        Ok(symbols
+            .iter()
+            .filter_map(|symbol| {
+                let price = tickers.get(symbol)?.parse::<f64>().ok()?;
+                Some((symbol.clone(), price))
+            })
+            .collect())
     }
 
-    async fn search_symbols(&self, query: &str) -> anyhow::Result<Vec<SymbolSearchResult>> {
+    async fn search_symbols(&self, query: &str) -> ProviderResult<Vec<SymbolSearchResult>> {
         let query_upper = query.to_uppercase();
         // Strip any partial USDT suffix so "BTCU"/"BTCUS"/"BTCUSD"/"BTCUSDT" all find BTC
         let search_base = strip_partial_usdt(&query_upper);
 
         // Fetch all active ticker prices; filter USDT pairs whose base starts with the query
-        let tickers: Vec<TickerPrice> = self
-            .client
-            .get("https://api.binance.com/api/v3/ticker/price")
-            .send()
-            .await?
-            .error_for_status()?
-            .json()
-            .await?;
+        let tickers = self.fetch_all_tickers().await?;
 
         let results = tickers
             .into_iter()
@@ -185,7 +263,8 @@ impl MarketDataProvider for BinanceProvider {
                         symbol: format!("{}USDT", base),
                         name: base.to_string(),
                         asset_type: "crypto".to_string(),
-                        provider: "Binance".to_string(),
+                        provider: self.name().to_string(),
+                        provider_id: t.symbol.clone(),
                         exchange: Some("Binance".to_string()),
                     })
                 } else {
@@ -1,12 +1,17 @@
-use crate::models::{DateRange, OHLCVRow, SymbolSearchResult};
-use crate::providers::MarketDataProvider;
+use crate::models::{Asset, DateRange, Interval, OHLCVRow, SymbolSearchResult};
+use crate::providers::error::{ProviderError, ProviderResult};
+use crate::providers::{self, MarketDataProvider};
+use crate::rate_limit::{RateLimiter, Throttle};
 use async_trait::async_trait;
 use serde::Deserialize;
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
 
 pub struct CoinGeckoProvider {
     client: reqwest::Client,
     api_key: Option<String>,
+    throttle: Option<Throttle>,
 }
 
 impl CoinGeckoProvider {
@@ -21,9 +26,28 @@ impl CoinGeckoProvider {
                 .build()
                 .unwrap_or_else(|_| reqwest::Client::new()),
             api_key,
+            throttle: None,
         }
     }
 
+    /// Draw every request from `limiter`, waiting at most `max_wait` for budget.
+    pub fn with_rate_limiter(
+        mut self,
+        limiter: Arc<RateLimiter>,
+        max_wait: Option<Duration>,
+    ) -> Self {
+        self.throttle = Some(Throttle::new(limiter, max_wait));
+        self
+    }
+
+    async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+        weight: u32,
+    ) -> ProviderResult<reqwest::Response> {
+        providers::send(self.throttle.as_ref(), self.name(), weight, request).await
+    }
+
     fn auth_params(&self) -> Vec<(&str, String)> {
         match &self.api_key {
             Some(key) => vec![("x_cg_demo_api_key", key.clone())],
@@ -63,6 +87,53 @@ struct MarketChartResponse {
     total_volumes: Vec<[f64; 2]>,
 }
 
+/// Bucket CoinGecko price points into candles of the requested interval.
+/// Points only carry a price, so open/high/low/close come from the first, max, min
+/// and last point in each bucket. `total_volumes` is a rolling 24h figure, so it is
+/// only attached to daily and weekly candles.
+fn aggregate_points(prices: &[[f64; 2]], volumes: &[[f64; 2]], interval: Interval) -> Vec<OHLCVRow> {
+    let mut points: Vec<(i64, f64)> = prices
+        .iter()
+        .map(|point| ((point[0] / 1000.0) as i64, point[1]))
+        .collect();
+    points.sort_by_key(|(ts, _)| *ts);
+
+    // Build volume map keyed by bucket for O(1) lookup (last point per bucket wins)
+    let volume_map: HashMap<i64, f64> = if interval.is_intraday() {
+        HashMap::new()
+    } else {
+        volumes
+            .iter()
+            .map(|point| (interval.align((point[0] / 1000.0) as i64), point[1]))
+            .collect()
+    };
+
+    let mut rows: Vec<OHLCVRow> = Vec::new();
+    for (ts, price) in points {
+        let bucket = interval.align(ts);
+        match rows.last_mut() {
+            Some(row) if row.ts == bucket => {
+                row.high = row.high.map(|h| h.max(price));
+                row.low = row.low.map(|l| l.min(price));
+                row.close = price;
+            }
+            _ => rows.push(OHLCVRow {
+                id: None,
+                asset_id: String::new(),
+                interval,
+                ts: bucket,
+                open: Some(price),
+                high: Some(price),
+                low: Some(price),
+                close: price,
+                volume: volume_map.get(&bucket).copied(),
+                provider: None,
+            }),
+        }
+    }
+    rows
+}
+
 #[derive(Deserialize)]
 struct CoinSearchResponse {
     coins: Vec<CoinSearchItem>,
@@ -70,6 +141,7 @@ struct CoinSearchResponse {
 
 #[derive(Deserialize)]
 struct CoinSearchItem {
+    id: String,
     symbol: String,
     name: String,
 }
@@ -91,12 +163,40 @@ impl MarketDataProvider for CoinGeckoProvider {
         "coingecko"
     }
 
-    async fn fetch_ohlcv(&self, symbol: &str, range: &DateRange) -> anyhow::Result<Vec<OHLCVRow>> {
-        let coin_id = ticker_to_coin_id(symbol);
+    fn default_symbol(&self, asset: &Asset) -> String {
+        ticker_to_coin_id(&asset.symbol)
+    }
 
-        // Clamp `from` to within the 365-day free-tier limit.
+    fn supports_interval(&self, interval: Interval) -> bool {
+        // The public API never returns points finer than 5 minutes
+        interval != Interval::OneMinute
+    }
+
+    async fn fetch_ohlcv(
+        &self,
+        symbol: &str,
+        interval: Interval,
+        range: &DateRange,
+    ) -> ProviderResult<Vec<OHLCVRow>> {
+        if !self.supports_interval(interval) {
+            return Err(ProviderError::NotFound(format!(
+                "{} {} candles on CoinGecko",
+                symbol,
+                interval.as_str()
+            )));
+        }
+        let coin_id = symbol;
+
+        // Granularity is chosen by CoinGecko from the range length: 5-minutely up to 1 day,
+        // hourly up to 90 days, daily beyond. Clamp `from` so the points we get back are at
+        // least as fine as the requested interval (and within the 365-day free-tier limit).
         // Handles range.from == 0 (first fetch via CoinGecko fallback path).
-        let limit = chrono::Utc::now().timestamp() - 364 * 86400;
+        let now = chrono::Utc::now().timestamp();
+        let limit = match interval {
+            Interval::OneMinute | Interval::FiveMinutes => now - 86400,
+            Interval::OneHour | Interval::FourHours => now - 89 * 86400,
+            Interval::OneDay | Interval::OneWeek => now - 364 * 86400,
+        };
         let from = if range.from == 0 || range.from < limit {
             limit
         } else {
@@ -105,7 +205,6 @@ impl MarketDataProvider for CoinGeckoProvider {
 
         // /market_chart/range returns { prices: [[ts_ms, close], ...], total_volumes: [[ts_ms, vol], ...] }
         // Uses explicit from/to Unix timestamps — perfect for incremental fetching.
-        // Daily granularity for ranges > 90 days; hourly for shorter ranges (deduped to daily below).
         let mut query: Vec<(&str, String)> = vec![
             ("vs_currency", "usd".to_string()),
             ("from", from.to_string()),
@@ -113,90 +212,90 @@ impl MarketDataProvider for CoinGeckoProvider {
         ];
         query.extend(self.auth_params());
 
-        let resp: MarketChartResponse = self
+        let request = self
             .client
             .get(format!(
                 "https://api.coingecko.com/api/v3/coins/{}/market_chart/range",
                 coin_id
             ))
-            .query(&query)
-            .send()
+            .query(&query);
+        let resp: MarketChartResponse = self
+            .send(request, 1)
             .await?
             .error_for_status()?
             .json()
             .await?;
 
-        // Build volume map keyed by ts_day for O(1) lookup
-        let volume_map: HashMap<i64, f64> = resp
-            .total_volumes
-            .iter()
-            .map(|point| {
-                let ts = (point[0] / 1000.0) as i64;
-                let ts_day = ts - (ts % 86400);
-                (ts_day, point[1])
-            })
-            .collect();
-
-        let mut rows: Vec<OHLCVRow> = resp
-            .prices
-            .iter()
-            .map(|point| {
-                let ts = (point[0] / 1000.0) as i64;
-                let ts_day = ts - (ts % 86400);
-                let close = point[1];
-                OHLCVRow {
-                    id: None,
-                    asset_id: String::new(),
-                    ts: ts_day,
-                    open: Some(close),
-                    high: Some(close),
-                    low: Some(close),
-                    close,
-                    volume: volume_map.get(&ts_day).copied(),
-                }
-            })
-            .collect();
-
-        // Sort by timestamp, then deduplicate by day (keeps last point per day for hourly ranges)
-        rows.sort_by_key(|r| r.ts);
-        rows.dedup_by_key(|r| r.ts);
-        Ok(rows)
+        Ok(aggregate_points(&resp.prices, &resp.total_volumes, interval))
     }
 
-    async fn fetch_current_price(&self, symbol: &str) -> anyhow::Result<f64> {
-        let coin_id = ticker_to_coin_id(symbol);
+    async fn fetch_current_price(&self, symbol: &str) -> ProviderResult<f64> {
+        let coin_id = symbol;
 
         let mut query: Vec<(&str, String)> = vec![
-            ("ids", coin_id.clone()),
+            ("ids", coin_id.to_string()),
             ("vs_currencies", "usd".to_string()),
         ];
         query.extend(self.auth_params());
 
-        let resp: SimplePriceResponse = self
+        let request = self
             .client
             .get("https://api.coingecko.com/api/v3/simple/price")
-            .query(&query)
-            .send()
+            .query(&query);
+        let resp: SimplePriceResponse = self
+            .send(request, 1)
             .await?
             .error_for_status()?
             .json()
             .await?;
 
         resp.prices
-            .get(&coin_id)
+            .get(coin_id)
             .and_then(|p| p.usd)
-            .ok_or_else(|| anyhow::anyhow!("No price found for {}", symbol))
+            .ok_or_else(|| ProviderError::NotFound(symbol.to_string()))
+    }
+
+    async fn fetch_current_prices(&self, symbols: &[String]) -> ProviderResult<HashMap<String, f64>> {
+        if symbols.is_empty() {
+            return Ok(HashMap::new());
+        }
+        // simple/price accepts a comma-separated id list
+        let mut query: Vec<(&str, String)> = vec![
+            ("ids", symbols.join(",")),
+            ("vs_currencies", "usd".to_string()),
+        ];
+        query.extend(self.auth_params());
+
+        let request = self
+            .client
+            .get("https://api.coingecko.com/api/v3/simple/price")
+            .query(&query);
+        let resp: SimplePriceResponse = self
+            .send(request, 1)
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        Ok(symbols
+            .iter()
+            .filter_map(|coin_id| {
+                let price = resp.prices.get(coin_id)?.usd?;
+                Some((coin_id.clone(), price))
+            })
+            .collect())
     }
 
-    async fn search_symbols(&self, query: &str) -> anyhow::Result<Vec<SymbolSearchResult>> {
+    async fn search_symbols(&self, query: &str) -> ProviderResult<Vec<SymbolSearchResult>> {
         let mut params: Vec<(&str, String)> = vec![("query", query.to_string())];
         params.extend(self.auth_params());
 
-        let resp: CoinSearchResponse = self
+        let request = self
             .client
             .get("https://api.coingecko.com/api/v3/search")
-            .query(&params)
-            .send()
+            .query(&params);
+        let resp: CoinSearchResponse = self
+            .send(request, 1)
             .await?
             .error_for_status()?
             .json()
@@ -210,7 +309,8 @@ impl MarketDataProvider for CoinGeckoProvider {
                 symbol: coin.symbol.to_uppercase(),
                 name: coin.name,
                 asset_type: "crypto".to_string(),
-                provider: "CoinGecko".to_string(),
+                provider: self.name().to_string(),
+                provider_id: coin.id,
                 exchange: None,
             })
             .collect();
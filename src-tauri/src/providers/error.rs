@@ -0,0 +1,119 @@
+use std::fmt;
+use std::time::Duration;
+
+/// Why a provider request failed, classified so callers can decide whether to
+/// retry, fall back to another provider, or tell the user to fix a key.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProviderError {
+    /// The provider has no data for this symbol (or this resolution).
+    NotFound(String),
+    /// Missing or invalid API key, or a plan that doesn't cover the request.
+    Auth(String),
+    /// Over the provider's limit; `retry_after` when it said how long to wait.
+    RateLimited { retry_after: Option<Duration> },
+    /// Network failure, timeout, or server error. Worth retrying.
+    Transient(String),
+    /// The response didn't have the shape we expected.
+    Parse(String),
+}
+
+pub type ProviderResult<T> = Result<T, ProviderError>;
+
+impl ProviderError {
+    /// Stable identifier surfaced to the frontend; don't rename.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ProviderError::NotFound(_) => "not_found",
+            ProviderError::Auth(_) => "auth",
+            ProviderError::RateLimited { .. } => "rate_limited",
+            ProviderError::Transient(_) => "transient",
+            ProviderError::Parse(_) => "parse",
+        }
+    }
+
+    pub fn is_transient(&self) -> bool {
+        matches!(self, ProviderError::Transient(_))
+    }
+
+    /// Classify a non-success HTTP status.
+    pub fn from_status(status: u16, context: &str) -> Self {
+        match status {
+            401 | 403 => ProviderError::Auth(format!("{} (HTTP {})", context, status)),
+            404 => ProviderError::NotFound(context.to_string()),
+            429 => ProviderError::RateLimited { retry_after: None },
+            500..=599 => ProviderError::Transient(format!("{} (HTTP {})", context, status)),
+            _ => ProviderError::Parse(format!("{}: unexpected HTTP {}", context, status)),
+        }
+    }
+}
+
+impl fmt::Display for ProviderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProviderError::NotFound(what) => write!(f, "Not found: {}", what),
+            ProviderError::Auth(msg) => write!(f, "Authentication failed: {}", msg),
+            ProviderError::RateLimited { retry_after: Some(wait) } => {
+                write!(f, "Rate limited. Try again in {}s.", wait.as_secs().max(1))
+            }
+            ProviderError::RateLimited { retry_after: None } => write!(f, "Rate limited. Try again later."),
+            ProviderError::Transient(msg) => write!(f, "Network error: {}", msg),
+            ProviderError::Parse(msg) => write!(f, "Unexpected response: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ProviderError {}
+
+impl From<reqwest::Error> for ProviderError {
+    fn from(e: reqwest::Error) -> Self {
+        if let Some(status) = e.status() {
+            return ProviderError::from_status(status.as_u16(), &e.to_string());
+        }
+        if e.is_decode() {
+            ProviderError::Parse(e.to_string())
+        } else {
+            // Connect, timeout, and body read failures
+            ProviderError::Transient(e.to_string())
+        }
+    }
+}
+
+impl From<serde_json::Error> for ProviderError {
+    fn from(e: serde_json::Error) -> Self {
+        ProviderError::Parse(e.to_string())
+    }
+}
+
+impl From<std::num::ParseFloatError> for ProviderError {
+    fn from(e: std::num::ParseFloatError) -> Self {
+        ProviderError::Parse(e.to_string())
+    }
+}
+
+impl From<chrono::ParseError> for ProviderError {
+    fn from(e: chrono::ParseError) -> Self {
+        ProviderError::Parse(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_status_classification() {
+        assert_eq!(ProviderError::from_status(401, "x").code(), "auth");
+        assert_eq!(ProviderError::from_status(403, "x").code(), "auth");
+        assert_eq!(ProviderError::from_status(404, "x").code(), "not_found");
+        assert_eq!(ProviderError::from_status(429, "x").code(), "rate_limited");
+        assert!(ProviderError::from_status(503, "x").is_transient());
+        assert_eq!(ProviderError::from_status(422, "x").code(), "parse");
+    }
+
+    #[test]
+    fn test_survives_anyhow_round_trip() {
+        let err: anyhow::Error = ProviderError::NotFound("FOO".into()).into();
+        assert_eq!(err.downcast_ref::<ProviderError>().map(|e| e.code()), Some("not_found"));
+        assert_eq!(err.to_string(), "Not found: FOO");
+    }
+}
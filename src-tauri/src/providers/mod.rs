@@ -1,16 +1,205 @@
 pub mod binance;
 pub mod coingecko;
+pub mod error;
 pub mod twelve_data;
 
-use crate::models::{DateRange, OHLCVRow, SymbolSearchResult};
+use crate::models::{Asset, AssetProfile, CorporateAction, DateRange, Interval, OHLCVRow, SymbolSearchResult};
+use crate::rate_limit::Throttle;
+use crate::streaming::Backoff;
 use async_trait::async_trait;
+use error::{ProviderError, ProviderResult};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Attempts per request when failures are transient (network errors, 5xx).
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Binance reports the weight used in the current minute on every REST response.
+const BINANCE_USED_WEIGHT_HEADER: &str = "x-mbx-used-weight-1m";
 
 #[async_trait]
 pub trait MarketDataProvider: Send + Sync {
     fn name(&self) -> &str;
-    async fn fetch_ohlcv(&self, symbol: &str, range: &DateRange) -> anyhow::Result<Vec<OHLCVRow>>;
-    async fn fetch_current_price(&self, symbol: &str) -> anyhow::Result<f64>;
-    async fn search_symbols(&self, _query: &str) -> anyhow::Result<Vec<SymbolSearchResult>> {
+    /// Best guess at this provider's identifier for an asset without a stored
+    /// mapping. The fetch methods below take the identifier itself and use it
+    /// verbatim.
+    fn default_symbol(&self, asset: &Asset) -> String {
+        asset.symbol.clone()
+    }
+    /// Whether the provider can serve candles at this resolution.
+    fn supports_interval(&self, _interval: Interval) -> bool {
+        true
+    }
+    async fn fetch_ohlcv(
+        &self,
+        symbol: &str,
+        interval: Interval,
+        range: &DateRange,
+    ) -> ProviderResult<Vec<OHLCVRow>>;
+    async fn fetch_current_price(&self, symbol: &str) -> ProviderResult<f64>;
+    /// Current prices for many symbols, keyed by the symbol as passed in.
+    /// Symbols the provider doesn't know are left out rather than failing the batch.
+    /// Providers with a multi-symbol endpoint override this to use one request.
+    async fn fetch_current_prices(&self, symbols: &[String]) -> ProviderResult<HashMap<String, f64>> {
+        let mut prices = HashMap::new();
+        for symbol in symbols {
+            if let Ok(price) = self.fetch_current_price(symbol).await {
+                prices.insert(symbol.clone(), price);
+            }
+        }
+        Ok(prices)
+    }
+    async fn search_symbols(&self, _query: &str) -> ProviderResult<Vec<SymbolSearchResult>> {
+        Ok(vec![])
+    }
+    /// Splits and dividends over the symbol's whole history (without `asset_id`).
+    /// Providers without corporate action data return none.
+    async fn fetch_corporate_actions(&self, _symbol: &str) -> ProviderResult<Vec<CorporateAction>> {
         Ok(vec![])
     }
+    /// Sector, industry and country for the symbol. Providers without profile
+    /// data return `None`.
+    async fn fetch_profile(&self, _symbol: &str) -> ProviderResult<Option<AssetProfile>> {
+        Ok(None)
+    }
+}
+
+/// Send a provider request: drawn from `throttle` when there is one, retried
+/// with exponential backoff on transient failures, and held back after 429s
+/// for as long as the throttle's deadline allows. 2xx and 400 responses are
+/// returned as-is (providers read their own 400 bodies); other statuses
+/// become typed errors.
+pub(crate) async fn send(
+    throttle: Option<&Throttle>,
+    provider: &str,
+    weight: u32,
+    request: reqwest::RequestBuilder,
+) -> ProviderResult<reqwest::Response> {
+    let deadline = throttle.and_then(Throttle::deadline);
+    let mut backoff = Backoff::new(Duration::from_millis(500), Duration::from_secs(4));
+    let mut attempt = 1;
+    loop {
+        if let Some(throttle) = throttle {
+            throttle.acquire(provider, weight, deadline).await?;
+        }
+        let attempt_request = request
+            .try_clone()
+            .ok_or_else(|| ProviderError::Parse("request body can't be retried".to_string()))?;
+
+        let result = match attempt_request.send().await {
+            Ok(resp) => check_status(throttle, provider, resp),
+            Err(e) => Err(ProviderError::from(e)),
+        };
+        match result {
+            // The throttle is now blocked; the next acquire waits it out or hits the deadline
+            Err(ProviderError::RateLimited { retry_after }) if throttle.is_some() => {
+                if let Some(throttle) = throttle {
+                    throttle.penalize(provider, retry_after);
+                }
+            }
+            Err(e) if e.is_transient() && attempt < MAX_ATTEMPTS => {
+                attempt += 1;
+                tokio::time::sleep(backoff.next_delay()).await;
+            }
+            other => return other,
+        }
+    }
+}
+
+fn check_status(
+    throttle: Option<&Throttle>,
+    provider: &str,
+    resp: reqwest::Response,
+) -> ProviderResult<reqwest::Response> {
+    if let (Some(throttle), Some(used)) = (throttle, header_value::<u32>(&resp, BINANCE_USED_WEIGHT_HEADER)) {
+        throttle.observe_used(provider, used);
+    }
+    let status = resp.status().as_u16();
+    match status {
+        200..=299 | 400 => Ok(resp),
+        // 418 is Binance's IP ban after ignoring 429s
+        418 | 429 => Err(ProviderError::RateLimited {
+            retry_after: header_value::<u64>(&resp, "retry-after").map(Duration::from_secs),
+        }),
+        _ => Err(ProviderError::from_status(status, &format!("{} {}", provider, resp.url().path()))),
+    }
+}
+
+fn header_value<T: std::str::FromStr>(resp: &reqwest::Response, name: &str) -> Option<T> {
+    resp.headers().get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rate_limit::RateLimiter;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Minimal HTTP server answering the n-th connection with `responses[n]`.
+    async fn spawn_server(responses: Vec<&'static str>) -> (String, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let hits = Arc::new(AtomicUsize::new(0));
+        let counter = hits.clone();
+        tokio::spawn(async move {
+            for response in responses {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf).await;
+                counter.fetch_add(1, Ordering::SeqCst);
+                stream.write_all(response.as_bytes()).await.unwrap();
+                let _ = stream.shutdown().await;
+            }
+        });
+        (url, hits)
+    }
+
+    const UNAVAILABLE: &str = "HTTP/1.1 503 Service Unavailable\r\nConnection: close\r\nContent-Length: 0\r\n\r\n";
+    const TOO_MANY: &str = "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 1\r\nConnection: close\r\nContent-Length: 0\r\n\r\n";
+    const UNAUTHORIZED: &str = "HTTP/1.1 401 Unauthorized\r\nConnection: close\r\nContent-Length: 0\r\n\r\n";
+    const OK: &str = "HTTP/1.1 200 OK\r\nConnection: close\r\nContent-Length: 2\r\n\r\nok";
+
+    #[tokio::test]
+    async fn test_send_retries_transient_errors() {
+        let (url, hits) = spawn_server(vec![UNAVAILABLE, UNAVAILABLE, OK]).await;
+        let resp = send(None, "test", 1, reqwest::Client::new().get(&url)).await.unwrap();
+        assert_eq!(resp.text().await.unwrap(), "ok");
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_send_gives_up_after_max_attempts() {
+        let (url, hits) = spawn_server(vec![UNAVAILABLE, UNAVAILABLE, UNAVAILABLE]).await;
+        let err = send(None, "test", 1, reqwest::Client::new().get(&url)).await.unwrap_err();
+        assert!(err.is_transient());
+        assert_eq!(hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_send_does_not_retry_auth_errors() {
+        let (url, hits) = spawn_server(vec![UNAUTHORIZED, OK]).await;
+        let err = send(None, "test", 1, reqwest::Client::new().get(&url)).await.unwrap_err();
+        assert_eq!(err.code(), "auth");
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_send_waits_out_retry_after() {
+        let (url, hits) = spawn_server(vec![TOO_MANY, OK]).await;
+        let throttle = Throttle::new(Arc::new(RateLimiter::new()), None);
+        let start = std::time::Instant::now();
+        let resp = send(Some(&throttle), "test", 1, reqwest::Client::new().get(&url)).await.unwrap();
+        assert_eq!(resp.status(), 200);
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+        assert!(start.elapsed() >= Duration::from_secs(1));
+
+        // Without room to wait, the 429 surfaces as a typed error
+        let (url, _) = spawn_server(vec![TOO_MANY]).await;
+        let throttle = Throttle::new(Arc::new(RateLimiter::new()), Some(Duration::ZERO));
+        let err = send(Some(&throttle), "test", 1, reqwest::Client::new().get(&url)).await.unwrap_err();
+        assert_eq!(err.code(), "rate_limited");
+    }
 }
@@ -1,12 +1,30 @@
-use crate::models::{DateRange, OHLCVRow, SymbolSearchResult};
-use crate::providers::MarketDataProvider;
+use crate::models::{
+    Asset, AssetProfile, AssetType, CorporateAction, CorporateActionType, DateRange, Interval,
+    OHLCVRow, SymbolSearchResult,
+};
+use crate::providers::error::{ProviderError, ProviderResult};
+use crate::providers::{self, MarketDataProvider};
+use crate::rate_limit::{RateLimiter, Throttle};
 use async_trait::async_trait;
 use chrono::DateTime;
 use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Twelve Data accepts up to 120 comma-separated symbols per batch request.
+const MAX_BATCH_SYMBOLS: usize = 120;
+
+/// Credits per symbol for the /splits and /dividends reference endpoints, each
+/// more than the free tier's whole minute, so callers fetch them rarely.
+const CORPORATE_ACTIONS_WEIGHT: u32 = 20;
+/// Credits charged for a company profile.
+const PROFILE_WEIGHT: u32 = 10;
 
 pub struct TwelveDataProvider {
     api_key: String,
     client: reqwest::Client,
+    throttle: Option<Throttle>,
 }
 
 impl TwelveDataProvider {
@@ -17,6 +35,57 @@ impl TwelveDataProvider {
                 .user_agent("atlas/0.1")
                 .build()
                 .unwrap_or_else(|_| reqwest::Client::new()),
+            throttle: None,
+        }
+    }
+
+    /// Draw every request from `limiter`, waiting at most `max_wait` for budget.
+    pub fn with_rate_limiter(
+        mut self,
+        limiter: Arc<RateLimiter>,
+        max_wait: Option<Duration>,
+    ) -> Self {
+        self.throttle = Some(Throttle::new(limiter, max_wait));
+        self
+    }
+
+    /// Twelve Data bills credits per symbol, so `weight` is the symbol count.
+    async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+        weight: u32,
+    ) -> ProviderResult<reqwest::Response> {
+        providers::send(self.throttle.as_ref(), self.name(), weight, request).await
+    }
+
+    /// Symbols per batch request. Each one costs a credit and a batch costing
+    /// more than the plan's credits per minute is rejected outright, so
+    /// batches are capped at that budget as well as at Twelve Data's maximum.
+    fn batch_size(&self) -> usize {
+        let credits = self
+            .throttle
+            .as_ref()
+            .map_or(MAX_BATCH_SYMBOLS, |t| t.limit(self.name()).requests as usize);
+        credits.clamp(1, MAX_BATCH_SYMBOLS)
+    }
+
+    /// Errors come back in the body with HTTP 200 and an HTTP-like code. 429
+    /// means the minute's credits are spent, so the limiter backs off a full window.
+    fn api_error(&self, code: Option<u16>, message: Option<String>) -> ProviderError {
+        let message = message.unwrap_or_else(|| "Unknown error".to_string());
+        match code {
+            Some(429) => {
+                if let Some(throttle) = &self.throttle {
+                    throttle.penalize(self.name(), None);
+                }
+                ProviderError::RateLimited { retry_after: None }
+            }
+            // Unknown symbols come back as 400 or 404 depending on the endpoint
+            Some(400 | 404) if message.to_lowercase().contains("not found") => {
+                ProviderError::NotFound(message)
+            }
+            Some(code) => ProviderError::from_status(code, &message),
+            None => ProviderError::Parse(message),
         }
     }
 }
@@ -24,6 +93,7 @@ impl TwelveDataProvider {
 #[derive(Deserialize)]
 struct TimeSeriesResponse {
     values: Option<Vec<TimeSeriesValue>>,
+    code: Option<u16>,
     status: Option<String>,
     message: Option<String>,
 }
@@ -41,6 +111,47 @@ struct TimeSeriesValue {
 #[derive(Deserialize)]
 struct PriceResponse {
     price: Option<String>,
+    code: Option<u16>,
+    status: Option<String>,
+    message: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct SplitsResponse {
+    splits: Option<Vec<SplitItem>>,
+    code: Option<u16>,
+    status: Option<String>,
+    message: Option<String>,
+}
+
+/// `from_factor` shares for every `to_factor`: a 4-for-1 split is 4 and 1.
+#[derive(Deserialize)]
+struct SplitItem {
+    date: String,
+    from_factor: f64,
+    to_factor: f64,
+}
+
+#[derive(Deserialize)]
+struct DividendsResponse {
+    dividends: Option<Vec<DividendItem>>,
+    code: Option<u16>,
+    status: Option<String>,
+    message: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct DividendItem {
+    ex_date: String,
+    amount: f64,
+}
+
+#[derive(Deserialize)]
+struct ProfileResponse {
+    sector: Option<String>,
+    industry: Option<String>,
+    country: Option<String>,
+    code: Option<u16>,
     status: Option<String>,
     message: Option<String>,
 }
@@ -51,60 +162,85 @@ impl MarketDataProvider for TwelveDataProvider {
         "twelve_data"
     }
 
-    async fn fetch_ohlcv(&self, symbol: &str, range: &DateRange) -> anyhow::Result<Vec<OHLCVRow>> {
+    fn default_symbol(&self, asset: &Asset) -> String {
+        match &asset.exchange {
+            Some(exchange) => format!("{}:{}", asset.symbol, exchange),
+            None => asset.symbol.clone(),
+        }
+    }
+
+    async fn fetch_ohlcv(
+        &self,
+        symbol: &str,
+        interval: Interval,
+        range: &DateRange,
+    ) -> ProviderResult<Vec<OHLCVRow>> {
+        // Intraday requests need a time component; daily and weekly stay date-only.
+        let date_format = if interval.is_intraday() {
+            "%Y-%m-%d %H:%M:%S"
+        } else {
+            "%Y-%m-%d"
+        };
+
         let end = DateTime::from_timestamp(range.to, 0)
-            .ok_or_else(|| anyhow::anyhow!("Invalid end timestamp"))?
-            .format("%Y-%m-%d")
+            .ok_or_else(|| ProviderError::Parse("invalid end timestamp".to_string()))?
+            .format(date_format)
             .to_string();
 
         // On first fetch (from=0), use 1990-01-01 as start_date to get max history.
         // TwelveData requires an explicit start_date to return more than ~1 year.
         let start_ts = if range.from == 0 { 631152000 } else { range.from };
         let start = DateTime::from_timestamp(start_ts, 0)
-            .ok_or_else(|| anyhow::anyhow!("Invalid start timestamp"))?
-            .format("%Y-%m-%d")
+            .ok_or_else(|| ProviderError::Parse("invalid start timestamp".to_string()))?
+            .format(date_format)
             .to_string();
 
-        let params = vec![
-            ("symbol", symbol.to_string()),
-            ("interval", "1day".to_string()),
+        let mut params = symbol_params(symbol);
+        params.extend([
+            ("interval", to_td_interval(interval).to_string()),
             ("start_date", start),
             ("end_date", end),
             ("apikey", self.api_key.clone()),
             ("format", "JSON".to_string()),
             ("outputsize", "5000".to_string()),
-        ];
+            // Stored candles are raw; splits and dividends are applied on read
+            ("adjust", "none".to_string()),
+        ]);
+        if interval.is_intraday() {
+            // Intraday datetimes default to the exchange's local time
+            params.push(("timezone", "UTC".to_string()));
+        }
 
-        let resp: TimeSeriesResponse = self
+        let request = self
             .client
             .get("https://api.twelvedata.com/time_series")
-            .query(&params)
-            .send()
+            .query(&params);
+        let resp: TimeSeriesResponse = self
+            .send(request, 1)
             .await?
             .json()
             .await?;
 
         if resp.status.as_deref() == Some("error") {
-            anyhow::bail!(
-                "Twelve Data API error: {}",
-                resp.message.unwrap_or_else(|| "Unknown error".to_string())
-            );
+            return Err(self.api_error(resp.code, resp.message));
         }
 
         let values = resp.values.unwrap_or_default();
         let mut rows = Vec::with_capacity(values.len());
 
         for v in values {
-            let ts = parse_date_to_unix(&v.datetime)?;
+            let ts = interval.align(parse_datetime_to_unix(&v.datetime)?);
             rows.push(OHLCVRow {
                 id: None,
                 asset_id: String::new(),
+                interval,
                 ts,
                 open: Some(v.open.parse::<f64>()?),
                 high: Some(v.high.parse::<f64>()?),
                 low: Some(v.low.parse::<f64>()?),
                 close: v.close.parse::<f64>()?,
                 volume: v.volume.as_ref().and_then(|v| v.parse::<f64>().ok()),
+                provider: None,
             });
         }
 
@@ -112,35 +248,139 @@ impl MarketDataProvider for TwelveDataProvider {
         Ok(rows)
     }
 
-    async fn fetch_current_price(&self, symbol: &str) -> anyhow::Result<f64> {
-        let resp: PriceResponse = self
+    async fn fetch_current_price(&self, symbol: &str) -> ProviderResult<f64> {
+        let mut params = symbol_params(symbol);
+        params.push(("apikey", self.api_key.clone()));
+        let request = self
             .client
             .get("https://api.twelvedata.com/price")
-            .query(&[("symbol", symbol), ("apikey", &self.api_key)])
-            .send()
+            .query(&params);
+        let resp: PriceResponse = self
+            .send(request, 1)
             .await?
             .json()
             .await?;
 
         if resp.status.as_deref() == Some("error") {
-            anyhow::bail!(
-                "Twelve Data API error: {}",
-                resp.message.unwrap_or_else(|| "Unknown error".to_string())
-            );
+            return Err(self.api_error(resp.code, resp.message));
         }
 
-        resp.price
-            .ok_or_else(|| anyhow::anyhow!("No price returned"))?
-            .parse::<f64>()
-            .map_err(|e| anyhow::anyhow!("Failed to parse price: {}", e))
+        Ok(resp
+            .price
+            .ok_or_else(|| ProviderError::Parse("no price returned".to_string()))?
+            .parse::<f64>()?)
     }
 
-    async fn search_symbols(&self, query: &str) -> anyhow::Result<Vec<SymbolSearchResult>> {
-        let resp: SymbolSearchResponse = self
+    async fn fetch_current_prices(&self, symbols: &[String]) -> ProviderResult<HashMap<String, f64>> {
+        let mut prices = HashMap::new();
+        for chunk in symbols.chunks(self.batch_size()) {
+            let request = self
+                .client
+                .get("https://api.twelvedata.com/price")
+                .query(&[("symbol", chunk.join(",").as_str()), ("apikey", &self.api_key)]);
+            let resp: serde_json::Value = self
+                .send(request, chunk.len() as u32)
+                .await?
+                .json()
+                .await?;
+
+            // Request-level errors (bad key, out of credits) are a single object in both modes
+            let top: PriceResponse = serde_json::from_value(resp.clone())?;
+            if top.status.as_deref() == Some("error") {
+                return Err(self.api_error(top.code, top.message));
+            }
+
+            // One symbol comes back unwrapped ({"price": "..."}); several come back
+            // keyed by symbol ({"AAPL": {"price": "..."}, ...})
+            let by_symbol: HashMap<String, PriceResponse> = if chunk.len() == 1 {
+                HashMap::from([(chunk[0].clone(), top)])
+            } else {
+                serde_json::from_value(resp)?
+            };
+
+            for symbol in chunk {
+                if let Some(price) = by_symbol
+                    .get(symbol)
+                    .and_then(|r| r.price.as_deref())
+                    .and_then(|p| p.parse::<f64>().ok())
+                {
+                    prices.insert(symbol.clone(), price);
+                }
+            }
+        }
+        Ok(prices)
+    }
+
+    async fn fetch_corporate_actions(&self, symbol: &str) -> ProviderResult<Vec<CorporateAction>> {
+        let mut params = symbol_params(symbol);
+        params.extend([("range", "full".to_string()), ("apikey", self.api_key.clone())]);
+        let action = |action_type, date: &str, value| -> ProviderResult<CorporateAction> {
+            Ok(CorporateAction {
+                id: None,
+                asset_id: String::new(),
+                action_type,
+                ts: parse_datetime_to_unix(date)?,
+                value,
+                provider: self.name().to_string(),
+            })
+        };
+
+        let request = self.client.get("https://api.twelvedata.com/splits").query(&params);
+        let splits: SplitsResponse = self
+            .send(request, CORPORATE_ACTIONS_WEIGHT)
+            .await?
+            .json()
+            .await?;
+        if splits.status.as_deref() == Some("error") {
+            return Err(self.api_error(splits.code, splits.message));
+        }
+
+        let request = self.client.get("https://api.twelvedata.com/dividends").query(&params);
+        let dividends: DividendsResponse = self
+            .send(request, CORPORATE_ACTIONS_WEIGHT)
+            .await?
+            .json()
+            .await?;
+        if dividends.status.as_deref() == Some("error") {
+            return Err(self.api_error(dividends.code, dividends.message));
+        }
+
+        let mut actions = Vec::new();
+        for split in splits.splits.unwrap_or_default() {
+            if split.from_factor > 0.0 && split.to_factor > 0.0 {
+                let ratio = split.from_factor / split.to_factor;
+                actions.push(action(CorporateActionType::Split, &split.date, ratio)?);
+            }
+        }
+        for dividend in dividends.dividends.unwrap_or_default() {
+            actions.push(action(CorporateActionType::Dividend, &dividend.ex_date, dividend.amount)?);
+        }
+        actions.sort_by_key(|a| a.ts);
+        Ok(actions)
+    }
+
+    async fn fetch_profile(&self, symbol: &str) -> ProviderResult<Option<AssetProfile>> {
+        let mut params = symbol_params(symbol);
+        params.push(("apikey", self.api_key.clone()));
+        let request = self.client.get("https://api.twelvedata.com/profile").query(&params);
+        let resp: ProfileResponse = self
+            .send(request, PROFILE_WEIGHT)
+            .await?
+            .json()
+            .await?;
+        if resp.status.as_deref() == Some("error") {
+            return Err(self.api_error(resp.code, resp.message));
+        }
+        Ok(Some(profile_from_response(resp)))
+    }
+
+    async fn search_symbols(&self, query: &str) -> ProviderResult<Vec<SymbolSearchResult>> {
+        let request = self
             .client
             .get("https://api.twelvedata.com/symbol_search")
-            .query(&[("symbol", query), ("outputsize", "10")])
-            .send()
+            .query(&[("symbol", query), ("outputsize", "10")]);
+        let resp: SymbolSearchResponse = self
+            .send(request, 1)
             .await?
             .json()
             .await?;
@@ -150,12 +390,21 @@ impl MarketDataProvider for TwelveDataProvider {
             .into_iter()
             .filter(|item| item.instrument_type != "Digital Currency")
             .take(10)
-            .map(|item| SymbolSearchResult {
-                symbol: item.symbol,
-                name: item.instrument_name,
-                asset_type: map_instrument_type(&item.instrument_type).to_string(),
-                provider: "TwelveData".to_string(),
-                exchange: Some(item.exchange),
+            .map(|item| {
+                let asset_type = map_instrument_type(&item.instrument_type);
+                // Pairs and commodities aren't exchange listings; only pin venues for those that are
+                let exchange = Some(item.exchange).filter(|e| asset_type.is_exchange_listed() && !e.is_empty());
+                SymbolSearchResult {
+                    provider_id: match &exchange {
+                        Some(exchange) => format!("{}:{}", item.symbol, exchange),
+                        None => item.symbol.clone(),
+                    },
+                    symbol: item.symbol,
+                    name: item.instrument_name,
+                    asset_type: asset_type.as_str().to_string(),
+                    provider: self.name().to_string(),
+                    exchange,
+                }
             })
             .collect();
 
@@ -177,22 +426,62 @@ struct SymbolSearchItem {
     country: Option<String>,
 }
 
-fn map_instrument_type(t: &str) -> &str {
+/// Query parameters naming `symbol`: either a bare ticker or `TICKER:EXCHANGE`
+/// as captured from search, which pins the listing on multi-exchange tickers.
+fn symbol_params(symbol: &str) -> Vec<(&'static str, String)> {
+    match symbol.rsplit_once(':') {
+        Some((ticker, exchange)) => vec![("symbol", ticker.to_string()), ("exchange", exchange.to_string())],
+        None => vec![("symbol", symbol.to_string())],
+    }
+}
+
+fn map_instrument_type(t: &str) -> AssetType {
     match t {
-        "Common Stock" | "Equity" => "stock",
-        "ETF" | "Exchange Traded Fund" => "stock",
-        "Physical Currency" | "Digital Currency" => "crypto",
-        "Commodity" | "Mutual Fund" => "commodity",
-        _ => "stock",
+        "ETF" | "Exchange Traded Fund" | "Exchange Traded Note" => AssetType::Etf,
+        "Mutual Fund" | "Closed-end Fund" => AssetType::MutualFund,
+        "Bond" | "Bond Fund" => AssetType::Bond,
+        "Physical Currency" => AssetType::Forex,
+        "Digital Currency" => AssetType::Crypto,
+        "Index" => AssetType::Index,
+        "Commodity" => AssetType::Commodity,
+        // Common and preferred stock, REITs, depositary receipts, ...
+        _ => AssetType::Stock,
+    }
+}
+
+/// Twelve Data reports missing fields as empty strings. The profile has no
+/// asset class; the asset type's default applies.
+fn profile_from_response(resp: ProfileResponse) -> AssetProfile {
+    let present = |v: Option<String>| v.map(|s| s.trim().to_string()).filter(|s| !s.is_empty());
+    AssetProfile {
+        sector: present(resp.sector),
+        industry: present(resp.industry),
+        country: present(resp.country),
+        asset_class: None,
     }
 }
 
-fn parse_date_to_unix(datetime: &str) -> anyhow::Result<i64> {
+fn to_td_interval(interval: Interval) -> &'static str {
+    match interval {
+        Interval::OneMinute => "1min",
+        Interval::FiveMinutes => "5min",
+        Interval::OneHour => "1h",
+        Interval::FourHours => "4h",
+        Interval::OneDay => "1day",
+        Interval::OneWeek => "1week",
+    }
+}
+
+/// Parse "YYYY-MM-DD HH:MM:SS" (intraday) or "YYYY-MM-DD" (daily+) as UTC.
+fn parse_datetime_to_unix(datetime: &str) -> ProviderResult<i64> {
+    if let Ok(dt) = chrono::NaiveDateTime::parse_from_str(datetime, "%Y-%m-%d %H:%M:%S") {
+        return Ok(dt.and_utc().timestamp());
+    }
     let dt = chrono::NaiveDate::parse_from_str(datetime, "%Y-%m-%d")
-        .map_err(|e| anyhow::anyhow!("Failed to parse date '{}': {}", datetime, e))?;
+        .map_err(|e| ProviderError::Parse(format!("Failed to parse date '{}': {}", datetime, e)))?;
     Ok(dt
         .and_hms_opt(0, 0, 0)
-        .ok_or_else(|| anyhow::anyhow!("Invalid time"))?
+        .ok_or_else(|| ProviderError::Parse("invalid time".to_string()))?
         .and_utc()
         .timestamp())
 }
@@ -0,0 +1,308 @@
+use crate::providers::error::{ProviderError, ProviderResult};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Providers whose limit can be overridden with a `{provider}_rate_limit` setting.
+pub const CONFIGURABLE_PROVIDERS: [&str; 3] = ["binance", "coingecko", "twelve_data"];
+
+/// Settings key suffix for limit overrides, e.g. `twelve_data_rate_limit = "800/60"`.
+pub const RATE_LIMIT_SUFFIX: &str = "_rate_limit";
+
+/// `requests` units of budget per `per`. For Binance a unit is request weight.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+    pub requests: u32,
+    pub per: Duration,
+}
+
+impl RateLimit {
+    pub fn new(requests: u32, per_secs: u64) -> Self {
+        Self { requests, per: Duration::from_secs(per_secs) }
+    }
+
+    /// Free-tier limits, used until a setting overrides them.
+    pub fn default_for(provider: &str) -> Self {
+        match provider {
+            "twelve_data" => Self::new(8, 60),
+            "coingecko" => Self::new(30, 60),
+            "binance" => Self::new(6000, 60),
+            "alpha_vantage" => Self::new(25, 86400),
+            _ => Self::new(10, 60),
+        }
+    }
+
+    /// Parse the settings format "N/secs", e.g. "800/60".
+    pub fn parse(value: &str) -> anyhow::Result<Self> {
+        let (requests, secs) = value
+            .trim()
+            .split_once('/')
+            .ok_or_else(|| anyhow::anyhow!("Rate limit must look like \"requests/seconds\", e.g. \"8/60\""))?;
+        let requests: u32 = requests.trim().parse()?;
+        let secs: u64 = secs.trim().parse()?;
+        if requests == 0 || secs == 0 {
+            anyhow::bail!("Rate limit requests and seconds must both be positive");
+        }
+        Ok(Self::new(requests, secs))
+    }
+
+    fn refill_per_sec(&self) -> f64 {
+        self.requests as f64 / self.per.as_secs_f64()
+    }
+}
+
+struct Bucket {
+    limit: RateLimit,
+    tokens: f64,
+    refilled_at: Instant,
+    /// Set by a 429/Retry-After: nothing goes out before this, whatever the tokens say.
+    blocked_until: Option<Instant>,
+}
+
+impl Bucket {
+    fn new(limit: RateLimit) -> Self {
+        Self { limit, tokens: limit.requests as f64, refilled_at: Instant::now(), blocked_until: None }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.limit.refill_per_sec()).min(self.limit.requests as f64);
+        self.refilled_at = now;
+    }
+
+    /// Take `weight` tokens, or return how long until that many are available.
+    /// A request heavier than the whole bucket goes out once the bucket is full
+    /// and leaves it in debt, so later requests wait out its real cost.
+    fn try_take(&mut self, weight: u32, now: Instant) -> Option<Duration> {
+        if let Some(until) = self.blocked_until {
+            if until > now {
+                return Some(until - now);
+            }
+            self.blocked_until = None;
+        }
+        self.refill(now);
+        let needed = weight.min(self.limit.requests) as f64;
+        if self.tokens >= needed {
+            self.tokens -= weight as f64;
+            return None;
+        }
+        let missing = needed - self.tokens;
+        Some(Duration::from_secs_f64(missing / self.limit.refill_per_sec()))
+    }
+}
+
+/// Token bucket per provider, shared by every command and background task so
+/// that they all draw from the same budget.
+#[derive(Default)]
+pub struct RateLimiter {
+    buckets: Mutex<HashMap<String, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replace a provider's limit. Unused budget carries over, capped at the new size.
+    pub fn set_limit(&self, provider: &str, limit: RateLimit) {
+        if let Ok(mut buckets) = self.buckets.lock() {
+            let bucket = buckets.entry(provider.to_string()).or_insert_with(|| Bucket::new(limit));
+            bucket.refill(Instant::now());
+            bucket.limit = limit;
+            bucket.tokens = bucket.tokens.min(limit.requests as f64);
+        }
+    }
+
+    /// The limit a provider is held to: its override, or the free tier.
+    pub fn limit(&self, provider: &str) -> RateLimit {
+        self.buckets
+            .lock()
+            .ok()
+            .and_then(|buckets| buckets.get(provider).map(|b| b.limit))
+            .unwrap_or_else(|| RateLimit::default_for(provider))
+    }
+
+    /// Reserve `weight` units now. Returns `None` on success, or how long until
+    /// the reservation could succeed.
+    pub fn try_acquire(&self, provider: &str, weight: u32) -> anyhow::Result<Option<Duration>> {
+        let mut buckets = self.buckets.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+        let bucket = buckets
+            .entry(provider.to_string())
+            .or_insert_with(|| Bucket::new(RateLimit::default_for(provider)));
+        Ok(bucket.try_take(weight, Instant::now()))
+    }
+
+    /// Wait for `weight` units of budget. Fails straight away if the slot would
+    /// only free up after `deadline`; `None` waits as long as it takes.
+    pub async fn acquire(
+        &self,
+        provider: &str,
+        weight: u32,
+        deadline: Option<Instant>,
+    ) -> ProviderResult<()> {
+        while let Some(wait) = self
+            .try_acquire(provider, weight)
+            .map_err(|e| ProviderError::Transient(e.to_string()))?
+        {
+            if deadline.is_some_and(|d| Instant::now() + wait > d) {
+                return Err(ProviderError::RateLimited { retry_after: Some(wait) });
+            }
+            tokio::time::sleep(wait).await;
+        }
+        Ok(())
+    }
+
+    /// The provider rejected a request as over its limit. Block it for
+    /// `retry_after` (the provider's word is final, so tokens are kept for
+    /// afterwards), or a full window when the provider didn't say.
+    pub fn penalize(&self, provider: &str, retry_after: Option<Duration>) {
+        if let Ok(mut buckets) = self.buckets.lock() {
+            let bucket = buckets
+                .entry(provider.to_string())
+                .or_insert_with(|| Bucket::new(RateLimit::default_for(provider)));
+            bucket.blocked_until = Some(Instant::now() + retry_after.unwrap_or(bucket.limit.per));
+        }
+    }
+
+    /// Sync with usage the provider reports itself, which also counts requests
+    /// made outside this app (other tools on the same IP).
+    pub fn observe_used(&self, provider: &str, used: u32) {
+        if let Ok(mut buckets) = self.buckets.lock() {
+            let bucket = buckets
+                .entry(provider.to_string())
+                .or_insert_with(|| Bucket::new(RateLimit::default_for(provider)));
+            bucket.refill(Instant::now());
+            let remaining = bucket.limit.requests.saturating_sub(used) as f64;
+            bucket.tokens = bucket.tokens.min(remaining);
+        }
+    }
+}
+
+/// A provider's handle on the shared limiter, with how long its caller is
+/// willing to wait for budget (`None` = as long as it takes).
+#[derive(Clone)]
+pub struct Throttle {
+    limiter: Arc<RateLimiter>,
+    max_wait: Option<Duration>,
+}
+
+impl Throttle {
+    pub fn new(limiter: Arc<RateLimiter>, max_wait: Option<Duration>) -> Self {
+        Self { limiter, max_wait }
+    }
+
+    /// Deadline for one logical request, including its retries.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.max_wait.map(|w| Instant::now() + w)
+    }
+
+    pub async fn acquire(&self, provider: &str, weight: u32, deadline: Option<Instant>) -> ProviderResult<()> {
+        self.limiter.acquire(provider, weight, deadline).await
+    }
+
+    pub fn limit(&self, provider: &str) -> RateLimit {
+        self.limiter.limit(provider)
+    }
+
+    pub fn penalize(&self, provider: &str, retry_after: Option<Duration>) {
+        self.limiter.penalize(provider, retry_after);
+    }
+
+    pub fn observe_used(&self, provider: &str, used: u32) {
+        self.limiter.observe_used(provider, used);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rate_limit() {
+        assert_eq!(RateLimit::parse("800/60").unwrap(), RateLimit::new(800, 60));
+        assert_eq!(RateLimit::parse(" 25 / 86400 ").unwrap(), RateLimit::new(25, 86400));
+        assert!(RateLimit::parse("800").is_err());
+        assert!(RateLimit::parse("0/60").is_err());
+        assert!(RateLimit::parse("abc/60").is_err());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_bucket_allows_burst_then_blocks() {
+        let limiter = RateLimiter::new();
+        for _ in 0..8 {
+            assert!(limiter.try_acquire("twelve_data", 1).unwrap().is_none());
+        }
+        let wait = limiter.try_acquire("twelve_data", 1).unwrap().unwrap();
+        // One token refills every 60s / 8
+        assert_eq!(wait, Duration::from_millis(7500));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_limits_independent_per_provider() {
+        let limiter = RateLimiter::new();
+        for _ in 0..8 {
+            limiter.try_acquire("twelve_data", 1).unwrap();
+        }
+        // Different provider should still work
+        assert!(limiter.try_acquire("coingecko", 1).unwrap().is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_acquire_waits_for_refill() {
+        let limiter = RateLimiter::new();
+        for _ in 0..8 {
+            limiter.acquire("twelve_data", 1, None).await.unwrap();
+        }
+        let start = Instant::now();
+        limiter.acquire("twelve_data", 1, None).await.unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(7500));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_acquire_fails_past_deadline() {
+        let limiter = RateLimiter::new();
+        for _ in 0..8 {
+            limiter.try_acquire("twelve_data", 1).unwrap();
+        }
+        let deadline = Some(Instant::now() + Duration::from_secs(1));
+        let err = limiter.acquire("twelve_data", 1, deadline).await.unwrap_err();
+        assert_eq!(err, ProviderError::RateLimited { retry_after: Some(Duration::from_millis(7500)) });
+        let deadline = Some(Instant::now() + Duration::from_secs(10));
+        assert!(limiter.acquire("twelve_data", 1, deadline).await.is_ok());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_configured_limit_and_weight() {
+        let limiter = RateLimiter::new();
+        assert_eq!(limiter.limit("binance"), RateLimit::new(6000, 60));
+        limiter.set_limit("binance", RateLimit::new(10, 60));
+        assert_eq!(limiter.limit("binance"), RateLimit::new(10, 60));
+        assert!(limiter.try_acquire("binance", 4).unwrap().is_none());
+        assert!(limiter.try_acquire("binance", 4).unwrap().is_none());
+        assert!(limiter.try_acquire("binance", 4).unwrap().is_some());
+        // Heavier than the whole bucket: waits for a full bucket instead of forever,
+        // then the next request waits until the excess has refilled
+        limiter.set_limit("coingecko", RateLimit::new(2, 60));
+        assert!(limiter.try_acquire("coingecko", 5).unwrap().is_none());
+        assert_eq!(limiter.try_acquire("coingecko", 1).unwrap(), Some(Duration::from_secs(120)));
+        assert_eq!(limiter.try_acquire("coingecko", 5).unwrap(), Some(Duration::from_secs(150)));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_penalize_blocks_until_retry_after() {
+        let limiter = RateLimiter::new();
+        limiter.penalize("coingecko", Some(Duration::from_secs(30)));
+        assert_eq!(limiter.try_acquire("coingecko", 1).unwrap(), Some(Duration::from_secs(30)));
+        tokio::time::advance(Duration::from_secs(30)).await;
+        assert!(limiter.try_acquire("coingecko", 1).unwrap().is_none());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_observe_used_drains_bucket() {
+        let limiter = RateLimiter::new();
+        limiter.observe_used("binance", 5998);
+        assert!(limiter.try_acquire("binance", 2).unwrap().is_none());
+        assert!(limiter.try_acquire("binance", 2).unwrap().is_some());
+    }
+}
@@ -0,0 +1,136 @@
+use crate::commands::alerts::notify_alerts;
+use crate::commands::trash::auto_purge_trash;
+use crate::commands::prices::{sync_prices, FetchPolicy};
+use crate::db::queries;
+use crate::models::{Interval, RefreshProgress};
+use crate::state::AppState;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::async_runtime::JoinHandle;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio::sync::Notify;
+
+/// Tauri event name carrying a `RefreshProgress` payload.
+pub const REFRESH_PROGRESS_EVENT: &str = "price-refresh-progress";
+
+/// Settings key shared with the frontend Preferences card (seconds, "0" = manual only).
+pub const REFRESH_INTERVAL_KEY: &str = "refresh_interval";
+
+const DEFAULT_REFRESH_INTERVAL_SECS: u64 = 3600;
+
+/// Parse the stored refresh interval. `None` means manual refresh only.
+pub fn parse_refresh_interval(value: Option<&str>) -> Option<Duration> {
+    let default = Duration::from_secs(DEFAULT_REFRESH_INTERVAL_SECS);
+    match value.map(str::trim) {
+        None | Some("") => Some(default),
+        Some(v) => match v.parse::<u64>() {
+            Ok(0) => None,
+            Ok(secs) => Some(Duration::from_secs(secs)),
+            Err(_) => Some(default),
+        },
+    }
+}
+
+/// Background price refresh task. Dropping the handle stops it.
+pub struct SchedulerHandle {
+    wake: Arc<Notify>,
+    task: JoinHandle<()>,
+}
+
+impl SchedulerHandle {
+    /// Re-read the refresh interval now (e.g. after the setting changed).
+    pub fn wake(&self) {
+        self.wake.notify_one();
+    }
+}
+
+impl Drop for SchedulerHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Start refreshing all active assets: once immediately, then on the configured interval.
+pub fn spawn(app: AppHandle) -> SchedulerHandle {
+    let wake = Arc::new(Notify::new());
+    let task = tauri::async_runtime::spawn(run(app, wake.clone()));
+    SchedulerHandle { wake, task }
+}
+
+async fn run(app: AppHandle, wake: Arc<Notify>) {
+    loop {
+        let setting = app
+            .state::<AppState>()
+            .with_db(|conn| queries::settings::get_setting(conn, REFRESH_INTERVAL_KEY))
+            .ok()
+            .flatten();
+        match parse_refresh_interval(setting.as_deref()) {
+            Some(every) => {
+                refresh_all(&app, every).await;
+                tokio::select! {
+                    _ = tokio::time::sleep(every) => {}
+                    _ = wake.notified() => {}
+                }
+            }
+            // Manual only: idle until the setting changes
+            None => wake.notified().await,
+        }
+    }
+}
+
+async fn refresh_all(app: &AppHandle, every: Duration) {
+    let state = app.state::<AppState>();
+    auto_purge_trash(&state);
+    let Ok(assets) = state.with_db(queries::assets::list_assets) else {
+        return;
+    };
+
+    // Anything fetched within this cycle (minus a little slack) is fresh enough,
+    // and rate limits queue instead of failing.
+    let policy = FetchPolicy {
+        max_age_secs: (every.as_secs() as i64 - 60).max(0),
+        max_wait: None,
+    };
+    let total = assets.len();
+    for (i, asset) in assets.iter().enumerate() {
+        let result = sync_prices(&state, &asset.id, Interval::OneDay, policy).await;
+        if result.is_ok() {
+            notify_alerts(app, &state, &asset.id);
+        }
+        let _ = app.emit(
+            REFRESH_PROGRESS_EVENT,
+            RefreshProgress {
+                completed: i + 1,
+                total,
+                asset_id: Some(asset.id.clone()),
+                symbol: Some(asset.symbol.clone()),
+                error: result.err().map(|e| e.to_string()),
+                done: false,
+            },
+        );
+    }
+    let _ = app.emit(
+        REFRESH_PROGRESS_EVENT,
+        RefreshProgress {
+            completed: total,
+            total,
+            asset_id: None,
+            symbol: None,
+            error: None,
+            done: true,
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_refresh_interval() {
+        assert_eq!(parse_refresh_interval(None), Some(Duration::from_secs(3600)));
+        assert_eq!(parse_refresh_interval(Some("1800")), Some(Duration::from_secs(1800)));
+        assert_eq!(parse_refresh_interval(Some("0")), None);
+        assert_eq!(parse_refresh_interval(Some("garbage")), Some(Duration::from_secs(3600)));
+    }
+}
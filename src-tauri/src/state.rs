@@ -1,14 +1,18 @@
+use crate::db::queries;
+use crate::rate_limit::{RateLimit, RateLimiter, CONFIGURABLE_PROVIDERS, RATE_LIMIT_SUFFIX};
+use crate::scheduler::SchedulerHandle;
+use crate::streaming::StreamHandle;
 use rusqlite::Connection;
-use std::collections::HashMap;
-use std::collections::VecDeque;
 use std::path::PathBuf;
-use std::sync::Mutex;
-use tokio::time::Instant;
+use std::sync::{Arc, Mutex};
+use tauri::AppHandle;
 
 pub struct AppState {
     pub db: Mutex<Option<Connection>>,
     pub db_path: PathBuf,
-    pub rate_limits: Mutex<HashMap<String, VecDeque<Instant>>>,
+    pub rate_limiter: Arc<RateLimiter>,
+    pub price_stream: Mutex<Option<StreamHandle>>,
+    pub scheduler: Mutex<Option<SchedulerHandle>>,
 }
 
 impl AppState {
@@ -16,7 +20,9 @@ impl AppState {
         Self {
             db: Mutex::new(None),
             db_path,
-            rate_limits: Mutex::new(HashMap::new()),
+            rate_limiter: Arc::new(RateLimiter::new()),
+            price_stream: Mutex::new(None),
+            scheduler: Mutex::new(None),
         }
     }
 
@@ -29,32 +35,32 @@ impl AppState {
         f(conn)
     }
 
-    pub fn check_rate_limit(&self, provider: &str) -> anyhow::Result<()> {
-        let max_requests = match provider {
-            "twelve_data" => 8,
-            "coingecko" => 30,
-            "binance" => 60,
-            "alpha_vantage" => 25,
-            _ => 10,
-        };
-        let window = match provider {
-            "alpha_vantage" => std::time::Duration::from_secs(86400),
-            _ => std::time::Duration::from_secs(60),
-        };
-
-        let mut limits = self.rate_limits.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
-        let queue = limits.entry(provider.to_string()).or_default();
+    /// Start the background refresh scheduler (replacing any running one).
+    pub fn start_scheduler(&self, app: AppHandle) -> anyhow::Result<()> {
+        let mut scheduler = self.scheduler.lock().map_err(|e| anyhow::anyhow!("Lock error: {}", e))?;
+        *scheduler = Some(crate::scheduler::spawn(app));
+        Ok(())
+    }
 
-        let now = Instant::now();
-        while queue.front().is_some_and(|t| now.duration_since(*t) > window) {
-            queue.pop_front();
+    /// Nudge the scheduler to pick up a changed refresh interval.
+    pub fn wake_scheduler(&self) {
+        if let Ok(scheduler) = self.scheduler.lock() {
+            if let Some(handle) = scheduler.as_ref() {
+                handle.wake();
+            }
         }
+    }
 
-        if queue.len() >= max_requests {
-            anyhow::bail!("Rate limit exceeded for {}. Please wait before making more requests.", provider);
+    /// Apply `{provider}_rate_limit` settings, falling back to free-tier limits.
+    pub fn load_rate_limits(&self) -> anyhow::Result<()> {
+        for provider in CONFIGURABLE_PROVIDERS {
+            let key = format!("{}{}", provider, RATE_LIMIT_SUFFIX);
+            let limit = self
+                .with_db(|conn| queries::settings::get_setting(conn, &key))?
+                .and_then(|v| RateLimit::parse(&v).ok())
+                .unwrap_or_else(|| RateLimit::default_for(provider));
+            self.rate_limiter.set_limit(provider, limit);
         }
-
-        queue.push_back(now);
         Ok(())
     }
 }
@@ -62,32 +68,27 @@ impl AppState {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::db::test_db;
 
-    #[test]
-    fn test_rate_limit_allows_within_limit() {
+    #[tokio::test(start_paused = true)]
+    async fn test_load_rate_limits_from_settings() {
         let state = AppState::new(PathBuf::from("/tmp/test.db"));
-        for _ in 0..8 {
-            state.check_rate_limit("twelve_data").unwrap();
+        {
+            let conn = test_db();
+            queries::settings::set_setting(&conn, "twelve_data_rate_limit", "2/60").unwrap();
+            queries::settings::set_setting(&conn, "coingecko_rate_limit", "garbage").unwrap();
+            *state.db.lock().unwrap() = Some(conn);
         }
-    }
+        state.load_rate_limits().unwrap();
 
-    #[test]
-    fn test_rate_limit_blocks_over_limit() {
-        let state = AppState::new(PathBuf::from("/tmp/test.db"));
-        for _ in 0..8 {
-            state.check_rate_limit("twelve_data").unwrap();
+        for _ in 0..2 {
+            assert!(state.rate_limiter.try_acquire("twelve_data", 1).unwrap().is_none());
         }
-        let result = state.check_rate_limit("twelve_data");
-        assert!(result.is_err());
-    }
-
-    #[test]
-    fn test_rate_limits_independent_per_provider() {
-        let state = AppState::new(PathBuf::from("/tmp/test.db"));
-        for _ in 0..8 {
-            state.check_rate_limit("twelve_data").unwrap();
+        assert!(state.rate_limiter.try_acquire("twelve_data", 1).unwrap().is_some());
+        // Unparseable value keeps the free-tier default of 30/min
+        for _ in 0..30 {
+            assert!(state.rate_limiter.try_acquire("coingecko", 1).unwrap().is_none());
         }
-        // Different provider should still work
-        state.check_rate_limit("coingecko").unwrap();
+        assert!(state.rate_limiter.try_acquire("coingecko", 1).unwrap().is_some());
     }
 }
@@ -0,0 +1,193 @@
+use crate::models::PriceTick;
+use crate::streaming::{wait_for_shutdown, Backoff, Subscription, TickSink};
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+pub const BINANCE_WS_URL: &str = "wss://stream.binance.com:9443";
+
+/// Combined-stream envelope: {"stream":"btcusdt@miniTicker","data":{...}}
+#[derive(Deserialize)]
+struct StreamEnvelope {
+    data: MiniTicker,
+}
+
+/// 24h mini ticker payload — only the fields we use.
+#[derive(Deserialize)]
+struct MiniTicker {
+    /// Event time (ms)
+    #[serde(rename = "E")]
+    event_time: i64,
+    /// Symbol, e.g. "BTCUSDT"
+    #[serde(rename = "s")]
+    symbol: String,
+    /// Last price
+    #[serde(rename = "c")]
+    close: String,
+}
+
+/// Build the combined-stream URL for all subscriptions.
+pub fn stream_url(base_url: &str, subscriptions: &[Subscription]) -> String {
+    let streams: Vec<String> = subscriptions
+        .iter()
+        .map(|s| format!("{}@miniTicker", s.provider_symbol.to_lowercase()))
+        .collect();
+    format!("{}/stream?streams={}", base_url.trim_end_matches('/'), streams.join("/"))
+}
+
+fn parse_message(text: &str, by_symbol: &HashMap<String, Subscription>) -> Option<PriceTick> {
+    let envelope: StreamEnvelope = serde_json::from_str(text).ok()?;
+    let sub = by_symbol.get(&envelope.data.symbol.to_uppercase())?;
+    let price = envelope.data.close.parse::<f64>().ok()?;
+    Some(PriceTick {
+        asset_id: sub.asset_id.clone(),
+        symbol: sub.symbol.clone(),
+        price,
+        ts: envelope.data.event_time / 1000,
+        provider: "binance".to_string(),
+    })
+}
+
+/// Follow Binance mini-ticker streams until `shutdown` flips, reconnecting with
+/// exponential backoff whenever the socket drops (Binance also closes every 24h).
+pub async fn run(
+    base_url: String,
+    subscriptions: Vec<Subscription>,
+    sink: TickSink,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    if subscriptions.is_empty() {
+        return;
+    }
+    let url = stream_url(&base_url, &subscriptions);
+    let by_symbol: HashMap<String, Subscription> = subscriptions
+        .into_iter()
+        .map(|s| (s.provider_symbol.to_uppercase(), s))
+        .collect();
+    let mut backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(60));
+
+    loop {
+        if let Ok((ws, _)) = connect_async(url.as_str()).await {
+            backoff.reset();
+            let (mut write, mut read) = ws.split();
+            loop {
+                tokio::select! {
+                    _ = wait_for_shutdown(&mut shutdown) => {
+                        let _ = write.send(Message::Close(None)).await;
+                        return;
+                    }
+                    msg = read.next() => match msg {
+                        Some(Ok(Message::Text(text))) => {
+                            if let Some(tick) = parse_message(&text, &by_symbol) {
+                                sink(tick);
+                            }
+                        }
+                        // Pings are answered by tungstenite itself
+                        Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                        Some(Ok(_)) => {}
+                    }
+                }
+            }
+        }
+
+        tokio::select! {
+            _ = wait_for_shutdown(&mut shutdown) => return,
+            _ = tokio::time::sleep(backoff.next_delay()) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use tokio::net::TcpListener;
+    use tokio::sync::mpsc;
+
+    fn sub(symbol: &str) -> Subscription {
+        Subscription {
+            asset_id: format!("id-{}", symbol),
+            symbol: symbol.to_string(),
+            provider_symbol: format!("{}USDT", symbol),
+        }
+    }
+
+    fn ticker(symbol: &str, price: &str) -> String {
+        format!(
+            r#"{{"stream":"{}@miniTicker","data":{{"e":"24hrMiniTicker","E":1700000000123,"s":"{}","c":"{}","o":"1","h":"1","l":"1","v":"1","q":"1"}}}}"#,
+            symbol.to_lowercase(),
+            symbol,
+            price
+        )
+    }
+
+    /// Local stand-in for the Binance stream: each accepted connection sends one
+    /// message per entry in `batches` and then closes.
+    async fn spawn_server(batches: Vec<Vec<String>>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            for batch in batches {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+                for msg in batch {
+                    ws.send(Message::Text(msg)).await.unwrap();
+                }
+                let _ = ws.close(None).await;
+            }
+        });
+        format!("ws://{}", addr)
+    }
+
+    fn channel_sink() -> (TickSink, mpsc::UnboundedReceiver<PriceTick>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let sink: TickSink = Arc::new(move |tick| {
+            let _ = tx.send(tick);
+        });
+        (sink, rx)
+    }
+
+    #[test]
+    fn test_stream_url() {
+        let url = stream_url("wss://example.com/", &[sub("BTC"), sub("ETH")]);
+        assert_eq!(url, "wss://example.com/stream?streams=btcusdt@miniTicker/ethusdt@miniTicker");
+    }
+
+    #[test]
+    fn test_parse_message_ignores_unknown_symbols() {
+        let by_symbol: HashMap<String, Subscription> =
+            [("BTCUSDT".to_string(), sub("BTC"))].into_iter().collect();
+        let tick = parse_message(&ticker("BTCUSDT", "42000.5"), &by_symbol).unwrap();
+        assert_eq!(tick.asset_id, "id-BTC");
+        assert_eq!(tick.price, 42000.5);
+        assert_eq!(tick.ts, 1700000000);
+        assert!(parse_message(&ticker("ETHUSDT", "2000"), &by_symbol).is_none());
+        assert!(parse_message("not json", &by_symbol).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_streams_ticks_and_reconnects() {
+        let url = spawn_server(vec![
+            vec![ticker("BTCUSDT", "100.0")],
+            vec![ticker("ETHUSDT", "200.0")],
+        ])
+        .await;
+        let (sink, mut rx) = channel_sink();
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let task = tokio::spawn(run(url, vec![sub("BTC"), sub("ETH")], sink, shutdown_rx));
+
+        let first = tokio::time::timeout(Duration::from_secs(5), rx.recv()).await.unwrap().unwrap();
+        assert_eq!(first.symbol, "BTC");
+        assert_eq!(first.price, 100.0);
+
+        // Server closed the first connection; the client should come back after backoff
+        let second = tokio::time::timeout(Duration::from_secs(5), rx.recv()).await.unwrap().unwrap();
+        assert_eq!(second.symbol, "ETH");
+
+        shutdown_tx.send(true).unwrap();
+        tokio::time::timeout(Duration::from_secs(5), task).await.unwrap().unwrap();
+    }
+}
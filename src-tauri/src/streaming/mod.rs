@@ -0,0 +1,94 @@
+pub mod binance_ws;
+pub mod poller;
+
+use crate::models::PriceTick;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+
+/// Tauri event name carrying a `PriceTick` payload.
+pub const PRICE_TICK_EVENT: &str = "price-tick";
+
+/// Callback invoked for every live price. The command layer forwards these as
+/// Tauri events; tests collect them into a channel.
+pub type TickSink = Arc<dyn Fn(PriceTick) + Send + Sync>;
+
+/// One asset to follow, with the symbol in the provider's own notation.
+#[derive(Debug, Clone)]
+pub struct Subscription {
+    pub asset_id: String,
+    pub symbol: String,
+    pub provider_symbol: String,
+}
+
+/// Exponential backoff between reconnect attempts, reset after a successful connect.
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    min: Duration,
+    max: Duration,
+    current: Duration,
+}
+
+impl Backoff {
+    pub fn new(min: Duration, max: Duration) -> Self {
+        Self { min, max, current: min }
+    }
+
+    /// Delay to wait now; doubles the next one up to `max`.
+    pub fn next_delay(&mut self) -> Duration {
+        let delay = self.current;
+        self.current = (self.current * 2).min(self.max);
+        delay
+    }
+
+    pub fn reset(&mut self) {
+        self.current = self.min;
+    }
+}
+
+/// Running stream/poll tasks. Dropping the handle (or calling `stop`) shuts them down.
+pub struct StreamHandle {
+    shutdown: watch::Sender<bool>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl StreamHandle {
+    pub fn new(shutdown: watch::Sender<bool>, tasks: Vec<JoinHandle<()>>) -> Self {
+        Self { shutdown, tasks }
+    }
+}
+
+impl Drop for StreamHandle {
+    fn drop(&mut self) {
+        let _ = self.shutdown.send(true);
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+/// Wait until the shutdown flag flips (or the sender is gone).
+pub(crate) async fn wait_for_shutdown(shutdown: &mut watch::Receiver<bool>) {
+    while !*shutdown.borrow() {
+        if shutdown.changed().await.is_err() {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        let mut backoff = Backoff::new(Duration::from_secs(1), Duration::from_secs(5));
+        assert_eq!(backoff.next_delay(), Duration::from_secs(1));
+        assert_eq!(backoff.next_delay(), Duration::from_secs(2));
+        assert_eq!(backoff.next_delay(), Duration::from_secs(4));
+        assert_eq!(backoff.next_delay(), Duration::from_secs(5));
+        backoff.reset();
+        assert_eq!(backoff.next_delay(), Duration::from_secs(1));
+    }
+}
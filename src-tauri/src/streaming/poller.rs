@@ -0,0 +1,105 @@
+use crate::models::PriceTick;
+use crate::providers::MarketDataProvider;
+use crate::streaming::{wait_for_shutdown, Subscription, TickSink};
+use chrono::Utc;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Polling fallback for providers without a push API: fetch the current price of
+/// every subscription once per `every` (one batch request) and forward it to the sink.
+/// Rate limiting is the provider's own throttle; a refused request skips the round.
+pub async fn run(
+    provider: Arc<dyn MarketDataProvider>,
+    subscriptions: Vec<Subscription>,
+    every: Duration,
+    sink: TickSink,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    if subscriptions.is_empty() {
+        return;
+    }
+    let symbols: Vec<String> = subscriptions.iter().map(|s| s.provider_symbol.clone()).collect();
+    loop {
+        // A failed poll is not fatal — the next round tries again
+        if let Ok(prices) = provider.fetch_current_prices(&symbols).await {
+            let ts = Utc::now().timestamp();
+            for sub in &subscriptions {
+                if let Some(&price) = prices.get(&sub.provider_symbol) {
+                    sink(PriceTick {
+                        asset_id: sub.asset_id.clone(),
+                        symbol: sub.symbol.clone(),
+                        price,
+                        ts,
+                        provider: provider.name().to_string(),
+                    });
+                }
+            }
+        }
+
+        tokio::select! {
+            _ = wait_for_shutdown(&mut shutdown) => return,
+            _ = tokio::time::sleep(every) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DateRange, Interval, OHLCVRow};
+    use crate::providers::error::{ProviderError, ProviderResult};
+    use async_trait::async_trait;
+    use tokio::sync::mpsc;
+
+    struct FixedPrice;
+
+    #[async_trait]
+    impl MarketDataProvider for FixedPrice {
+        fn name(&self) -> &str {
+            "fixed"
+        }
+        async fn fetch_ohlcv(
+            &self,
+            _symbol: &str,
+            _interval: Interval,
+            _range: &DateRange,
+        ) -> ProviderResult<Vec<OHLCVRow>> {
+            Ok(vec![])
+        }
+        async fn fetch_current_price(&self, symbol: &str) -> ProviderResult<f64> {
+            match symbol {
+                "AAPL" => Ok(190.0),
+                _ => Err(ProviderError::NotFound(symbol.to_string())),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_poller_emits_ticks_and_skips_failures() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let sink: TickSink = Arc::new(move |tick| {
+            let _ = tx.send(tick);
+        });
+        let subs = vec![
+            Subscription { asset_id: "a".into(), symbol: "BAD".into(), provider_symbol: "BAD".into() },
+            Subscription { asset_id: "b".into(), symbol: "AAPL".into(), provider_symbol: "AAPL".into() },
+        ];
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let task = tokio::spawn(run(
+            Arc::new(FixedPrice),
+            subs,
+            Duration::from_secs(60),
+            sink,
+            shutdown_rx,
+        ));
+
+        let tick = tokio::time::timeout(Duration::from_secs(5), rx.recv()).await.unwrap().unwrap();
+        assert_eq!(tick.asset_id, "b");
+        assert_eq!(tick.price, 190.0);
+        assert_eq!(tick.provider, "fixed");
+
+        shutdown_tx.send(true).unwrap();
+        tokio::time::timeout(Duration::from_secs(5), task).await.unwrap().unwrap();
+    }
+}
@@ -7,11 +7,13 @@ import { Button } from "@/components/ui/button";
 interface Props {
   data: OHLCVRow[];
   height?: number;
+  /** Show hours/minutes on the time axis (intraday candles) */
+  timeVisible?: boolean;
 }
 
 type ChartType = "candlestick" | "line";
 
-export function AssetChart({ data, height = 400 }: Props) {
+export function AssetChart({ data, height = 400, timeVisible = false }: Props) {
   const containerRef = useRef<HTMLDivElement>(null);
   const chartRef = useRef<IChartApi | null>(null);
   const canShowCandlestick = useMemo(() => hasRealOHLC(data), [data]);
@@ -44,7 +46,7 @@ export function AssetChart({ data, height = 400 }: Props) {
       },
       timeScale: {
         borderColor: "#27272a",
-        timeVisible: false,
+        timeVisible,
       },
       rightPriceScale: {
         borderColor: "#27272a",
@@ -85,7 +87,7 @@ export function AssetChart({ data, height = 400 }: Props) {
       chart.remove();
       chartRef.current = null;
     };
-  }, [data, height, chartType]);
+  }, [data, height, chartType, timeVisible]);
 
   return (
     <div>
@@ -3,13 +3,22 @@ import { Header } from "./Header";
 import { useNavigationStore } from "@/stores/navigationStore";
 import { Dashboard } from "@/pages/Dashboard";
 import { TransactionsPage } from "@/pages/Transactions";
+import { CashPage } from "@/pages/Cash";
 import { AssetManagerPage } from "@/pages/AssetManager";
 import { SettingsPage } from "@/pages/Settings";
 import { RealizedPnLPage } from "@/pages/RealizedPnL";
 import { PerformancePage } from "@/pages/Performance";
+import { RebalancePage } from "@/pages/Rebalance";
+import { WatchlistsPage } from "@/pages/Watchlists";
+import { AlertsPage } from "@/pages/Alerts";
+import { TrashPage } from "@/pages/Trash";
+import { useBackgroundRefresh } from "@/hooks/usePrices";
+import { useAlertNotifications } from "@/hooks/useAlerts";
 
 export function AppShell() {
   const { activePage } = useNavigationStore();
+  useBackgroundRefresh();
+  useAlertNotifications();
 
   return (
     <div className="flex h-screen overflow-hidden">
@@ -19,8 +28,13 @@ export function AppShell() {
         <main className="flex-1 overflow-y-scroll p-6">
           {activePage === "dashboard" && <Dashboard />}
           {activePage === "transactions" && <TransactionsPage />}
+          {activePage === "cash" && <CashPage />}
           {activePage === "realized-pnl" && <RealizedPnLPage />}
           {activePage === "performance" && <PerformancePage />}
+          {activePage === "rebalance" && <RebalancePage />}
+          {activePage === "watchlists" && <WatchlistsPage />}
+          {activePage === "alerts" && <AlertsPage />}
+          {activePage === "trash" && <TrashPage />}
           {activePage === "asset-manager" && <AssetManagerPage />}
           {activePage === "settings" && <SettingsPage />}
         </main>
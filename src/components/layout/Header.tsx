@@ -1,26 +1,19 @@
 import { Button } from "@/components/ui/button";
 import { useNavigationStore } from "@/stores/navigationStore";
 import { useAssetsStore } from "@/stores/assetsStore";
-import { useAssets } from "@/hooks/useAssets";
-import { useRefreshAsset } from "@/hooks/usePrices";
+import { useFetchQuotes } from "@/hooks/usePrices";
 import { RefreshCw } from "lucide-react";
-import { useState } from "react";
 
 export function Header() {
   const { activePage } = useNavigationStore();
   const { selectedAssetId } = useAssetsStore();
-  const { data: assets } = useAssets();
-  const refreshMutation = useRefreshAsset();
-  const [refreshing, setRefreshing] = useState(false);
+  const quotesMutation = useFetchQuotes();
+  const refreshing = quotesMutation.isPending;
 
   const title = activePage === "dashboard" ? "Dashboard" : "Settings";
 
-  async function handleRefreshAll() {
-    setRefreshing(true);
-    for (const asset of assets ?? []) {
-      await refreshMutation.mutateAsync(asset.id);
-    }
-    setRefreshing(false);
+  function handleRefreshAll() {
+    quotesMutation.mutate();
   }
 
   return (
@@ -1,12 +1,17 @@
 import { cn } from "@/lib/utils";
 import { useNavigationStore, type Page } from "@/stores/navigationStore";
-import { LayoutDashboard, Receipt, TrendingUp, Activity, Database, Settings } from "lucide-react";
+import { LayoutDashboard, Receipt, Wallet, TrendingUp, Activity, Scale, Eye, Bell, Trash2, Database, Settings } from "lucide-react";
 
 const navItems: { page: Page; label: string; icon: typeof LayoutDashboard }[] = [
   { page: "dashboard", label: "Dashboard", icon: LayoutDashboard },
   { page: "transactions", label: "Transactions", icon: Receipt },
+  { page: "cash", label: "Cash", icon: Wallet },
   { page: "realized-pnl", label: "Realized P&L", icon: TrendingUp },
   { page: "performance", label: "Performance", icon: Activity },
+  { page: "rebalance", label: "Rebalance", icon: Scale },
+  { page: "watchlists", label: "Watchlists", icon: Eye },
+  { page: "alerts", label: "Alerts", icon: Bell },
+  { page: "trash", label: "Trash", icon: Trash2 },
   { page: "asset-manager", label: "Asset Manager", icon: Database },
   { page: "settings", label: "Settings", icon: Settings },
 ];
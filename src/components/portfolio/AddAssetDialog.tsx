@@ -19,19 +19,31 @@ import {
 import { useAddAsset } from "@/hooks/useAssets";
 import { useSymbolSearch } from "@/hooks/useSymbolSearch";
 import { addTransaction } from "@/lib/tauri/transactions";
+import { ASSET_TYPE_LABELS, isAssetType } from "@/lib/utils/assetTypes";
 import type { AssetType, SymbolSearchResult } from "@/types";
 import { Plus, Search } from "lucide-react";
 
 const providerBadgeClass: Record<string, string> = {
-  TwelveData: "border-blue-500/40 bg-blue-500/10 text-blue-400",
-  CoinGecko: "border-violet-500/40 bg-violet-500/10 text-violet-400",
-  Binance: "border-yellow-500/40 bg-yellow-500/10 text-yellow-400",
+  twelve_data: "border-blue-500/40 bg-blue-500/10 text-blue-400",
+  coingecko: "border-violet-500/40 bg-violet-500/10 text-violet-400",
+  binance: "border-yellow-500/40 bg-yellow-500/10 text-yellow-400",
+};
+
+const providerLabel: Record<string, string> = {
+  twelve_data: "TwelveData",
+  coingecko: "CoinGecko",
+  binance: "Binance",
 };
 
 const typeBadgeClass: Record<string, string> = {
   stock: "text-blue-400",
   crypto: "text-violet-400",
   commodity: "text-amber-400",
+  etf: "text-sky-400",
+  mutual_fund: "text-teal-400",
+  bond: "text-emerald-400",
+  forex: "text-lime-400",
+  index: "text-indigo-400",
 };
 
 export function AddAssetDialog() {
@@ -40,7 +52,10 @@ export function AddAssetDialog() {
   const [showResults, setShowResults] = useState(false);
   const [symbol, setSymbol] = useState("");
   const [name, setName] = useState("");
+  const [exchange, setExchange] = useState("");
   const [assetType, setAssetType] = useState<AssetType>("stock");
+  // The picked search result, kept so its provider identifier is stored with the asset
+  const [selected, setSelected] = useState<SymbolSearchResult | null>(null);
   const [purchaseMode, setPurchaseMode] = useState<"quantity" | "total" | "conversion">("quantity");
   const [purchaseDate, setPurchaseDate] = useState(() => new Date().toISOString().slice(0, 10));
 
@@ -57,7 +72,9 @@ export function AddAssetDialog() {
     setShowResults(false);
     setSymbol("");
     setName("");
+    setExchange("");
     setAssetType("stock");
+    setSelected(null);
     setPurchaseMode("quantity");
     setPurchaseDate(new Date().toISOString().slice(0, 10));
     setPurchaseQty("");
@@ -69,9 +86,10 @@ export function AddAssetDialog() {
   function handleSelectResult(result: SymbolSearchResult) {
     setSymbol(result.symbol);
     setName(result.name);
-    const mapped = result.asset_type as AssetType;
-    if (mapped === "stock" || mapped === "crypto" || mapped === "commodity") {
-      setAssetType(mapped);
+    setExchange(result.exchange ?? "");
+    setSelected(result);
+    if (isAssetType(result.asset_type)) {
+      setAssetType(result.asset_type);
     }
     setShowResults(false);
     setSearchQuery("");
@@ -156,7 +174,19 @@ export function AddAssetDialog() {
     }
 
     try {
-      const asset = await addAsset.mutateAsync({ symbol: symbol.trim(), name: name.trim(), assetType });
+      // A symbol or exchange edited after picking a result no longer matches its identifier
+      const picked =
+        selected && selected.symbol === symbol.trim() && (selected.exchange ?? "") === exchange.trim()
+          ? selected
+          : null;
+      const asset = await addAsset.mutateAsync({
+        symbol: symbol.trim(),
+        name: name.trim(),
+        assetType,
+        exchange: exchange.trim() || undefined,
+        provider: picked?.provider,
+        providerId: picked?.provider_id,
+      });
       if (hasAll && parsedQty !== undefined && parsedPrice !== undefined) {
         const ts = Math.floor(new Date(purchaseDate).getTime() / 1000);
         await addTransaction(asset.id, "buy", parsedQty, parsedPrice, ts, undefined);
@@ -224,10 +254,10 @@ export function AddAssetDialog() {
                         <span className="text-xs text-zinc-500">{result.exchange}</span>
                       )}
                       <span className={`text-xs ${typeBadgeClass[result.asset_type] ?? "text-zinc-400"}`}>
-                        {result.asset_type}
+                        {isAssetType(result.asset_type) ? ASSET_TYPE_LABELS[result.asset_type] : result.asset_type}
                       </span>
                       <span className={`rounded border px-1.5 py-0.5 text-xs ${providerBadgeClass[result.provider] ?? ""}`}>
-                        {result.provider}
+                        {providerLabel[result.provider] ?? result.provider}
                       </span>
                     </div>
                   </button>
@@ -246,6 +276,15 @@ export function AddAssetDialog() {
               onChange={(e) => setSymbol(e.target.value)}
             />
           </div>
+          <div className="space-y-2">
+            <Label htmlFor="exchange">Exchange (optional)</Label>
+            <Input
+              id="exchange"
+              placeholder="e.g. NYSE, TSX"
+              value={exchange}
+              onChange={(e) => setExchange(e.target.value)}
+            />
+          </div>
           <div className="space-y-2">
             <Label htmlFor="name">Name</Label>
             <Input
@@ -262,9 +301,9 @@ export function AddAssetDialog() {
                 <SelectValue />
               </SelectTrigger>
               <SelectContent>
-                <SelectItem value="stock">Stock</SelectItem>
-                <SelectItem value="crypto">Crypto</SelectItem>
-                <SelectItem value="commodity">Commodity</SelectItem>
+                {Object.entries(ASSET_TYPE_LABELS).map(([value, label]) => (
+                  <SelectItem key={value} value={value}>{label}</SelectItem>
+                ))}
               </SelectContent>
             </Select>
           </div>
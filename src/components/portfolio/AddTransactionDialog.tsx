@@ -18,6 +18,8 @@ import {
 } from "@/components/ui/select";
 import { useAddTransaction, useUpdateTransaction } from "@/hooks/useTransactions";
 import { useAssets } from "@/hooks/useAssets";
+import { useAccounts } from "@/hooks/useCash";
+import { errorMessage } from "@/lib/utils/commandError";
 import type { Transaction, TxType } from "@/types";
 import { Plus } from "lucide-react";
 
@@ -43,6 +45,7 @@ function daysInMonth(year: string, month: string): number {
 }
 
 const CURRENT_YEAR = new Date().getFullYear();
+const NO_ACCOUNT = "none";
 const YEAR_OPTIONS = Array.from({ length: CURRENT_YEAR - 1989 }, (_, i) => CURRENT_YEAR - i);
 
 export function AddTransactionDialog({ assetId, transaction, open: controlledOpen, onOpenChange }: Props) {
@@ -66,8 +69,11 @@ export function AddTransactionDialog({ assetId, transaction, open: controlledOpe
   const [dateMonth, setDateMonth] = useState(initM ?? "");
   const [dateDay, setDateDay] = useState(initD ?? "");
   const [notes, setNotes] = useState(transaction?.notes ?? "");
+  const [fee, setFee] = useState(transaction && transaction.fee > 0 ? String(transaction.fee) : "");
+  const [accountId, setAccountId] = useState(transaction?.account_id ?? NO_ACCOUNT);
   const [error, setError] = useState("");
   const { data: assets } = useAssets();
+  const { data: accounts } = useAccounts();
   const addTransaction = useAddTransaction(effectiveAssetId || selectedAssetId);
   const updateTx = useUpdateTransaction(effectiveAssetId || selectedAssetId);
 
@@ -84,6 +90,8 @@ export function AddTransactionDialog({ assetId, transaction, open: controlledOpe
     setDateMonth(todayParts[1] ?? "");
     setDateDay(todayParts[2] ?? "");
     setNotes("");
+    setFee("");
+    setAccountId(NO_ACCOUNT);
     setError("");
   }
 
@@ -151,6 +159,13 @@ export function AddTransactionDialog({ assetId, transaction, open: controlledOpe
     }
     const ts = Math.floor(new Date(`${dateYear}-${dateMonth}-${dateDay}`).getTime() / 1000);
 
+    const feeValue = fee.trim() ? parseFloat(fee) : 0;
+    if (isNaN(feeValue) || feeValue < 0) {
+      setError("Fee must be zero or a positive number");
+      return;
+    }
+    const account = accountId === NO_ACCOUNT ? undefined : accountId;
+
     try {
       if (isEdit) {
         await updateTx.mutateAsync({
@@ -160,6 +175,8 @@ export function AddTransactionDialog({ assetId, transaction, open: controlledOpe
           priceUsd: price,
           ts,
           notes: notes.trim() || undefined,
+          fee: feeValue,
+          accountId: account,
         });
       } else {
         await addTransaction.mutateAsync({
@@ -168,12 +185,14 @@ export function AddTransactionDialog({ assetId, transaction, open: controlledOpe
           priceUsd: price,
           ts,
           notes: notes.trim() || undefined,
+          fee: feeValue,
+          accountId: account,
         });
       }
       reset();
       setOpen(false);
     } catch (err) {
-      setError(String(err));
+      setError(errorMessage(err));
     }
   }
 
@@ -196,7 +215,7 @@ export function AddTransactionDialog({ assetId, transaction, open: controlledOpe
         <form onSubmit={handleSubmit} className="space-y-4">
           {isEdit && transaction.locked_at !== null && (
             <div className="rounded-md border border-amber-500/30 bg-amber-500/10 px-3 py-2 text-sm text-amber-400">
-              This transaction is locked. Changes will affect your portfolio history.
+              This transaction is locked. Unlock it before making changes.
             </div>
           )}
           {!isEdit && !assetId && (
@@ -209,7 +228,7 @@ export function AddTransactionDialog({ assetId, transaction, open: controlledOpe
                 <SelectContent>
                   {(assets ?? []).map((a) => (
                     <SelectItem key={a.id} value={a.id}>
-                      {a.symbol} — {a.name}
+                      {a.symbol}{a.exchange ? ` (${a.exchange})` : ""} — {a.name}
                     </SelectItem>
                   ))}
                 </SelectContent>
@@ -399,6 +418,34 @@ export function AddTransactionDialog({ assetId, transaction, open: controlledOpe
                 </Select>
             </div>
           </div>
+          <div className="flex gap-2">
+            <div className="flex-1 space-y-2">
+              <Label htmlFor="fee">Fee (USD)</Label>
+              <Input
+                id="fee"
+                type="number"
+                step="any"
+                min="0"
+                placeholder="0"
+                value={fee}
+                onChange={(e) => setFee(e.target.value)}
+              />
+            </div>
+            <div className="flex-1 space-y-2">
+              <Label>Cash account</Label>
+              <Select value={accountId} onValueChange={setAccountId}>
+                <SelectTrigger>
+                  <SelectValue />
+                </SelectTrigger>
+                <SelectContent>
+                  <SelectItem value={NO_ACCOUNT}>None</SelectItem>
+                  {(accounts ?? []).map((a) => (
+                    <SelectItem key={a.id} value={a.id}>{a.name}</SelectItem>
+                  ))}
+                </SelectContent>
+              </Select>
+            </div>
+          </div>
           <div className="space-y-2">
             <Label htmlFor="notes">Notes (optional)</Label>
             <Input
@@ -409,11 +456,13 @@ export function AddTransactionDialog({ assetId, transaction, open: controlledOpe
             />
           </div>
           {error && <p className="text-sm text-destructive">{error}</p>}
-          <Button type="submit" className="w-full" disabled={isPending}>
+          <Button
+            type="submit"
+            className="w-full"
+            disabled={isPending || (isEdit && transaction.locked_at !== null)}
+          >
             {isPending
               ? (isEdit ? "Saving..." : "Adding...")
-              : isEdit && transaction.locked_at !== null
-              ? "Override & Save"
               : isEdit
               ? "Save Changes"
               : "Add Transaction"}
@@ -3,31 +3,76 @@ import { Badge } from "@/components/ui/badge";
 import { AssetChart } from "@/components/charts/AssetChart";
 import { AddTransactionDialog } from "@/components/portfolio/AddTransactionDialog";
 import { TransactionList } from "@/components/portfolio/TransactionList";
+import { TransactionHistory } from "@/components/portfolio/TransactionHistory";
 import { HoldingSummary } from "@/components/portfolio/HoldingSummary";
-import { usePrices, useRefreshAsset } from "@/hooks/usePrices";
-import { useRemoveAsset, useAllCacheMeta } from "@/hooks/useAssets";
+import { ProviderSymbols } from "@/components/portfolio/ProviderSymbols";
+import { AssetMetadataEditor } from "@/components/portfolio/AssetMetadataEditor";
+import {
+  useBackfillPriceGaps,
+  usePriceGaps,
+  usePrices,
+  useReconcilePrices,
+  useRefreshAsset,
+} from "@/hooks/usePrices";
+import { useRemoveAsset, useAllCacheMeta, useMarketStatus } from "@/hooks/useAssets";
 import { useTransactions } from "@/hooks/useTransactions";
 import { useAssetsStore } from "@/stores/assetsStore";
 import { formatCurrency, formatPercent } from "@/lib/utils/formatCurrency";
 import { formatDateTime, formatRelativeTime, formatDate, daysAgo } from "@/lib/utils/dateHelpers";
-import type { Asset } from "@/types";
-import { ArrowLeft, RefreshCw, Trash2 } from "lucide-react";
+import { priceErrorHint } from "@/lib/utils/commandError";
+import { ASSET_TYPE_LABELS, hasCorporateActions } from "@/lib/utils/assetTypes";
+import type { Asset, Interval, PriceAdjustment, PriceDivergence } from "@/types";
+import { ArrowLeft, FileWarning, RefreshCw, Trash2 } from "lucide-react";
 import { useState } from "react";
 
 const providerBadgeClass: Record<string, string> = {
-  TwelveData: "border-blue-500/40 bg-blue-500/10 text-blue-400",
-  CoinGecko: "border-violet-500/40 bg-violet-500/10 text-violet-400",
-  Binance: "border-yellow-500/40 bg-yellow-500/10 text-yellow-400",
+  twelve_data: "border-blue-500/40 bg-blue-500/10 text-blue-400",
+  coingecko: "border-violet-500/40 bg-violet-500/10 text-violet-400",
+  binance: "border-yellow-500/40 bg-yellow-500/10 text-yellow-400",
 };
 
+const intervalOptions: { value: Interval; label: string }[] = [
+  { value: "5m", label: "5m" },
+  { value: "1h", label: "1H" },
+  { value: "4h", label: "4H" },
+  { value: "1d", label: "1D" },
+  { value: "1w", label: "1W" },
+];
+
+const adjustmentOptions: { value: PriceAdjustment; label: string; title: string }[] = [
+  { value: "raw", label: "Raw", title: "Prices as traded" },
+  { value: "split", label: "Split adj.", title: "Earlier prices scaled by later splits" },
+  { value: "total", label: "Total return", title: "Split-adjusted with dividends reinvested" },
+];
+
 interface Props {
   asset: Asset;
 }
 
 export function AssetDetail({ asset }: Props) {
-  const { data: prices, isLoading } = usePrices(asset.id);
+  const [chartInterval, setChartInterval] = useState<Interval>("1d");
+  // Only equity-like types have splits and dividends to adjust for
+  const adjustable = hasCorporateActions(asset.asset_type);
+  const [adjustment, setAdjustment] = useState<PriceAdjustment>(
+    adjustable ? "split" : "raw",
+  );
+  const {
+    data: prices,
+    isLoading,
+    error: pricesError,
+  } = usePrices(asset.id, chartInterval, adjustment);
   const refreshAsset = useRefreshAsset();
+  const { data: gaps } = usePriceGaps(asset.id, chartInterval);
+  const backfillGaps = useBackfillPriceGaps();
+  const missingCandles = gaps?.reduce((sum, gap) => sum + gap.missing, 0) ?? 0;
+  const reconcile = useReconcilePrices();
+  const worstDivergence = reconcile.data?.divergences.reduce<PriceDivergence | undefined>(
+    (worst, d) => (!worst || Math.abs(d.diff_pct) > Math.abs(worst.diff_pct) ? d : worst),
+    undefined,
+  );
   const removeAsset = useRemoveAsset();
+  const { data: marketStatuses } = useMarketStatus();
+  const marketStatus = marketStatuses?.find((m) => m.asset_id === asset.id);
   const setSelectedAssetId = useAssetsStore((s) => s.setSelectedAssetId);
   const [confirmDelete, setConfirmDelete] = useState(false);
   const { data: allMeta } = useAllCacheMeta();
@@ -36,7 +81,8 @@ export function AssetDetail({ asset }: Props) {
   const sortedPrices = [...(prices ?? [])].sort((a, b) => a.ts - b.ts);
   const latestPrice = sortedPrices.length > 0 ? sortedPrices[sortedPrices.length - 1]! : null;
 
-  const cacheMeta = allMeta?.find((m) => m.asset_id === asset.id) ?? null;
+  const cacheMeta =
+    allMeta?.find((m) => m.asset_id === asset.id && m.interval === chartInterval) ?? null;
 
   const buyCount = txs?.filter((t) => t.tx_type === "buy").length ?? 0;
   const sellCount = txs?.filter((t) => t.tx_type === "sell").length ?? 0;
@@ -74,12 +120,41 @@ export function AssetDetail({ asset }: Props) {
           <div>
             <div className="flex items-center gap-2">
               <h2 className="text-2xl font-bold">{asset.symbol}</h2>
-              <Badge variant="outline">{asset.asset_type}</Badge>
+              {asset.exchange && <Badge variant="outline">{asset.exchange}</Badge>}
+              <Badge variant="outline">{ASSET_TYPE_LABELS[asset.asset_type]}</Badge>
+              {marketStatus && marketStatus.next_close !== null && (
+                <Badge
+                  variant={marketStatus.is_open ? "default" : "secondary"}
+                  title={
+                    marketStatus.is_open
+                      ? `${marketStatus.market} closes ${formatDateTime(marketStatus.next_close)}`
+                      : marketStatus.next_open !== null
+                        ? `${marketStatus.market} opens ${formatDateTime(marketStatus.next_open)}`
+                        : marketStatus.market
+                  }
+                >
+                  {marketStatus.is_open ? "Market open" : "Market closed"}
+                </Badge>
+              )}
             </div>
             <p className="text-sm text-muted-foreground">{asset.name}</p>
           </div>
         </div>
         <div className="flex gap-2">
+          {missingCandles > 0 && (
+            <Button
+              variant="ghost"
+              size="sm"
+              onClick={() => backfillGaps.mutate({ assetId: asset.id, interval: chartInterval })}
+              disabled={backfillGaps.isPending}
+              title={`${missingCandles} missing ${chartInterval} candles in stored history`}
+            >
+              <FileWarning className="h-4 w-4" />
+              <span className="ml-2">
+                {backfillGaps.isPending ? "Filling..." : `Fill ${missingCandles} Gaps`}
+              </span>
+            </Button>
+          )}
           <Button
             variant="ghost"
             size="sm"
@@ -140,9 +215,36 @@ export function AssetDetail({ asset }: Props) {
             {cacheMeta.provider}
           </span>
           · Fetched {formatRelativeTime(cacheMeta.last_fetched)}
+          {asset.asset_type === "crypto" && (
+            <button
+              className="underline underline-offset-2 hover:text-foreground"
+              disabled={reconcile.isPending}
+              onClick={() =>
+                reconcile.mutate({
+                  assetId: asset.id,
+                  // Crypto has two sources; compare against whichever isn't stored
+                  referenceProvider: cacheMeta.provider === "coingecko" ? "binance" : "coingecko",
+                })
+              }
+            >
+              {reconcile.isPending ? "Cross-checking..." : "Cross-check"}
+            </button>
+          )}
         </p>
       )}
-      {historyDays !== null && firstPrice && lastPrice && (
+      {reconcile.data && (
+        <p className="text-xs text-muted-foreground">
+          {worstDivergence
+            ? `${reconcile.data.divergences.length} of ${reconcile.data.compared} days differ from ${reconcile.data.reference_provider} by more than ${reconcile.data.threshold_pct}% (worst ${formatPercent(worstDivergence.diff_pct)} on ${formatDate(worstDivergence.ts)}).`
+            : `Matches ${reconcile.data.reference_provider} within ${reconcile.data.threshold_pct}% on all ${reconcile.data.compared} days.`}
+        </p>
+      )}
+      {reconcile.error && (
+        <p className="text-xs text-red">{priceErrorHint(reconcile.error)}</p>
+      )}
+      <ProviderSymbols asset={asset} />
+      <AssetMetadataEditor asset={asset} />
+      {historyDays !== null && firstPrice && lastPrice && chartInterval === "1d" && (
         <p className="text-xs text-muted-foreground">
           Price history: {formatDate(firstPrice.ts)} – {formatDate(lastPrice.ts)}{" "}
           <span className="text-zinc-600">({historyDays.toLocaleString()} days)</span>
@@ -150,10 +252,42 @@ export function AssetDetail({ asset }: Props) {
       )}
 
       {isLoading && <p className="text-muted-foreground">Loading price data...</p>}
-      {!isLoading && sortedPrices.length > 0 && <AssetChart data={sortedPrices} />}
+      <div className="flex gap-1">
+        {intervalOptions.map((opt) => (
+          <Button
+            key={opt.value}
+            variant={chartInterval === opt.value ? "default" : "ghost"}
+            size="sm"
+            onClick={() => setChartInterval(opt.value)}
+          >
+            {opt.label}
+          </Button>
+        ))}
+        {adjustable && (
+          <div className="ml-auto flex gap-1">
+            {adjustmentOptions.map((opt) => (
+              <Button
+                key={opt.value}
+                variant={adjustment === opt.value ? "default" : "ghost"}
+                size="sm"
+                title={opt.title}
+                onClick={() => setAdjustment(opt.value)}
+              >
+                {opt.label}
+              </Button>
+            ))}
+          </div>
+        )}
+      </div>
+      {!isLoading && sortedPrices.length > 0 && (
+        <AssetChart
+          data={sortedPrices}
+          timeVisible={chartInterval !== "1d" && chartInterval !== "1w"}
+        />
+      )}
       {!isLoading && sortedPrices.length === 0 && (
         <p className="text-muted-foreground">
-          No price data available. Try refreshing or check your API key in Settings.
+          No price data available. {priceErrorHint(pricesError ?? refreshAsset.error)}
         </p>
       )}
 
@@ -176,6 +310,7 @@ export function AssetDetail({ asset }: Props) {
           <AddTransactionDialog assetId={asset.id} />
         </div>
         <TransactionList assetId={asset.id} />
+        <TransactionHistory assetId={asset.id} />
       </div>
     </div>
   );
@@ -0,0 +1,92 @@
+import { Input } from "@/components/ui/input";
+import { useAssetMetadata, useRefreshAssetProfile, useUpdateAssetMetadata } from "@/hooks/useAssets";
+import { hasCorporateActions } from "@/lib/utils/assetTypes";
+import { priceErrorHint } from "@/lib/utils/commandError";
+import type { Asset, AssetMetadata } from "@/types";
+
+type TextField = "sector" | "industry" | "country" | "asset_class";
+
+const fields: { field: TextField; label: string; placeholder: string }[] = [
+  { field: "sector", label: "Sector", placeholder: "e.g. Technology" },
+  { field: "industry", label: "Industry", placeholder: "e.g. Semiconductors" },
+  { field: "country", label: "Country", placeholder: "e.g. United States" },
+  { field: "asset_class", label: "Class", placeholder: "e.g. Equity" },
+];
+
+interface Props {
+  asset: Asset;
+}
+
+/** Sector, country, class and tags for allocation breakdowns, saved on blur. */
+export function AssetMetadataEditor({ asset }: Props) {
+  const { data: allMetadata } = useAssetMetadata();
+  const update = useUpdateAssetMetadata();
+  const refreshProfile = useRefreshAssetProfile();
+  const metadata = allMetadata?.find((m) => m.asset_id === asset.id);
+
+  function save(changes: Partial<AssetMetadata>) {
+    if (metadata) update.mutate({ ...metadata, ...changes });
+  }
+
+  if (!metadata) return null;
+
+  const tagsText = metadata.tags.join(", ");
+
+  return (
+    <div className="flex flex-wrap items-center gap-3 text-xs text-muted-foreground">
+      {fields.map(({ field, label, placeholder }) => {
+        const current = metadata[field] ?? "";
+        return (
+          <label key={`${field}-${current}`} className="flex items-center gap-2">
+            {label}:
+            <Input
+              className="h-7 w-32 text-xs"
+              defaultValue={current}
+              placeholder={placeholder}
+              onBlur={(e) => {
+                if (e.target.value.trim() !== current) save({ [field]: e.target.value.trim() || null });
+              }}
+              onKeyDown={(e) => {
+                if (e.key === "Enter") e.currentTarget.blur();
+              }}
+            />
+          </label>
+        );
+      })}
+      <label key={`tags-${tagsText}`} className="flex items-center gap-2">
+        Tags:
+        <Input
+          className="h-7 w-48 text-xs"
+          defaultValue={tagsText}
+          placeholder="e.g. long-term, speculative"
+          onBlur={(e) => {
+            const tags = e.target.value.split(",").map((t) => t.trim()).filter((t) => t.length > 0);
+            if (tags.join(", ") !== tagsText) save({ tags });
+          }}
+          onKeyDown={(e) => {
+            if (e.key === "Enter") e.currentTarget.blur();
+          }}
+        />
+      </label>
+      <label className="flex items-center gap-2" title="Let sells take the position below zero">
+        <input
+          type="checkbox"
+          checked={metadata.allow_short}
+          onChange={(e) => save({ allow_short: e.target.checked })}
+        />
+        Allow short selling
+      </label>
+      {hasCorporateActions(asset.asset_type) && (
+        <button
+          className="underline underline-offset-2 hover:text-foreground disabled:opacity-50"
+          disabled={refreshProfile.isPending}
+          onClick={() => refreshProfile.mutate(asset.id)}
+        >
+          {refreshProfile.isPending ? "Fetching profile..." : "Fetch profile"}
+        </button>
+      )}
+      {update.error && <span className="text-red">{String(update.error)}</span>}
+      {refreshProfile.error && <span className="text-red">{priceErrorHint(refreshProfile.error)}</span>}
+    </div>
+  );
+}
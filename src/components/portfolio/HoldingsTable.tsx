@@ -57,7 +57,12 @@ export function HoldingsTable({ rows, onSelect }: Props) {
                 <div className="flex items-center gap-2.5">
                   <span style={{ backgroundColor: row.color }} className="inline-block h-2 w-2 shrink-0 rounded-full" />
                   <div className="min-w-0">
-                    <div className="font-semibold leading-tight text-zinc-100">{row.asset.symbol}</div>
+                    <div className="font-semibold leading-tight text-zinc-100">
+                      {row.asset.symbol}
+                      {row.asset.exchange && (
+                        <span className="ml-1.5 text-[10px] font-medium text-zinc-500">{row.asset.exchange}</span>
+                      )}
+                    </div>
                     <div className="truncate text-xs leading-tight text-zinc-500">{row.asset.name}</div>
                   </div>
                 </div>
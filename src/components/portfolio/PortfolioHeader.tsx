@@ -2,6 +2,8 @@ import { formatCurrency, formatPercent } from "@/lib/utils/formatCurrency";
 
 interface Props {
   totalValue: number;
+  /** USD cash already included in `totalValue`. */
+  cashValue: number;
   change24hValue: number;
   change24hPct: number;
   totalUnrealizedPnL: number;
@@ -10,6 +12,7 @@ interface Props {
 
 export function PortfolioHeader({
   totalValue,
+  cashValue,
   change24hValue,
   change24hPct,
   totalUnrealizedPnL,
@@ -20,7 +23,9 @@ export function PortfolioHeader({
 
   return (
     <div>
-      <p className="text-xs font-medium text-zinc-500 mb-1">Total Portfolio Value</p>
+      <p className="text-xs font-medium text-zinc-500 mb-1">
+        {cashValue !== 0 ? `Net Worth · incl. ${formatCurrency(cashValue)} cash` : "Total Portfolio Value"}
+      </p>
       <div className="flex items-end gap-3">
         <p className="text-4xl font-bold tracking-tight text-white">
           {formatCurrency(totalValue)}
@@ -0,0 +1,59 @@
+import { Input } from "@/components/ui/input";
+import { useProviderSymbols, useSetProviderSymbol } from "@/hooks/useAssets";
+import type { Asset } from "@/types";
+
+const providerLabel: Record<string, string> = {
+  binance: "Binance",
+  coingecko: "CoinGecko",
+  twelve_data: "Twelve Data",
+};
+
+const placeholder: Record<string, string> = {
+  binance: "e.g. BTCUSDT",
+  coingecko: "e.g. bitcoin",
+  twelve_data: "e.g. SHOP:TSX",
+};
+
+interface Props {
+  asset: Asset;
+}
+
+/** The identifier each of the asset's providers knows it by. Empty means derived from the ticker. */
+export function ProviderSymbols({ asset }: Props) {
+  const { data: mappings } = useProviderSymbols(asset.id);
+  const setProviderSymbol = useSetProviderSymbol();
+  // Cash has no provider at all
+  const providers =
+    asset.asset_type === "cash" ? [] : asset.asset_type === "crypto" ? ["binance", "coingecko"] : ["twelve_data"];
+
+  function save(provider: string, value: string) {
+    const current = mappings?.find((m) => m.provider === provider)?.provider_symbol ?? "";
+    if (value.trim() === current) return;
+    setProviderSymbol.mutate({ assetId: asset.id, provider, providerSymbol: value.trim() });
+  }
+
+  if (!mappings || providers.length === 0) return null;
+
+  return (
+    <div className="flex flex-wrap items-center gap-3 text-xs text-muted-foreground">
+      {providers.map((provider) => {
+        const current = mappings.find((m) => m.provider === provider)?.provider_symbol ?? "";
+        return (
+          <label key={`${provider}-${current}`} className="flex items-center gap-2">
+            {providerLabel[provider]} symbol:
+            <Input
+              className="h-7 w-36 text-xs"
+              defaultValue={current}
+              placeholder={placeholder[provider]}
+              onBlur={(e) => save(provider, e.target.value)}
+              onKeyDown={(e) => {
+                if (e.key === "Enter") e.currentTarget.blur();
+              }}
+            />
+          </label>
+        );
+      })}
+      {setProviderSymbol.error && <span className="text-red">{String(setProviderSymbol.error)}</span>}
+    </div>
+  );
+}
@@ -0,0 +1,103 @@
+import { Badge } from "@/components/ui/badge";
+import { Button } from "@/components/ui/button";
+import { useRevertTransactionChange, useTransactionHistory } from "@/hooks/useTransactions";
+import { formatCurrency } from "@/lib/utils/formatCurrency";
+import { formatDate, formatDateTime } from "@/lib/utils/dateHelpers";
+import { errorMessage } from "@/lib/utils/commandError";
+import type { AuditAction, AuditEntry, Transaction } from "@/types";
+import { History, Undo2 } from "lucide-react";
+import { useState } from "react";
+
+const actionLabel: Record<AuditAction, string> = {
+  insert: "Added",
+  update: "Edited",
+  delete: "Deleted",
+  restore: "Restored",
+  lock: "Locked",
+  unlock: "Unlocked",
+  purge: "Purged",
+};
+
+const fields: { key: keyof Transaction; label: string; format: (tx: Transaction) => string }[] = [
+  { key: "tx_type", label: "Type", format: (tx) => tx.tx_type },
+  { key: "quantity", label: "Quantity", format: (tx) => String(tx.quantity) },
+  { key: "price_usd", label: "Price", format: (tx) => formatCurrency(tx.price_usd) },
+  { key: "ts", label: "Date", format: (tx) => formatDate(tx.ts) },
+  { key: "fee", label: "Fee", format: (tx) => formatCurrency(tx.fee) },
+  { key: "notes", label: "Notes", format: (tx) => tx.notes ?? "—" },
+  { key: "account_id", label: "Account", format: (tx) => (tx.account_id ? "set" : "none") },
+];
+
+/** Fields an edit changed, as "Label: old → new". */
+function describeChanges(entry: AuditEntry): string[] {
+  const { before, after } = entry;
+  if (!before || !after) return [];
+  return fields
+    .filter((f) => before[f.key] !== after[f.key])
+    .map((f) => `${f.label}: ${f.format(before)} → ${f.format(after)}`);
+}
+
+interface Props {
+  assetId: string;
+}
+
+/** Every logged change to the asset's transactions, with a revert for each. */
+export function TransactionHistory({ assetId }: Props) {
+  const [open, setOpen] = useState(false);
+  const { data: history } = useTransactionHistory(assetId);
+  const revert = useRevertTransactionChange(assetId);
+
+  if (!history || history.length === 0) return null;
+
+  return (
+    <div className="space-y-2">
+      <Button variant="ghost" size="sm" onClick={() => setOpen(!open)}>
+        <History className="h-4 w-4" />
+        <span className="ml-2">
+          {open ? "Hide history" : `History (${history.length})`}
+        </span>
+      </Button>
+      {open && (
+        <div className="space-y-1">
+          {history.map((entry) => {
+            const tx = entry.after ?? entry.before;
+            return (
+              <div
+                key={entry.id}
+                className="flex items-center justify-between rounded-md border border-border bg-card/30 px-3 py-2 text-xs"
+              >
+                <div className="space-y-0.5">
+                  <div className="flex items-center gap-2">
+                    <Badge variant="outline">{actionLabel[entry.action]}</Badge>
+                    {tx && (
+                      <span>
+                        {tx.tx_type === "buy" ? "Buy" : "Sell"} {tx.quantity} @ {formatCurrency(tx.price_usd)}
+                      </span>
+                    )}
+                    {entry.reverts !== null && <span className="text-muted-foreground">(revert)</span>}
+                  </div>
+                  {describeChanges(entry).map((change) => (
+                    <p key={change} className="text-muted-foreground">
+                      {change}
+                    </p>
+                  ))}
+                  <p className="text-muted-foreground">{formatDateTime(entry.ts)}</p>
+                </div>
+                <Button
+                  variant="ghost"
+                  size="sm"
+                  title="Undo this change"
+                  onClick={() => revert.mutate(entry.id)}
+                  disabled={revert.isPending}
+                >
+                  <Undo2 className="h-3 w-3" />
+                </Button>
+              </div>
+            );
+          })}
+          {revert.error && <p className="text-xs text-red">{errorMessage(revert.error)}</p>}
+        </div>
+      )}
+    </div>
+  );
+}
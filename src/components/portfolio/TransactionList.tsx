@@ -3,6 +3,7 @@ import { Button } from "@/components/ui/button";
 import { useTransactions, useDeleteTransaction } from "@/hooks/useTransactions";
 import { formatCurrency } from "@/lib/utils/formatCurrency";
 import { formatDate } from "@/lib/utils/dateHelpers";
+import { errorMessage } from "@/lib/utils/commandError";
 import { Trash2 } from "lucide-react";
 
 interface Props {
@@ -60,6 +61,7 @@ export function TransactionList({ assetId }: Props) {
           </div>
         </div>
       ))}
+      {deleteTx.error && <p className="text-xs text-red">{errorMessage(deleteTx.error)}</p>}
     </div>
   );
 }
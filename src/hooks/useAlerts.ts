@@ -0,0 +1,73 @@
+import { useEffect } from "react";
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import { createAlert, deleteAlert, listAlerts, onAlertTriggered, updateAlert } from "@/lib/tauri/alerts";
+import type { Alert, AlertKind } from "@/types";
+
+export function useAlerts() {
+  return useQuery({
+    queryKey: ["alerts"],
+    queryFn: () => listAlerts(),
+  });
+}
+
+export function useCreateAlert() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (params: { assetId: string; kind: AlertKind; threshold: number; windowDays?: number | null }) =>
+      createAlert(params.assetId, params.kind, params.threshold, params.windowDays),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["alerts"] });
+    },
+  });
+}
+
+export function useUpdateAlert() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (alert: Alert) => updateAlert(alert),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["alerts"] });
+    },
+  });
+}
+
+export function useDeleteAlert() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (id: string) => deleteAlert(id),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["alerts"] });
+    },
+  });
+}
+
+/**
+ * Show an OS notification for every alert the backend fires. Uses the
+ * webview's Notification API; asks for permission once on mount.
+ */
+export function useAlertNotifications() {
+  const queryClient = useQueryClient();
+
+  useEffect(() => {
+    const supported = typeof Notification !== "undefined";
+    if (supported && Notification.permission === "default") {
+      Notification.requestPermission().catch(() => {});
+    }
+
+    let unlisten: (() => void) | null = null;
+    let cancelled = false;
+    onAlertTriggered((event) => {
+      queryClient.invalidateQueries({ queryKey: ["alerts"] });
+      if (supported && Notification.permission === "granted") {
+        new Notification(`${event.symbol} alert`, { body: event.message, tag: event.alert_id });
+      }
+    }).then((fn) => {
+      if (cancelled) fn();
+      else unlisten = fn;
+    });
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, [queryClient]);
+}
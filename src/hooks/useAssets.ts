@@ -1,7 +1,18 @@
 import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
-import { listAssets, addAsset, removeAsset, listAllAssets, purgeAsset } from "@/lib/tauri/assets";
-import { listCacheMeta } from "@/lib/tauri/prices";
-import type { AssetType } from "@/types";
+import {
+  listAssets,
+  addAsset,
+  removeAsset,
+  listAllAssets,
+  purgeAsset,
+  listMarketStatus,
+  listProviderSymbols,
+  setProviderSymbol,
+  listAssetMetadata,
+  updateAssetMetadata,
+} from "@/lib/tauri/assets";
+import { listCacheMeta, refreshAssetProfile } from "@/lib/tauri/prices";
+import type { AssetMetadata, AssetType } from "@/types";
 
 export function useAssets() {
   return useQuery({
@@ -17,13 +28,20 @@ export function useAddAsset() {
       symbol,
       name,
       assetType,
+      exchange,
+      provider,
+      providerId,
     }: {
       symbol: string;
       name: string;
       assetType: AssetType;
-    }) => addAsset(symbol, name, assetType),
+      exchange?: string;
+      provider?: string;
+      providerId?: string;
+    }) => addAsset(symbol, name, assetType, exchange, provider, providerId),
     onSuccess: () => {
       queryClient.invalidateQueries({ queryKey: ["assets"] });
+      queryClient.invalidateQueries({ queryKey: ["assetMetadata"] });
     },
   });
 }
@@ -34,6 +52,7 @@ export function useRemoveAsset() {
     mutationFn: (id: string) => removeAsset(id),
     onSuccess: () => {
       queryClient.invalidateQueries({ queryKey: ["assets"] });
+      queryClient.invalidateQueries({ queryKey: ["trash"] });
       queryClient.invalidateQueries({ queryKey: ["allAssets"] });
       queryClient.invalidateQueries({ queryKey: ["prices"] });
     },
@@ -54,12 +73,75 @@ export function useAllCacheMeta() {
   });
 }
 
+/** Market open/closed per active asset, rechecked every minute. */
+export function useMarketStatus() {
+  return useQuery({
+    queryKey: ["marketStatus"],
+    queryFn: listMarketStatus,
+    refetchInterval: 60_000,
+  });
+}
+
+export function useProviderSymbols(assetId: string) {
+  return useQuery({
+    queryKey: ["providerSymbols", assetId],
+    queryFn: () => listProviderSymbols(assetId),
+    enabled: !!assetId,
+  });
+}
+
+export function useSetProviderSymbol() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({
+      assetId,
+      provider,
+      providerSymbol,
+    }: {
+      assetId: string;
+      provider: string;
+      providerSymbol: string;
+    }) => setProviderSymbol(assetId, provider, providerSymbol),
+    onSuccess: (_, { assetId }) => {
+      queryClient.invalidateQueries({ queryKey: ["providerSymbols", assetId] });
+    },
+  });
+}
+
+export function useAssetMetadata() {
+  return useQuery({
+    queryKey: ["assetMetadata"],
+    queryFn: listAssetMetadata,
+  });
+}
+
+export function useUpdateAssetMetadata() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (metadata: AssetMetadata) => updateAssetMetadata(metadata),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["assetMetadata"] });
+    },
+  });
+}
+
+export function useRefreshAssetProfile() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (assetId: string) => refreshAssetProfile(assetId),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["assetMetadata"] });
+    },
+  });
+}
+
 export function usePurgeAsset() {
   const queryClient = useQueryClient();
   return useMutation({
     mutationFn: (id: string) => purgeAsset(id),
     onSuccess: () => {
       queryClient.invalidateQueries({ queryKey: ["allAssets"] });
+      queryClient.invalidateQueries({ queryKey: ["trash"] });
       queryClient.invalidateQueries({ queryKey: ["assets"] });
       queryClient.invalidateQueries({ queryKey: ["prices"] });
     },
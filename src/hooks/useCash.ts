@@ -0,0 +1,91 @@
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import {
+  addCashEntry,
+  createAccount,
+  deleteAccount,
+  deleteCashEntry,
+  getCashBalances,
+  listAccounts,
+  listCashLedger,
+} from "@/lib/tauri/cash";
+import type { CashEntryKind } from "@/types";
+
+export function useAccounts() {
+  return useQuery({
+    queryKey: ["cash", "accounts"],
+    queryFn: listAccounts,
+  });
+}
+
+export function useCashBalances() {
+  return useQuery({
+    queryKey: ["cash", "balances"],
+    queryFn: getCashBalances,
+  });
+}
+
+export function useCashLedger(accountId?: string) {
+  return useQuery({
+    queryKey: ["cash", "ledger", accountId ?? "all"],
+    queryFn: () => listCashLedger(accountId),
+  });
+}
+
+export function useCreateAccount() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (params: { name: string; currency?: string }) =>
+      createAccount(params.name, params.currency),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["cash"] });
+    },
+  });
+}
+
+export function useDeleteAccount() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (id: string) => deleteAccount(id),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["cash"] });
+      queryClient.invalidateQueries({ queryKey: ["transactions"] });
+    },
+  });
+}
+
+export function useAddCashEntry() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (params: {
+      accountId: string;
+      kind: Exclude<CashEntryKind, "buy" | "sell">;
+      amount: number;
+      ts: number;
+      currency?: string;
+      assetId?: string;
+      notes?: string;
+    }) =>
+      addCashEntry(
+        params.accountId,
+        params.kind,
+        params.amount,
+        params.ts,
+        params.currency,
+        params.assetId,
+        params.notes,
+      ),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["cash"] });
+    },
+  });
+}
+
+export function useDeleteCashEntry() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (id: string) => deleteCashEntry(id),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["cash"] });
+    },
+  });
+}
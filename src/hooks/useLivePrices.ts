@@ -0,0 +1,34 @@
+import { useEffect, useState } from "react";
+import { onPriceTick, startPriceStream, stopPriceStream } from "@/lib/tauri/streaming";
+import type { PriceTick } from "@/types";
+
+/**
+ * Latest streamed price per asset id. (Re)starts the backend stream whenever
+ * the tracked asset list changes and stops it on unmount.
+ */
+export function useLivePrices(assetIds: string[]): Map<string, PriceTick> {
+  const [ticks, setTicks] = useState<Map<string, PriceTick>>(() => new Map());
+  const key = [...assetIds].sort().join(",");
+
+  useEffect(() => {
+    if (key === "") return;
+    let unlisten: (() => void) | null = null;
+    let cancelled = false;
+
+    onPriceTick((tick) => {
+      setTicks((prev) => new Map(prev).set(tick.asset_id, tick));
+    }).then((fn) => {
+      if (cancelled) fn();
+      else unlisten = fn;
+    });
+    startPriceStream().catch(() => {});
+
+    return () => {
+      cancelled = true;
+      unlisten?.();
+      stopPriceStream().catch(() => {});
+    };
+  }, [key]);
+
+  return ticks;
+}
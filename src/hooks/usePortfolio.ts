@@ -0,0 +1,52 @@
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import {
+  deleteAllocationTarget,
+  getAllocation,
+  listAllocationTargets,
+  planRebalance,
+  setAllocationTarget,
+} from "@/lib/tauri/portfolio";
+import type { AllocationTarget } from "@/types";
+
+export function useAllocation() {
+  return useQuery({
+    queryKey: ["allocation"],
+    queryFn: getAllocation,
+  });
+}
+
+export function useAllocationTargets() {
+  return useQuery({
+    queryKey: ["allocationTargets"],
+    queryFn: listAllocationTargets,
+  });
+}
+
+export function useRebalancePlan(minTradeValue: number, includeCash: boolean) {
+  return useQuery({
+    queryKey: ["rebalancePlan", minTradeValue, includeCash],
+    queryFn: () => planRebalance(minTradeValue, includeCash),
+  });
+}
+
+export function useSetAllocationTarget() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (target: AllocationTarget) => setAllocationTarget(target),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["allocationTargets"] });
+      queryClient.invalidateQueries({ queryKey: ["rebalancePlan"] });
+    },
+  });
+}
+
+export function useDeleteAllocationTarget() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (assetId: string) => deleteAllocationTarget(assetId),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["allocationTargets"] });
+      queryClient.invalidateQueries({ queryKey: ["rebalancePlan"] });
+    },
+  });
+}
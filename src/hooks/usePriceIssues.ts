@@ -0,0 +1,23 @@
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import { acceptPriceIssue, listPriceIssues, rejectPriceIssue } from "@/lib/tauri/priceIssues";
+import type { PriceIssue } from "@/types";
+
+export function usePendingPriceIssues() {
+  return useQuery({
+    queryKey: ["price-issues", "pending"],
+    queryFn: () => listPriceIssues(),
+  });
+}
+
+/** Accept or reject a quarantined candle; either way the asset's prices may change. */
+export function useResolvePriceIssue() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ id, accept }: { id: number; accept: boolean }) =>
+      accept ? acceptPriceIssue(id) : rejectPriceIssue(id),
+    onSuccess: (issue: PriceIssue) => {
+      queryClient.invalidateQueries({ queryKey: ["price-issues"] });
+      queryClient.invalidateQueries({ queryKey: ["prices", issue.candle.asset_id] });
+    },
+  });
+}
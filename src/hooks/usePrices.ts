@@ -1,10 +1,28 @@
+import { useEffect, useState } from "react";
 import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
-import { fetchPrices, refreshAsset } from "@/lib/tauri/prices";
+import {
+  backfillPriceGaps,
+  fetchPrices,
+  fetchQuotes,
+  onRefreshProgress,
+  reconcilePrices,
+  refreshAsset,
+  scanPriceGaps,
+} from "@/lib/tauri/prices";
+import type { Interval, PriceAdjustment, RefreshProgress } from "@/types";
 
-export function usePrices(assetId: string | null) {
+export function usePrices(
+  assetId: string | null,
+  interval: Interval = "1d",
+  adjustment: PriceAdjustment = "raw",
+) {
   return useQuery({
-    queryKey: ["prices", assetId],
-    queryFn: () => fetchPrices(assetId!),
+    // Raw daily keeps the short key so it shares cache with Dashboard/Performance
+    queryKey:
+      interval === "1d" && adjustment === "raw"
+        ? ["prices", assetId]
+        : ["prices", assetId, interval, adjustment],
+    queryFn: () => fetchPrices(assetId!, interval, adjustment),
     enabled: !!assetId,
   });
 }
@@ -18,3 +36,74 @@ export function useRefreshAsset() {
     },
   });
 }
+
+export function usePriceGaps(assetId: string | null, interval: Interval = "1d") {
+  return useQuery({
+    queryKey: ["price-gaps", assetId, interval],
+    queryFn: () => scanPriceGaps(assetId!, interval),
+    enabled: !!assetId,
+  });
+}
+
+export function useBackfillPriceGaps() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: ({ assetId, interval }: { assetId: string; interval: Interval }) =>
+      backfillPriceGaps(assetId, interval),
+    onSuccess: (remaining, { assetId, interval }) => {
+      queryClient.setQueryData(["price-gaps", assetId, interval], remaining);
+      queryClient.invalidateQueries({ queryKey: ["prices", assetId] });
+    },
+  });
+}
+
+export function useReconcilePrices() {
+  return useMutation({
+    mutationFn: ({ assetId, referenceProvider }: { assetId: string; referenceProvider: string }) =>
+      reconcilePrices(assetId, referenceProvider),
+  });
+}
+
+export function useFetchQuotes() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: fetchQuotes,
+    onSuccess: (quotes) => {
+      for (const quote of quotes) {
+        queryClient.invalidateQueries({ queryKey: ["prices", quote.asset_id] });
+      }
+    },
+  });
+}
+
+/**
+ * Follows the backend scheduler: refetches an asset's prices as soon as the
+ * background refresh has stored them, and exposes the latest progress event.
+ */
+export function useBackgroundRefresh() {
+  const queryClient = useQueryClient();
+  const [progress, setProgress] = useState<RefreshProgress | null>(null);
+
+  useEffect(() => {
+    let unlisten: (() => void) | null = null;
+    let cancelled = false;
+    onRefreshProgress((p) => {
+      setProgress(p);
+      if (p.asset_id && !p.error) {
+        queryClient.invalidateQueries({ queryKey: ["prices", p.asset_id] });
+      }
+      if (p.done) {
+        queryClient.invalidateQueries({ queryKey: ["cacheMeta"] });
+      }
+    }).then((fn) => {
+      if (cancelled) fn();
+      else unlisten = fn;
+    });
+    return () => {
+      cancelled = true;
+      unlisten?.();
+    };
+  }, [queryClient]);
+
+  return progress;
+}
@@ -0,0 +1,20 @@
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import { getStorageReport, optimizeDatabase } from "@/lib/tauri/storage";
+
+export function useStorageReport() {
+  return useQuery({
+    queryKey: ["storage"],
+    queryFn: getStorageReport,
+  });
+}
+
+export function useOptimizeDatabase() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: optimizeDatabase,
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["storage"] });
+      queryClient.invalidateQueries({ queryKey: ["prices"] });
+    },
+  });
+}
@@ -1,4 +1,4 @@
-import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import { useQuery, useMutation, useQueryClient, type QueryClient } from "@tanstack/react-query";
 import {
   listTransactions,
   addTransaction,
@@ -7,8 +7,14 @@ import {
   lockTransaction,
   unlockTransaction,
   getHoldingSummary,
+  listTransactionHistory,
+  revertTransactionChange,
+  bulkAddTransactions,
+  bulkDeleteTransactions,
+  bulkLockTransactions,
+  bulkEditTransactions,
 } from "@/lib/tauri/transactions";
-import type { TxType } from "@/types";
+import type { NewTransaction, TransactionEdit, TransactionSelection, TxType } from "@/types";
 
 export function useTransactions(assetId: string) {
   return useQuery({
@@ -33,6 +39,8 @@ export function useAddTransaction(assetId: string) {
       priceUsd: number;
       ts: number;
       notes?: string;
+      fee?: number;
+      accountId?: string;
     }) =>
       addTransaction(
         assetId,
@@ -41,10 +49,16 @@ export function useAddTransaction(assetId: string) {
         params.priceUsd,
         params.ts,
         params.notes,
+        params.fee,
+        params.accountId,
       ),
     onSuccess: () => {
       queryClient.invalidateQueries({ queryKey: ["transactions", assetId] });
+      queryClient.invalidateQueries({ queryKey: ["transactionHistory", assetId] });
       queryClient.invalidateQueries({ queryKey: ["holdingSummary", assetId] });
+      queryClient.invalidateQueries({ queryKey: ["cash"] });
+      // A first trade moves a watch-only asset into the portfolio
+      queryClient.invalidateQueries({ queryKey: ["assets"] });
     },
   });
 }
@@ -59,6 +73,8 @@ export function useUpdateTransaction(assetId: string) {
       priceUsd: number;
       ts: number;
       notes?: string;
+      fee?: number;
+      accountId?: string;
     }) =>
       updateTransaction(
         params.id,
@@ -67,10 +83,14 @@ export function useUpdateTransaction(assetId: string) {
         params.priceUsd,
         params.ts,
         params.notes,
+        params.fee,
+        params.accountId,
       ),
     onSuccess: () => {
       queryClient.invalidateQueries({ queryKey: ["transactions", assetId] });
+      queryClient.invalidateQueries({ queryKey: ["transactionHistory", assetId] });
       queryClient.invalidateQueries({ queryKey: ["holdingSummary", assetId] });
+      queryClient.invalidateQueries({ queryKey: ["cash"] });
     },
   });
 }
@@ -80,8 +100,11 @@ export function useDeleteTransaction(assetId: string) {
   return useMutation({
     mutationFn: (id: string) => deleteTransaction(id),
     onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["trash"] });
       queryClient.invalidateQueries({ queryKey: ["transactions", assetId] });
+      queryClient.invalidateQueries({ queryKey: ["transactionHistory", assetId] });
       queryClient.invalidateQueries({ queryKey: ["holdingSummary", assetId] });
+      queryClient.invalidateQueries({ queryKey: ["cash"] });
     },
   });
 }
@@ -92,6 +115,7 @@ export function useLockTransaction(assetId: string) {
     mutationFn: (id: string) => lockTransaction(id),
     onSuccess: () => {
       queryClient.invalidateQueries({ queryKey: ["transactions", assetId] });
+      queryClient.invalidateQueries({ queryKey: ["transactionHistory", assetId] });
     },
   });
 }
@@ -99,9 +123,73 @@ export function useLockTransaction(assetId: string) {
 export function useUnlockTransaction(assetId: string) {
   const queryClient = useQueryClient();
   return useMutation({
-    mutationFn: (id: string) => unlockTransaction(id),
+    mutationFn: (params: { id: string; reason: string }) => unlockTransaction(params.id, params.reason),
     onSuccess: () => {
       queryClient.invalidateQueries({ queryKey: ["transactions", assetId] });
+      queryClient.invalidateQueries({ queryKey: ["transactionHistory", assetId] });
     },
   });
 }
+
+export function useTransactionHistory(assetId: string) {
+  return useQuery({
+    queryKey: ["transactionHistory", assetId],
+    queryFn: () => listTransactionHistory(undefined, assetId),
+  });
+}
+
+export function useRevertTransactionChange(assetId: string) {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (auditId: number) => revertTransactionChange(auditId),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["transactions", assetId] });
+      queryClient.invalidateQueries({ queryKey: ["transactionHistory", assetId] });
+      queryClient.invalidateQueries({ queryKey: ["holdingSummary", assetId] });
+      queryClient.invalidateQueries({ queryKey: ["cash"] });
+    },
+  });
+}
+
+/** Bulk changes can span assets, so everything derived from transactions is refetched. */
+function invalidateAllTransactions(queryClient: QueryClient) {
+  queryClient.invalidateQueries({ queryKey: ["transactions"] });
+  queryClient.invalidateQueries({ queryKey: ["transactionHistory"] });
+  queryClient.invalidateQueries({ queryKey: ["holdingSummary"] });
+  queryClient.invalidateQueries({ queryKey: ["cash"] });
+  queryClient.invalidateQueries({ queryKey: ["trash"] });
+  queryClient.invalidateQueries({ queryKey: ["assets"] });
+}
+
+export function useBulkAddTransactions() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (transactions: NewTransaction[]) => bulkAddTransactions(transactions),
+    onSuccess: () => invalidateAllTransactions(queryClient),
+  });
+}
+
+export function useBulkDeleteTransactions() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (selection: TransactionSelection) => bulkDeleteTransactions(selection),
+    onSuccess: () => invalidateAllTransactions(queryClient),
+  });
+}
+
+export function useBulkLockTransactions() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (selection: TransactionSelection) => bulkLockTransactions(selection),
+    onSuccess: () => invalidateAllTransactions(queryClient),
+  });
+}
+
+export function useBulkEditTransactions() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (params: { selection: TransactionSelection; edit: TransactionEdit }) =>
+      bulkEditTransactions(params.selection, params.edit),
+    onSuccess: () => invalidateAllTransactions(queryClient),
+  });
+}
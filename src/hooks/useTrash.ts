@@ -0,0 +1,67 @@
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import {
+  emptyTrash,
+  listTrash,
+  purgeTransaction,
+  restoreDeletedAsset,
+  restoreTransaction,
+} from "@/lib/tauri/trash";
+
+export function useTrash() {
+  return useQuery({
+    queryKey: ["trash"],
+    queryFn: listTrash,
+  });
+}
+
+export function useRestoreDeletedAsset() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (id: string) => restoreDeletedAsset(id),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["trash"] });
+      queryClient.invalidateQueries({ queryKey: ["assets"] });
+      queryClient.invalidateQueries({ queryKey: ["allAssets"] });
+      queryClient.invalidateQueries({ queryKey: ["transactions"] });
+      queryClient.invalidateQueries({ queryKey: ["holdingSummary"] });
+      queryClient.invalidateQueries({ queryKey: ["cash"] });
+    },
+  });
+}
+
+export function useRestoreTransaction() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (id: string) => restoreTransaction(id),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["trash"] });
+      queryClient.invalidateQueries({ queryKey: ["transactions"] });
+      queryClient.invalidateQueries({ queryKey: ["transactionHistory"] });
+      queryClient.invalidateQueries({ queryKey: ["holdingSummary"] });
+      queryClient.invalidateQueries({ queryKey: ["cash"] });
+    },
+  });
+}
+
+export function usePurgeTransaction() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (id: string) => purgeTransaction(id),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["trash"] });
+      queryClient.invalidateQueries({ queryKey: ["transactionHistory"] });
+    },
+  });
+}
+
+export function useEmptyTrash() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: emptyTrash,
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["trash"] });
+      queryClient.invalidateQueries({ queryKey: ["allAssets"] });
+      queryClient.invalidateQueries({ queryKey: ["transactionHistory"] });
+    },
+  });
+}
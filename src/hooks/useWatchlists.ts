@@ -0,0 +1,135 @@
+import { useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
+import {
+  addWatchlistItem,
+  createWatchlist,
+  deleteWatchlist,
+  listWatchlists,
+  removeWatchlistItem,
+  renameWatchlist,
+  reorderWatchlistItems,
+  reorderWatchlists,
+  updateWatchlistItemNotes,
+  watchSymbol,
+} from "@/lib/tauri/watchlists";
+import type { AssetType } from "@/types";
+
+export function useWatchlists() {
+  return useQuery({
+    queryKey: ["watchlists"],
+    queryFn: listWatchlists,
+  });
+}
+
+export function useCreateWatchlist() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (name: string) => createWatchlist(name),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["watchlists"] });
+    },
+  });
+}
+
+export function useRenameWatchlist() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (params: { id: string; name: string }) => renameWatchlist(params.id, params.name),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["watchlists"] });
+    },
+  });
+}
+
+export function useDeleteWatchlist() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (id: string) => deleteWatchlist(id),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["watchlists"] });
+      queryClient.invalidateQueries({ queryKey: ["allAssets"] });
+    },
+  });
+}
+
+export function useReorderWatchlists() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (ids: string[]) => reorderWatchlists(ids),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["watchlists"] });
+    },
+  });
+}
+
+export function useAddWatchlistItem() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (params: { watchlistId: string; assetId: string }) =>
+      addWatchlistItem(params.watchlistId, params.assetId),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["watchlists"] });
+    },
+  });
+}
+
+export function useWatchSymbol() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (params: {
+      watchlistId: string;
+      symbol: string;
+      name: string;
+      assetType: AssetType;
+      exchange?: string;
+      provider?: string;
+      providerId?: string;
+    }) =>
+      watchSymbol(
+        params.watchlistId,
+        params.symbol,
+        params.name,
+        params.assetType,
+        params.exchange,
+        params.provider,
+        params.providerId,
+      ),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["watchlists"] });
+      queryClient.invalidateQueries({ queryKey: ["allAssets"] });
+    },
+  });
+}
+
+export function useUpdateWatchlistItemNotes() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (params: { watchlistId: string; assetId: string; notes: string }) =>
+      updateWatchlistItemNotes(params.watchlistId, params.assetId, params.notes),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["watchlists"] });
+    },
+  });
+}
+
+export function useRemoveWatchlistItem() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (params: { watchlistId: string; assetId: string }) =>
+      removeWatchlistItem(params.watchlistId, params.assetId),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["watchlists"] });
+      queryClient.invalidateQueries({ queryKey: ["allAssets"] });
+    },
+  });
+}
+
+export function useReorderWatchlistItems() {
+  const queryClient = useQueryClient();
+  return useMutation({
+    mutationFn: (params: { watchlistId: string; assetIds: string[] }) =>
+      reorderWatchlistItems(params.watchlistId, params.assetIds),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["watchlists"] });
+    },
+  });
+}
@@ -0,0 +1,30 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import type { Alert, AlertKind, AlertTriggered } from "@/types";
+
+export async function listAlerts(assetId?: string): Promise<Alert[]> {
+  return invoke<Alert[]>("list_alerts", { assetId: assetId ?? null });
+}
+
+export async function createAlert(
+  assetId: string,
+  kind: AlertKind,
+  threshold: number,
+  windowDays?: number | null,
+): Promise<Alert> {
+  return invoke<Alert>("create_alert", { assetId, kind, threshold, windowDays: windowDays ?? null });
+}
+
+/** Saving re-arms the alert, so a condition that already holds fires on the next fetch. */
+export async function updateAlert(alert: Alert): Promise<void> {
+  return invoke<void>("update_alert", { alert });
+}
+
+export async function deleteAlert(id: string): Promise<void> {
+  return invoke<void>("delete_alert", { id });
+}
+
+/** Emitted after a daily price fetch or scheduled refresh when an alert fires. */
+export async function onAlertTriggered(handler: (event: AlertTriggered) => void): Promise<UnlistenFn> {
+  return listen<AlertTriggered>("alert-triggered", (event) => handler(event.payload));
+}
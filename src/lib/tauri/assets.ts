@@ -1,5 +1,5 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { Asset, AssetType } from "@/types";
+import type { Asset, AssetMetadata, AssetType, MarketStatus, ProviderSymbol } from "@/types";
 
 export async function listAssets(): Promise<Asset[]> {
   return invoke<Asset[]>("list_assets");
@@ -9,11 +9,17 @@ export async function addAsset(
   symbol: string,
   name: string,
   assetType: AssetType,
+  exchange?: string,
+  provider?: string,
+  providerId?: string,
 ): Promise<Asset> {
   return invoke<Asset>("add_asset", {
     symbol,
     name,
     assetType: assetType,
+    exchange: exchange ?? null,
+    provider: provider ?? null,
+    providerId: providerId ?? null,
   });
 }
 
@@ -28,3 +34,29 @@ export async function listAllAssets(): Promise<Asset[]> {
 export async function purgeAsset(id: string): Promise<void> {
   return invoke<void>("purge_asset", { id });
 }
+
+export async function listMarketStatus(): Promise<MarketStatus[]> {
+  return invoke<MarketStatus[]>("list_market_status");
+}
+
+export async function listProviderSymbols(assetId: string): Promise<ProviderSymbol[]> {
+  return invoke<ProviderSymbol[]>("list_provider_symbols", { assetId });
+}
+
+/** An empty `providerSymbol` removes the mapping. */
+export async function setProviderSymbol(
+  assetId: string,
+  provider: string,
+  providerSymbol: string,
+): Promise<void> {
+  return invoke<void>("set_provider_symbol", { assetId, provider, providerSymbol });
+}
+
+export async function listAssetMetadata(): Promise<AssetMetadata[]> {
+  return invoke<AssetMetadata[]>("list_asset_metadata");
+}
+
+/** Blank fields are cleared; `tags` replaces the asset's tags. */
+export async function updateAssetMetadata(metadata: AssetMetadata): Promise<void> {
+  return invoke<void>("update_asset_metadata", { metadata });
+}
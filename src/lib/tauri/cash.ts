@@ -0,0 +1,47 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { Account, CashBalance, CashEntryKind, CashLedgerEntry } from "@/types";
+
+export async function createAccount(name: string, currency?: string): Promise<Account> {
+  return invoke<Account>("create_account", { name, currency: currency ?? null });
+}
+
+export async function listAccounts(): Promise<Account[]> {
+  return invoke<Account[]>("list_accounts");
+}
+
+export async function deleteAccount(id: string): Promise<void> {
+  return invoke<void>("delete_account", { id });
+}
+
+/** Deposits, withdrawals and dividends; buys and sells come from transactions. */
+export async function addCashEntry(
+  accountId: string,
+  kind: Exclude<CashEntryKind, "buy" | "sell">,
+  amount: number,
+  ts: number,
+  currency?: string,
+  assetId?: string,
+  notes?: string,
+): Promise<CashLedgerEntry> {
+  return invoke<CashLedgerEntry>("add_cash_entry", {
+    accountId,
+    kind,
+    amount,
+    currency: currency ?? null,
+    ts,
+    assetId: assetId ?? null,
+    notes: notes ?? null,
+  });
+}
+
+export async function deleteCashEntry(id: string): Promise<void> {
+  return invoke<void>("delete_cash_entry", { id });
+}
+
+export async function listCashLedger(accountId?: string): Promise<CashLedgerEntry[]> {
+  return invoke<CashLedgerEntry[]>("list_cash_ledger", { accountId: accountId ?? null });
+}
+
+export async function getCashBalances(): Promise<CashBalance[]> {
+  return invoke<CashBalance[]>("get_cash_balances");
+}
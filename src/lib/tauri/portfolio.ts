@@ -0,0 +1,27 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { AllocationReport, AllocationTarget, RebalancePlan } from "@/types";
+
+export async function getAllocation(): Promise<AllocationReport> {
+  return invoke<AllocationReport>("get_allocation");
+}
+
+export async function listAllocationTargets(): Promise<AllocationTarget[]> {
+  return invoke<AllocationTarget[]>("list_allocation_targets");
+}
+
+/** Rejected if targets across assets would add up to more than 100%. */
+export async function setAllocationTarget(target: AllocationTarget): Promise<void> {
+  return invoke<void>("set_allocation_target", { target });
+}
+
+export async function deleteAllocationTarget(assetId: string): Promise<void> {
+  return invoke<void>("delete_allocation_target", { assetId });
+}
+
+/** Trades toward the targets; USD cash is invested unless `includeCash` is false. */
+export async function planRebalance(minTradeValue?: number, includeCash?: boolean): Promise<RebalancePlan> {
+  return invoke<RebalancePlan>("plan_rebalance", {
+    minTradeValue: minTradeValue ?? null,
+    includeCash: includeCash ?? null,
+  });
+}
@@ -0,0 +1,21 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { PriceIssue, PriceIssueStatus } from "@/types";
+
+/** Quarantined candles, newest first; pending ones unless `status` says otherwise. */
+export async function listPriceIssues(
+  assetId?: string,
+  status?: PriceIssueStatus | "all",
+): Promise<PriceIssue[]> {
+  return invoke<PriceIssue[]>("list_price_issues", {
+    assetId: assetId ?? null,
+    status: status ?? null,
+  });
+}
+
+export async function acceptPriceIssue(id: number): Promise<PriceIssue> {
+  return invoke<PriceIssue>("accept_price_issue", { id });
+}
+
+export async function rejectPriceIssue(id: number): Promise<PriceIssue> {
+  return invoke<PriceIssue>("reject_price_issue", { id });
+}
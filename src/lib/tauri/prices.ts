@@ -1,14 +1,88 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { OHLCVRow, PriceCacheMeta } from "@/types";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import type {
+  CorporateAction,
+  Interval,
+  OHLCVRow,
+  PriceCacheMeta,
+  PriceAdjustment,
+  PriceGap,
+  PriceTick,
+  ReconciliationReport,
+  RefreshProgress,
+} from "@/types";
 
-export async function fetchPrices(assetId: string): Promise<OHLCVRow[]> {
-  return invoke<OHLCVRow[]>("fetch_prices", { assetId });
+export async function fetchPrices(
+  assetId: string,
+  interval?: Interval,
+  adjustment?: PriceAdjustment,
+): Promise<OHLCVRow[]> {
+  return invoke<OHLCVRow[]>("fetch_prices", {
+    assetId,
+    interval: interval ?? null,
+    adjustment: adjustment ?? null,
+  });
 }
 
-export async function refreshAsset(assetId: string): Promise<OHLCVRow[]> {
-  return invoke<OHLCVRow[]>("refresh_asset", { assetId });
+export async function refreshAsset(
+  assetId: string,
+  interval?: Interval,
+): Promise<OHLCVRow[]> {
+  return invoke<OHLCVRow[]>("refresh_asset", { assetId, interval: interval ?? null });
+}
+
+/** Latest price for every asset via provider batch endpoints. */
+export async function fetchQuotes(): Promise<PriceTick[]> {
+  return invoke<PriceTick[]>("fetch_quotes");
+}
+
+/** Gaps in stored history for one asset, or every asset when omitted. */
+export async function scanPriceGaps(assetId?: string, interval?: Interval): Promise<PriceGap[]> {
+  return invoke<PriceGap[]>("scan_price_gaps", {
+    assetId: assetId ?? null,
+    interval: interval ?? null,
+  });
+}
+
+/** Refetch only the missing ranges; resolves with the gaps that remain. */
+export async function backfillPriceGaps(assetId: string, interval?: Interval): Promise<PriceGap[]> {
+  return invoke<PriceGap[]>("backfill_price_gaps", { assetId, interval: interval ?? null });
+}
+
+export async function listCorporateActions(assetId: string): Promise<CorporateAction[]> {
+  return invoke<CorporateAction[]>("list_corporate_actions", { assetId });
+}
+
+/**
+ * Compare stored history with `referenceProvider` ("binance", "coingecko" or
+ * "twelve_data"). Defaults to the last 90 daily candles and a 1% threshold.
+ */
+export async function reconcilePrices(
+  assetId: string,
+  referenceProvider: string,
+  options: { interval?: Interval; from?: number; to?: number; thresholdPct?: number } = {},
+): Promise<ReconciliationReport> {
+  return invoke<ReconciliationReport>("reconcile_prices", {
+    assetId,
+    referenceProvider,
+    interval: options.interval ?? null,
+    from: options.from ?? null,
+    to: options.to ?? null,
+    thresholdPct: options.thresholdPct ?? null,
+  });
+}
+
+/** Re-fetch the company profile; fields the user has set are kept. */
+export async function refreshAssetProfile(assetId: string): Promise<void> {
+  return invoke<void>("refresh_asset_profile", { assetId });
 }
 
 export async function listCacheMeta(): Promise<PriceCacheMeta[]> {
   return invoke<PriceCacheMeta[]>("list_cache_meta");
 }
+
+export async function onRefreshProgress(
+  handler: (progress: RefreshProgress) => void,
+): Promise<UnlistenFn> {
+  return listen<RefreshProgress>("price-refresh-progress", (event) => handler(event.payload));
+}
@@ -0,0 +1,11 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { OptimizeResult, StorageReport } from "@/types";
+
+export async function getStorageReport(): Promise<StorageReport> {
+  return invoke<StorageReport>("get_storage_report");
+}
+
+/** Apply retention, drop orphaned price rows and VACUUM the database. */
+export async function optimizeDatabase(): Promise<OptimizeResult> {
+  return invoke<OptimizeResult>("optimize_database");
+}
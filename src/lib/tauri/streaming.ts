@@ -0,0 +1,15 @@
+import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
+import type { PriceTick } from "@/types";
+
+export async function startPriceStream(): Promise<void> {
+  return invoke<void>("start_price_stream");
+}
+
+export async function stopPriceStream(): Promise<void> {
+  return invoke<void>("stop_price_stream");
+}
+
+export async function onPriceTick(handler: (tick: PriceTick) => void): Promise<UnlistenFn> {
+  return listen<PriceTick>("price-tick", (event) => handler(event.payload));
+}
@@ -1,5 +1,14 @@
 import { invoke } from "@tauri-apps/api/core";
-import type { Transaction, TxType, AssetHoldingSummary } from "@/types";
+import type {
+  Transaction,
+  TxType,
+  AssetHoldingSummary,
+  AuditEntry,
+  NewTransaction,
+  PeriodClose,
+  TransactionEdit,
+  TransactionSelection,
+} from "@/types";
 
 export async function addTransaction(
   assetId: string,
@@ -8,6 +17,8 @@ export async function addTransaction(
   priceUsd: number,
   ts: number,
   notes?: string,
+  fee?: number,
+  accountId?: string,
 ): Promise<Transaction> {
   return invoke<Transaction>("add_transaction", {
     assetId,
@@ -16,13 +27,20 @@ export async function addTransaction(
     priceUsd,
     ts,
     notes: notes ?? null,
+    fee: fee ?? null,
+    accountId: accountId ?? null,
   });
 }
 
+/** With `splitAdjusted`, quantities and prices are restated in post-split shares. */
 export async function listTransactions(
   assetId: string,
+  splitAdjusted?: boolean,
 ): Promise<Transaction[]> {
-  return invoke<Transaction[]>("list_transactions", { assetId });
+  return invoke<Transaction[]>("list_transactions", {
+    assetId,
+    splitAdjusted: splitAdjusted ?? null,
+  });
 }
 
 export async function updateTransaction(
@@ -32,6 +50,8 @@ export async function updateTransaction(
   priceUsd: number,
   ts: number,
   notes?: string,
+  fee?: number,
+  accountId?: string,
 ): Promise<void> {
   return invoke<void>("update_transaction", {
     id,
@@ -40,6 +60,8 @@ export async function updateTransaction(
     priceUsd,
     ts,
     notes: notes ?? null,
+    fee: fee ?? null,
+    accountId: accountId ?? null,
   });
 }
 
@@ -47,12 +69,69 @@ export async function deleteTransaction(id: string): Promise<void> {
   return invoke<void>("delete_transaction", { id });
 }
 
+/** Adds all rows or, if any is invalid or would oversell, none. */
+export async function bulkAddTransactions(transactions: NewTransaction[]): Promise<Transaction[]> {
+  return invoke<Transaction[]>("bulk_add_transactions", { transactions });
+}
+
+/** Returns how many were deleted. */
+export async function bulkDeleteTransactions(selection: TransactionSelection): Promise<number> {
+  return invoke<number>("bulk_delete_transactions", { selection });
+}
+
+/** Returns how many were newly locked. */
+export async function bulkLockTransactions(selection: TransactionSelection): Promise<number> {
+  return invoke<number>("bulk_lock_transactions", { selection });
+}
+
+/** Reassign, re-date or annotate in one go. Returns how many changed. */
+export async function bulkEditTransactions(
+  selection: TransactionSelection,
+  edit: TransactionEdit,
+): Promise<number> {
+  return invoke<number>("bulk_edit_transactions", { selection, edit });
+}
+
 export async function lockTransaction(id: string): Promise<void> {
   return invoke<void>("lock_transaction", { id });
 }
 
-export async function unlockTransaction(id: string): Promise<void> {
-  return invoke<void>("unlock_transaction", { id });
+/**
+ * Lock every transaction dated before `beforeTs`, of one asset or all, and keep
+ * new ones out of that period until it is reopened. Returns how many were locked.
+ */
+export async function lockTransactionsBefore(beforeTs: number, assetId?: string): Promise<number> {
+  return invoke<number>("lock_transactions_before", { beforeTs, assetId: assetId ?? null });
+}
+
+/** Reopen the closed periods of one asset, or the global ones. Returns how many transactions were unlocked. */
+export async function reopenPeriod(reason: string, assetId?: string): Promise<number> {
+  return invoke<number>("reopen_period", { assetId: assetId ?? null, reason });
+}
+
+/** Every period close, reopened ones included, most recent first. */
+export async function listPeriodCloses(): Promise<PeriodClose[]> {
+  return invoke<PeriodClose[]>("list_period_closes");
+}
+
+/** The reason is kept in the transaction's history. */
+export async function unlockTransaction(id: string, reason: string): Promise<void> {
+  return invoke<void>("unlock_transaction", { id, reason });
+}
+
+/** Logged changes to one transaction or to every transaction of an asset, newest first. */
+export async function listTransactionHistory(
+  transactionId?: string,
+  assetId?: string,
+): Promise<AuditEntry[]> {
+  return invoke<AuditEntry[]>("list_transaction_history", {
+    transactionId: transactionId ?? null,
+    assetId: assetId ?? null,
+  });
+}
+
+export async function revertTransactionChange(auditId: number): Promise<AuditEntry> {
+  return invoke<AuditEntry>("revert_transaction_change", { auditId });
 }
 
 export async function getHoldingSummary(
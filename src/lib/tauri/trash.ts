@@ -0,0 +1,26 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { Asset, Trash, TrashPurge } from "@/types";
+
+/** Settings key: days deleted items stay in the trash ("0" = until emptied). */
+export const TRASH_RETENTION_KEY = "trash_retention_days";
+
+export async function listTrash(): Promise<Trash> {
+  return invoke<Trash>("list_trash");
+}
+
+/** Restores the asset together with the transactions removed with it. */
+export async function restoreDeletedAsset(id: string): Promise<Asset> {
+  return invoke<Asset>("restore_deleted_asset", { id });
+}
+
+export async function restoreTransaction(id: string): Promise<void> {
+  return invoke<void>("restore_transaction", { id });
+}
+
+export async function purgeTransaction(id: string): Promise<void> {
+  return invoke<void>("purge_transaction", { id });
+}
+
+export async function emptyTrash(): Promise<TrashPurge> {
+  return invoke<TrashPurge>("empty_trash");
+}
@@ -0,0 +1,63 @@
+import { invoke } from "@tauri-apps/api/core";
+import type { Asset, AssetType, Watchlist } from "@/types";
+
+export async function listWatchlists(): Promise<Watchlist[]> {
+  return invoke<Watchlist[]>("list_watchlists");
+}
+
+export async function createWatchlist(name: string): Promise<Watchlist> {
+  return invoke<Watchlist>("create_watchlist", { name });
+}
+
+export async function renameWatchlist(id: string, name: string): Promise<void> {
+  return invoke<void>("rename_watchlist", { id, name });
+}
+
+/** Assets followed only from this list are removed with it. */
+export async function deleteWatchlist(id: string): Promise<void> {
+  return invoke<void>("delete_watchlist", { id });
+}
+
+export async function reorderWatchlists(ids: string[]): Promise<void> {
+  return invoke<void>("reorder_watchlists", { ids });
+}
+
+export async function addWatchlistItem(watchlistId: string, assetId: string, notes?: string): Promise<void> {
+  return invoke<void>("add_watchlist_item", { watchlistId, assetId, notes: notes ?? null });
+}
+
+/**
+ * Follow a search result. New assets are watch-only: priced and charted but
+ * kept out of the portfolio until traded or added to it.
+ */
+export async function watchSymbol(
+  watchlistId: string,
+  symbol: string,
+  name: string,
+  assetType: AssetType,
+  exchange?: string,
+  provider?: string,
+  providerId?: string,
+): Promise<Asset> {
+  return invoke<Asset>("watch_symbol", {
+    watchlistId,
+    symbol,
+    name,
+    assetType,
+    exchange: exchange ?? null,
+    provider: provider ?? null,
+    providerId: providerId ?? null,
+  });
+}
+
+export async function updateWatchlistItemNotes(watchlistId: string, assetId: string, notes: string): Promise<void> {
+  return invoke<void>("update_watchlist_item_notes", { watchlistId, assetId, notes });
+}
+
+export async function removeWatchlistItem(watchlistId: string, assetId: string): Promise<void> {
+  return invoke<void>("remove_watchlist_item", { watchlistId, assetId });
+}
+
+export async function reorderWatchlistItems(watchlistId: string, assetIds: string[]): Promise<void> {
+  return invoke<void>("reorder_watchlist_items", { watchlistId, assetIds });
+}
@@ -0,0 +1,47 @@
+import { describe, it, expect } from "vitest";
+import { groupAllocation } from "../allocation";
+import type { AssetMetadata } from "@/types";
+
+const meta = (asset_id: string, fields: Partial<AssetMetadata>): AssetMetadata => ({
+  asset_id,
+  sector: null,
+  industry: null,
+  country: null,
+  asset_class: null,
+  tags: [],
+  ...fields,
+});
+
+const metadata = [
+  meta("aapl", { sector: "Technology", tags: ["long-term"] }),
+  meta("nvda", { sector: "Technology", tags: ["long-term", "speculative"] }),
+  meta("btc", { asset_class: "Crypto" }),
+];
+
+const positions = [
+  { assetId: "aapl", value: 500 },
+  { assetId: "nvda", value: 300 },
+  { assetId: "btc", value: 200 },
+  { assetId: "short", value: -100 },
+];
+
+describe("groupAllocation", () => {
+  it("sums long positions by dimension, largest first", () => {
+    expect(groupAllocation(positions, metadata, "sector")).toEqual([
+      { label: "Technology", value: 800, pct: 80 },
+      { label: "Unclassified", value: 200, pct: 20 },
+    ]);
+  });
+
+  it("splits multi-tagged assets evenly between their tags", () => {
+    expect(groupAllocation(positions, metadata, "tag")).toEqual([
+      { label: "long-term", value: 650, pct: 65 },
+      { label: "Untagged", value: 200, pct: 20 },
+      { label: "speculative", value: 150, pct: 15 },
+    ]);
+  });
+
+  it("returns no groups without long positions", () => {
+    expect(groupAllocation([], metadata, "country")).toEqual([]);
+  });
+});
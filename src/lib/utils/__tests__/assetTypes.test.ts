@@ -0,0 +1,19 @@
+import { describe, it, expect } from "vitest";
+import { hasCorporateActions, isAssetType } from "../assetTypes";
+
+describe("isAssetType", () => {
+  it("accepts backend asset types only", () => {
+    expect(isAssetType("mutual_fund")).toBe(true);
+    expect(isAssetType("cash")).toBe(true);
+    expect(isAssetType("Mutual Fund")).toBe(false);
+    expect(isAssetType("toString")).toBe(false);
+  });
+});
+
+describe("hasCorporateActions", () => {
+  it("is limited to equity-like types", () => {
+    expect(hasCorporateActions("etf")).toBe(true);
+    expect(hasCorporateActions("forex")).toBe(false);
+    expect(hasCorporateActions("cash")).toBe(false);
+  });
+});
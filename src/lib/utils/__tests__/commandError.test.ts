@@ -0,0 +1,25 @@
+import { describe, it, expect } from "vitest";
+import { errorMessage, isCommandError, priceErrorHint } from "../commandError";
+
+describe("commandError", () => {
+  it("recognizes structured errors", () => {
+    expect(isCommandError({ code: "auth", message: "bad key" })).toBe(true);
+    expect(isCommandError("plain string")).toBe(false);
+    expect(isCommandError(null)).toBe(false);
+  });
+
+  it("errorMessage handles both shapes", () => {
+    expect(errorMessage({ code: "internal", message: "Database not unlocked" })).toBe(
+      "Database not unlocked",
+    );
+    expect(errorMessage("Asset not found")).toBe("Asset not found");
+  });
+
+  it("priceErrorHint branches on code", () => {
+    expect(priceErrorHint({ code: "auth", message: "x" })).toContain("API key");
+    expect(priceErrorHint({ code: "rate_limited", message: "x" })).toContain("rate limit");
+    expect(priceErrorHint({ code: "parse", message: "Unexpected response: x" })).toBe(
+      "Unexpected response: x",
+    );
+  });
+});
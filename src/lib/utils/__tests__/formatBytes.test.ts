@@ -0,0 +1,15 @@
+import { describe, it, expect } from "vitest";
+import { formatBytes } from "../formatBytes";
+
+describe("formatBytes", () => {
+  it("keeps small sizes in bytes", () => {
+    expect(formatBytes(0)).toBe("0 B");
+    expect(formatBytes(1023)).toBe("1023 B");
+  });
+
+  it("scales to larger units", () => {
+    expect(formatBytes(1536)).toBe("1.5 KB");
+    expect(formatBytes(5 * 1024 * 1024)).toBe("5.0 MB");
+    expect(formatBytes(3 * 1024 ** 4)).toBe("3072.0 GB");
+  });
+});
@@ -0,0 +1,49 @@
+import type { AssetMetadata } from "@/types";
+
+export type AllocationDimension = "asset" | "sector" | "country" | "asset_class" | "tag";
+
+export const ALLOCATION_DIMENSION_LABELS: Record<AllocationDimension, string> = {
+  asset: "Asset",
+  sector: "Sector",
+  country: "Country",
+  asset_class: "Class",
+  tag: "Tag",
+};
+
+export interface AllocationGroup {
+  label: string;
+  value: number;
+  pct: number;
+}
+
+/**
+ * Sum long position values by a metadata dimension, largest first. Assets
+ * without a value land in "Unclassified" ("Untagged" for tags); an asset with
+ * several tags is split evenly between them so the groups still add up to 100%.
+ */
+export function groupAllocation(
+  positions: { assetId: string; value: number }[],
+  metadata: AssetMetadata[],
+  dimension: Exclude<AllocationDimension, "asset">,
+): AllocationGroup[] {
+  const byAsset = new Map(metadata.map((m) => [m.asset_id, m]));
+  const totals = new Map<string, number>();
+  let total = 0;
+
+  for (const { assetId, value } of positions) {
+    if (value <= 0) continue;
+    total += value;
+    const meta = byAsset.get(assetId);
+    const labels =
+      dimension === "tag"
+        ? meta && meta.tags.length > 0 ? meta.tags : ["Untagged"]
+        : [meta?.[dimension] ?? "Unclassified"];
+    for (const label of labels) {
+      totals.set(label, (totals.get(label) ?? 0) + value / labels.length);
+    }
+  }
+
+  return [...totals.entries()]
+    .map(([label, value]) => ({ label, value, pct: total > 0 ? (value / total) * 100 : 0 }))
+    .sort((a, b) => b.value - a.value);
+}
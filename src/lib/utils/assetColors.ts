@@ -23,3 +23,8 @@ export function buildColorMap(assets: Asset[]): Map<string, string> {
   });
   return map;
 }
+
+/** Palette color for the `index`th series that isn't tied to one asset. */
+export function paletteColor(index: number): string {
+  return PALETTE[index % PALETTE.length]!;
+}
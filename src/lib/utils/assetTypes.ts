@@ -0,0 +1,22 @@
+import type { AssetType } from "@/types";
+
+export const ASSET_TYPE_LABELS: Record<AssetType, string> = {
+  stock: "Stock",
+  etf: "ETF",
+  mutual_fund: "Mutual Fund",
+  bond: "Bond",
+  forex: "Forex",
+  index: "Index",
+  crypto: "Crypto",
+  commodity: "Commodity",
+  cash: "Cash",
+};
+
+export function isAssetType(value: string): value is AssetType {
+  return Object.prototype.hasOwnProperty.call(ASSET_TYPE_LABELS, value);
+}
+
+/** Types with splits and dividends, which get the price adjustment toggle. */
+export function hasCorporateActions(assetType: AssetType): boolean {
+  return assetType === "stock" || assetType === "etf" || assetType === "mutual_fund";
+}
@@ -0,0 +1,32 @@
+import type { CommandError } from "@/types";
+
+export function isCommandError(err: unknown): err is CommandError {
+  return (
+    typeof err === "object" &&
+    err !== null &&
+    typeof (err as CommandError).code === "string" &&
+    typeof (err as CommandError).message === "string"
+  );
+}
+
+/** Readable text for anything a command rejected with (structured or plain string). */
+export function errorMessage(err: unknown): string {
+  return isCommandError(err) ? err.message : String(err);
+}
+
+/** What the user can do about a failed price fetch. */
+export function priceErrorHint(err: unknown): string {
+  if (!isCommandError(err)) return "Try refreshing or check your API key in Settings.";
+  switch (err.code) {
+    case "auth":
+      return "Check your API key in Settings.";
+    case "not_found":
+      return "The data provider doesn't know this symbol.";
+    case "rate_limited":
+      return "Provider rate limit reached. Try again in a minute.";
+    case "transient":
+      return "Couldn't reach the data provider. Check your connection and retry.";
+    default:
+      return err.message;
+  }
+}
@@ -0,0 +1,197 @@
+import { useMemo, useState } from "react";
+import { useAssets } from "@/hooks/useAssets";
+import { useAlerts, useCreateAlert, useDeleteAlert, useUpdateAlert } from "@/hooks/useAlerts";
+import { Badge } from "@/components/ui/badge";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import { formatDateTime } from "@/lib/utils/dateHelpers";
+import type { Alert, AlertKind } from "@/types";
+import { Trash2 } from "lucide-react";
+
+const kindLabel: Record<AlertKind, string> = {
+  price_above: "Price above",
+  price_below: "Price below",
+  pct_move: "% move",
+  drawdown: "Drawdown from high",
+  position_above: "Position value above",
+  position_below: "Position value below",
+};
+
+function isPercent(kind: AlertKind): boolean {
+  return kind === "pct_move" || kind === "drawdown";
+}
+
+function describeCondition(alert: Alert): string {
+  if (alert.kind === "pct_move") {
+    return `moves ±${alert.threshold}% in ${alert.window_days ?? 1} day(s)`;
+  }
+  if (alert.kind === "drawdown") {
+    const window = alert.window_days ? `${alert.window_days}-day high` : "high";
+    return `falls ${alert.threshold}% below its ${window}`;
+  }
+  return `${kindLabel[alert.kind].toLowerCase()} $${alert.threshold.toLocaleString()}`;
+}
+
+export function AlertsPage() {
+  const { data: assets } = useAssets();
+  const { data: alerts } = useAlerts();
+  const createAlert = useCreateAlert();
+  const updateAlert = useUpdateAlert();
+  const deleteAlert = useDeleteAlert();
+
+  const [assetId, setAssetId] = useState("");
+  const [kind, setKind] = useState<AlertKind>("price_above");
+  const [threshold, setThreshold] = useState("");
+  const [windowDays, setWindowDays] = useState("");
+  const [error, setError] = useState("");
+
+  const symbols = useMemo(() => new Map((assets ?? []).map((a) => [a.id, a.symbol])), [assets]);
+  const notificationsBlocked = typeof Notification !== "undefined" && Notification.permission === "denied";
+
+  async function handleCreate(e: React.FormEvent) {
+    e.preventDefault();
+    setError("");
+    const value = parseFloat(threshold);
+    if (!assetId) {
+      setError("Please select an asset");
+      return;
+    }
+    if (isNaN(value)) {
+      setError("Threshold must be a number");
+      return;
+    }
+    const days = parseInt(windowDays, 10);
+    try {
+      await createAlert.mutateAsync({
+        assetId,
+        kind,
+        threshold: value,
+        windowDays: isPercent(kind) && !isNaN(days) ? days : null,
+      });
+      setThreshold("");
+    } catch (err) {
+      setError(String(err));
+    }
+  }
+
+  return (
+    <div className="space-y-4">
+      <h2 className="text-base font-semibold text-zinc-100">Alerts</h2>
+
+      <form
+        onSubmit={handleCreate}
+        className="space-y-3 rounded-xl border border-zinc-800 bg-zinc-900/60 p-6"
+      >
+        <p className="text-xs font-semibold uppercase tracking-wider text-zinc-500">New alert</p>
+        <div className="flex gap-2">
+          <Select value={assetId} onValueChange={setAssetId}>
+            <SelectTrigger className="w-40">
+              <SelectValue placeholder="Asset" />
+            </SelectTrigger>
+            <SelectContent>
+              {(assets ?? [])
+                .filter((a) => a.asset_type !== "cash")
+                .map((a) => (
+                  <SelectItem key={a.id} value={a.id}>{a.symbol}</SelectItem>
+                ))}
+            </SelectContent>
+          </Select>
+          <Select value={kind} onValueChange={(v) => setKind(v as AlertKind)}>
+            <SelectTrigger className="w-52">
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              {(Object.keys(kindLabel) as AlertKind[]).map((k) => (
+                <SelectItem key={k} value={k}>{kindLabel[k]}</SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+          <Input
+            className="w-32"
+            type="number"
+            step="any"
+            min="0"
+            placeholder={isPercent(kind) ? "Percent" : "USD"}
+            value={threshold}
+            onChange={(e) => setThreshold(e.target.value)}
+          />
+          {isPercent(kind) && (
+            <Input
+              className="w-32"
+              type="number"
+              min="1"
+              placeholder={kind === "pct_move" ? "Days (1)" : "Days (all)"}
+              value={windowDays}
+              onChange={(e) => setWindowDays(e.target.value)}
+            />
+          )}
+          <Button type="submit" size="sm" disabled={createAlert.isPending}>Add</Button>
+        </div>
+        {error && <p className="text-sm text-destructive">{error}</p>}
+        <p className="text-xs text-muted-foreground">
+          Alerts are checked against daily closes after each price fetch and scheduled refresh. Each one
+          notifies once, then again only after its condition has cleared.
+          {notificationsBlocked && " Notifications are blocked for this app; enable them in system settings."}
+        </p>
+      </form>
+
+      <div className="rounded-xl border border-zinc-800 bg-zinc-900/60">
+        {(alerts ?? []).length === 0 ? (
+          <p className="py-10 text-center text-sm text-muted-foreground">No alerts yet.</p>
+        ) : (
+          <table className="w-full text-sm">
+            <thead>
+              <tr className="border-b border-zinc-800 text-left text-xs uppercase tracking-wider text-zinc-500">
+                <th className="px-4 py-3">Asset</th>
+                <th className="px-4 py-3">Condition</th>
+                <th className="px-4 py-3">Status</th>
+                <th className="px-4 py-3">Last fired</th>
+                <th className="px-4 py-3">Enabled</th>
+                <th className="px-4 py-3" />
+              </tr>
+            </thead>
+            <tbody>
+              {(alerts ?? []).map((alert) => (
+                <tr key={alert.id} className="border-b border-zinc-800/50">
+                  <td className="px-4 py-3 font-semibold text-zinc-100">{symbols.get(alert.asset_id) ?? "—"}</td>
+                  <td className="px-4 py-3 text-zinc-300">{describeCondition(alert)}</td>
+                  <td className="px-4 py-3">
+                    <Badge variant={alert.triggered ? "destructive" : "outline"}>
+                      {alert.triggered ? "Triggered" : "Armed"}
+                    </Badge>
+                  </td>
+                  <td className="px-4 py-3 text-zinc-400">
+                    {alert.last_triggered_at ? formatDateTime(alert.last_triggered_at) : "—"}
+                  </td>
+                  <td className="px-4 py-3">
+                    <input
+                      type="checkbox"
+                      checked={alert.enabled}
+                      onChange={(e) => updateAlert.mutate({ ...alert, enabled: e.target.checked })}
+                    />
+                  </td>
+                  <td className="px-4 py-3 text-right">
+                    <button
+                      className="text-zinc-500 hover:text-red-400"
+                      title="Delete alert"
+                      onClick={() => deleteAlert.mutate(alert.id)}
+                    >
+                      <Trash2 className="h-3.5 w-3.5" />
+                    </button>
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        )}
+      </div>
+    </div>
+  );
+}
@@ -3,6 +3,7 @@ import { useAllAssets, useAllCacheMeta, usePurgeAsset } from "@/hooks/useAssets"
 import { Badge } from "@/components/ui/badge";
 import { Button } from "@/components/ui/button";
 import { formatDate } from "@/lib/utils/dateHelpers";
+import { ASSET_TYPE_LABELS } from "@/lib/utils/assetTypes";
 import { Trash2 } from "lucide-react";
 import type { PriceCacheMeta } from "@/types";
 
@@ -29,7 +30,7 @@ export function AssetManagerPage() {
 
   const cacheMetaMap = useMemo(() => {
     const map = new Map<string, PriceCacheMeta>();
-    cacheMetas?.forEach((m) => map.set(m.asset_id, m));
+    cacheMetas?.filter((m) => m.interval === "1d").forEach((m) => map.set(m.asset_id, m));
     return map;
   }, [cacheMetas]);
 
@@ -69,11 +70,16 @@ export function AssetManagerPage() {
                     key={asset.id}
                     className={`border-b border-zinc-800/50 ${isDeleted ? "opacity-50" : ""}`}
                   >
-                    <td className="px-4 py-3 font-semibold text-zinc-100">{asset.symbol}</td>
+                    <td className="px-4 py-3 font-semibold text-zinc-100">
+                      {asset.symbol}
+                      {asset.exchange && (
+                        <span className="ml-1.5 text-xs font-normal text-zinc-500">{asset.exchange}</span>
+                      )}
+                    </td>
                     <td className="px-4 py-3 text-zinc-400 max-w-[200px] truncate">{asset.name}</td>
                     <td className="px-4 py-3">
                       <Badge variant="outline" className="text-xs">
-                        {asset.asset_type}
+                        {ASSET_TYPE_LABELS[asset.asset_type]}
                       </Badge>
                     </td>
                     <td className="px-4 py-3">
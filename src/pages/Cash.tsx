@@ -0,0 +1,287 @@
+import { useMemo, useState } from "react";
+import { useAssets } from "@/hooks/useAssets";
+import {
+  useAccounts,
+  useAddCashEntry,
+  useCashBalances,
+  useCashLedger,
+  useCreateAccount,
+  useDeleteAccount,
+  useDeleteCashEntry,
+} from "@/hooks/useCash";
+import { Badge } from "@/components/ui/badge";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import {
+  Select,
+  SelectContent,
+  SelectItem,
+  SelectTrigger,
+  SelectValue,
+} from "@/components/ui/select";
+import { formatDate } from "@/lib/utils/dateHelpers";
+import type { CashEntryKind } from "@/types";
+import { Trash2 } from "lucide-react";
+
+type ManualKind = Exclude<CashEntryKind, "buy" | "sell">;
+
+const ALL_ACCOUNTS = "all";
+
+const kindLabel: Record<CashEntryKind, string> = {
+  deposit: "Deposit",
+  withdrawal: "Withdrawal",
+  dividend: "Dividend",
+  buy: "Buy",
+  sell: "Sell",
+};
+
+function formatMoney(value: number, currency: string): string {
+  return new Intl.NumberFormat("en-US", { style: "currency", currency }).format(value);
+}
+
+export function CashPage() {
+  const { data: accounts } = useAccounts();
+  const { data: balances } = useCashBalances();
+  const { data: assets } = useAssets();
+  const [filterAccountId, setFilterAccountId] = useState(ALL_ACCOUNTS);
+  const { data: ledger } = useCashLedger(filterAccountId === ALL_ACCOUNTS ? undefined : filterAccountId);
+  const createAccount = useCreateAccount();
+  const deleteAccount = useDeleteAccount();
+  const addEntry = useAddCashEntry();
+  const deleteEntry = useDeleteCashEntry();
+
+  const [accountName, setAccountName] = useState("");
+  const [accountCurrency, setAccountCurrency] = useState("USD");
+  const [entryAccountId, setEntryAccountId] = useState("");
+  const [entryKind, setEntryKind] = useState<ManualKind>("deposit");
+  const [entryAmount, setEntryAmount] = useState("");
+  const [entryDate, setEntryDate] = useState(new Date().toISOString().slice(0, 10));
+  const [entryNotes, setEntryNotes] = useState("");
+  const [error, setError] = useState("");
+  const [confirmAccountId, setConfirmAccountId] = useState<string | null>(null);
+
+  const accountNames = useMemo(
+    () => new Map((accounts ?? []).map((a) => [a.id, a.name])),
+    [accounts],
+  );
+  const assetSymbols = useMemo(
+    () => new Map((assets ?? []).map((a) => [a.id, a.symbol])),
+    [assets],
+  );
+
+  async function handleCreateAccount(e: React.FormEvent) {
+    e.preventDefault();
+    setError("");
+    if (!accountName.trim()) return;
+    try {
+      await createAccount.mutateAsync({ name: accountName.trim(), currency: accountCurrency.trim() || undefined });
+      setAccountName("");
+    } catch (err) {
+      setError(String(err));
+    }
+  }
+
+  async function handleAddEntry(e: React.FormEvent) {
+    e.preventDefault();
+    setError("");
+    const amount = parseFloat(entryAmount);
+    if (!entryAccountId) {
+      setError("Please select an account");
+      return;
+    }
+    if (isNaN(amount) || amount <= 0) {
+      setError("Amount must be a positive number");
+      return;
+    }
+    const ts = Math.floor(new Date(entryDate).getTime() / 1000);
+    try {
+      await addEntry.mutateAsync({
+        accountId: entryAccountId,
+        kind: entryKind,
+        amount,
+        ts,
+        notes: entryNotes.trim() || undefined,
+      });
+      setEntryAmount("");
+      setEntryNotes("");
+    } catch (err) {
+      setError(String(err));
+    }
+  }
+
+  return (
+    <div className="space-y-4">
+      <h2 className="text-base font-semibold text-zinc-100">Cash</h2>
+
+      <div className="grid gap-4 md:grid-cols-2">
+        <div className="space-y-3 rounded-xl border border-zinc-800 bg-zinc-900/60 p-6">
+          <p className="text-xs font-semibold uppercase tracking-wider text-zinc-500">Balances</p>
+          {(balances ?? []).length === 0 && (
+            <p className="text-sm text-muted-foreground">No accounts yet.</p>
+          )}
+          {(balances ?? []).map((b) => (
+            <div key={`${b.account_id}-${b.currency}`} className="flex items-center justify-between text-sm">
+              <span className="text-zinc-300">
+                {b.account_name}
+                <span className="ml-1.5 text-xs text-zinc-500">{b.currency}</span>
+              </span>
+              <span className="flex items-center gap-2">
+                <span className={b.balance < 0 ? "text-red-400" : "text-zinc-100"}>
+                  {formatMoney(b.balance, b.currency)}
+                </span>
+                {confirmAccountId === b.account_id ? (
+                  <Button
+                    size="sm"
+                    variant="destructive"
+                    onClick={() => {
+                      deleteAccount.mutate(b.account_id);
+                      setConfirmAccountId(null);
+                    }}
+                  >
+                    Confirm
+                  </Button>
+                ) : (
+                  <button
+                    className="text-zinc-500 hover:text-red-400"
+                    title="Delete account"
+                    onClick={() => setConfirmAccountId(b.account_id)}
+                  >
+                    <Trash2 className="h-3.5 w-3.5" />
+                  </button>
+                )}
+              </span>
+            </div>
+          ))}
+          <form onSubmit={handleCreateAccount} className="flex gap-2 pt-2">
+            <Input
+              placeholder="New account name"
+              value={accountName}
+              onChange={(e) => setAccountName(e.target.value)}
+            />
+            <Input
+              className="w-20"
+              placeholder="USD"
+              value={accountCurrency}
+              onChange={(e) => setAccountCurrency(e.target.value.toUpperCase())}
+            />
+            <Button type="submit" size="sm" disabled={createAccount.isPending}>Add</Button>
+          </form>
+        </div>
+
+        <form
+          onSubmit={handleAddEntry}
+          className="space-y-3 rounded-xl border border-zinc-800 bg-zinc-900/60 p-6"
+        >
+          <p className="text-xs font-semibold uppercase tracking-wider text-zinc-500">Record cash movement</p>
+          <div className="flex gap-2">
+            <Select value={entryAccountId} onValueChange={setEntryAccountId}>
+              <SelectTrigger className="flex-1">
+                <SelectValue placeholder="Account" />
+              </SelectTrigger>
+              <SelectContent>
+                {(accounts ?? []).map((a) => (
+                  <SelectItem key={a.id} value={a.id}>{a.name}</SelectItem>
+                ))}
+              </SelectContent>
+            </Select>
+            <Select value={entryKind} onValueChange={(v) => setEntryKind(v as ManualKind)}>
+              <SelectTrigger className="w-36">
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                <SelectItem value="deposit">Deposit</SelectItem>
+                <SelectItem value="withdrawal">Withdrawal</SelectItem>
+                <SelectItem value="dividend">Dividend</SelectItem>
+              </SelectContent>
+            </Select>
+          </div>
+          <div className="flex gap-2">
+            <Input
+              type="number"
+              step="any"
+              min="0"
+              placeholder="Amount"
+              value={entryAmount}
+              onChange={(e) => setEntryAmount(e.target.value)}
+            />
+            <Input type="date" value={entryDate} onChange={(e) => setEntryDate(e.target.value)} />
+          </div>
+          <Input
+            placeholder="Notes (optional)"
+            value={entryNotes}
+            onChange={(e) => setEntryNotes(e.target.value)}
+          />
+          <Button type="submit" size="sm" className="w-full" disabled={addEntry.isPending}>
+            Record
+          </Button>
+        </form>
+      </div>
+
+      {error && <p className="text-sm text-destructive">{error}</p>}
+
+      <div className="rounded-xl border border-zinc-800 bg-zinc-900/60">
+        <div className="flex items-center justify-between border-b border-zinc-800 px-4 py-3">
+          <p className="text-xs font-semibold uppercase tracking-wider text-zinc-500">Ledger</p>
+          <Select value={filterAccountId} onValueChange={setFilterAccountId}>
+            <SelectTrigger className="h-8 w-44">
+              <SelectValue />
+            </SelectTrigger>
+            <SelectContent>
+              <SelectItem value={ALL_ACCOUNTS}>All accounts</SelectItem>
+              {(accounts ?? []).map((a) => (
+                <SelectItem key={a.id} value={a.id}>{a.name}</SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+        </div>
+        {(ledger ?? []).length === 0 ? (
+          <p className="py-10 text-center text-sm text-muted-foreground">No cash movements.</p>
+        ) : (
+          <table className="w-full text-sm">
+            <thead>
+              <tr className="border-b border-zinc-800 text-left text-xs uppercase tracking-wider text-zinc-500">
+                <th className="px-4 py-3">Date</th>
+                <th className="px-4 py-3">Account</th>
+                <th className="px-4 py-3">Kind</th>
+                <th className="px-4 py-3">Asset</th>
+                <th className="px-4 py-3">Notes</th>
+                <th className="px-4 py-3 text-right">Amount</th>
+                <th className="px-4 py-3" />
+              </tr>
+            </thead>
+            <tbody>
+              {(ledger ?? []).map((entry) => (
+                <tr key={entry.id} className="border-b border-zinc-800/50">
+                  <td className="px-4 py-3 text-zinc-400">{formatDate(entry.ts)}</td>
+                  <td className="px-4 py-3 text-zinc-300">{accountNames.get(entry.account_id) ?? "—"}</td>
+                  <td className="px-4 py-3">
+                    <Badge variant="outline">{kindLabel[entry.kind]}</Badge>
+                  </td>
+                  <td className="px-4 py-3 text-zinc-400">
+                    {entry.asset_id ? assetSymbols.get(entry.asset_id) ?? "—" : ""}
+                  </td>
+                  <td className="px-4 py-3 text-zinc-500 max-w-[200px] truncate">{entry.notes}</td>
+                  <td className={`px-4 py-3 text-right ${entry.amount < 0 ? "text-red-400" : "text-emerald-400"}`}>
+                    {formatMoney(entry.amount, entry.currency)}
+                  </td>
+                  <td className="px-4 py-3 text-right">
+                    {/* Trade entries follow their transaction and are edited there */}
+                    {entry.transaction_id === null && (
+                      <button
+                        className="text-zinc-500 hover:text-red-400"
+                        title="Delete entry"
+                        onClick={() => deleteEntry.mutate(entry.id)}
+                      >
+                        <Trash2 className="h-3.5 w-3.5" />
+                      </button>
+                    )}
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        )}
+      </div>
+    </div>
+  );
+}
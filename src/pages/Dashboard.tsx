@@ -1,6 +1,8 @@
 import { useMemo, useState } from "react";
 import { useQueries } from "@tanstack/react-query";
-import { useAssets } from "@/hooks/useAssets";
+import { useAssets, useAssetMetadata } from "@/hooks/useAssets";
+import { useLivePrices } from "@/hooks/useLivePrices";
+import { useCashBalances } from "@/hooks/useCash";
 import { useAssetsStore } from "@/stores/assetsStore";
 import { AddAssetDialog } from "@/components/portfolio/AddAssetDialog";
 import { AddTransactionDialog } from "@/components/portfolio/AddTransactionDialog";
@@ -13,7 +15,12 @@ import { AllocationBar } from "@/components/portfolio/AllocationBar";
 import { HoldingsTable } from "@/components/portfolio/HoldingsTable";
 import { fetchPrices } from "@/lib/tauri/prices";
 import { getHoldingSummary, listTransactions } from "@/lib/tauri/transactions";
-import { buildColorMap } from "@/lib/utils/assetColors";
+import { buildColorMap, paletteColor } from "@/lib/utils/assetColors";
+import {
+  ALLOCATION_DIMENSION_LABELS,
+  groupAllocation,
+  type AllocationDimension,
+} from "@/lib/utils/allocation";
 import { calcChange } from "@/lib/utils/priceUtils";
 import { daysAgo } from "@/lib/utils/dateHelpers";
 import type { OHLCVRow, AssetHoldingSummary, Transaction } from "@/types";
@@ -25,6 +32,8 @@ export function Dashboard() {
   const selectedAsset = assets?.find((a) => a.id === selectedAssetId);
 
   const [txDialogOpen, setTxDialogOpen] = useState(false);
+  const [allocationDimension, setAllocationDimension] = useState<AllocationDimension>("asset");
+  const { data: metadata } = useAssetMetadata();
 
   const priceResults = useQueries({
     queries: (assets ?? []).map((asset) => ({
@@ -40,6 +49,9 @@ export function Dashboard() {
     })),
   });
 
+  const liveTicks = useLivePrices((assets ?? []).map((a) => a.id));
+  const { data: cashBalances } = useCashBalances();
+
   const transactionResults = useQueries({
     queries: (assets ?? []).map((asset) => ({
       queryKey: ["transactions", asset.id] as const,
@@ -61,7 +73,8 @@ export function Dashboard() {
     const assetPrices = assets.map((asset, i) => {
       const data = priceResults[i]?.data ?? [];
       const sorted = [...data].sort((a, b) => a.ts - b.ts);
-      const latestPrice = sorted.length > 0 ? sorted[sorted.length - 1]!.close : null;
+      const lastClose = sorted.length > 0 ? sorted[sorted.length - 1]!.close : null;
+      const latestPrice = liveTicks.get(asset.id)?.price ?? lastClose;
       const holding: AssetHoldingSummary | null = holdingResults[i]?.data ?? null;
       const isHeld = holding !== null && (holding.total_bought > 0 || holding.total_sold > 0);
 
@@ -91,6 +104,13 @@ export function Dashboard() {
       return { asset, sorted, latestPrice, holding, isHeld };
     });
 
+    // Net worth counts USD cash; other currencies are listed on the Cash page unconverted
+    const cashValue = (cashBalances ?? [])
+      .filter((b) => b.currency === "USD")
+      .reduce((sum, b) => sum + b.balance, 0);
+    totalValue += cashValue;
+    totalValue24hAgo += cashValue;
+
     const change24hValue = totalValue - totalValue24hAgo;
     const change24hPct =
       totalValue24hAgo > 0 ? (change24hValue / totalValue24hAgo) * 100 : 0;
@@ -165,11 +185,24 @@ export function Dashboard() {
     });
 
     return {
-      totalValue, change24hValue, change24hPct,
+      totalValue, cashValue, change24hValue, change24hPct,
       totalUnrealizedPnL, totalPnLPct,
       allPrices, allTransactions, holdingRows, segments,
     };
-  }, [assets, priceResults, holdingResults, transactionResults]);
+  }, [assets, priceResults, holdingResults, transactionResults, liveTicks, cashBalances]);
+
+  const allocationSegments = useMemo(() => {
+    if (!derived || allocationDimension === "asset") return derived?.segments ?? [];
+    const positions = derived.holdingRows
+      .filter((row) => row.isHeld && row.assetValue !== null)
+      .map((row) => ({ assetId: row.asset.id, value: row.assetValue! }));
+    return groupAllocation(positions, metadata ?? [], allocationDimension).map((group, i) => ({
+      assetId: group.label,
+      symbol: group.label,
+      pct: group.pct,
+      color: paletteColor(i),
+    }));
+  }, [derived, metadata, allocationDimension]);
 
   if (selectedAsset) {
     return <AssetDetail asset={selectedAsset} />;
@@ -199,6 +232,7 @@ export function Dashboard() {
           <div className="space-y-4 rounded-xl border border-zinc-800 bg-zinc-900/60 p-6">
             <PortfolioHeader
               totalValue={derived.totalValue}
+              cashValue={derived.cashValue}
               change24hValue={derived.change24hValue}
               change24hPct={derived.change24hPct}
               totalUnrealizedPnL={derived.totalUnrealizedPnL}
@@ -210,10 +244,24 @@ export function Dashboard() {
           {/* Section B: Allocation bar */}
           {derived.segments.length > 0 && (
             <div className="space-y-3 rounded-xl border border-zinc-800 bg-zinc-900/60 p-6">
-              <p className="text-xs font-semibold uppercase tracking-wider text-zinc-500">
-                Allocation
-              </p>
-              <AllocationBar segments={derived.segments} />
+              <div className="flex items-center justify-between">
+                <p className="text-xs font-semibold uppercase tracking-wider text-zinc-500">
+                  Allocation
+                </p>
+                <div className="flex rounded-md bg-zinc-800 p-0.5">
+                  {(Object.keys(ALLOCATION_DIMENSION_LABELS) as AllocationDimension[]).map((dimension) => (
+                    <button
+                      key={dimension}
+                      type="button"
+                      className={`rounded px-2.5 py-0.5 text-xs font-medium transition-colors ${allocationDimension === dimension ? "bg-zinc-700 text-zinc-100 shadow-sm" : "text-zinc-400 hover:text-zinc-300"}`}
+                      onClick={() => setAllocationDimension(dimension)}
+                    >
+                      {ALLOCATION_DIMENSION_LABELS[dimension]}
+                    </button>
+                  ))}
+                </div>
+              </div>
+              <AllocationBar segments={allocationSegments} />
             </div>
           )}
 
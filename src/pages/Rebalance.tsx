@@ -0,0 +1,225 @@
+import { useMemo, useState } from "react";
+import { useAssets } from "@/hooks/useAssets";
+import {
+  useAllocation,
+  useAllocationTargets,
+  useDeleteAllocationTarget,
+  useRebalancePlan,
+  useSetAllocationTarget,
+} from "@/hooks/usePortfolio";
+import { Badge } from "@/components/ui/badge";
+import { Input } from "@/components/ui/input";
+import { formatCurrency } from "@/lib/utils/formatCurrency";
+import { ASSET_TYPE_LABELS, isAssetType } from "@/lib/utils/assetTypes";
+import type { AllocationReport, AllocationTarget } from "@/types";
+import { Trash2 } from "lucide-react";
+
+const breakdowns: { key: keyof Omit<AllocationReport, "total_value">; label: string }[] = [
+  { key: "by_asset", label: "Asset" },
+  { key: "by_type", label: "Type" },
+  { key: "by_tag", label: "Tag" },
+  { key: "by_sector", label: "Sector" },
+  { key: "by_account", label: "Account" },
+];
+
+export function RebalancePage() {
+  const { data: assets } = useAssets();
+  const { data: allocation } = useAllocation();
+  const { data: targets } = useAllocationTargets();
+  const setTarget = useSetAllocationTarget();
+  const deleteTarget = useDeleteAllocationTarget();
+  const [breakdown, setBreakdown] = useState<(typeof breakdowns)[number]["key"]>("by_asset");
+  const [minTradeValue, setMinTradeValue] = useState("0");
+  const [includeCash, setIncludeCash] = useState(true);
+  const { data: plan, error: planError } = useRebalancePlan(parseFloat(minTradeValue) || 0, includeCash);
+
+  const symbols = useMemo(() => new Map((assets ?? []).map((a) => [a.id, a.symbol])), [assets]);
+  const targetTotal = (targets ?? []).reduce((sum, t) => sum + t.target_pct, 0);
+  const untargeted = (assets ?? []).filter(
+    (a) => a.asset_type !== "cash" && !(targets ?? []).some((t) => t.asset_id === a.id),
+  );
+
+  function save(target: AllocationTarget, changes: Partial<AllocationTarget>) {
+    setTarget.mutate({ ...target, ...changes });
+  }
+
+  const slices = allocation?.[breakdown] ?? [];
+
+  return (
+    <div className="space-y-4">
+      <h2 className="text-base font-semibold text-zinc-100">Allocation & Rebalancing</h2>
+
+      <div className="space-y-3 rounded-xl border border-zinc-800 bg-zinc-900/60 p-6">
+        <div className="flex items-center justify-between">
+          <p className="text-xs font-semibold uppercase tracking-wider text-zinc-500">
+            Current allocation{allocation ? ` · ${formatCurrency(allocation.total_value)}` : ""}
+          </p>
+          <div className="flex rounded-md bg-zinc-800 p-0.5">
+            {breakdowns.map(({ key, label }) => (
+              <button
+                key={key}
+                type="button"
+                className={`rounded px-2.5 py-0.5 text-xs font-medium transition-colors ${breakdown === key ? "bg-zinc-700 text-zinc-100 shadow-sm" : "text-zinc-400 hover:text-zinc-300"}`}
+                onClick={() => setBreakdown(key)}
+              >
+                {label}
+              </button>
+            ))}
+          </div>
+        </div>
+        {slices.length === 0 && <p className="text-sm text-muted-foreground">Nothing held yet.</p>}
+        {slices.map((slice) => (
+          <div key={slice.key} className="flex items-center gap-3 text-sm">
+            <span className="w-40 truncate text-zinc-300">
+              {breakdown === "by_type" && isAssetType(slice.label) ? ASSET_TYPE_LABELS[slice.label] : slice.label}
+            </span>
+            <div className="h-2 flex-1 overflow-hidden rounded-full bg-zinc-950">
+              <div className="h-full bg-primary" style={{ width: `${Math.max(0, Math.min(100, slice.pct))}%` }} />
+            </div>
+            <span className="w-16 text-right text-zinc-400">{slice.pct.toFixed(1)}%</span>
+            <span className="w-28 text-right text-zinc-200">{formatCurrency(slice.value)}</span>
+          </div>
+        ))}
+      </div>
+
+      <div className="space-y-3 rounded-xl border border-zinc-800 bg-zinc-900/60 p-6">
+        <div className="flex items-center justify-between">
+          <p className="text-xs font-semibold uppercase tracking-wider text-zinc-500">Targets</p>
+          <span className={`text-xs ${targetTotal > 100 ? "text-red-400" : "text-zinc-500"}`}>
+            {targetTotal.toFixed(1)}% assigned{targetTotal < 100 ? ` · ${(100 - targetTotal).toFixed(1)}% cash` : ""}
+          </span>
+        </div>
+        {(targets ?? []).map((target) => (
+          <div key={`${target.asset_id}-${target.target_pct}-${target.lot_size}`} className="flex items-center gap-3 text-sm">
+            <span className="w-24 font-semibold text-zinc-100">{symbols.get(target.asset_id) ?? "—"}</span>
+            <label className="flex items-center gap-1.5 text-xs text-muted-foreground">
+              Target %
+              <Input
+                className="h-7 w-20 text-xs"
+                type="number"
+                step="any"
+                min="0"
+                max="100"
+                defaultValue={target.target_pct}
+                onBlur={(e) => {
+                  const value = parseFloat(e.target.value);
+                  if (!isNaN(value) && value !== target.target_pct) save(target, { target_pct: value });
+                }}
+              />
+            </label>
+            <label className="flex items-center gap-1.5 text-xs text-muted-foreground">
+              Lot size
+              <Input
+                className="h-7 w-20 text-xs"
+                type="number"
+                step="any"
+                min="0"
+                defaultValue={target.lot_size}
+                onBlur={(e) => {
+                  const value = parseFloat(e.target.value) || 0;
+                  if (value !== target.lot_size) save(target, { lot_size: value });
+                }}
+              />
+            </label>
+            <label className="flex items-center gap-1.5 text-xs text-muted-foreground">
+              <input
+                type="checkbox"
+                checked={target.no_sell}
+                onChange={(e) => save(target, { no_sell: e.target.checked })}
+              />
+              No sell
+            </label>
+            <button
+              className="ml-auto text-zinc-500 hover:text-red-400"
+              title="Remove target"
+              onClick={() => deleteTarget.mutate(target.asset_id)}
+            >
+              <Trash2 className="h-3.5 w-3.5" />
+            </button>
+          </div>
+        ))}
+        {untargeted.length > 0 && (
+          <div className="flex flex-wrap items-center gap-2 pt-2 text-xs text-muted-foreground">
+            Add target:
+            {untargeted.map((asset) => (
+              <button
+                key={asset.id}
+                className="rounded border border-zinc-700 px-2 py-0.5 hover:text-foreground"
+                onClick={() => setTarget.mutate({ asset_id: asset.id, target_pct: 0, lot_size: 0, no_sell: false })}
+              >
+                {asset.symbol}
+              </button>
+            ))}
+          </div>
+        )}
+        {setTarget.error && <p className="text-xs text-red">{String(setTarget.error)}</p>}
+      </div>
+
+      <div className="space-y-3 rounded-xl border border-zinc-800 bg-zinc-900/60 p-6">
+        <div className="flex items-center justify-between">
+          <p className="text-xs font-semibold uppercase tracking-wider text-zinc-500">Suggested trades</p>
+          <div className="flex items-center gap-4 text-xs text-muted-foreground">
+            <label className="flex items-center gap-1.5">
+              Min trade $
+              <Input
+                className="h-7 w-20 text-xs"
+                type="number"
+                step="any"
+                min="0"
+                value={minTradeValue}
+                onChange={(e) => setMinTradeValue(e.target.value)}
+              />
+            </label>
+            <label className="flex items-center gap-1.5">
+              <input type="checkbox" checked={includeCash} onChange={(e) => setIncludeCash(e.target.checked)} />
+              Invest cash
+            </label>
+          </div>
+        </div>
+        {planError && <p className="text-xs text-red">{String(planError)}</p>}
+        {plan && plan.trades.length === 0 && (
+          <p className="text-sm text-muted-foreground">Already on target.</p>
+        )}
+        {plan && plan.trades.length > 0 && (
+          <table className="w-full text-sm">
+            <thead>
+              <tr className="border-b border-zinc-800 text-left text-xs uppercase tracking-wider text-zinc-500">
+                <th className="py-2">Asset</th>
+                <th className="py-2">Action</th>
+                <th className="py-2 text-right">Quantity</th>
+                <th className="py-2 text-right">Value</th>
+                <th className="py-2 text-right">Now → Target</th>
+              </tr>
+            </thead>
+            <tbody>
+              {plan.trades.map((trade) => (
+                <tr key={trade.asset_id} className="border-b border-zinc-800/50">
+                  <td className="py-2 font-semibold text-zinc-100">{symbols.get(trade.asset_id) ?? "—"}</td>
+                  <td className="py-2">
+                    <Badge variant={trade.action === "buy" ? "default" : "destructive"}>
+                      {trade.action === "buy" ? "Buy" : "Sell"}
+                    </Badge>
+                  </td>
+                  <td className="py-2 text-right text-zinc-300">
+                    {trade.quantity.toLocaleString(undefined, { maximumFractionDigits: 8 })}
+                  </td>
+                  <td className="py-2 text-right text-zinc-300">{formatCurrency(trade.value)}</td>
+                  <td className="py-2 text-right text-zinc-400">
+                    {trade.current_pct.toFixed(1)}% → {trade.target_pct.toFixed(1)}%
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        )}
+        {plan && (
+          <p className="text-xs text-muted-foreground">
+            Cash {formatCurrency(plan.cash_before)} → {formatCurrency(plan.cash_after)}
+            {plan.unpriced_asset_ids.length > 0 &&
+              ` · No price yet for ${plan.unpriced_asset_ids.map((id) => symbols.get(id) ?? id).join(", ")}`}
+          </p>
+        )}
+      </div>
+    </div>
+  );
+}
@@ -11,6 +11,12 @@ import {
   SelectValue,
 } from "@/components/ui/select";
 import { saveApiKey, hasApiKey, removeApiKey, getSetting, saveSetting } from "@/lib/tauri/settings";
+import { TRASH_RETENTION_KEY } from "@/lib/tauri/trash";
+import { useAssets } from "@/hooks/useAssets";
+import { usePendingPriceIssues, useResolvePriceIssue } from "@/hooks/usePriceIssues";
+import { useOptimizeDatabase, useStorageReport } from "@/hooks/useStorage";
+import { formatDateTime } from "@/lib/utils/dateHelpers";
+import { formatBytes } from "@/lib/utils/formatBytes";
 import { Check, X } from "lucide-react";
 
 interface ApiKeyFieldProps {
@@ -88,13 +94,172 @@ function ApiKeyField({ provider, label, description }: ApiKeyFieldProps) {
   );
 }
 
+interface RateLimitFieldProps {
+  provider: string;
+  label: string;
+  defaultLimit: string;
+}
+
+function RateLimitField({ provider, label, defaultLimit }: RateLimitFieldProps) {
+  const settingKey = `${provider}_rate_limit`;
+  const [value, setValue] = useState("");
+  const [message, setMessage] = useState("");
+
+  useEffect(() => {
+    getSetting(settingKey).then((val) => {
+      if (val) setValue(val);
+    }).catch(() => {});
+  }, [settingKey]);
+
+  async function handleSave() {
+    try {
+      await saveSetting(settingKey, value.trim());
+      setMessage(value.trim() ? "Saved" : "Reset to default");
+      setTimeout(() => setMessage(""), 2000);
+    } catch (err) {
+      setMessage(String(err));
+    }
+  }
+
+  return (
+    <div className="space-y-2">
+      <Label>{label}</Label>
+      <div className="flex gap-2">
+        <Input
+          className="w-48"
+          placeholder={defaultLimit}
+          value={value}
+          onChange={(e) => setValue(e.target.value)}
+        />
+        <Button size="sm" onClick={handleSave}>
+          Save
+        </Button>
+      </div>
+      {message && <p className="text-xs text-muted-foreground">{message}</p>}
+    </div>
+  );
+}
+
+const issueKindLabel = {
+  non_positive: "Zero/negative",
+  ohlc_inconsistent: "Bad OHLC",
+  spike: "Spike",
+} as const;
+
+function PriceIssueList() {
+  const { data: issues } = usePendingPriceIssues();
+  const { data: assets } = useAssets();
+  const resolve = useResolvePriceIssue();
+  const symbolById = new Map(assets?.map((a) => [a.id, a.symbol]));
+
+  if (!issues?.length) {
+    return <p className="text-sm text-muted-foreground">No quarantined prices.</p>;
+  }
+  return (
+    <div className="space-y-2">
+      {issues.map((issue) => (
+        <div key={issue.id} className="flex items-center gap-3 text-sm">
+          <span className="w-20 font-medium">
+            {symbolById.get(issue.candle.asset_id) ?? "?"} {issue.candle.interval}
+          </span>
+          <span className="w-36 text-muted-foreground">{formatDateTime(issue.candle.ts)}</span>
+          <span className="w-28">{issueKindLabel[issue.kind]}</span>
+          <span className="flex-1 truncate text-muted-foreground" title={issue.detail}>
+            {issue.detail}
+          </span>
+          <Button
+            variant="ghost"
+            size="sm"
+            title="Genuine price: store it"
+            disabled={resolve.isPending}
+            onClick={() => resolve.mutate({ id: issue.id, accept: true })}
+          >
+            <Check className="h-4 w-4" />
+          </Button>
+          <Button
+            variant="ghost"
+            size="sm"
+            title="Bad data: keep it out"
+            disabled={resolve.isPending}
+            onClick={() => resolve.mutate({ id: issue.id, accept: false })}
+          >
+            <X className="h-4 w-4" />
+          </Button>
+        </div>
+      ))}
+    </div>
+  );
+}
+
+/** Assets listed in the storage breakdown; the rest are summed. */
+const STORAGE_TOP_ASSETS = 8;
+
+function StorageSummary() {
+  const { data: report } = useStorageReport();
+  const optimize = useOptimizeDatabase();
+
+  if (!report) return null;
+  const top = report.assets.slice(0, STORAGE_TOP_ASSETS);
+  const restBytes = report.assets.slice(STORAGE_TOP_ASSETS).reduce((sum, a) => sum + a.bytes, 0);
+  return (
+    <div className="space-y-3 text-sm">
+      <p>
+        Database size: <span className="font-medium">{formatBytes(report.total_bytes)}</span>
+        {report.free_bytes > 0 && (
+          <span className="text-muted-foreground"> ({formatBytes(report.free_bytes)} reclaimable)</span>
+        )}
+      </p>
+      <div className="space-y-1">
+        {top.map((a) => (
+          <div key={a.asset_id} className="flex gap-3">
+            <span className="w-20 font-medium">
+              {a.symbol}
+              {a.deleted && <span className="text-muted-foreground"> (deleted)</span>}
+            </span>
+            <span className="w-40 text-muted-foreground">
+              {a.price_rows.toLocaleString()} candles
+              {a.intraday_rows > 0 && ` (${a.intraday_rows.toLocaleString()} intraday)`}
+            </span>
+            <span>{formatBytes(a.bytes)}</span>
+          </div>
+        ))}
+        {restBytes > 0 && (
+          <p className="text-muted-foreground">
+            {report.assets.length - top.length} more assets: {formatBytes(restBytes)}
+          </p>
+        )}
+      </div>
+      {report.orphan_rows > 0 && (
+        <p className="text-muted-foreground">
+          {report.orphan_rows.toLocaleString()} price rows belong to assets that no longer exist.
+        </p>
+      )}
+      <div className="flex items-center gap-3">
+        <Button variant="outline" size="sm" onClick={() => optimize.mutate()} disabled={optimize.isPending}>
+          {optimize.isPending ? "Optimizing..." : "Optimize Database"}
+        </Button>
+        {optimize.data && (
+          <span className="text-muted-foreground">
+            {formatBytes(optimize.data.bytes_before)} → {formatBytes(optimize.data.bytes_after)}
+          </span>
+        )}
+        {optimize.error && <span className="text-red">{String(optimize.error)}</span>}
+      </div>
+    </div>
+  );
+}
+
 export function SettingsPage() {
   const [refreshInterval, setRefreshInterval] = useState("3600");
+  const [trashRetention, setTrashRetention] = useState("0");
 
   useEffect(() => {
     getSetting("refresh_interval").then((val) => {
       if (val) setRefreshInterval(val);
     }).catch(() => {});
+    getSetting(TRASH_RETENTION_KEY).then((val) => {
+      if (val) setTrashRetention(val);
+    }).catch(() => {});
   }, []);
 
   async function handleRefreshIntervalChange(value: string) {
@@ -102,6 +267,11 @@ export function SettingsPage() {
     await saveSetting("refresh_interval", value);
   }
 
+  async function handleTrashRetentionChange(value: string) {
+    setTrashRetention(value);
+    await saveSetting(TRASH_RETENTION_KEY, value);
+  }
+
   return (
     <div className="max-w-2xl space-y-6">
       <Card className="bg-card">
@@ -152,6 +322,62 @@ export function SettingsPage() {
               </SelectContent>
             </Select>
           </div>
+          <div className="space-y-2">
+            <Label>Empty Trash Automatically</Label>
+            <Select value={trashRetention} onValueChange={handleTrashRetentionChange}>
+              <SelectTrigger className="w-48">
+                <SelectValue />
+              </SelectTrigger>
+              <SelectContent>
+                <SelectItem value="7">After 7 days</SelectItem>
+                <SelectItem value="30">After 30 days</SelectItem>
+                <SelectItem value="90">After 90 days</SelectItem>
+                <SelectItem value="365">After a year</SelectItem>
+                <SelectItem value="0">Never</SelectItem>
+              </SelectContent>
+            </Select>
+          </div>
+        </CardContent>
+      </Card>
+
+      <Card className="bg-card">
+        <CardHeader>
+          <CardTitle>Rate Limits</CardTitle>
+          <CardDescription>
+            Requests per window as &quot;requests/seconds&quot;. Leave empty for the free-tier
+            default; raise it if you have a paid plan. Binance limits are in request weight.
+          </CardDescription>
+        </CardHeader>
+        <CardContent className="space-y-4">
+          <RateLimitField provider="twelve_data" label="Twelve Data" defaultLimit="8/60" />
+          <RateLimitField provider="coingecko" label="CoinGecko" defaultLimit="30/60" />
+          <RateLimitField provider="binance" label="Binance" defaultLimit="6000/60" />
+        </CardContent>
+      </Card>
+
+      <Card className="bg-card">
+        <CardHeader>
+          <CardTitle>Data Quality</CardTitle>
+          <CardDescription>
+            Fetched prices that look wrong (zero, inconsistent high/low, or an isolated spike) are
+            held back from charts until you accept or reject them.
+          </CardDescription>
+        </CardHeader>
+        <CardContent>
+          <PriceIssueList />
+        </CardContent>
+      </Card>
+
+      <Card className="bg-card">
+        <CardHeader>
+          <CardTitle>Storage</CardTitle>
+          <CardDescription>
+            Daily prices are kept forever. Intraday candles past their retention window (1m: 7
+            days, 5m: 30 days, 1h: 180 days, 4h: 2 years) are folded into daily candles.
+          </CardDescription>
+        </CardHeader>
+        <CardContent>
+          <StorageSummary />
         </CardContent>
       </Card>
 
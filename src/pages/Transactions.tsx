@@ -1,12 +1,27 @@
 import { useMemo, useState } from "react";
-import { useQueries, useMutation, useQueryClient } from "@tanstack/react-query";
+import { useQueries, useQuery, useMutation, useQueryClient } from "@tanstack/react-query";
 import { useAssets } from "@/hooks/useAssets";
+import {
+  useBulkDeleteTransactions,
+  useBulkEditTransactions,
+  useBulkLockTransactions,
+} from "@/hooks/useTransactions";
 import { AddTransactionDialog } from "@/components/portfolio/AddTransactionDialog";
 import { Badge } from "@/components/ui/badge";
 import { Button } from "@/components/ui/button";
-import { listTransactions, deleteTransaction, lockTransaction, unlockTransaction } from "@/lib/tauri/transactions";
+import { Input } from "@/components/ui/input";
+import {
+  listTransactions,
+  deleteTransaction,
+  lockTransaction,
+  lockTransactionsBefore,
+  listPeriodCloses,
+  reopenPeriod,
+  unlockTransaction,
+} from "@/lib/tauri/transactions";
 import { formatCurrency } from "@/lib/utils/formatCurrency";
 import { formatDate } from "@/lib/utils/dateHelpers";
+import { errorMessage } from "@/lib/utils/commandError";
 import type { Asset, Transaction } from "@/types";
 import { Lock, LockOpen, Pencil, Trash2 } from "lucide-react";
 import {
@@ -27,7 +42,16 @@ export function TransactionsPage() {
   const [filterAssetId, setFilterAssetId] = useState<string | null>(null);
   const [editingTx, setEditingTx] = useState<TxWithAsset | null>(null);
   const [unlockingTxId, setUnlockingTxId] = useState<string | null>(null);
-  const [confirmDeleteId, setConfirmDeleteId] = useState<string | null>(null);
+  const [unlockReason, setUnlockReason] = useState("");
+  const [closeBefore, setCloseBefore] = useState("");
+  const [reopenReason, setReopenReason] = useState("");
+  const [selected, setSelected] = useState<Set<string>>(new Set());
+  const [moveTo, setMoveTo] = useState("");
+  const [shiftDays, setShiftDays] = useState("");
+  const [bulkNotes, setBulkNotes] = useState("");
+  const bulkDelete = useBulkDeleteTransactions();
+  const bulkLock = useBulkLockTransactions();
+  const bulkEdit = useBulkEditTransactions();
 
   const txResults = useQueries({
     queries: (assets ?? []).map((asset) => ({
@@ -39,8 +63,11 @@ export function TransactionsPage() {
   const deleteMutation = useMutation({
     mutationFn: ({ id }: { id: string; assetId: string }) => deleteTransaction(id),
     onSuccess: (_, { assetId }) => {
+      queryClient.invalidateQueries({ queryKey: ["trash"] });
       queryClient.invalidateQueries({ queryKey: ["transactions", assetId] });
+      queryClient.invalidateQueries({ queryKey: ["transactionHistory", assetId] });
       queryClient.invalidateQueries({ queryKey: ["holdingSummary", assetId] });
+      queryClient.invalidateQueries({ queryKey: ["cash"] });
     },
   });
 
@@ -48,14 +75,42 @@ export function TransactionsPage() {
     mutationFn: ({ id }: { id: string; assetId: string }) => lockTransaction(id),
     onSuccess: (_, { assetId }) => {
       queryClient.invalidateQueries({ queryKey: ["transactions", assetId] });
+      queryClient.invalidateQueries({ queryKey: ["transactionHistory", assetId] });
     },
   });
 
   const unlockMutation = useMutation({
-    mutationFn: ({ id }: { id: string; assetId: string }) => unlockTransaction(id),
+    mutationFn: ({ id, reason }: { id: string; assetId: string; reason: string }) =>
+      unlockTransaction(id, reason),
     onSuccess: (_, { assetId }) => {
       queryClient.invalidateQueries({ queryKey: ["transactions", assetId] });
+      queryClient.invalidateQueries({ queryKey: ["transactionHistory", assetId] });
       setUnlockingTxId(null);
+      setUnlockReason("");
+    },
+  });
+
+  const { data: periodCloses } = useQuery({ queryKey: ["periodCloses"], queryFn: listPeriodCloses });
+  // The close the current scope (the filtered asset, or all) can reopen
+  const openClose = periodCloses?.find((c) => c.reopened_at === null && c.asset_id === filterAssetId);
+
+  const closePeriodMutation = useMutation({
+    mutationFn: (beforeTs: number) => lockTransactionsBefore(beforeTs, filterAssetId ?? undefined),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["transactions"] });
+      queryClient.invalidateQueries({ queryKey: ["transactionHistory"] });
+      queryClient.invalidateQueries({ queryKey: ["periodCloses"] });
+      setCloseBefore("");
+    },
+  });
+
+  const reopenPeriodMutation = useMutation({
+    mutationFn: (reason: string) => reopenPeriod(reason, filterAssetId ?? undefined),
+    onSuccess: () => {
+      queryClient.invalidateQueries({ queryKey: ["transactions"] });
+      queryClient.invalidateQueries({ queryKey: ["transactionHistory"] });
+      queryClient.invalidateQueries({ queryKey: ["periodCloses"] });
+      setReopenReason("");
     },
   });
 
@@ -79,6 +134,19 @@ export function TransactionsPage() {
 
   const isLoading = assetsLoading || txResults.some((r) => r.isLoading);
 
+  // Only rows still shown count, so changing the asset filter never acts on hidden ones
+  const selectedIds = displayedTransactions.filter((tx) => selected.has(tx.id)).map((tx) => tx.id);
+  const allSelected = displayedTransactions.length > 0 && selectedIds.length === displayedTransactions.length;
+  const bulkPending = bulkDelete.isPending || bulkLock.isPending || bulkEdit.isPending;
+  const shiftValue = parseInt(shiftDays);
+
+  function toggleSelected(id: string) {
+    const next = new Set(selected);
+    if (next.has(id)) next.delete(id);
+    else next.add(id);
+    setSelected(next);
+  }
+
   return (
     <div className="space-y-4">
       <div className="flex items-center justify-between">
@@ -102,9 +170,146 @@ export function TransactionsPage() {
               </SelectContent>
             </Select>
           )}
+          <Input
+            type="date"
+            className="h-8 w-36 text-sm"
+            value={closeBefore}
+            onChange={(e) => setCloseBefore(e.target.value)}
+            title="Lock every transaction dated before this day"
+          />
+          <Button
+            variant="ghost"
+            size="sm"
+            disabled={!closeBefore || closePeriodMutation.isPending}
+            onClick={() => closePeriodMutation.mutate(Math.floor(new Date(closeBefore).getTime() / 1000))}
+          >
+            <Lock className="mr-1 h-3 w-3" />
+            Close period
+          </Button>
           <AddTransactionDialog />
         </div>
       </div>
+      {closePeriodMutation.data !== undefined && (
+        <p className="text-xs text-muted-foreground">
+          Locked {closePeriodMutation.data} transaction{closePeriodMutation.data !== 1 ? "s" : ""}.
+        </p>
+      )}
+      {openClose && (
+        <div className="flex items-center gap-2 text-xs text-muted-foreground">
+          <span>Books closed before {formatDate(openClose.closed_before)}.</span>
+          <Input
+            className="h-7 w-48 text-xs"
+            placeholder="Reason for reopening"
+            value={reopenReason}
+            onChange={(e) => setReopenReason(e.target.value)}
+          />
+          <Button
+            variant="ghost"
+            size="sm"
+            disabled={!reopenReason.trim() || reopenPeriodMutation.isPending}
+            onClick={() => reopenPeriodMutation.mutate(reopenReason)}
+          >
+            <LockOpen className="mr-1 h-3 w-3" />
+            Reopen
+          </Button>
+        </div>
+      )}
+      {selectedIds.length > 0 && (
+        <div className="flex flex-wrap items-center gap-2 rounded-xl border border-zinc-800 bg-zinc-900/60 px-4 py-2 text-xs">
+          <span className="text-zinc-300">{selectedIds.length} selected</span>
+          <Button
+            variant="ghost"
+            size="sm"
+            disabled={bulkPending}
+            onClick={() => bulkLock.mutate({ ids: selectedIds })}
+          >
+            <Lock className="mr-1 h-3 w-3" />
+            Lock
+          </Button>
+          <Button
+            variant="ghost"
+            size="sm"
+            className="hover:text-red-400"
+            disabled={bulkPending}
+            onClick={() => bulkDelete.mutate({ ids: selectedIds }, { onSuccess: () => setSelected(new Set()) })}
+          >
+            <Trash2 className="mr-1 h-3 w-3" />
+            Delete
+          </Button>
+          <Select value={moveTo} onValueChange={setMoveTo}>
+            <SelectTrigger className="h-7 w-32 text-xs">
+              <SelectValue placeholder="Move to..." />
+            </SelectTrigger>
+            <SelectContent>
+              {(assets ?? []).map((a) => (
+                <SelectItem key={a.id} value={a.id}>
+                  {a.symbol}
+                </SelectItem>
+              ))}
+            </SelectContent>
+          </Select>
+          <Button
+            variant="ghost"
+            size="sm"
+            disabled={!moveTo || bulkPending}
+            onClick={() =>
+              bulkEdit.mutate({ selection: { ids: selectedIds }, edit: { asset_id: moveTo } }, { onSuccess: () => setMoveTo("") })
+            }
+          >
+            Move
+          </Button>
+          <Input
+            type="number"
+            className="h-7 w-20 text-xs"
+            placeholder="± days"
+            value={shiftDays}
+            onChange={(e) => setShiftDays(e.target.value)}
+          />
+          <Button
+            variant="ghost"
+            size="sm"
+            disabled={isNaN(shiftValue) || shiftValue === 0 || bulkPending}
+            onClick={() =>
+              bulkEdit.mutate(
+                { selection: { ids: selectedIds }, edit: { shift_secs: shiftValue * 86400 } },
+                { onSuccess: () => setShiftDays("") },
+              )
+            }
+          >
+            Shift dates
+          </Button>
+          <Input
+            className="h-7 w-40 text-xs"
+            placeholder="Notes (blank clears)"
+            value={bulkNotes}
+            onChange={(e) => setBulkNotes(e.target.value)}
+          />
+          <Button
+            variant="ghost"
+            size="sm"
+            disabled={bulkPending}
+            onClick={() =>
+              bulkEdit.mutate({ selection: { ids: selectedIds }, edit: { notes: bulkNotes } }, { onSuccess: () => setBulkNotes("") })
+            }
+          >
+            Set notes
+          </Button>
+          <Button variant="ghost" size="sm" onClick={() => setSelected(new Set())}>
+            Clear
+          </Button>
+        </div>
+      )}
+      {[
+        closePeriodMutation.error,
+        reopenPeriodMutation.error,
+        unlockMutation.error,
+        deleteMutation.error,
+        bulkDelete.error,
+        bulkLock.error,
+        bulkEdit.error,
+      ].map(
+        (err, i) => err && <p key={i} className="text-xs text-red-400">{errorMessage(err)}</p>,
+      )}
 
       {isLoading && <p className="text-muted-foreground">Loading transactions...</p>}
 
@@ -125,6 +330,14 @@ export function TransactionsPage() {
           <table className="w-full text-sm">
             <thead className="sticky top-0 z-10">
               <tr className="border-b border-zinc-800 bg-zinc-900">
+                <th className="w-8 pl-4 py-3">
+                  <input
+                    type="checkbox"
+                    checked={allSelected}
+                    onChange={() => setSelected(allSelected ? new Set() : new Set(displayedTransactions.map((tx) => tx.id)))}
+                    title="Select all shown"
+                  />
+                </th>
                 <th className="px-4 py-3 text-left text-xs font-semibold uppercase tracking-wider text-zinc-500">
                   Date
                 </th>
@@ -149,6 +362,9 @@ export function TransactionsPage() {
             <tbody>
               {displayedTransactions.map((tx) => (
                 <tr key={tx.id} className="border-b border-zinc-800/50 hover:bg-zinc-800/30">
+                  <td className="w-8 pl-4 py-3">
+                    <input type="checkbox" checked={selected.has(tx.id)} onChange={() => toggleSelected(tx.id)} />
+                  </td>
                   <td className="px-4 py-3 text-zinc-300">{tx.ts === 0 ? <span className="text-zinc-500 italic">Unknown date</span> : formatDate(tx.ts)}</td>
                   <td className="px-4 py-3">
                     <div className="flex items-center gap-2">
@@ -172,41 +388,43 @@ export function TransactionsPage() {
                   <td className="px-4 py-3 text-right">
                     {unlockingTxId === tx.id ? (
                       <div className="flex items-center justify-end gap-1">
-                        <span className="text-xs text-amber-400">Unlock?</span>
+                        <Input
+                          className="h-7 w-44 text-xs"
+                          placeholder="Reason for unlocking"
+                          value={unlockReason}
+                          onChange={(e) => setUnlockReason(e.target.value)}
+                          autoFocus
+                        />
                         <Button
                           variant="ghost"
                           size="sm"
-                          onClick={() => unlockMutation.mutate({ id: tx.id, assetId: tx.asset.id })}
-                          disabled={unlockMutation.isPending}
+                          onClick={() =>
+                            unlockMutation.mutate({ id: tx.id, assetId: tx.asset.id, reason: unlockReason })
+                          }
+                          disabled={!unlockReason.trim() || unlockMutation.isPending}
                         >
-                          Yes
-                        </Button>
-                        <Button variant="ghost" size="sm" onClick={() => setUnlockingTxId(null)}>
-                          Cancel
+                          Unlock
                         </Button>
-                      </div>
-                    ) : confirmDeleteId === tx.id ? (
-                      <div className="flex items-center justify-end gap-1">
-                        <span className="text-xs text-red-400">Delete locked?</span>
                         <Button
                           variant="ghost"
                           size="sm"
-                          className="text-red-400 hover:text-red-300"
                           onClick={() => {
-                            deleteMutation.mutate({ id: tx.id, assetId: tx.asset.id });
-                            setConfirmDeleteId(null);
+                            setUnlockingTxId(null);
+                            setUnlockReason("");
                           }}
-                          disabled={deleteMutation.isPending}
                         >
-                          Delete
-                        </Button>
-                        <Button variant="ghost" size="sm" onClick={() => setConfirmDeleteId(null)}>
                           Cancel
                         </Button>
                       </div>
                     ) : (
                       <div className="flex items-center justify-end gap-1">
-                        <Button variant="ghost" size="sm" onClick={() => setEditingTx(tx)}>
+                        <Button
+                          variant="ghost"
+                          size="sm"
+                          onClick={() => setEditingTx(tx)}
+                          disabled={tx.locked_at !== null}
+                          title={tx.locked_at !== null ? "Unlock to edit" : undefined}
+                        >
                           <Pencil className="h-3 w-3" />
                         </Button>
                         <Button
@@ -229,14 +447,9 @@ export function TransactionsPage() {
                           variant="ghost"
                           size="sm"
                           className="hover:text-red-400"
-                          onClick={() => {
-                            if (tx.locked_at !== null) {
-                              setConfirmDeleteId(tx.id);
-                            } else {
-                              deleteMutation.mutate({ id: tx.id, assetId: tx.asset.id });
-                            }
-                          }}
-                          disabled={deleteMutation.isPending}
+                          onClick={() => deleteMutation.mutate({ id: tx.id, assetId: tx.asset.id })}
+                          disabled={tx.locked_at !== null || deleteMutation.isPending}
+                          title={tx.locked_at !== null ? "Unlock to delete" : undefined}
                         >
                           <Trash2 className="h-3 w-3" />
                         </Button>
@@ -0,0 +1,157 @@
+import { useMemo, useState } from "react";
+import { useAllAssets, usePurgeAsset } from "@/hooks/useAssets";
+import {
+  useEmptyTrash,
+  usePurgeTransaction,
+  useRestoreDeletedAsset,
+  useRestoreTransaction,
+  useTrash,
+} from "@/hooks/useTrash";
+import { Badge } from "@/components/ui/badge";
+import { Button } from "@/components/ui/button";
+import { formatCurrency } from "@/lib/utils/formatCurrency";
+import { formatDate, formatRelativeTime } from "@/lib/utils/dateHelpers";
+import { errorMessage } from "@/lib/utils/commandError";
+import { ArchiveRestore, Trash2 } from "lucide-react";
+
+export function TrashPage() {
+  const { data: trash, isLoading } = useTrash();
+  const { data: allAssets } = useAllAssets();
+  const restoreAsset = useRestoreDeletedAsset();
+  const purgeAsset = usePurgeAsset();
+  const restoreTx = useRestoreTransaction();
+  const purgeTx = usePurgeTransaction();
+  const emptyTrash = useEmptyTrash();
+  const [confirmEmpty, setConfirmEmpty] = useState(false);
+
+  const symbols = useMemo(() => new Map((allAssets ?? []).map((a) => [a.id, a.symbol])), [allAssets]);
+  const deletedAssetIds = useMemo(() => new Set((trash?.assets ?? []).map((a) => a.id)), [trash]);
+  const isEmpty = !trash || (trash.assets.length === 0 && trash.transactions.length === 0);
+  const error = restoreAsset.error ?? purgeAsset.error ?? restoreTx.error ?? purgeTx.error ?? emptyTrash.error;
+
+  return (
+    <div className="space-y-4">
+      <div className="flex items-center justify-between">
+        <h2 className="text-base font-semibold text-zinc-100">Trash</h2>
+        {!isEmpty &&
+          (confirmEmpty ? (
+            <div className="flex items-center gap-1">
+              <span className="text-xs text-red-400">Delete everything for good?</span>
+              <Button
+                variant="destructive"
+                size="sm"
+                onClick={() => emptyTrash.mutate(undefined, { onSettled: () => setConfirmEmpty(false) })}
+                disabled={emptyTrash.isPending}
+              >
+                Empty
+              </Button>
+              <Button variant="ghost" size="sm" onClick={() => setConfirmEmpty(false)}>
+                Cancel
+              </Button>
+            </div>
+          ) : (
+            <Button variant="ghost" size="sm" onClick={() => setConfirmEmpty(true)}>
+              <Trash2 className="mr-1 h-3 w-3" />
+              Empty trash
+            </Button>
+          ))}
+      </div>
+      <p className="text-xs text-muted-foreground">
+        Restoring an asset also restores the transactions deleted with it. Automatic purging is set
+        under Settings → Preferences.
+      </p>
+      {error && <p className="text-xs text-red-400">{errorMessage(error)}</p>}
+
+      {isLoading && <p className="text-muted-foreground">Loading trash...</p>}
+      {!isLoading && isEmpty && <p className="text-muted-foreground">The trash is empty.</p>}
+
+      {trash && trash.assets.length > 0 && (
+        <div className="rounded-xl border border-zinc-800 bg-zinc-900/60">
+          <p className="px-4 pt-4 text-xs font-semibold uppercase tracking-wider text-zinc-500">Assets</p>
+          <table className="w-full text-sm">
+            <tbody>
+              {trash.assets.map((asset) => (
+                <tr key={asset.id} className="border-b border-zinc-800/50 last:border-0">
+                  <td className="px-4 py-3">
+                    <span className="font-semibold text-zinc-100">{asset.symbol}</span>{" "}
+                    <span className="text-zinc-400">{asset.name}</span>
+                  </td>
+                  <td className="px-4 py-3">
+                    <Badge variant="outline">{asset.asset_type}</Badge>
+                  </td>
+                  <td className="px-4 py-3 text-zinc-400">
+                    Deleted {asset.deleted_at !== null ? formatRelativeTime(asset.deleted_at) : ""}
+                  </td>
+                  <td className="px-4 py-3 text-right">
+                    <Button
+                      variant="ghost"
+                      size="sm"
+                      title="Restore with its transactions"
+                      onClick={() => restoreAsset.mutate(asset.id)}
+                      disabled={restoreAsset.isPending}
+                    >
+                      <ArchiveRestore className="h-3 w-3" />
+                    </Button>
+                    <Button
+                      variant="ghost"
+                      size="sm"
+                      className="hover:text-red-400"
+                      title="Delete for good"
+                      onClick={() => purgeAsset.mutate(asset.id)}
+                      disabled={purgeAsset.isPending}
+                    >
+                      <Trash2 className="h-3 w-3" />
+                    </Button>
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        </div>
+      )}
+
+      {trash && trash.transactions.length > 0 && (
+        <div className="rounded-xl border border-zinc-800 bg-zinc-900/60">
+          <p className="px-4 pt-4 text-xs font-semibold uppercase tracking-wider text-zinc-500">Transactions</p>
+          <table className="w-full text-sm">
+            <tbody>
+              {trash.transactions.map((tx) => (
+                <tr key={tx.id} className="border-b border-zinc-800/50 last:border-0">
+                  <td className="px-4 py-3 text-zinc-300">{formatDate(tx.ts)}</td>
+                  <td className="px-4 py-3 font-semibold text-zinc-100">{symbols.get(tx.asset_id) ?? "—"}</td>
+                  <td className="px-4 py-3 text-zinc-300">
+                    {tx.tx_type === "buy" ? "Buy" : "Sell"} {tx.quantity} @ {formatCurrency(tx.price_usd)}
+                  </td>
+                  <td className="px-4 py-3 text-zinc-400">
+                    Deleted {tx.deleted_at !== null ? formatRelativeTime(tx.deleted_at) : ""}
+                  </td>
+                  <td className="px-4 py-3 text-right">
+                    <Button
+                      variant="ghost"
+                      size="sm"
+                      title={deletedAssetIds.has(tx.asset_id) ? "Restore the asset first" : "Restore"}
+                      onClick={() => restoreTx.mutate(tx.id)}
+                      disabled={deletedAssetIds.has(tx.asset_id) || restoreTx.isPending}
+                    >
+                      <ArchiveRestore className="h-3 w-3" />
+                    </Button>
+                    <Button
+                      variant="ghost"
+                      size="sm"
+                      className="hover:text-red-400"
+                      title="Delete for good"
+                      onClick={() => purgeTx.mutate(tx.id)}
+                      disabled={purgeTx.isPending}
+                    >
+                      <Trash2 className="h-3 w-3" />
+                    </Button>
+                  </td>
+                </tr>
+              ))}
+            </tbody>
+          </table>
+        </div>
+      )}
+    </div>
+  );
+}
@@ -0,0 +1,351 @@
+import { useMemo, useState } from "react";
+import { useAddAsset, useAllAssets, useAssets } from "@/hooks/useAssets";
+import { usePrices } from "@/hooks/usePrices";
+import { useSymbolSearch } from "@/hooks/useSymbolSearch";
+import {
+  useCreateWatchlist,
+  useDeleteWatchlist,
+  useRemoveWatchlistItem,
+  useRenameWatchlist,
+  useReorderWatchlistItems,
+  useReorderWatchlists,
+  useUpdateWatchlistItemNotes,
+  useWatchSymbol,
+  useWatchlists,
+} from "@/hooks/useWatchlists";
+import { Button } from "@/components/ui/button";
+import { Input } from "@/components/ui/input";
+import { formatCurrency } from "@/lib/utils/formatCurrency";
+import { calcChange } from "@/lib/utils/priceUtils";
+import { ASSET_TYPE_LABELS, isAssetType } from "@/lib/utils/assetTypes";
+import type { Asset, SymbolSearchResult, Watchlist, WatchlistItem } from "@/types";
+import { ArrowDown, ArrowUp, ChevronLeft, ChevronRight, Search, Trash2 } from "lucide-react";
+
+/** `ids` with the entry at `index` swapped with its neighbour in `direction`. */
+function moved(ids: string[], index: number, direction: -1 | 1): string[] {
+  const next = [...ids];
+  const target = index + direction;
+  if (target < 0 || target >= next.length) return next;
+  [next[index], next[target]] = [next[target]!, next[index]!];
+  return next;
+}
+
+function WatchlistRow({
+  watchlist,
+  item,
+  index,
+  asset,
+  inPortfolio,
+}: {
+  watchlist: Watchlist;
+  item: WatchlistItem;
+  index: number;
+  asset: Asset | undefined;
+  inPortfolio: boolean;
+}) {
+  const { data: prices } = usePrices(item.asset_id);
+  const updateNotes = useUpdateWatchlistItemNotes();
+  const removeItem = useRemoveWatchlistItem();
+  const reorder = useReorderWatchlistItems();
+  const addAsset = useAddAsset();
+
+  const sorted = [...(prices ?? [])].sort((a, b) => a.ts - b.ts);
+  const last = sorted[sorted.length - 1];
+  const dayChange = calcChange(sorted, 1);
+  const monthChange = calcChange(sorted, 30);
+  const ids = watchlist.items.map((i) => i.asset_id);
+
+  return (
+    <tr className="border-b border-zinc-800/50">
+      <td className="px-4 py-2">
+        <div className="flex flex-col">
+          <button
+            className="text-zinc-500 hover:text-zinc-200 disabled:opacity-30"
+            title="Move up"
+            disabled={index === 0}
+            onClick={() => reorder.mutate({ watchlistId: watchlist.id, assetIds: moved(ids, index, -1) })}
+          >
+            <ArrowUp className="h-3 w-3" />
+          </button>
+          <button
+            className="text-zinc-500 hover:text-zinc-200 disabled:opacity-30"
+            title="Move down"
+            disabled={index === ids.length - 1}
+            onClick={() => reorder.mutate({ watchlistId: watchlist.id, assetIds: moved(ids, index, 1) })}
+          >
+            <ArrowDown className="h-3 w-3" />
+          </button>
+        </div>
+      </td>
+      <td className="px-4 py-2">
+        <div className="font-semibold text-zinc-100">{asset?.symbol ?? "—"}</div>
+        <div className="max-w-48 truncate text-xs text-zinc-500">
+          {asset?.name}
+          {asset && isAssetType(asset.asset_type) && ` · ${ASSET_TYPE_LABELS[asset.asset_type]}`}
+        </div>
+      </td>
+      <td className="px-4 py-2 text-right text-zinc-200">{last ? formatCurrency(last.close) : "—"}</td>
+      {[dayChange, monthChange].map((change, i) => (
+        <td
+          key={i}
+          className={`px-4 py-2 text-right ${change === null ? "text-zinc-500" : change >= 0 ? "text-green-400" : "text-red-400"}`}
+        >
+          {change === null ? "—" : `${change >= 0 ? "+" : ""}${change.toFixed(2)}%`}
+        </td>
+      ))}
+      <td className="px-4 py-2">
+        <Input
+          key={item.notes ?? ""}
+          className="h-7 text-xs"
+          placeholder="Notes"
+          defaultValue={item.notes ?? ""}
+          onBlur={(e) => {
+            if (e.target.value !== (item.notes ?? "")) {
+              updateNotes.mutate({ watchlistId: watchlist.id, assetId: item.asset_id, notes: e.target.value });
+            }
+          }}
+        />
+      </td>
+      <td className="px-4 py-2 text-right">
+        <div className="flex items-center justify-end gap-3">
+          {asset && !inPortfolio && (
+            <button
+              className="text-xs text-zinc-400 hover:text-zinc-100"
+              title="Track in the portfolio"
+              onClick={() =>
+                addAsset.mutate({
+                  symbol: asset.symbol,
+                  name: asset.name,
+                  assetType: asset.asset_type,
+                  exchange: asset.exchange ?? undefined,
+                })
+              }
+            >
+              Add to portfolio
+            </button>
+          )}
+          <button
+            className="text-zinc-500 hover:text-red-400"
+            title="Remove from watchlist"
+            onClick={() => removeItem.mutate({ watchlistId: watchlist.id, assetId: item.asset_id })}
+          >
+            <Trash2 className="h-3.5 w-3.5" />
+          </button>
+        </div>
+      </td>
+    </tr>
+  );
+}
+
+export function WatchlistsPage() {
+  const { data: watchlists } = useWatchlists();
+  const { data: allAssets } = useAllAssets();
+  const { data: portfolioAssets } = useAssets();
+  const createWatchlist = useCreateWatchlist();
+  const renameWatchlist = useRenameWatchlist();
+  const deleteWatchlist = useDeleteWatchlist();
+  const reorderWatchlists = useReorderWatchlists();
+  const watchSymbol = useWatchSymbol();
+
+  const [selectedId, setSelectedId] = useState<string | null>(null);
+  const [newName, setNewName] = useState("");
+  const [searchQuery, setSearchQuery] = useState("");
+  const [confirmDelete, setConfirmDelete] = useState(false);
+  const [error, setError] = useState("");
+  const { data: searchResults, isLoading: isSearching } = useSymbolSearch(searchQuery);
+
+  const assets = useMemo(
+    () => new Map((allAssets ?? []).filter((a) => a.deleted_at === null).map((a) => [a.id, a])),
+    [allAssets],
+  );
+  const portfolioIds = useMemo(() => new Set((portfolioAssets ?? []).map((a) => a.id)), [portfolioAssets]);
+  const lists = watchlists ?? [];
+  const selectedIndex = Math.max(0, lists.findIndex((w) => w.id === selectedId));
+  const selected = lists[selectedIndex];
+
+  async function handleCreate(e: React.FormEvent) {
+    e.preventDefault();
+    setError("");
+    if (!newName.trim()) return;
+    try {
+      const created = await createWatchlist.mutateAsync(newName.trim());
+      setSelectedId(created.id);
+      setNewName("");
+    } catch (err) {
+      setError(String(err));
+    }
+  }
+
+  async function handleWatch(result: SymbolSearchResult) {
+    if (!selected || !isAssetType(result.asset_type)) return;
+    setError("");
+    try {
+      await watchSymbol.mutateAsync({
+        watchlistId: selected.id,
+        symbol: result.symbol,
+        name: result.name,
+        assetType: result.asset_type,
+        exchange: result.exchange ?? undefined,
+        provider: result.provider,
+        providerId: result.provider_id,
+      });
+      setSearchQuery("");
+    } catch (err) {
+      setError(String(err));
+    }
+  }
+
+  return (
+    <div className="space-y-4">
+      <h2 className="text-base font-semibold text-zinc-100">Watchlists</h2>
+
+      <div className="flex flex-wrap items-center gap-2">
+        {lists.map((list) => (
+          <button
+            key={list.id}
+            className={`rounded-md px-3 py-1.5 text-sm transition-colors ${list.id === selected?.id ? "bg-zinc-700 text-zinc-100" : "text-zinc-400 hover:text-zinc-200"}`}
+            onClick={() => {
+              setSelectedId(list.id);
+              setConfirmDelete(false);
+            }}
+          >
+            {list.name}
+            <span className="ml-1.5 text-xs text-zinc-500">{list.items.length}</span>
+          </button>
+        ))}
+        <form onSubmit={handleCreate} className="flex gap-2">
+          <Input
+            className="h-8 w-40"
+            placeholder="New watchlist"
+            value={newName}
+            onChange={(e) => setNewName(e.target.value)}
+          />
+          <Button type="submit" size="sm" disabled={createWatchlist.isPending}>Create</Button>
+        </form>
+      </div>
+
+      {error && <p className="text-sm text-destructive">{error}</p>}
+
+      {!selected ? (
+        <p className="py-10 text-center text-sm text-muted-foreground">
+          Create a watchlist to follow assets without adding them to your portfolio.
+        </p>
+      ) : (
+        <div className="rounded-xl border border-zinc-800 bg-zinc-900/60">
+          <div className="flex items-center gap-3 border-b border-zinc-800 px-4 py-3">
+            <Input
+              key={selected.id + selected.name}
+              className="h-8 w-48 font-semibold"
+              defaultValue={selected.name}
+              onBlur={(e) => {
+                const name = e.target.value.trim();
+                if (name && name !== selected.name) renameWatchlist.mutate({ id: selected.id, name });
+              }}
+            />
+            <button
+              className="text-zinc-500 hover:text-zinc-200 disabled:opacity-30"
+              title="Move list left"
+              disabled={selectedIndex === 0}
+              onClick={() => reorderWatchlists.mutate(moved(lists.map((w) => w.id), selectedIndex, -1))}
+            >
+              <ChevronLeft className="h-4 w-4" />
+            </button>
+            <button
+              className="text-zinc-500 hover:text-zinc-200 disabled:opacity-30"
+              title="Move list right"
+              disabled={selectedIndex === lists.length - 1}
+              onClick={() => reorderWatchlists.mutate(moved(lists.map((w) => w.id), selectedIndex, 1))}
+            >
+              <ChevronRight className="h-4 w-4" />
+            </button>
+            <div className="relative ml-auto w-72">
+              <Search className="absolute left-3 top-1/2 h-4 w-4 -translate-y-1/2 text-muted-foreground" />
+              <Input
+                className="h-8 pl-9"
+                placeholder="Add symbol..."
+                value={searchQuery}
+                onChange={(e) => setSearchQuery(e.target.value)}
+                autoComplete="off"
+              />
+              {searchQuery.trim().length >= 1 && (
+                <div className="absolute z-50 mt-1 max-h-56 w-full overflow-y-auto rounded-md border border-zinc-700 bg-zinc-900 shadow-lg">
+                  {isSearching && (
+                    <div className="px-4 py-3 text-sm text-muted-foreground animate-pulse">Searching...</div>
+                  )}
+                  {!isSearching && searchResults && searchResults.length === 0 && (
+                    <div className="px-4 py-3 text-sm text-muted-foreground">No results found.</div>
+                  )}
+                  {!isSearching &&
+                    searchResults?.map((result, i) => (
+                      <button
+                        key={`${result.provider}-${result.symbol}-${i}`}
+                        type="button"
+                        className="flex w-full items-center justify-between px-4 py-2 text-left transition-colors hover:bg-zinc-800"
+                        onClick={() => handleWatch(result)}
+                      >
+                        <span className="flex min-w-0 items-center gap-2">
+                          <span className="font-semibold text-zinc-100">{result.symbol}</span>
+                          <span className="truncate text-sm text-zinc-400">{result.name}</span>
+                        </span>
+                        {result.exchange && <span className="ml-2 text-xs text-zinc-500">{result.exchange}</span>}
+                      </button>
+                    ))}
+                </div>
+              )}
+            </div>
+            {confirmDelete ? (
+              <Button
+                size="sm"
+                variant="destructive"
+                onClick={() => {
+                  deleteWatchlist.mutate(selected.id);
+                  setConfirmDelete(false);
+                  setSelectedId(null);
+                }}
+              >
+                Confirm
+              </Button>
+            ) : (
+              <button
+                className="text-zinc-500 hover:text-red-400"
+                title="Delete watchlist"
+                onClick={() => setConfirmDelete(true)}
+              >
+                <Trash2 className="h-4 w-4" />
+              </button>
+            )}
+          </div>
+          {selected.items.length === 0 ? (
+            <p className="py-10 text-center text-sm text-muted-foreground">Search for a symbol to add it.</p>
+          ) : (
+            <table className="w-full text-sm">
+              <thead>
+                <tr className="border-b border-zinc-800 text-left text-xs uppercase tracking-wider text-zinc-500">
+                  <th className="w-8 px-4 py-3" />
+                  <th className="px-4 py-3">Asset</th>
+                  <th className="px-4 py-3 text-right">Last</th>
+                  <th className="px-4 py-3 text-right">1D</th>
+                  <th className="px-4 py-3 text-right">30D</th>
+                  <th className="px-4 py-3">Notes</th>
+                  <th className="px-4 py-3" />
+                </tr>
+              </thead>
+              <tbody>
+                {selected.items.map((item, index) => (
+                  <WatchlistRow
+                    key={item.asset_id}
+                    watchlist={selected}
+                    item={item}
+                    index={index}
+                    asset={assets.get(item.asset_id)}
+                    inPortfolio={portfolioIds.has(item.asset_id)}
+                  />
+                ))}
+              </tbody>
+            </table>
+          )}
+        </div>
+      )}
+    </div>
+  );
+}
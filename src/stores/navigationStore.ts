@@ -1,6 +1,6 @@
 import { create } from "zustand";
 
-export type Page = "dashboard" | "transactions" | "realized-pnl" | "performance" | "asset-manager" | "settings";
+export type Page = "dashboard" | "transactions" | "cash" | "realized-pnl" | "performance" | "rebalance" | "watchlists" | "alerts" | "trash" | "asset-manager" | "settings";
 
 interface NavigationState {
   activePage: Page;
@@ -1,4 +1,13 @@
-export type AssetType = "stock" | "crypto" | "commodity";
+export type AssetType =
+  | "stock"
+  | "etf"
+  | "mutual_fund"
+  | "bond"
+  | "forex"
+  | "index"
+  | "crypto"
+  | "commodity"
+  | "cash";
 
 export interface Asset {
   id: string;
@@ -6,23 +15,168 @@ export interface Asset {
   name: string;
   asset_type: AssetType;
   currency: string;
+  /** Listing venue; the same symbol can be tracked once per exchange and type */
+  exchange: string | null;
   added_at: number;
   deleted_at: number | null;
 }
 
+export type Interval = "1m" | "5m" | "1h" | "4h" | "1d" | "1w";
+
 export interface OHLCVRow {
   id: number | null;
   asset_id: string;
+  interval: Interval;
   ts: number;
   open: number | null;
   high: number | null;
   low: number | null;
   close: number;
   volume: number | null;
+  /** Provider the candle came from; null for rows stored before provenance was tracked. */
+  provider: string | null;
+}
+
+export interface PriceTick {
+  asset_id: string;
+  symbol: string;
+  price: number;
+  ts: number;
+  provider: string;
+}
+
+/** Corrections applied to stored raw candles by `fetch_prices`. */
+export type PriceAdjustment = "raw" | "split" | "total";
+
+export interface CorporateAction {
+  id: number | null;
+  asset_id: string;
+  action_type: "split" | "dividend";
+  /** Ex-date, 00:00 UTC. */
+  ts: number;
+  /** Split ratio (4 for 4-for-1) or cash dividend per share. */
+  value: number;
+  provider: string;
+}
+
+export type PriceIssueKind = "non_positive" | "ohlc_inconsistent" | "spike";
+export type PriceIssueStatus = "pending" | "accepted" | "rejected";
+
+/** A fetched candle held out of price history by the ingest quality checks. */
+export interface PriceIssue {
+  id: number;
+  candle: OHLCVRow;
+  kind: PriceIssueKind;
+  detail: string;
+  status: PriceIssueStatus;
+  detected_at: number;
+  resolved_at: number | null;
+}
+
+export interface AssetStorage {
+  asset_id: string;
+  symbol: string;
+  deleted: boolean;
+  price_rows: number;
+  intraday_rows: number;
+  reference_rows: number;
+  /** Estimated share of the price tables' pages. */
+  bytes: number;
+}
+
+export interface StorageReport {
+  total_bytes: number;
+  free_bytes: number;
+  /** Largest first. */
+  assets: AssetStorage[];
+  orphan_rows: number;
+}
+
+export interface OptimizeResult {
+  daily_rows_added: number;
+  intraday_rows_removed: number;
+  orphan_rows_removed: number;
+  bytes_before: number;
+  bytes_after: number;
+}
+
+export interface MarketStatus {
+  asset_id: string;
+  /** Exchange code ("NYSE", "TSX", "LSE", "XETRA"), "24/7" or "Weekdays". */
+  market: string;
+  is_open: boolean;
+  next_open: number | null;
+  next_close: number | null;
+}
+
+export interface PriceDivergence {
+  ts: number;
+  provider: string | null;
+  close: number;
+  reference_close: number;
+  /** Stored close relative to the reference, in percent. */
+  diff_pct: number;
+}
+
+export interface ReconciliationReport {
+  asset_id: string;
+  interval: Interval;
+  reference_provider: string;
+  from: number;
+  to: number;
+  compared: number;
+  threshold_pct: number;
+  divergences: PriceDivergence[];
+}
+
+/** Missing candles inside stored history; `from`/`to` are the first and last missing candles. */
+export interface PriceGap {
+  asset_id: string;
+  symbol: string;
+  interval: Interval;
+  from: number;
+  to: number;
+  missing: number;
+}
+
+export interface RefreshProgress {
+  completed: number;
+  total: number;
+  asset_id: string | null;
+  symbol: string | null;
+  error: string | null;
+  done: boolean;
+}
+
+/** Stable codes from `ProviderError::code` plus command-level ones. */
+export type CommandErrorCode =
+  | "not_found"
+  | "auth"
+  | "rate_limited"
+  | "transient"
+  | "parse"
+  | "invalid_input"
+  | "oversell"
+  | "period_closed"
+  | "internal";
+
+/** A problem with one input field, keyed by the command argument name. */
+export interface FieldError {
+  field: string;
+  message: string;
+}
+
+/** Rejection value of price and transaction commands. */
+export interface CommandError {
+  code: CommandErrorCode;
+  message: string;
+  /** Per-field problems for invalid_input and oversell. */
+  fields?: FieldError[];
 }
 
 export interface PriceCacheMeta {
   asset_id: string;
+  interval: Interval;
   provider: string;
   last_fetched: number;
 }
@@ -40,16 +194,233 @@ export interface Transaction {
   created_at: number;
   deleted_at: number | null;
   locked_at: number | null;
+  /** Commission in USD: added to a buy's cost, taken from a sell's proceeds. */
+  fee: number;
+  /** Account whose cash pays for a buy or receives a sell. */
+  account_id: string | null;
+}
+
+/** One row of a bulk insert. */
+export interface NewTransaction {
+  asset_id: string;
+  tx_type: TxType;
+  quantity: number;
+  price_usd: number;
+  ts: number;
+  notes: string | null;
+  fee: number;
+  account_id: string | null;
+}
+
+/** Live transactions matching every given condition; date bounds are inclusive. */
+export interface TransactionFilter {
+  asset_id?: string | null;
+  tx_type?: TxType | null;
+  from_ts?: number | null;
+  to_ts?: number | null;
+  created_from?: number | null;
+  created_to?: number | null;
+}
+
+/** The transactions a bulk operation applies to. */
+export type TransactionSelection = { ids: string[] } | { filter: TransactionFilter };
+
+/** Changes a bulk edit makes; unset fields are left alone and blank notes clear them. */
+export interface TransactionEdit {
+  asset_id?: string | null;
+  /** Seconds added to each date; unknown dates stay unknown. */
+  shift_secs?: number | null;
+  notes?: string | null;
+}
+
+/** Soft-deleted items that can still be restored. */
+export interface Trash {
+  assets: Asset[];
+  transactions: Transaction[];
+}
+
+export interface TrashPurge {
+  assets: number;
+  transactions: number;
+}
+
+export type AuditAction = "insert" | "update" | "delete" | "restore" | "lock" | "unlock" | "purge";
+
+/** One logged change to a transaction; `before` is null for an insert, `after` for a purge. */
+export interface AuditEntry {
+  id: number;
+  transaction_id: string;
+  asset_id: string;
+  action: AuditAction;
+  before: Transaction | null;
+  after: Transaction | null;
+  ts: number;
+  /** The entry this change undid, if it was a revert. */
+  reverts: number | null;
+  /** Why the change was made; always set for an unlock. */
+  reason: string | null;
+}
+
+/** Books closed before `closed_before`, for one asset or (`asset_id` null) all. */
+export interface PeriodClose {
+  id: number;
+  asset_id: string | null;
+  closed_before: number;
+  closed_at: number;
+  reopened_at: number | null;
+  /** Why the period was reopened; always set once it is. */
+  reopen_reason: string | null;
+}
+
+export interface Account {
+  id: string;
+  name: string;
+  currency: string;
+  created_at: number;
+}
+
+export type CashEntryKind = "deposit" | "withdrawal" | "dividend" | "buy" | "sell";
+
+/** One movement of cash. `amount` is signed: credits positive, debits negative. */
+export interface CashLedgerEntry {
+  id: string;
+  account_id: string;
+  kind: CashEntryKind;
+  amount: number;
+  currency: string;
+  ts: number;
+  asset_id: string | null;
+  /** Set for buy and sell entries, which mirror a transaction. */
+  transaction_id: string | null;
+  notes: string | null;
+}
+
+export interface CashBalance {
+  account_id: string;
+  account_name: string;
+  currency: string;
+  balance: number;
 }
 
 export interface SymbolSearchResult {
   symbol: string;
   name: string;
   asset_type: string;
+  /** "binance", "coingecko" or "twelve_data" */
   provider: string;
+  /** The identifier the provider knows the instrument by, e.g. "uniswap" or "SHOP:TSX" */
+  provider_id: string;
   exchange: string | null;
 }
 
+/** Descriptive fields for allocation breakdowns; tags double as user-defined groups. */
+export interface AssetMetadata {
+  asset_id: string;
+  sector: string | null;
+  industry: string | null;
+  country: string | null;
+  /** The asset type's default class when unset. */
+  asset_class: string | null;
+  tags: string[];
+  /** Sells may take the position below zero. */
+  allow_short: boolean;
+}
+
+export interface AllocationSlice {
+  /** Asset id, asset type, tag, sector or account id; empty for "none". */
+  key: string;
+  label: string;
+  value: number;
+  pct: number;
+}
+
+/** Portfolio value split several ways, at the latest stored prices, USD cash included. */
+export interface AllocationReport {
+  total_value: number;
+  by_asset: AllocationSlice[];
+  by_type: AllocationSlice[];
+  by_tag: AllocationSlice[];
+  by_sector: AllocationSlice[];
+  by_account: AllocationSlice[];
+}
+
+export interface AllocationTarget {
+  asset_id: string;
+  /** Percent of the rebalanced total (targeted assets plus cash). */
+  target_pct: number;
+  /** Trades are whole multiples of this quantity; 0 allows any fraction. */
+  lot_size: number;
+  no_sell: boolean;
+}
+
+export interface RebalanceTrade {
+  asset_id: string;
+  action: TxType;
+  quantity: number;
+  price: number;
+  value: number;
+  current_pct: number;
+  /** Target after constraints, which can differ from the stored target. */
+  target_pct: number;
+}
+
+export interface RebalancePlan {
+  total_value: number;
+  cash_before: number;
+  cash_after: number;
+  trades: RebalanceTrade[];
+  unpriced_asset_ids: string[];
+}
+
+export interface WatchlistItem {
+  asset_id: string;
+  position: number;
+  notes: string | null;
+  added_at: number;
+}
+
+export interface Watchlist {
+  id: string;
+  name: string;
+  position: number;
+  created_at: number;
+  /** In display order. */
+  items: WatchlistItem[];
+}
+
+export type AlertKind = "price_above" | "price_below" | "pct_move" | "drawdown" | "position_above" | "position_below";
+
+export interface Alert {
+  id: string;
+  asset_id: string;
+  kind: AlertKind;
+  /** USD for price and position alerts, percent for pct_move and drawdown. */
+  threshold: number;
+  window_days: number | null;
+  enabled: boolean;
+  /** The condition held at the last check; fires again only after it clears. */
+  triggered: boolean;
+  last_triggered_at: number | null;
+  created_at: number;
+}
+
+export interface AlertTriggered {
+  alert_id: string;
+  asset_id: string;
+  symbol: string;
+  kind: AlertKind;
+  threshold: number;
+  value: number;
+  message: string;
+  ts: number;
+}
+
+export interface ProviderSymbol {
+  asset_id: string;
+  provider: string;
+  provider_symbol: string;
+}
+
 export interface AssetHoldingSummary {
   total_bought: number;
   total_sold: number;